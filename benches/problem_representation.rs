@@ -0,0 +1,142 @@
+// Memory and time used by the ILP representation of a large colloscope.
+// Run with: cargo bench --bench problem_representation
+
+use collomatique::gen::colloscope::Variable;
+use collomatique::ilp::linexpr::Expr;
+use collomatique::ilp::{DefaultRepr, ProblemBuilder};
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+// Keeps track of the bytes currently allocated and of the peak since the last reset
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn reset_peak() -> usize {
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(allocated, Ordering::Relaxed);
+    allocated
+}
+
+fn megabytes(bytes: usize) -> f64 {
+    bytes as f64 / (1024. * 1024.)
+}
+
+const SUBJECTS: usize = 10;
+const SLOTS_PER_SUBJECT: usize = 300;
+const GROUPS_PER_SUBJECT: usize = 15;
+const STUDENTS: usize = 45;
+
+// Same shape as the problems built by gen::colloscope, without the details:
+// at most one group per slot, the same number of slots for each group
+// and one group per subject for each student
+fn problem_builder() -> ProblemBuilder<Variable> {
+    let group_in_slot = |subject, slot, group| Variable::GroupInSlot {
+        subject,
+        slot,
+        group,
+    };
+    let student_in_group = |subject, student, group| Variable::StudentInGroup {
+        subject,
+        student,
+        group,
+    };
+
+    let mut variables = Vec::new();
+    let mut constraints = Vec::new();
+    for subject in 0..SUBJECTS {
+        for slot in 0..SLOTS_PER_SUBJECT {
+            let mut expr = Expr::constant(0);
+            for group in 0..GROUPS_PER_SUBJECT {
+                variables.push(group_in_slot(subject, slot, group));
+                expr = expr + Expr::var(group_in_slot(subject, slot, group));
+            }
+            constraints.push(expr.leq(&Expr::constant(1)));
+        }
+        for group in 0..GROUPS_PER_SUBJECT {
+            let mut expr = Expr::constant(0);
+            for slot in 0..SLOTS_PER_SUBJECT {
+                expr = expr + Expr::var(group_in_slot(subject, slot, group));
+            }
+            let count = i32::try_from(SLOTS_PER_SUBJECT / GROUPS_PER_SUBJECT).unwrap();
+            constraints.push(expr.eq(&Expr::constant(count)));
+        }
+        for student in 0..STUDENTS {
+            let mut expr = Expr::constant(0);
+            for group in 0..GROUPS_PER_SUBJECT {
+                variables.push(student_in_group(subject, student, group));
+                expr = expr + Expr::var(student_in_group(subject, student, group));
+            }
+            constraints.push(expr.eq(&Expr::constant(1)));
+        }
+    }
+
+    ProblemBuilder::new()
+        .add_bool_variables(variables)
+        .unwrap()
+        .add_constraints(constraints)
+        .unwrap()
+}
+
+fn main() {
+    let baseline = reset_peak();
+
+    let start = Instant::now();
+    let problem = problem_builder().build::<DefaultRepr<Variable>>();
+    let build_time = start.elapsed();
+    let retained = ALLOCATED.load(Ordering::Relaxed) - baseline;
+    let peak = PEAK.load(Ordering::Relaxed) - baseline;
+
+    let stats = problem.stats();
+    println!(
+        "{} variables, {} constraints, {} non-zero coefficients",
+        stats.bool_variable_count, stats.constraint_count, stats.nonzero_count
+    );
+    println!(
+        "build: {:.3} s, retained: {:.1} MiB, peak: {:.1} MiB",
+        build_time.as_secs_f64(),
+        megabytes(retained),
+        megabytes(peak)
+    );
+
+    let variables: Vec<_> = problem.get_variables().to_vec();
+    let mut config = problem.default_config();
+    let start = Instant::now();
+    for (i, var) in variables.iter().enumerate() {
+        config.set_bool(var, i % 2 == 0).unwrap();
+    }
+    let set_time = start.elapsed();
+
+    let start = Instant::now();
+    let lhs = config.compute_lhs();
+    let lhs_time = start.elapsed();
+    assert_eq!(lhs.len(), stats.constraint_count);
+
+    println!(
+        "set every variable: {:.3} s, compute_lhs: {:.3} s",
+        set_time.as_secs_f64(),
+        lhs_time.as_secs_f64()
+    );
+}
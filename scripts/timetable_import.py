@@ -0,0 +1,119 @@
+import collomatique
+
+def import_timetable(db, timetable_csv, rules_csv):
+    import collomatique
+
+    days = {
+        "lundi": collomatique.Weekday.Monday,
+        "mardi": collomatique.Weekday.Tuesday,
+        "mercredi": collomatique.Weekday.Wednesday,
+        "jeudi": collomatique.Weekday.Thursday,
+        "vendredi": collomatique.Weekday.Friday,
+        "samedi": collomatique.Weekday.Saturday,
+        "dimanche": collomatique.Weekday.Sunday,
+    }
+
+    # Accepts "08h00", "8h", "8:00"
+    def parse_time(text):
+        parts = text.strip().lower().replace(":", "h").split("h")
+        hour = int(parts[0])
+        minute = int(parts[1]) if len(parts) > 1 and parts[1] != "" else 0
+        return collomatique.Time(hour, minute)
+
+    def find_week_pattern(db, name):
+        week_patterns = db.week_patterns_get_all()
+        for (key, wp) in week_patterns.items():
+            if wp.name == name:
+                return key
+        return None
+
+    # Courses without a "Semaines" column (or with an empty one) take place every week
+    def all_weeks_pattern(db):
+        name = "Toutes les semaines"
+        handle = find_week_pattern(db, name)
+        if handle is None:
+            week_count = db.general_data_get().week_count
+            collo_object = collomatique.WeekPattern(name)
+            collo_object.weeks = set(range(week_count))
+            handle = db.week_patterns_create(collo_object)
+        return handle
+
+    def find_subject_group(db, subject_group):
+        subject_groups = db.subject_groups_get_all()
+        for (key, sg) in subject_groups.items():
+            if sg.name == subject_group:
+                return key
+        return None
+
+    def find_subject(db, subject):
+        subjects = db.subjects_get_all()
+        for (key, s) in subjects.items():
+            if s.name == subject:
+                return key
+        return None
+
+    def find_incompat(db, name):
+        incompats = db.incompats_get_all()
+        for (key, i) in incompats.items():
+            if i.name == name:
+                return key
+        return None
+
+    # Each course of a timetable group is a separate incompat group.
+    # With max_count = 0, no colle can overlap any of them.
+    courses = {}
+    for line in timetable_csv.map:
+        day = days[line["Jour"][0].strip().lower()]
+        start = collomatique.SlotStart(day, parse_time(line["Heure"][0]))
+        # IncompatSlot accepts "1h30", "2h" or a number of minutes ("90")
+        duration = line["Durée"][0].strip().replace(":", "h")
+
+        week_pattern_name = line["Semaines"][0].strip() if "Semaines" in line else ""
+        if week_pattern_name == "":
+            week_pattern_handle = all_weeks_pattern(db)
+        else:
+            week_pattern_handle = find_week_pattern(db, week_pattern_name)
+            if week_pattern_handle is None:
+                raise ValueError("Unknown week pattern \"%s\"" % week_pattern_name)
+
+        key = (line["Matière"][0].strip(), line["Groupe"][0].strip())
+        slot = collomatique.IncompatSlot(week_pattern_handle, start, duration)
+        course_slots = courses.setdefault(key, [])
+        if slot not in course_slots:
+            course_slots.append(slot)
+
+    students = db.students_get_all()
+
+    # Groups without a rule are followed by the whole class
+    def follows_group(db, student_handle, group):
+        rules = [rule_map for rule_map in rules_csv.map if rule_map["Groupe"][0] == group]
+        if len(rules) == 0:
+            return True
+        for rule_map in rules:
+            subject_group_handle = find_subject_group(db, rule_map["Groupement"][0])
+            subject_handle = find_subject(db, rule_map["Matière"][0])
+            if subject_group_handle is None or subject_handle is None:
+                continue
+            if db.subject_group_for_student_get(student_handle, subject_group_handle) == subject_handle:
+                return True
+        return False
+
+    for ((subject, group), slots) in courses.items():
+        name = "EDT %s" % subject if group == "" else "EDT %s (%s)" % (subject, group)
+
+        collo_object = collomatique.Incompat(name)
+        collo_object.max_count = 0
+        collo_object.groups = [{slot} for slot in slots]
+
+        incompat_handle = find_incompat(db, name)
+        if incompat_handle is None:
+            incompat_handle = db.incompats_create(collo_object)
+        else:
+            db.incompats_update(incompat_handle, collo_object)
+
+        for student_handle in students:
+            enabled = follows_group(db, student_handle, group)
+            db.incompat_for_student_set(student_handle, incompat_handle, enabled)
+
+rules_csv = collomatique.load_csv("scripts/timetable_rules.csv", has_headers = True)
+import_timetable(db, csv, rules_csv)
@@ -622,6 +622,8 @@ pub struct CostsAdjustments {
     pub interrogations_per_week_range_for_all_students: i32,
     pub balancing: i32,
     pub consecutive_slots: i32,
+    // Names of the kinds of constraints that should only be penalized (with their weight)
+    pub soft_constraints: BTreeMap<String, i32>,
 }
 
 impl Default for CostsAdjustments {
@@ -633,6 +635,7 @@ impl Default for CostsAdjustments {
             interrogations_per_week_range_for_all_students: 1,
             balancing: 1,
             consecutive_slots: 1,
+            soft_constraints: BTreeMap::new(),
         }
     }
 }
@@ -644,6 +647,8 @@ pub struct GeneralData {
     pub week_count: NonZeroU32,
     pub periodicity_cuts: BTreeSet<NonZeroU32>,
     pub costs_adjustments: CostsAdjustments,
+    // Incompatibility slots may cross midnight (for instance boarding school study slots)
+    pub overnight_incompat_slots: bool,
 }
 
 use std::collections::BTreeSet;
@@ -695,6 +700,32 @@ pub struct SlotStart {
     pub time: crate::time::Time,
 }
 
+impl SlotStart {
+    pub fn parse(
+        text: &str,
+        locale: crate::time::Locale,
+    ) -> Result<Self, crate::time::QuickEntryError> {
+        let (day, time) = locale.parse_day_and_time(text)?;
+        Ok(SlotStart { day, time })
+    }
+}
+
+// Every locale is tried in turn, errors are reported for the default locale
+impl std::str::FromStr for SlotStart {
+    type Err = crate::time::QuickEntryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let default_result = SlotStart::parse(s, crate::time::Locale::default());
+        if default_result.is_ok() {
+            return default_result;
+        }
+        crate::time::Locale::ALL
+            .into_iter()
+            .find_map(|locale| SlotStart::parse(s, locale).ok())
+            .ok_or_else(|| default_result.unwrap_err())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct IncompatSlot<WeekPatternId: OrdId> {
     pub week_pattern_id: WeekPatternId,
@@ -13,6 +13,8 @@ pub enum Error {
     RepresentationError(String),
     #[error("json error")]
     JsonError(#[from] serde_json::Error),
+    #[error("io error")]
+    IoError(#[from] std::io::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -25,6 +27,8 @@ pub enum NewError {
     DatabaseAlreadyExists(std::path::PathBuf),
     #[error("sqlx error")]
     SqlxError(#[from] sqlx::Error),
+    #[error("io error")]
+    IoError(#[from] std::io::Error),
 }
 
 pub type NewResult<T> = std::result::Result<T, NewError>;
@@ -35,19 +39,92 @@ pub enum OpenError {
     InvalidPath,
     #[error("Database {0} does not exist")]
     DatabaseDoesNotExist(std::path::PathBuf),
+    #[error("Database uses schema version {found} but only versions up to {supported} are supported (it was probably created with a newer version of collomatique)")]
+    UnsupportedSchemaVersion { found: u32, supported: u32 },
+    #[error("Database does not look like a collomatique database (schema version {version}), missing tables: {}", missing_tables.join(", "))]
+    UnrecognizedSchema {
+        version: u32,
+        missing_tables: Vec<String>,
+    },
+    #[error("Invalid text file: {0}")]
+    InvalidTextFile(String),
     #[error("sqlx error")]
     SqlxError(#[from] sqlx::Error),
+    #[error("io error")]
+    IoError(#[from] std::io::Error),
 }
 
 pub type OpenResult<T> = std::result::Result<T, OpenError>;
 
 use sqlx::sqlite::SqlitePool;
 
-#[derive(Debug)]
+// Cloning a store gives another handle to the same database
+#[derive(Debug, Clone)]
 pub struct Store {
     pool: SqlitePool,
+    applied_migrations: Vec<&'static str>,
+    // Set when the database is kept in memory and saved as a text file
+    text_path: Option<std::path::PathBuf>,
 }
 
+// Schema version stored in the user_version pragma of the database.
+// It must be increased (and a migration added) whenever the tables change.
+pub const SCHEMA_VERSION: u32 = 1;
+
+type MigrationFn =
+    for<'c> fn(&'c mut sqlx::SqliteConnection) -> futures::future::BoxFuture<'c, sqlx::Result<()>>;
+
+struct Migration {
+    // The migration upgrades a database from this version to the next one
+    from: u32,
+    // Tables a database at version `from` must have for the migration to apply
+    expected_tables: &'static [&'static str],
+    description: &'static str,
+    run: MigrationFn,
+}
+
+const VERSION_1_TABLES: &[&str] = &[
+    "incompats",
+    "incompat_groups",
+    "week_patterns",
+    "weeks",
+    "incompat_group_items",
+    "general_data",
+    "teachers",
+    "students",
+    "subject_groups",
+    "group_lists",
+    "subjects",
+    "groupings",
+    "grouping_incompats",
+    "grouping_incompat_items",
+    "time_slots",
+    "grouping_items",
+    "student_incompats",
+    "student_subjects",
+    "groups",
+    "group_list_items",
+    "group_items",
+    "colloscopes",
+    "collo_subjects",
+    "collo_time_slots",
+    "collo_weeks",
+    "collo_groups",
+    "collo_week_items",
+    "collo_group_items",
+    "slot_selections",
+    "slot_groups",
+    "slot_group_items",
+];
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    // An empty or foreign sqlite file also has version 0 and must not be stamped
+    expected_tables: VERSION_1_TABLES,
+    description: "Databases created before schema versioning have the same tables as version 1",
+    run: |_conn| Box::pin(async { Ok(()) }),
+}];
+
 use serde::{Deserialize, Serialize};
 use sqlx::migrate::MigrateDatabase;
 use std::num::NonZeroU32;
@@ -60,6 +137,8 @@ struct CostsAdjustmentsDb {
     interrogations_per_week_range_for_all_students: i32,
     balancing: i32,
     consecutive_slots: i32,
+    #[serde(default)]
+    soft_constraints: BTreeMap<String, i32>,
 }
 
 impl Default for CostsAdjustmentsDb {
@@ -71,6 +150,7 @@ impl Default for CostsAdjustmentsDb {
             interrogations_per_week_range_for_all_students: 1,
             balancing: 1,
             consecutive_slots: 1,
+            soft_constraints: BTreeMap::new(),
         }
     }
 }
@@ -82,6 +162,8 @@ struct GeneralDataDb {
     week_count: NonZeroU32,
     periodicity_cuts: BTreeSet<NonZeroU32>,
     costs_adjustments: CostsAdjustmentsDb,
+    #[serde(default)]
+    overnight_incompat_slots: bool,
 }
 
 impl Store {
@@ -365,10 +447,15 @@ CREATE TABLE "slot_group_items" (
             week_count: NonZeroU32::new(30).unwrap(),
             periodicity_cuts: BTreeSet::new(),
             costs_adjustments: CostsAdjustmentsDb::default(),
+            overnight_incompat_slots: false,
         }).expect("should serialize to valid json"))
         .execute(pool)
         .await?;
 
+        sqlx::query(&format!("PRAGMA user_version = {}", SCHEMA_VERSION))
+            .execute(pool)
+            .await?;
+
         Ok(())
     }
 
@@ -388,7 +475,11 @@ CREATE TABLE "slot_group_items" (
 
         Self::fill_empty_db(&pool).await?;
 
-        Ok(Store { pool })
+        Ok(Store {
+            pool,
+            applied_migrations: vec![],
+            text_path: None,
+        })
     }
 
     pub async fn open_db(path: &std::path::Path) -> OpenResult<Self> {
@@ -402,10 +493,82 @@ CREATE TABLE "slot_group_items" (
         use std::str::FromStr;
         let options =
             SqliteConnectOptions::from_str(&db_url)?.journal_mode(SqliteJournalMode::Delete);
+        let pool = SqlitePool::connect_with(options).await?;
+
+        let applied_migrations = Self::migrate(&pool).await?;
+
         Ok(Store {
-            pool: SqlitePool::connect_with(options).await?,
+            pool,
+            applied_migrations,
+            text_path: None,
         })
     }
+
+    async fn schema_version(pool: &SqlitePool) -> sqlx::Result<u32> {
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(pool)
+            .await?;
+        Ok(version as u32)
+    }
+
+    async fn missing_tables(pool: &SqlitePool, tables: &[&str]) -> sqlx::Result<Vec<String>> {
+        let existing_tables: BTreeSet<String> =
+            sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table'")
+                .fetch_all(pool)
+                .await?
+                .into_iter()
+                .collect();
+        Ok(tables
+            .iter()
+            .filter(|table| !existing_tables.contains(**table))
+            .map(|table| table.to_string())
+            .collect())
+    }
+
+    // Brings the database to the current schema version.
+    // Returns the description of the migrations that were applied.
+    async fn migrate(pool: &SqlitePool) -> OpenResult<Vec<&'static str>> {
+        let version = Self::schema_version(pool).await?;
+        if version > SCHEMA_VERSION {
+            return Err(OpenError::UnsupportedSchemaVersion {
+                found: version,
+                supported: SCHEMA_VERSION,
+            });
+        }
+        if version == SCHEMA_VERSION {
+            return Ok(vec![]);
+        }
+
+        let first_migration = MIGRATIONS
+            .iter()
+            .find(|m| m.from == version)
+            .expect("There should be a migration for every version before SCHEMA_VERSION");
+        let missing_tables = Self::missing_tables(pool, first_migration.expected_tables).await?;
+        if !missing_tables.is_empty() {
+            return Err(OpenError::UnrecognizedSchema {
+                version,
+                missing_tables,
+            });
+        }
+
+        let mut applied_migrations = vec![];
+        let mut transaction = pool.begin().await?;
+        for migration in MIGRATIONS.iter().filter(|m| m.from >= version) {
+            (migration.run)(&mut transaction).await?;
+            applied_migrations.push(migration.description);
+        }
+        sqlx::query(&format!("PRAGMA user_version = {}", SCHEMA_VERSION))
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+
+        Ok(applied_migrations)
+    }
+
+    // Migrations applied when the database was opened
+    pub fn applied_migrations(&self) -> &[&'static str] {
+        &self.applied_migrations
+    }
 }
 
 use super::*;
@@ -422,6 +585,7 @@ mod subject_group_for_student;
 mod subject_groups;
 mod subjects;
 mod teachers;
+mod text;
 mod time_slots;
 mod week_patterns;
 
@@ -465,7 +629,9 @@ impl Storage for Store {
                     .interrogations_per_week_range_for_all_students,
                 balancing: general_data.costs_adjustments.balancing,
                 consecutive_slots: general_data.costs_adjustments.consecutive_slots,
+                soft_constraints: general_data.costs_adjustments.soft_constraints.clone(),
             },
+            overnight_incompat_slots: general_data.overnight_incompat_slots,
         };
 
         let mut conn = self.pool.acquire().await.map_err(Error::from)?;
@@ -532,7 +698,9 @@ impl Storage for Store {
                     .interrogations_per_week_range_for_all_students,
                 balancing: general_data_json.costs_adjustments.balancing,
                 consecutive_slots: general_data_json.costs_adjustments.consecutive_slots,
+                soft_constraints: general_data_json.costs_adjustments.soft_constraints,
             },
+            overnight_incompat_slots: general_data_json.overnight_incompat_slots,
         };
 
         Ok(general_data)
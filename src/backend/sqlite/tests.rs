@@ -2,7 +2,11 @@ use super::*;
 
 async fn prepare_empty_db(pool: sqlx::SqlitePool) -> Store {
     Store::fill_empty_db(&pool).await.unwrap();
-    Store { pool }
+    Store {
+        pool,
+        applied_migrations: vec![],
+        text_path: None,
+    }
 }
 
 mod colloscopes;
@@ -16,6 +20,7 @@ mod subject_group_for_student;
 mod subject_groups;
 mod subjects;
 mod teachers;
+mod text;
 mod time_slots;
 mod week_patterns;
 
@@ -31,6 +36,7 @@ async fn general_data_get_1(pool: SqlitePool) {
         week_count: NonZeroU32::new(30).unwrap(),
         periodicity_cuts: BTreeSet::new(),
         costs_adjustments: CostsAdjustments::default(),
+        overnight_incompat_slots: false,
     };
 
     assert_eq!(general_data, general_data_expected);
@@ -59,6 +65,7 @@ WHERE id = 1
         week_count: NonZeroU32::new(25).unwrap(),
         periodicity_cuts: BTreeSet::from([NonZeroU32::new(10).unwrap()]),
         costs_adjustments: CostsAdjustments::default(),
+        overnight_incompat_slots: false,
     };
 
     assert_eq!(general_data, general_data_expected);
@@ -93,7 +100,10 @@ WHERE id = 1
             interrogations_per_week_range_for_all_students: 2,
             balancing: 4,
             consecutive_slots: 6,
+            soft_constraints: BTreeMap::new(),
         },
+        // Missing in databases written before the setting existed
+        overnight_incompat_slots: false,
     };
 
     assert_eq!(general_data, general_data_expected);
@@ -110,6 +120,7 @@ async fn general_data_set(pool: SqlitePool) {
             week_count: NonZeroU32::new(25).unwrap(),
             periodicity_cuts: BTreeSet::from([NonZeroU32::new(10).unwrap()]),
             costs_adjustments: CostsAdjustments::default(),
+            overnight_incompat_slots: true,
         })
     }
     .await
@@ -123,7 +134,76 @@ async fn general_data_set(pool: SqlitePool) {
         week_count: NonZeroU32::new(25).unwrap(),
         periodicity_cuts: BTreeSet::from([NonZeroU32::new(10).unwrap()]),
         costs_adjustments: CostsAdjustments::default(),
+        overnight_incompat_slots: true,
     };
 
     assert_eq!(general_data, general_data_expected);
 }
+
+#[sqlx::test]
+async fn new_db_has_current_schema_version(pool: SqlitePool) {
+    let store = prepare_empty_db(pool).await;
+
+    let version = Store::schema_version(&store.pool).await.unwrap();
+    assert_eq!(version, SCHEMA_VERSION);
+
+    let applied_migrations = Store::migrate(&store.pool).await.unwrap();
+    assert!(applied_migrations.is_empty());
+}
+
+#[sqlx::test]
+async fn unversioned_db_is_migrated(pool: SqlitePool) {
+    let store = prepare_empty_db(pool).await;
+    sqlx::query("PRAGMA user_version = 0")
+        .execute(&store.pool)
+        .await
+        .unwrap();
+
+    let applied_migrations = Store::migrate(&store.pool).await.unwrap();
+    assert_eq!(applied_migrations.len(), MIGRATIONS.len());
+
+    let version = Store::schema_version(&store.pool).await.unwrap();
+    assert_eq!(version, SCHEMA_VERSION);
+}
+
+#[sqlx::test]
+async fn foreign_db_is_not_stamped(pool: SqlitePool) {
+    sqlx::query("CREATE TABLE \"teachers\" (\"teacher_id\" INTEGER NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let result = Store::migrate(&pool).await;
+    assert!(matches!(
+        result,
+        Err(OpenError::UnrecognizedSchema { version: 0, ref missing_tables })
+            if missing_tables.len() == VERSION_1_TABLES.len() - 1
+                && !missing_tables.contains(&String::from("teachers"))
+    ));
+
+    let version = Store::schema_version(&pool).await.unwrap();
+    assert_eq!(version, 0);
+}
+
+#[sqlx::test]
+async fn newer_db_is_rejected(pool: SqlitePool) {
+    let store = prepare_empty_db(pool).await;
+    sqlx::query(&format!("PRAGMA user_version = {}", SCHEMA_VERSION + 1))
+        .execute(&store.pool)
+        .await
+        .unwrap();
+
+    let result = Store::migrate(&store.pool).await;
+    assert!(matches!(
+        result,
+        Err(OpenError::UnsupportedSchemaVersion { found, supported })
+            if found == SCHEMA_VERSION + 1 && supported == SCHEMA_VERSION
+    ));
+}
+
+#[test]
+fn migrations_cover_every_version() {
+    let froms: Vec<_> = MIGRATIONS.iter().map(|migration| migration.from).collect();
+    let expected: Vec<_> = (0..SCHEMA_VERSION).collect();
+    assert_eq!(froms, expected);
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+use crate::backend::sqlite::text::TextFile;
+use serde_json::{Map, Value};
+
+#[sqlx::test]
+async fn text_dump_roundtrip(pool: sqlx::SqlitePool) {
+    let mut store = prepare_empty_db(pool).await;
+
+    for (surname, firstname) in [("Martin", "Alice"), ("Durand", "Bernard")] {
+        store
+            .teachers_add(&Teacher {
+                surname: String::from(surname),
+                firstname: String::from(firstname),
+                contact: String::new(),
+            })
+            .await
+            .unwrap();
+    }
+    let text_file = Store::dump_text(&store.pool).await.unwrap();
+
+    let other_pool = Store::memory_pool().await.unwrap();
+    Store::fill_empty_db(&other_pool).await.unwrap();
+    Store::load_text(&other_pool, &text_file).await.unwrap();
+    let other_text_file = Store::dump_text(&other_pool).await.unwrap();
+
+    assert_eq!(text_file, other_text_file);
+    assert_eq!(text_file.tables["teachers"].len(), 2);
+    assert_eq!(text_file.tables["general_data"].len(), 1);
+}
+
+#[sqlx::test]
+async fn text_dump_is_sorted(pool: sqlx::SqlitePool) {
+    let mut store = prepare_empty_db(pool).await;
+
+    let id = store
+        .teachers_add(&Teacher {
+            surname: String::from("Martin"),
+            firstname: String::from("Alice"),
+            contact: String::new(),
+        })
+        .await
+        .unwrap();
+    store
+        .teachers_add(&Teacher {
+            surname: String::from("Durand"),
+            firstname: String::from("Bernard"),
+            contact: String::new(),
+        })
+        .await
+        .unwrap();
+    // Updated rows must not move
+    store
+        .teachers_update(
+            id,
+            &Teacher {
+                surname: String::from("Martin"),
+                firstname: String::from("Alice"),
+                contact: String::from("alice@example.com"),
+            },
+        )
+        .await
+        .unwrap();
+
+    let text = store.to_text().await.unwrap();
+    let martin = text.find("Martin").unwrap();
+    let durand = text.find("Durand").unwrap();
+    assert!(martin < durand);
+    assert!(text.find("\"colloscopes\"").unwrap() < text.find("\"teachers\"").unwrap());
+    assert!(text.ends_with("}\n"));
+}
+
+#[sqlx::test]
+async fn text_file_with_unknown_table_is_rejected(pool: sqlx::SqlitePool) {
+    Store::fill_empty_db(&pool).await.unwrap();
+
+    let text_file = TextFile {
+        schema_version: SCHEMA_VERSION,
+        tables: BTreeMap::from([(String::from("unknown"), vec![])]),
+    };
+    let result = Store::load_text(&pool, &text_file).await;
+    assert!(matches!(result, Err(OpenError::InvalidTextFile(_))));
+}
+
+#[sqlx::test]
+async fn text_file_with_unknown_column_is_rejected(pool: sqlx::SqlitePool) {
+    Store::fill_empty_db(&pool).await.unwrap();
+
+    let row = Map::from_iter([(
+        String::from("surname\" TEXT); DROP TABLE teachers; --"),
+        Value::from("Martin"),
+    )]);
+    let text_file = TextFile {
+        schema_version: SCHEMA_VERSION,
+        tables: BTreeMap::from([(String::from("teachers"), vec![row])]),
+    };
+    let result = Store::load_text(&pool, &text_file).await;
+    assert!(matches!(result, Err(OpenError::InvalidTextFile(_))));
+}
+
+#[sqlx::test]
+async fn text_reload_does_not_reuse_ids(pool: sqlx::SqlitePool) {
+    let mut store = prepare_empty_db(pool).await;
+
+    sqlx::query(
+        r#"
+INSERT INTO teachers (surname, firstname, contact)
+VALUES ("Durand", "Bernard", ""), ("Dupont", "Leonard", "");
+        "#,
+    )
+    .execute(&store.pool)
+    .await
+    .unwrap();
+    unsafe {
+        store
+            .teachers_remove_unchecked(super::super::teachers::Id(2))
+            .await
+            .unwrap();
+    }
+    let text_file = Store::dump_text(&store.pool).await.unwrap();
+
+    let other_pool = Store::memory_pool().await.unwrap();
+    Store::fill_empty_db(&other_pool).await.unwrap();
+    Store::load_text(&other_pool, &text_file).await.unwrap();
+    let mut other_store = Store {
+        pool: other_pool,
+        applied_migrations: vec![],
+        text_path: None,
+    };
+
+    let id = other_store
+        .teachers_add(&Teacher {
+            surname: String::from("Tessier"),
+            firstname: String::from("Lucie"),
+            contact: String::new(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(id, super::super::teachers::Id(3));
+}
@@ -0,0 +1,275 @@
+use super::*;
+
+use serde_json::{Map, Value};
+use sqlx::{Column, Row, TypeInfo, ValueRef};
+use std::collections::BTreeMap;
+
+// Canonical text representation of the database: every table with its rows.
+// Tables and columns are sorted by name (serde_json maps are sorted)
+// and rows by the values of their columns, so saving the same data
+// always produces the same bytes.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub(super) struct TextFile {
+    pub(super) schema_version: u32,
+    pub(super) tables: BTreeMap<String, Vec<Map<String, Value>>>,
+}
+
+impl Store {
+    // Text files are loaded in an in-memory database.
+    // The pool must keep its only connection alive, otherwise the data is lost.
+    pub(super) async fn memory_pool() -> sqlx::Result<SqlitePool> {
+        use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let options = SqliteConnectOptions::from_str("sqlite::memory:")?;
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await
+    }
+
+    // sqlite_sequence is kept so that ids of deleted rows are not reused after a reload
+    async fn table_names(pool: &SqlitePool) -> sqlx::Result<Vec<String>> {
+        sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND (name NOT LIKE 'sqlite_%' OR name = 'sqlite_sequence') ORDER BY name",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    async fn column_names(
+        transaction: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        table: &str,
+    ) -> sqlx::Result<Vec<String>> {
+        sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+            .bind(table)
+            .fetch_all(&mut **transaction)
+            .await
+    }
+
+    fn column_to_json(row: &sqlx::sqlite::SqliteRow, index: usize) -> Result<Value> {
+        let raw = row.try_get_raw(index)?;
+        if raw.is_null() {
+            return Ok(Value::Null);
+        }
+        let value = match raw.type_info().name() {
+            "INTEGER" => Value::from(row.try_get::<i64, _>(index)?),
+            "TEXT" => Value::from(row.try_get::<String, _>(index)?),
+            type_name => {
+                return Err(Error::RepresentationError(format!(
+                    "Column type {} cannot be written to a text file",
+                    type_name
+                )))
+            }
+        };
+        Ok(value)
+    }
+
+    pub(super) async fn dump_text(pool: &SqlitePool) -> Result<TextFile> {
+        let mut tables = BTreeMap::new();
+        for table in Self::table_names(pool).await? {
+            let column_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info(?)")
+                .bind(&table)
+                .fetch_one(pool)
+                .await?;
+            let order_by = (1..=column_count)
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let rows = sqlx::query(&format!(
+                r#"SELECT * FROM "{}" ORDER BY {}"#,
+                table, order_by
+            ))
+            .fetch_all(pool)
+            .await?;
+
+            let mut json_rows = Vec::with_capacity(rows.len());
+            for row in &rows {
+                let mut json_row = Map::new();
+                for column in row.columns() {
+                    json_row.insert(
+                        column.name().to_string(),
+                        Self::column_to_json(row, column.ordinal())?,
+                    );
+                }
+                json_rows.push(json_row);
+            }
+            tables.insert(table, json_rows);
+        }
+
+        Ok(TextFile {
+            schema_version: Self::schema_version(pool).await?,
+            tables,
+        })
+    }
+
+    pub(super) async fn load_text(pool: &SqlitePool, text_file: &TextFile) -> OpenResult<()> {
+        let known_tables = Self::table_names(pool).await?;
+
+        let mut transaction = pool.begin().await?;
+        // Tables are filled in alphabetical order, not in dependency order
+        sqlx::query("PRAGMA defer_foreign_keys = ON")
+            .execute(&mut *transaction)
+            .await?;
+
+        for table in &known_tables {
+            sqlx::query(&format!(r#"DELETE FROM "{}""#, table))
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        // Inserting rows updates sqlite_sequence so it must be restored last.
+        // Files without it get the largest ids back as sequence values.
+        let (sequences, tables): (Vec<_>, Vec<_>) = text_file
+            .tables
+            .iter()
+            .partition(|(table, _rows)| table.as_str() == "sqlite_sequence");
+
+        for (table, rows) in tables.into_iter().chain(sequences) {
+            if !known_tables.contains(table) {
+                return Err(OpenError::InvalidTextFile(format!(
+                    "unknown table \"{}\"",
+                    table
+                )));
+            }
+            let known_columns = Self::column_names(&mut transaction, table).await?;
+            for row in rows {
+                if let Some(column) = row.keys().find(|column| !known_columns.contains(column)) {
+                    return Err(OpenError::InvalidTextFile(format!(
+                        "unknown column \"{}\" in table \"{}\"",
+                        column, table
+                    )));
+                }
+                if table == "sqlite_sequence" {
+                    // Rows might already exist for tables filled above
+                    sqlx::query("DELETE FROM sqlite_sequence WHERE name = ?")
+                        .bind(row.get("name").and_then(Value::as_str))
+                        .execute(&mut *transaction)
+                        .await?;
+                }
+
+                let columns = row
+                    .keys()
+                    .map(|column| format!(r#""{}""#, column))
+                    .collect::<Vec<_>>();
+                let placeholders = vec!["?"; columns.len()];
+                let sql = format!(
+                    r#"INSERT INTO "{}" ({}) VALUES ({})"#,
+                    table,
+                    columns.join(", "),
+                    placeholders.join(", ")
+                );
+
+                let mut query = sqlx::query(&sql);
+                for value in row.values() {
+                    query = match value {
+                        Value::Null => query.bind(None::<i64>),
+                        Value::Number(n) => match n.as_i64() {
+                            Some(n) => query.bind(n),
+                            None => {
+                                return Err(OpenError::InvalidTextFile(format!(
+                                    "{} is not a valid integer in table \"{}\"",
+                                    n, table
+                                )))
+                            }
+                        },
+                        Value::String(s) => query.bind(s.clone()),
+                        _ => {
+                            return Err(OpenError::InvalidTextFile(format!(
+                                "unexpected value {} in table \"{}\"",
+                                value, table
+                            )))
+                        }
+                    };
+                }
+                query.execute(&mut *transaction).await?;
+            }
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    pub async fn new_text(path: &std::path::Path) -> NewResult<Self> {
+        if path.exists() {
+            return Err(NewError::DatabaseAlreadyExists(path.to_path_buf()));
+        }
+
+        let pool = Self::memory_pool().await?;
+        Self::fill_empty_db(&pool).await?;
+
+        let store = Store {
+            pool,
+            applied_migrations: vec![],
+            text_path: Some(path.to_path_buf()),
+        };
+        store.save().await.map_err(|e| match e {
+            Error::SqlxError(e) => NewError::SqlxError(e),
+            Error::IoError(e) => NewError::IoError(e),
+            e => NewError::IoError(std::io::Error::other(e)),
+        })?;
+
+        Ok(store)
+    }
+
+    pub async fn open_text(path: &std::path::Path) -> OpenResult<Self> {
+        if !path.exists() {
+            return Err(OpenError::DatabaseDoesNotExist(path.to_path_buf()));
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        let text_file: TextFile = serde_json::from_str(&content)
+            .map_err(|e| OpenError::InvalidTextFile(e.to_string()))?;
+        // Text files appeared with schema version 1 and are only written at the
+        // current version. A schema change must convert the rows of older files here.
+        if text_file.schema_version != SCHEMA_VERSION {
+            return Err(OpenError::UnsupportedSchemaVersion {
+                found: text_file.schema_version,
+                supported: SCHEMA_VERSION,
+            });
+        }
+
+        let pool = Self::memory_pool().await?;
+        Self::fill_empty_db(&pool).await?;
+        Self::load_text(&pool, &text_file).await?;
+
+        Ok(Store {
+            pool,
+            applied_migrations: vec![],
+            text_path: Some(path.to_path_buf()),
+        })
+    }
+
+    // Canonical text representation of the current content
+    pub async fn to_text(&self) -> Result<String> {
+        let text_file = Self::dump_text(&self.pool).await?;
+        let mut text = serde_json::to_string_pretty(&text_file)?;
+        text.push('\n');
+        Ok(text)
+    }
+
+    // Writes the content back to the text file (if the store was opened from one).
+    // Sqlite files are already up to date as every operation is applied directly.
+    pub async fn save(&self) -> Result<()> {
+        let Some(path) = &self.text_path else {
+            return Ok(());
+        };
+
+        let text = self.to_text().await?;
+        if std::fs::read_to_string(path).ok().as_deref() == Some(text.as_str()) {
+            return Ok(());
+        }
+
+        // Write to a temporary file first so that a crash never leaves a truncated file
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        std::fs::write(&tmp_path, text)?;
+        std::fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+}
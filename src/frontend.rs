@@ -1,4 +1,10 @@
+pub mod conflicts;
 pub mod csv;
+pub mod html;
+pub mod ical;
+pub mod interrogations;
+pub mod mailmerge;
+pub mod print;
 pub mod python;
 pub mod shell;
 pub mod state;
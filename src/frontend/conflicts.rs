@@ -0,0 +1,302 @@
+use super::state::{
+    GroupListHandle, IncompatHandle, StudentHandle, SubjectGroupHandle, SubjectHandle,
+    TeacherHandle, WeekPatternHandle,
+};
+use crate::backend;
+use crate::time::{Locale, SlotSet};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(test)]
+mod tests;
+
+// One group interrogated in a time slot of a subject
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InterrogationRef {
+    pub subject: SubjectHandle,
+    pub time_slot: usize,
+    pub group: usize,
+}
+
+// Weeks are 0-based
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Conflict {
+    TeacherDoubleBooked {
+        teacher: TeacherHandle,
+        week: u32,
+        first: InterrogationRef,
+        second: InterrogationRef,
+    },
+    StudentDoubleBooked {
+        student: StudentHandle,
+        week: u32,
+        first: InterrogationRef,
+        second: InterrogationRef,
+    },
+    IncompatViolated {
+        student: StudentHandle,
+        incompat: IncompatHandle,
+        week: u32,
+        count: usize,
+        max_count: usize,
+    },
+}
+
+struct Scheduled {
+    interrogation: InterrogationRef,
+    teacher: TeacherHandle,
+    week: u32,
+    slot: SlotSet,
+    students: Vec<StudentHandle>,
+}
+
+// Incompatibilities a student is subject to: their own registrations
+// and the incompatibilities of the subjects they are interrogated in
+fn incompats_for_student(
+    student: StudentHandle,
+    scheduled: &[Scheduled],
+    subjects: &BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    registrations: &BTreeMap<StudentHandle, BTreeSet<IncompatHandle>>,
+) -> BTreeSet<IncompatHandle> {
+    let mut output = registrations.get(&student).cloned().unwrap_or_default();
+    for item in scheduled {
+        if !item.students.contains(&student) {
+            continue;
+        }
+        let incompat = subjects
+            .get(&item.interrogation.subject)
+            .and_then(|subject| subject.incompat_id);
+        output.extend(incompat);
+    }
+    output
+}
+
+// Lists double bookings (a teacher or a student in two overlapping interrogations
+// of the same week) and incompatibilities hit more often than allowed.
+// This is meant to be run again after each edit of a stored colloscope,
+// so that problems show up without waiting for the solver.
+pub fn find_conflicts(
+    colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    subjects: &BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    incompats: &BTreeMap<IncompatHandle, backend::Incompat<WeekPatternHandle>>,
+    week_patterns: &BTreeMap<WeekPatternHandle, backend::WeekPattern>,
+    registrations: &BTreeMap<StudentHandle, BTreeSet<IncompatHandle>>,
+) -> BTreeSet<Conflict> {
+    let mut scheduled = Vec::new();
+    for interrogation in super::interrogations::flatten(colloscope) {
+        let Some(subject) = subjects.get(&interrogation.subject_handle) else {
+            continue;
+        };
+        scheduled.push(Scheduled {
+            interrogation: InterrogationRef {
+                subject: interrogation.subject_handle,
+                time_slot: interrogation.time_slot_index,
+                group: interrogation.group,
+            },
+            teacher: interrogation.time_slot.teacher_id,
+            week: interrogation.week,
+            slot: SlotSet::new().with_slot(
+                interrogation.time_slot.start.day,
+                &interrogation.time_slot.start.time,
+                subject.duration.get(),
+            ),
+            students: interrogation.students,
+        });
+    }
+
+    let mut conflicts = BTreeSet::new();
+
+    for (i, first) in scheduled.iter().enumerate() {
+        for second in &scheduled[i + 1..] {
+            if first.week != second.week || !first.slot.overlaps_set(&second.slot) {
+                continue;
+            }
+
+            // Several groups in the same time slot are allowed (max_groups_per_slot)
+            let same_time_slot = first.interrogation.subject == second.interrogation.subject
+                && first.interrogation.time_slot == second.interrogation.time_slot;
+            if first.teacher == second.teacher && !same_time_slot {
+                conflicts.insert(Conflict::TeacherDoubleBooked {
+                    teacher: first.teacher,
+                    week: first.week,
+                    first: first.interrogation,
+                    second: second.interrogation,
+                });
+            }
+            for student in &first.students {
+                if second.students.contains(student) {
+                    conflicts.insert(Conflict::StudentDoubleBooked {
+                        student: *student,
+                        week: first.week,
+                        first: first.interrogation,
+                        second: second.interrogation,
+                    });
+                }
+            }
+        }
+    }
+
+    let students: BTreeSet<_> = scheduled
+        .iter()
+        .flat_map(|item| item.students.iter().copied())
+        .chain(registrations.keys().copied())
+        .collect();
+    let is_in_pattern = |handle: &WeekPatternHandle, week: u32| {
+        week_patterns
+            .get(handle)
+            .map(|pattern| pattern.weeks.contains(&backend::Week::new(week)))
+            .unwrap_or(false)
+    };
+
+    for student in students {
+        let student_scheduled: Vec<_> = scheduled
+            .iter()
+            .filter(|item| item.students.contains(&student))
+            .collect();
+        let weeks: BTreeSet<_> = student_scheduled.iter().map(|item| item.week).collect();
+
+        for incompat_handle in incompats_for_student(student, &scheduled, subjects, registrations) {
+            let Some(incompat) = incompats.get(&incompat_handle) else {
+                continue;
+            };
+
+            for &week in &weeks {
+                // Same rule as the generator: a group applies on a week if one of its slots does
+                let count = incompat
+                    .groups
+                    .iter()
+                    .filter(|group| {
+                        group
+                            .slots
+                            .iter()
+                            .any(|slot| is_in_pattern(&slot.week_pattern_id, week))
+                    })
+                    .filter(|group| {
+                        group.slots.iter().any(|slot| {
+                            student_scheduled.iter().any(|item| {
+                                item.week == week
+                                    && item.slot.overlaps(
+                                        &slot.start.day,
+                                        &slot.start.time,
+                                        slot.duration.get(),
+                                    )
+                            })
+                        })
+                    })
+                    .count();
+
+                if count > incompat.max_count {
+                    conflicts.insert(Conflict::IncompatViolated {
+                        student,
+                        incompat: incompat_handle,
+                        week,
+                        count,
+                        max_count: incompat.max_count,
+                    });
+                }
+            }
+        }
+    }
+
+    conflicts
+}
+
+impl Conflict {
+    pub fn describe(
+        &self,
+        colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+        teachers: &BTreeMap<TeacherHandle, backend::Teacher>,
+        subjects: &BTreeMap<
+            SubjectHandle,
+            backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+        >,
+        students: &BTreeMap<StudentHandle, backend::Student>,
+        incompats: &BTreeMap<IncompatHandle, backend::Incompat<WeekPatternHandle>>,
+        locale: Locale,
+    ) -> String {
+        let interrogation = |interrogation: &InterrogationRef| -> String {
+            let subject_name = subjects
+                .get(&interrogation.subject)
+                .map(|subject| subject.name.as_str())
+                .unwrap_or("?");
+            let collo_subject = colloscope.subjects.get(&interrogation.subject);
+            let group_name = collo_subject
+                .and_then(|subject| subject.group_list.groups.get(interrogation.group))
+                .map(String::as_str)
+                .unwrap_or("?");
+            let slot = collo_subject
+                .and_then(|subject| subject.time_slots.get(interrogation.time_slot))
+                .map(|time_slot| {
+                    format!(
+                        "{} {}",
+                        locale.weekday_name(time_slot.start.day),
+                        locale.format_time(&time_slot.start.time)
+                    )
+                })
+                .unwrap_or_default();
+            format!("{} {} ({})", subject_name, slot, group_name)
+        };
+        let teacher_name = |handle: &TeacherHandle| {
+            teachers
+                .get(handle)
+                .map(|teacher| format!("{} {}", teacher.firstname, teacher.surname))
+                .unwrap_or_default()
+        };
+        let student_name = |handle: &StudentHandle| {
+            students
+                .get(handle)
+                .map(|student| format!("{} {}", student.firstname, student.surname))
+                .unwrap_or_default()
+        };
+
+        match self {
+            Conflict::TeacherDoubleBooked {
+                teacher,
+                week,
+                first,
+                second,
+            } => format!(
+                "Week {}: {} has two interrogations at the same time: {} and {}",
+                week + 1,
+                teacher_name(teacher),
+                interrogation(first),
+                interrogation(second)
+            ),
+            Conflict::StudentDoubleBooked {
+                student,
+                week,
+                first,
+                second,
+            } => format!(
+                "Week {}: {} has two interrogations at the same time: {} and {}",
+                week + 1,
+                student_name(student),
+                interrogation(first),
+                interrogation(second)
+            ),
+            Conflict::IncompatViolated {
+                student,
+                incompat,
+                week,
+                count,
+                max_count,
+            } => format!(
+                "Week {}: interrogations of {} overlap incompatibility \"{}\" {} time(s) (at most {} allowed)",
+                week + 1,
+                student_name(student),
+                incompats
+                    .get(incompat)
+                    .map(|incompat| incompat.name.as_str())
+                    .unwrap_or("?"),
+                count,
+                max_count
+            ),
+        }
+    }
+}
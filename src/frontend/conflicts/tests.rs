@@ -0,0 +1,267 @@
+use super::*;
+
+use crate::time::{Time, Weekday};
+
+use std::num::{NonZeroU32, NonZeroUsize};
+
+fn start(day: Weekday, hour: u32, min: u32) -> backend::SlotStart {
+    backend::SlotStart {
+        day,
+        time: Time::from_hm(hour, min).unwrap(),
+    }
+}
+
+fn subject(
+    incompat_id: Option<IncompatHandle>,
+) -> backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle> {
+    backend::Subject {
+        name: String::from("Subject"),
+        subject_group_id: SubjectGroupHandle::from_index(0),
+        incompat_id,
+        group_list_id: None,
+        duration: NonZeroU32::new(60).unwrap(),
+        students_per_group: NonZeroUsize::new(1).unwrap()..=NonZeroUsize::new(3).unwrap(),
+        period: NonZeroU32::new(1).unwrap(),
+        period_is_strict: false,
+        is_tutorial: false,
+        max_groups_per_slot: NonZeroUsize::new(2).unwrap(),
+        balancing_requirements: backend::BalancingRequirements {
+            constraints: backend::BalancingConstraints::OptimizeOnly,
+            slot_selections: backend::BalancingSlotSelections::TeachersAndTimeSlots,
+        },
+    }
+}
+
+fn time_slot(
+    teacher: usize,
+    start: backend::SlotStart,
+    assignments: &[(u32, &[usize])],
+) -> backend::ColloscopeTimeSlot<TeacherHandle> {
+    backend::ColloscopeTimeSlot {
+        teacher_id: TeacherHandle::from_index(teacher),
+        start,
+        room: String::new(),
+        group_assignments: assignments
+            .iter()
+            .map(|(week, groups)| (backend::Week::new(*week), groups.iter().copied().collect()))
+            .collect(),
+    }
+}
+
+// students gives the group of each student
+fn collo_subject(
+    time_slots: Vec<backend::ColloscopeTimeSlot<TeacherHandle>>,
+    students: &[(usize, usize)],
+) -> backend::ColloscopeSubject<TeacherHandle, StudentHandle> {
+    backend::ColloscopeSubject {
+        time_slots,
+        group_list: backend::ColloscopeGroupList {
+            name: String::from("Groups"),
+            groups: vec![String::from("1"), String::from("2")],
+            students_mapping: students
+                .iter()
+                .map(|(student, group)| (StudentHandle::from_index(*student), *group))
+                .collect(),
+        },
+    }
+}
+
+fn colloscope(
+    subjects: Vec<backend::ColloscopeSubject<TeacherHandle, StudentHandle>>,
+) -> backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle> {
+    backend::Colloscope {
+        name: String::from("Colloscope"),
+        subjects: subjects
+            .into_iter()
+            .enumerate()
+            .map(|(i, subject)| (SubjectHandle::from_index(i), subject))
+            .collect(),
+    }
+}
+
+fn interrogation(subject: usize, time_slot: usize, group: usize) -> InterrogationRef {
+    InterrogationRef {
+        subject: SubjectHandle::from_index(subject),
+        time_slot,
+        group,
+    }
+}
+
+fn conflicts_without_incompats(
+    colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+) -> BTreeSet<Conflict> {
+    let subjects = colloscope
+        .subjects
+        .keys()
+        .map(|handle| (*handle, subject(None)))
+        .collect();
+    find_conflicts(
+        colloscope,
+        &subjects,
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+}
+
+#[test]
+fn teacher_double_booked() {
+    let colloscope = colloscope(vec![
+        collo_subject(
+            vec![time_slot(0, start(Weekday::Monday, 8, 0), &[(0, &[0])])],
+            &[(0, 0)],
+        ),
+        collo_subject(
+            vec![time_slot(0, start(Weekday::Monday, 8, 30), &[(0, &[0])])],
+            &[(1, 0)],
+        ),
+    ]);
+
+    assert_eq!(
+        conflicts_without_incompats(&colloscope),
+        BTreeSet::from([Conflict::TeacherDoubleBooked {
+            teacher: TeacherHandle::from_index(0),
+            week: 0,
+            first: interrogation(0, 0, 0),
+            second: interrogation(1, 0, 0),
+        }])
+    );
+}
+
+#[test]
+fn student_double_booked() {
+    let colloscope = colloscope(vec![
+        collo_subject(
+            vec![time_slot(0, start(Weekday::Monday, 8, 0), &[(2, &[0])])],
+            &[(0, 0), (1, 0)],
+        ),
+        collo_subject(
+            vec![time_slot(1, start(Weekday::Monday, 8, 30), &[(2, &[1])])],
+            &[(0, 1), (2, 1)],
+        ),
+    ]);
+
+    assert_eq!(
+        conflicts_without_incompats(&colloscope),
+        BTreeSet::from([Conflict::StudentDoubleBooked {
+            student: StudentHandle::from_index(0),
+            week: 2,
+            first: interrogation(0, 0, 0),
+            second: interrogation(1, 0, 1),
+        }])
+    );
+}
+
+#[test]
+fn consecutive_interrogations_do_not_conflict() {
+    let colloscope = colloscope(vec![
+        collo_subject(
+            vec![
+                time_slot(0, start(Weekday::Monday, 8, 0), &[(0, &[0])]),
+                time_slot(0, start(Weekday::Tuesday, 8, 0), &[(0, &[0])]),
+            ],
+            &[(0, 0)],
+        ),
+        collo_subject(
+            vec![
+                time_slot(0, start(Weekday::Monday, 9, 0), &[(0, &[0])]),
+                time_slot(0, start(Weekday::Tuesday, 8, 0), &[(1, &[0])]),
+            ],
+            &[(0, 0)],
+        ),
+    ]);
+
+    assert!(conflicts_without_incompats(&colloscope).is_empty());
+}
+
+#[test]
+fn groups_sharing_a_time_slot_do_not_conflict() {
+    // Several groups in the same slot are allowed by max_groups_per_slot
+    let colloscope = colloscope(vec![collo_subject(
+        vec![time_slot(0, start(Weekday::Monday, 8, 0), &[(0, &[0, 1])])],
+        &[(0, 0), (1, 1)],
+    )]);
+
+    assert!(conflicts_without_incompats(&colloscope).is_empty());
+}
+
+#[test]
+fn incompat_violated() {
+    let incompat_handle = IncompatHandle::from_index(0);
+    let week_pattern_handle = WeekPatternHandle::from_index(0);
+
+    let colloscope = colloscope(vec![
+        // Student 0 is registered to the incompatibility
+        collo_subject(
+            vec![time_slot(
+                0,
+                start(Weekday::Monday, 8, 0),
+                &[(0, &[0]), (1, &[0])],
+            )],
+            &[(0, 0)],
+        ),
+        // Student 1 is subject to it through the subject
+        collo_subject(
+            vec![time_slot(1, start(Weekday::Monday, 7, 0), &[(0, &[0])])],
+            &[(1, 0)],
+        ),
+    ]);
+    let subjects = BTreeMap::from([
+        (SubjectHandle::from_index(0), subject(None)),
+        (SubjectHandle::from_index(1), subject(Some(incompat_handle))),
+    ]);
+    let incompats = BTreeMap::from([(
+        incompat_handle,
+        backend::Incompat {
+            name: String::from("Lunch"),
+            max_count: 0,
+            groups: BTreeSet::from([backend::IncompatGroup {
+                slots: BTreeSet::from([backend::IncompatSlot {
+                    week_pattern_id: week_pattern_handle,
+                    start: start(Weekday::Monday, 7, 30),
+                    duration: NonZeroU32::new(60).unwrap(),
+                }]),
+            }]),
+        },
+    )]);
+    // The incompatibility does not apply on week 1
+    let week_patterns = BTreeMap::from([(
+        week_pattern_handle,
+        backend::WeekPattern {
+            name: String::from("Week 1"),
+            weeks: BTreeSet::from([backend::Week::new(0)]),
+        },
+    )]);
+    let registrations = BTreeMap::from([(
+        StudentHandle::from_index(0),
+        BTreeSet::from([incompat_handle]),
+    )]);
+
+    let conflicts = find_conflicts(
+        &colloscope,
+        &subjects,
+        &incompats,
+        &week_patterns,
+        &registrations,
+    );
+
+    assert_eq!(
+        conflicts,
+        BTreeSet::from([
+            Conflict::IncompatViolated {
+                student: StudentHandle::from_index(0),
+                incompat: incompat_handle,
+                week: 0,
+                count: 1,
+                max_count: 0,
+            },
+            Conflict::IncompatViolated {
+                student: StudentHandle::from_index(1),
+                incompat: incompat_handle,
+                week: 0,
+                count: 1,
+                max_count: 0,
+            },
+        ])
+    );
+}
@@ -1,6 +1,14 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Read;
 
+use super::state::{
+    GroupListHandle, IncompatHandle, StudentHandle, SubjectGroupHandle, SubjectHandle,
+    TeacherHandle,
+};
+use crate::backend;
+use crate::time::{Locale, WeekMapping};
+
 use thiserror::Error;
 
 #[cfg(test)]
@@ -16,6 +24,20 @@ pub enum Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("Error while writing CSV: {0}")]
+    CSV(#[from] ::csv::Error),
+    #[error("Error while writing file: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Colloscope is not compatible with the provided database")]
+    BadColloscope,
+    #[error("Colloscope is inconsistent: a group number is invalid")]
+    InvalidGroupNumber,
+}
+
+pub type ExportResult<T> = std::result::Result<T, ExportError>;
+
 #[derive(Debug, Clone)]
 pub struct Content {
     content: Vec<u8>,
@@ -109,3 +131,116 @@ impl Content {
         Ok(Extract { headers, lines })
     }
 }
+
+const EXPORT_HEADERS: [&str; 12] = [
+    "week",
+    "date",
+    "day",
+    "start",
+    "end",
+    "subject",
+    "teacher",
+    "teacher_contact",
+    "room",
+    "group",
+    "students",
+    "student_emails",
+];
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions<'a> {
+    // The date column is empty if no week mapping is given
+    pub week_mapping: Option<&'a WeekMapping>,
+    pub params: Params,
+    pub locale: Locale,
+}
+
+// One line per group interrogated in a time slot for a given week (for mail merge
+// or import in other tools)
+pub fn export_colloscope_to_csv(
+    colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    teachers: &BTreeMap<TeacherHandle, backend::Teacher>,
+    subjects: &BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    students: &BTreeMap<StudentHandle, backend::Student>,
+    options: &ExportOptions,
+    file: &std::path::Path,
+) -> ExportResult<()> {
+    let params = &options.params;
+    let locale = options.locale;
+
+    let mut interrogations = super::interrogations::flatten(colloscope);
+    interrogations
+        .sort_by(|i1, i2| (i1.week, &i1.time_slot.start).cmp(&(i2.week, &i2.time_slot.start)));
+
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(params.delimiter)
+        .from_path(file)?;
+    if params.has_headers {
+        writer.write_record(EXPORT_HEADERS)?;
+    }
+
+    for interrogation in interrogations {
+        let time_slot = interrogation.time_slot;
+        let subject = subjects
+            .get(&interrogation.subject_handle)
+            .ok_or(ExportError::BadColloscope)?;
+        let teacher = teachers
+            .get(&time_slot.teacher_id)
+            .ok_or(ExportError::BadColloscope)?;
+        let group_name = interrogation
+            .group_name
+            .ok_or(ExportError::InvalidGroupNumber)?;
+
+        let date = options
+            .week_mapping
+            .map(|week_mapping| {
+                week_mapping
+                    .week_start(interrogation.week)
+                    .get_date(time_slot.start.day)
+                    .to_iso_string()
+            })
+            .unwrap_or_default();
+        let end = time_slot
+            .start
+            .time
+            .add(subject.duration.get())
+            .map(|time| locale.format_time(&time))
+            .unwrap_or_default();
+
+        let group_students = interrogation
+            .students
+            .iter()
+            .map(|handle| students.get(handle).ok_or(ExportError::BadColloscope))
+            .collect::<ExportResult<Vec<_>>>()?;
+        let student_names: Vec<_> = group_students
+            .iter()
+            .map(|student| format!("{} {}", student.firstname, student.surname))
+            .collect();
+        let student_emails: Vec<_> = group_students
+            .iter()
+            .filter_map(|student| student.email.clone())
+            .collect();
+
+        writer.write_record([
+            (interrogation.week + 1).to_string(),
+            date,
+            locale.weekday_name(time_slot.start.day).to_string(),
+            locale.format_time(&time_slot.start.time),
+            end,
+            subject.name.clone(),
+            format!("{} {}", teacher.firstname, teacher.surname),
+            teacher.contact.clone(),
+            time_slot.room.clone(),
+            group_name.to_string(),
+            student_names.join(", "),
+            student_emails.join(", "),
+        ])?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
@@ -1,5 +1,10 @@
 use super::*;
 
+use crate::time::{Date, HolidayCalendar, Time, WeekStart, Weekday};
+
+use std::collections::BTreeSet;
+use std::num::{NonZeroU32, NonZeroUsize};
+
 #[test]
 fn simple_csv() {
     let test_text = r#""Name";"Column2";"Column3";"Original name"
@@ -126,3 +131,174 @@ fn wrong_delimiter() {
 
     assert_eq!(extracted, expected_result);
 }
+
+struct ExportData {
+    colloscope: backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    teachers: BTreeMap<TeacherHandle, backend::Teacher>,
+    subjects: BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    students: BTreeMap<StudentHandle, backend::Student>,
+}
+
+// One teacher interrogating a group of two students on weeks 1 to 3
+fn export_data() -> ExportData {
+    let teacher = TeacherHandle::from_index(0);
+    let subject = SubjectHandle::from_index(0);
+    let alice = StudentHandle::from_index(0);
+    let bob = StudentHandle::from_index(1);
+
+    let colloscope = backend::Colloscope {
+        name: String::from("MPSI 1"),
+        subjects: BTreeMap::from([(
+            subject,
+            backend::ColloscopeSubject {
+                time_slots: vec![backend::ColloscopeTimeSlot {
+                    teacher_id: teacher,
+                    start: backend::SlotStart {
+                        day: Weekday::Monday,
+                        time: Time::from_hm(8, 0).unwrap(),
+                    },
+                    room: String::from("B12"),
+                    group_assignments: (0..3)
+                        .map(|week| (backend::Week::new(week), BTreeSet::from([0])))
+                        .collect(),
+                }],
+                group_list: backend::ColloscopeGroupList {
+                    name: String::from("Groups"),
+                    groups: vec![String::from("1")],
+                    students_mapping: BTreeMap::from([(alice, 0), (bob, 0)]),
+                },
+            },
+        )]),
+    };
+
+    ExportData {
+        colloscope,
+        teachers: BTreeMap::from([(
+            teacher,
+            backend::Teacher {
+                surname: String::from("Dupont"),
+                firstname: String::from("Jean"),
+                contact: String::from("jean.dupont@example.org"),
+            },
+        )]),
+        subjects: BTreeMap::from([(
+            subject,
+            backend::Subject {
+                name: String::from("Maths"),
+                subject_group_id: SubjectGroupHandle::from_index(0),
+                incompat_id: None,
+                group_list_id: None,
+                duration: NonZeroU32::new(60).unwrap(),
+                students_per_group: NonZeroUsize::new(2).unwrap()..=NonZeroUsize::new(3).unwrap(),
+                period: NonZeroU32::new(1).unwrap(),
+                period_is_strict: false,
+                is_tutorial: false,
+                max_groups_per_slot: NonZeroUsize::new(1).unwrap(),
+                balancing_requirements: backend::BalancingRequirements {
+                    constraints: backend::BalancingConstraints::OptimizeOnly,
+                    slot_selections: backend::BalancingSlotSelections::TeachersAndTimeSlots,
+                },
+            },
+        )]),
+        students: BTreeMap::from([
+            (
+                alice,
+                backend::Student {
+                    surname: String::from("Martin"),
+                    firstname: String::from("Alice"),
+                    email: Some(String::from("alice@example.org")),
+                    phone: None,
+                    no_consecutive_slots: false,
+                },
+            ),
+            (
+                bob,
+                backend::Student {
+                    surname: String::from("O'Neil"),
+                    firstname: String::from("Bob"),
+                    email: Some(String::from("bob@example.org")),
+                    phone: None,
+                    no_consecutive_slots: false,
+                },
+            ),
+        ]),
+    }
+}
+
+// Starts on 2024-09-02 with a holiday on the second week
+fn week_mapping() -> WeekMapping {
+    let first_week = WeekStart::new(Date::new(2024, 9, 2).unwrap()).unwrap();
+    WeekMapping::new(
+        first_week,
+        HolidayCalendar::new().add_week(first_week.next()),
+    )
+}
+
+fn export(data: &ExportData, week_mapping: Option<&WeekMapping>) -> Vec<Vec<String>> {
+    let file = std::env::temp_dir().join(format!("csv_export_test_{}", std::process::id()));
+    let options = ExportOptions {
+        week_mapping,
+        params: Params {
+            has_headers: true,
+            delimiter: b',',
+        },
+        locale: Locale::English,
+    };
+    export_colloscope_to_csv(
+        &data.colloscope,
+        &data.teachers,
+        &data.subjects,
+        &data.students,
+        &options,
+        &file,
+    )
+    .unwrap();
+    let extract = Content::from_csv_file(&file)
+        .unwrap()
+        .extract(&options.params)
+        .unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(extract.headers.unwrap(), EXPORT_HEADERS);
+    extract.lines
+}
+
+#[test]
+fn export_columns() {
+    let lines = export(&export_data(), Some(&week_mapping()));
+
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[1],
+        vec![
+            "2",
+            "2024-09-16",
+            "Monday",
+            "08:00",
+            "09:00",
+            "Maths",
+            "Jean Dupont",
+            "jean.dupont@example.org",
+            "B12",
+            "1",
+            "Alice Martin, Bob O'Neil",
+            "alice@example.org, bob@example.org",
+        ]
+    );
+    // The holiday week is skipped
+    let dates: Vec<_> = lines.iter().map(|line| line[1].as_str()).collect();
+    assert_eq!(dates, vec!["2024-09-02", "2024-09-16", "2024-09-23"]);
+}
+
+#[test]
+fn export_without_week_mapping_has_no_dates() {
+    let lines = export(&export_data(), None);
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines.iter().all(|line| line[1].is_empty()));
+    let weeks: Vec<_> = lines.iter().map(|line| line[0].as_str()).collect();
+    assert_eq!(weeks, vec!["1", "2", "3"]);
+}
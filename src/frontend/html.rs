@@ -0,0 +1,330 @@
+use super::state::{
+    GroupListHandle, IncompatHandle, StudentHandle, SubjectGroupHandle, SubjectHandle,
+    TeacherHandle,
+};
+use crate::backend;
+use crate::time::Locale;
+
+use thiserror::Error;
+
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Failed to write html file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Colloscope is not compatible with the provided database")]
+    BadColloscope,
+    #[error("Colloscope is inconsistent: a group number is invalid")]
+    InvalidGroupNumber,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+const STYLE: &str = r##"
+body { font-family: sans-serif; margin: 1em; }
+.filters { display: flex; flex-wrap: wrap; gap: 1em; margin-bottom: 1em; }
+.filters label { display: flex; flex-direction: column; font-size: 0.9em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #999; padding: 2px 6px; text-align: center; }
+th { background-color: #e8e8e8; position: sticky; top: 0; }
+#count { margin-bottom: 0.5em; color: #555; }
+"##;
+
+const SCRIPT: &str = r##"
+function applyFilters() {
+    const value = (id) => document.getElementById(id).value;
+    const week = value("filter-week");
+    const subject = value("filter-subject");
+    const teacher = value("filter-teacher");
+    const student = value("filter-student");
+
+    let count = 0;
+    for (const row of document.querySelectorAll("#interrogations tbody tr")) {
+        const visible = (week === "" || row.dataset.week === week)
+            && (subject === "" || row.dataset.subject === subject)
+            && (teacher === "" || row.dataset.teacher === teacher)
+            && (student === "" || row.dataset.students.split(" ").includes(student));
+        row.hidden = !visible;
+        if (visible) {
+            count += 1;
+        }
+    }
+    const counter = document.getElementById("count");
+    counter.textContent = count + counter.dataset.suffix;
+}
+
+for (const select of document.querySelectorAll(".filters select")) {
+    select.addEventListener("change", applyFilters);
+}
+applyFilters();
+"##;
+
+// One line of the table: a group interrogated on a given week
+struct Interrogation {
+    week: u32,
+    subject: usize,
+    teacher: usize,
+    students: Vec<usize>,
+    cells: [String; 7],
+    sort_key: (u32, crate::time::Weekday, crate::time::Time, usize, usize),
+}
+
+// UI strings of the page
+struct Labels {
+    all: &'static str,
+    count_suffix: &'static str,
+    week: &'static str,
+    time_slot: &'static str,
+    subject: &'static str,
+    teacher: &'static str,
+    room: &'static str,
+    group: &'static str,
+    student: &'static str,
+    students: &'static str,
+}
+
+fn labels(locale: Locale) -> Labels {
+    match locale {
+        Locale::French => Labels {
+            all: "Tous",
+            count_suffix: " colle(s)",
+            week: "Semaine",
+            time_slot: "Créneau",
+            subject: "Matière",
+            teacher: "Colleur",
+            room: "Salle",
+            group: "Groupe",
+            student: "Élève",
+            students: "Élèves",
+        },
+        Locale::English => Labels {
+            all: "All",
+            count_suffix: " interrogation(s)",
+            week: "Week",
+            time_slot: "Time slot",
+            subject: "Subject",
+            teacher: "Teacher",
+            room: "Room",
+            group: "Group",
+            student: "Student",
+            students: "Students",
+        },
+    }
+}
+
+fn build_select(id: &str, label: &str, all: &str, options: &[String]) -> String {
+    let mut output = format!(
+        "<label for=\"{}\">{}<select id=\"{}\"><option value=\"\">{}</option>",
+        id, label, id, all
+    );
+    for (i, option) in options.iter().enumerate() {
+        output += &format!("<option value=\"{}\">{}</option>", i, escape(option));
+    }
+    output += "</select></label>";
+    output
+}
+
+// Standalone page (no external resources) listing every interrogation of the colloscope.
+// Filtering is done by the browser so the page can be served as a static file.
+pub fn export_colloscope_to_html(
+    colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    teachers: &BTreeMap<TeacherHandle, backend::Teacher>,
+    subjects: &BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    students: &BTreeMap<StudentHandle, backend::Student>,
+    file: &std::path::Path,
+    locale: Locale,
+) -> Result<()> {
+    let mut sorted_teachers: Vec<_> = teachers.iter().collect();
+    sorted_teachers.sort_by(|(_, t1), (_, t2)| {
+        (&t1.surname, &t1.firstname).cmp(&(&t2.surname, &t2.firstname))
+    });
+    let teacher_indices: BTreeMap<_, _> = sorted_teachers
+        .iter()
+        .enumerate()
+        .map(|(i, (handle, _))| (**handle, i))
+        .collect();
+
+    let mut sorted_students: Vec<_> = students.iter().collect();
+    sorted_students.sort_by(|(_, s1), (_, s2)| {
+        (&s1.surname, &s1.firstname).cmp(&(&s2.surname, &s2.firstname))
+    });
+    let student_indices: BTreeMap<_, _> = sorted_students
+        .iter()
+        .enumerate()
+        .map(|(i, (handle, _))| (**handle, i))
+        .collect();
+
+    let subject_list: Vec<_> = colloscope.subjects.keys().copied().collect();
+
+    let subject_indices: BTreeMap<_, _> = subject_list
+        .iter()
+        .enumerate()
+        .map(|(i, handle)| (*handle, i))
+        .collect();
+
+    let mut week_count = 0;
+    let mut interrogations = Vec::new();
+    for interrogation in super::interrogations::flatten(colloscope) {
+        let time_slot = interrogation.time_slot;
+        let subject_index = subject_indices[&interrogation.subject_handle];
+        let subject_name = &subjects
+            .get(&interrogation.subject_handle)
+            .ok_or(Error::BadColloscope)?
+            .name;
+        let teacher = teachers
+            .get(&time_slot.teacher_id)
+            .ok_or(Error::BadColloscope)?;
+        let group_name = interrogation.group_name.ok_or(Error::InvalidGroupNumber)?;
+        week_count = week_count.max(interrogation.week + 1);
+
+        let mut group_students = interrogation
+            .students
+            .iter()
+            .map(|handle| {
+                student_indices
+                    .get(handle)
+                    .copied()
+                    .ok_or(Error::BadColloscope)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        group_students.sort();
+        let student_names: Vec<_> = group_students
+            .iter()
+            .map(|i| {
+                let student = sorted_students[*i].1;
+                format!("{} {}", student.firstname, student.surname)
+            })
+            .collect();
+
+        interrogations.push(Interrogation {
+            week: interrogation.week,
+            subject: subject_index,
+            teacher: teacher_indices[&time_slot.teacher_id],
+            cells: [
+                (interrogation.week + 1).to_string(),
+                format!(
+                    "{} {}",
+                    locale.weekday_name(time_slot.start.day),
+                    locale.format_time(&time_slot.start.time)
+                ),
+                subject_name.clone(),
+                format!("{} {}", teacher.firstname, teacher.surname),
+                time_slot.room.clone(),
+                group_name.to_string(),
+                student_names.join(", "),
+            ],
+            students: group_students,
+            sort_key: (
+                interrogation.week,
+                time_slot.start.day,
+                time_slot.start.time.clone(),
+                subject_index,
+                interrogation.group,
+            ),
+        });
+    }
+    interrogations.sort_by(|i1, i2| i1.sort_key.cmp(&i2.sort_key));
+
+    let labels = labels(locale);
+    let title = escape(&colloscope.name);
+    let mut output = format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        locale.code(),
+        title,
+        STYLE,
+        title
+    );
+
+    let weeks: Vec<_> = (1..=week_count).map(|week| week.to_string()).collect();
+    let subject_names = subject_list
+        .iter()
+        .map(|handle| {
+            subjects
+                .get(handle)
+                .map(|subject| subject.name.clone())
+                .ok_or(Error::BadColloscope)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let teacher_names: Vec<_> = sorted_teachers
+        .iter()
+        .map(|(_, teacher)| format!("{} {}", teacher.surname, teacher.firstname))
+        .collect();
+    let student_names: Vec<_> = sorted_students
+        .iter()
+        .map(|(_, student)| format!("{} {}", student.surname, student.firstname))
+        .collect();
+
+    output += "<div class=\"filters\">";
+    output += &build_select("filter-student", labels.student, labels.all, &student_names);
+    output += &build_select("filter-teacher", labels.teacher, labels.all, &teacher_names);
+    output += &build_select("filter-subject", labels.subject, labels.all, &subject_names);
+    output += &build_select("filter-week", labels.week, labels.all, &weeks);
+    output += &format!(
+        "</div>\n<div id=\"count\" data-suffix=\"{}\"></div>\n",
+        labels.count_suffix
+    );
+
+    output += "<table id=\"interrogations\">\n<thead><tr>";
+    for title in [
+        labels.week,
+        labels.time_slot,
+        labels.subject,
+        labels.teacher,
+        labels.room,
+        labels.group,
+        labels.students,
+    ] {
+        output += &format!("<th>{}</th>", title);
+    }
+    output += "</tr></thead>\n<tbody>\n";
+
+    for interrogation in &interrogations {
+        let students: Vec<_> = interrogation
+            .students
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        output += &format!(
+            "<tr data-week=\"{}\" data-subject=\"{}\" data-teacher=\"{}\" data-students=\"{}\">",
+            interrogation.week,
+            interrogation.subject,
+            interrogation.teacher,
+            students.join(" ")
+        );
+        for cell in &interrogation.cells {
+            output += &format!("<td>{}</td>", escape(cell));
+        }
+        output += "</tr>\n";
+    }
+
+    output += &format!(
+        "</tbody>\n</table>\n<script>{}</script>\n</body>\n</html>\n",
+        SCRIPT
+    );
+
+    std::fs::write(file, output)?;
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use super::*;
+
+use crate::time::{Time, Weekday};
+
+use std::collections::BTreeSet;
+use std::num::{NonZeroU32, NonZeroUsize};
+
+struct Data {
+    colloscope: backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    teachers: BTreeMap<TeacherHandle, backend::Teacher>,
+    subjects: BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    students: BTreeMap<StudentHandle, backend::Student>,
+}
+
+// One teacher interrogating a group of two students on weeks 1 to 3
+fn data() -> Data {
+    let teacher = TeacherHandle::from_index(0);
+    let subject = SubjectHandle::from_index(0);
+    let alice = StudentHandle::from_index(0);
+    let bob = StudentHandle::from_index(1);
+
+    let colloscope = backend::Colloscope {
+        name: String::from("MPSI <1>"),
+        subjects: BTreeMap::from([(
+            subject,
+            backend::ColloscopeSubject {
+                time_slots: vec![backend::ColloscopeTimeSlot {
+                    teacher_id: teacher,
+                    start: backend::SlotStart {
+                        day: Weekday::Monday,
+                        time: Time::from_hm(8, 0).unwrap(),
+                    },
+                    room: String::from("B12"),
+                    group_assignments: (0..3)
+                        .map(|week| (backend::Week::new(week), BTreeSet::from([0])))
+                        .collect(),
+                }],
+                group_list: backend::ColloscopeGroupList {
+                    name: String::from("Groups"),
+                    groups: vec![String::from("1")],
+                    students_mapping: BTreeMap::from([(alice, 0), (bob, 0)]),
+                },
+            },
+        )]),
+    };
+
+    Data {
+        colloscope,
+        teachers: BTreeMap::from([(
+            teacher,
+            backend::Teacher {
+                surname: String::from("Dupont"),
+                firstname: String::from("Jean"),
+                contact: String::new(),
+            },
+        )]),
+        subjects: BTreeMap::from([(
+            subject,
+            backend::Subject {
+                name: String::from("Maths & <Physique>"),
+                subject_group_id: SubjectGroupHandle::from_index(0),
+                incompat_id: None,
+                group_list_id: None,
+                duration: NonZeroU32::new(60).unwrap(),
+                students_per_group: NonZeroUsize::new(2).unwrap()..=NonZeroUsize::new(3).unwrap(),
+                period: NonZeroU32::new(1).unwrap(),
+                period_is_strict: false,
+                is_tutorial: false,
+                max_groups_per_slot: NonZeroUsize::new(1).unwrap(),
+                balancing_requirements: backend::BalancingRequirements {
+                    constraints: backend::BalancingConstraints::OptimizeOnly,
+                    slot_selections: backend::BalancingSlotSelections::TeachersAndTimeSlots,
+                },
+            },
+        )]),
+        students: BTreeMap::from([
+            (
+                alice,
+                backend::Student {
+                    surname: String::from("Martin"),
+                    firstname: String::from("Alice"),
+                    email: None,
+                    phone: None,
+                    no_consecutive_slots: false,
+                },
+            ),
+            (
+                bob,
+                backend::Student {
+                    surname: String::from("O'Neil"),
+                    firstname: String::from("Bob"),
+                    email: None,
+                    phone: None,
+                    no_consecutive_slots: false,
+                },
+            ),
+        ]),
+    }
+}
+
+fn export(data: &Data, locale: Locale) -> String {
+    let file = std::env::temp_dir().join(format!(
+        "html_test_{}_{}.html",
+        std::process::id(),
+        locale.code()
+    ));
+    export_colloscope_to_html(
+        &data.colloscope,
+        &data.teachers,
+        &data.subjects,
+        &data.students,
+        &file,
+        locale,
+    )
+    .unwrap();
+    let output = std::fs::read_to_string(&file).unwrap();
+    std::fs::remove_file(&file).unwrap();
+    output
+}
+
+#[test]
+fn escape_html_special_characters() {
+    assert_eq!(
+        escape("<a href=\"x\">Tom & Jerry's</a>"),
+        "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+    );
+}
+
+#[test]
+fn html_export_escapes_database_strings() {
+    let output = export(&data(), Locale::French);
+
+    assert!(output.contains("<title>MPSI &lt;1&gt;</title>"));
+    assert!(output.contains("<h1>MPSI &lt;1&gt;</h1>"));
+    assert!(output.contains("<td>Maths &amp; &lt;Physique&gt;</td>"));
+    assert!(output.contains("<td>Alice Martin, Bob O&#39;Neil</td>"));
+    assert!(output.contains("<option value=\"1\">O&#39;Neil Bob</option>"));
+    assert!(!output.contains("<Physique>"));
+    assert_eq!(output.matches("<tr data-week=").count(), 3);
+}
+
+#[test]
+fn html_export_follows_locale() {
+    let data = data();
+
+    let french = export(&data, Locale::French);
+    assert!(french.contains("<html lang=\"fr\">"));
+    assert!(french.contains("<option value=\"\">Tous</option>"));
+    assert!(french.contains("<th>Élèves</th>"));
+    assert!(french.contains("data-suffix=\" colle(s)\""));
+    assert!(french.contains("<td>Lundi 08h00</td>"));
+
+    let english = export(&data, Locale::English);
+    assert!(english.contains("<html lang=\"en\">"));
+    assert!(english.contains("<option value=\"\">All</option>"));
+    assert!(english.contains("<th>Students</th>"));
+    assert!(english.contains("data-suffix=\" interrogation(s)\""));
+    assert!(english.contains("<td>Monday 08:00</td>"));
+    for french_word in ["Tous", "Élève", "Colleur", "colle(s)"] {
+        assert!(!english.contains(french_word));
+    }
+}
@@ -0,0 +1,308 @@
+use super::state::{
+    GroupListHandle, IncompatHandle, StudentHandle, SubjectGroupHandle, SubjectHandle,
+    TeacherHandle,
+};
+use crate::backend;
+use crate::time::{Date, Time, WeekMapping};
+
+use thiserror::Error;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Failed to write ics file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Colloscope is not compatible with the provided database")]
+    BadColloscope,
+    #[error("Colloscope is inconsistent: a group number is invalid")]
+    InvalidGroupNumber,
+    #[error("An interrogation of subject \"{0}\" ends after midnight")]
+    InterrogationPastMidnight(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+// One interrogation with everything needed to write its VEVENT
+struct Event {
+    uid: String,
+    start: (Date, Time),
+    end: Time,
+    subject: String,
+    teacher: TeacherHandle,
+    students: Vec<StudentHandle>,
+    room: String,
+    group: String,
+}
+
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            ';' => output.push_str("\\;"),
+            ',' => output.push_str("\\,"),
+            '\n' => output.push_str("\\n"),
+            '\r' => {}
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+// Content lines are limited to 75 octets (RFC 5545 section 3.1)
+fn push_line(output: &mut String, line: &str) {
+    let mut current_len = 0;
+    for c in line.chars() {
+        if current_len + c.len_utf8() > 75 {
+            output.push_str("\r\n ");
+            current_len = 1;
+        }
+        output.push(c);
+        current_len += c.len_utf8();
+    }
+    output.push_str("\r\n");
+}
+
+fn format_date_time(date: Date, time: &Time) -> String {
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}00",
+        date.get_year(),
+        date.get_month(),
+        date.get_day(),
+        time.get_hour(),
+        time.get_min()
+    )
+}
+
+// DTSTAMP is given in UTC
+fn format_timestamp(time: std::time::SystemTime) -> String {
+    let seconds = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let date = Date::new(1970, 1, 1)
+        .expect("Epoch should be a valid date")
+        .add_days(i64::try_from(seconds / 86400).expect("Day count should fit in i64"));
+    let seconds_in_day = seconds % 86400;
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        date.get_year(),
+        date.get_month(),
+        date.get_day(),
+        seconds_in_day / 3600,
+        (seconds_in_day / 60) % 60,
+        seconds_in_day % 60
+    )
+}
+
+fn build_calendar<'a>(
+    name: &str,
+    events: impl Iterator<Item = &'a Event>,
+    summary: impl Fn(&Event) -> String,
+    description: impl Fn(&Event) -> String,
+    timestamp: &str,
+) -> String {
+    let mut output = String::new();
+    push_line(&mut output, "BEGIN:VCALENDAR");
+    push_line(&mut output, "VERSION:2.0");
+    push_line(&mut output, "PRODID:-//Collomatique//Collomatique//FR");
+    push_line(&mut output, "CALSCALE:GREGORIAN");
+    push_line(&mut output, &format!("X-WR-CALNAME:{}", escape(name)));
+
+    for event in events {
+        push_line(&mut output, "BEGIN:VEVENT");
+        push_line(&mut output, &format!("UID:{}", event.uid));
+        push_line(&mut output, &format!("DTSTAMP:{}", timestamp));
+        // Times are floating (local time of the school)
+        push_line(
+            &mut output,
+            &format!(
+                "DTSTART:{}",
+                format_date_time(event.start.0, &event.start.1)
+            ),
+        );
+        push_line(
+            &mut output,
+            &format!("DTEND:{}", format_date_time(event.start.0, &event.end)),
+        );
+        push_line(&mut output, &format!("SUMMARY:{}", escape(&summary(event))));
+        if !event.room.is_empty() {
+            push_line(&mut output, &format!("LOCATION:{}", escape(&event.room)));
+        }
+        push_line(
+            &mut output,
+            &format!("DESCRIPTION:{}", escape(&description(event))),
+        );
+        push_line(&mut output, "END:VEVENT");
+    }
+
+    push_line(&mut output, "END:VCALENDAR");
+    output
+}
+
+fn file_name(prefix: &str, surname: &str, firstname: &str, used: &mut BTreeSet<String>) -> String {
+    let base: String = format!("{}-{}-{}", prefix, surname, firstname)
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let mut name = format!("{}.ics", base);
+    let mut i = 2;
+    while used.contains(&name) {
+        name = format!("{}-{}.ics", base, i);
+        i += 1;
+    }
+    used.insert(name.clone());
+    name
+}
+
+// Writes one calendar per teacher and one per student in the given directory.
+// Returns the names of the files that were written.
+pub fn export_colloscope_to_ical(
+    colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    teachers: &BTreeMap<TeacherHandle, backend::Teacher>,
+    subjects: &BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    students: &BTreeMap<StudentHandle, backend::Student>,
+    week_mapping: &WeekMapping,
+    directory: &std::path::Path,
+) -> Result<Vec<String>> {
+    let subject_indices: BTreeMap<_, _> = colloscope
+        .subjects
+        .keys()
+        .enumerate()
+        .map(|(i, handle)| (*handle, i))
+        .collect();
+
+    let mut events = Vec::new();
+    for interrogation in super::interrogations::flatten(colloscope) {
+        let time_slot = interrogation.time_slot;
+        let subject = subjects
+            .get(&interrogation.subject_handle)
+            .ok_or(Error::BadColloscope)?;
+        let group_name = interrogation.group_name.ok_or(Error::InvalidGroupNumber)?;
+
+        let end = time_slot
+            .start
+            .time
+            .add(subject.duration.get())
+            .ok_or(Error::InterrogationPastMidnight(subject.name.clone()))?;
+        let date = week_mapping
+            .week_start(interrogation.week)
+            .get_date(time_slot.start.day);
+
+        events.push(Event {
+            uid: format!(
+                "{}-{}-{}@collomatique",
+                format_date_time(date, &time_slot.start.time),
+                subject_indices[&interrogation.subject_handle],
+                interrogation.group
+            ),
+            start: (date, time_slot.start.time.clone()),
+            end,
+            subject: subject.name.clone(),
+            teacher: time_slot.teacher_id,
+            students: interrogation.students,
+            room: time_slot.room.clone(),
+            group: group_name.to_string(),
+        });
+    }
+    events.sort_by(|e1, e2| e1.start.cmp(&e2.start));
+
+    let student_names = |event: &Event| -> String {
+        event
+            .students
+            .iter()
+            .filter_map(|handle| students.get(handle))
+            .map(|student| format!("{} {}", student.firstname, student.surname))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let teacher_name = |handle: &TeacherHandle| -> String {
+        teachers
+            .get(handle)
+            .map(|teacher| format!("{} {}", teacher.firstname, teacher.surname))
+            .unwrap_or_default()
+    };
+    let timestamp = format_timestamp(std::time::SystemTime::now());
+
+    std::fs::create_dir_all(directory)?;
+    let mut used_names = BTreeSet::new();
+
+    for (teacher_handle, teacher) in teachers {
+        let teacher_events: Vec<_> = events
+            .iter()
+            .filter(|event| event.teacher == *teacher_handle)
+            .collect();
+        if teacher_events.is_empty() {
+            continue;
+        }
+
+        let content = build_calendar(
+            &format!("{} - {}", colloscope.name, teacher_name(teacher_handle)),
+            teacher_events.into_iter(),
+            |event| format!("{} - Groupe {}", event.subject, event.group),
+            |event| student_names(event),
+            &timestamp,
+        );
+        let name = file_name(
+            "colleur",
+            &teacher.surname,
+            &teacher.firstname,
+            &mut used_names,
+        );
+        std::fs::write(directory.join(&name), content)?;
+    }
+
+    for (student_handle, student) in students {
+        let student_events: Vec<_> = events
+            .iter()
+            .filter(|event| event.students.contains(student_handle))
+            .collect();
+        if student_events.is_empty() {
+            continue;
+        }
+
+        let content = build_calendar(
+            &format!(
+                "{} - {} {}",
+                colloscope.name, student.firstname, student.surname
+            ),
+            student_events.into_iter(),
+            |event| format!("{} ({})", event.subject, teacher_name(&event.teacher)),
+            |event| {
+                let contact = teachers
+                    .get(&event.teacher)
+                    .map(|teacher| teacher.contact.clone())
+                    .unwrap_or_default();
+                let mut description = format!("Groupe {} : {}", event.group, student_names(event));
+                if !contact.is_empty() {
+                    description += &format!("\nContact : {}", contact);
+                }
+                description
+            },
+            &timestamp,
+        );
+        let name = file_name(
+            "eleve",
+            &student.surname,
+            &student.firstname,
+            &mut used_names,
+        );
+        std::fs::write(directory.join(&name), content)?;
+    }
+
+    Ok(used_names.into_iter().collect())
+}
@@ -0,0 +1,193 @@
+use super::*;
+
+use crate::time::{HolidayCalendar, WeekStart, Weekday};
+
+use std::num::{NonZeroU32, NonZeroUsize};
+
+struct Data {
+    colloscope: backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    teachers: BTreeMap<TeacherHandle, backend::Teacher>,
+    subjects: BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    students: BTreeMap<StudentHandle, backend::Student>,
+}
+
+// One teacher interrogating a group of two students on weeks 1 to 3.
+// The subject name is long enough to be folded.
+fn data() -> Data {
+    let teacher = TeacherHandle::from_index(0);
+    let subject = SubjectHandle::from_index(0);
+    let alice = StudentHandle::from_index(0);
+    let bob = StudentHandle::from_index(1);
+
+    let colloscope = backend::Colloscope {
+        name: String::from("MPSI 1"),
+        subjects: BTreeMap::from([(
+            subject,
+            backend::ColloscopeSubject {
+                time_slots: vec![backend::ColloscopeTimeSlot {
+                    teacher_id: teacher,
+                    start: backend::SlotStart {
+                        day: Weekday::Monday,
+                        time: Time::from_hm(8, 0).unwrap(),
+                    },
+                    room: String::from("B12, bâtiment A; 2e étage"),
+                    group_assignments: (0..3)
+                        .map(|week| (backend::Week::new(week), BTreeSet::from([0])))
+                        .collect(),
+                }],
+                group_list: backend::ColloscopeGroupList {
+                    name: String::from("Groups"),
+                    groups: vec![String::from("1")],
+                    students_mapping: BTreeMap::from([(alice, 0), (bob, 0)]),
+                },
+            },
+        )]),
+    };
+
+    Data {
+        colloscope,
+        teachers: BTreeMap::from([(
+            teacher,
+            backend::Teacher {
+                surname: String::from("Dupont"),
+                firstname: String::from("Jean"),
+                contact: String::from("jean.dupont@example.org"),
+            },
+        )]),
+        subjects: BTreeMap::from([(
+            subject,
+            backend::Subject {
+                name: String::from(
+                    "Mathématiques approfondies et physique pour la préparation aux concours",
+                ),
+                subject_group_id: SubjectGroupHandle::from_index(0),
+                incompat_id: None,
+                group_list_id: None,
+                duration: NonZeroU32::new(60).unwrap(),
+                students_per_group: NonZeroUsize::new(2).unwrap()..=NonZeroUsize::new(3).unwrap(),
+                period: NonZeroU32::new(1).unwrap(),
+                period_is_strict: false,
+                is_tutorial: false,
+                max_groups_per_slot: NonZeroUsize::new(1).unwrap(),
+                balancing_requirements: backend::BalancingRequirements {
+                    constraints: backend::BalancingConstraints::OptimizeOnly,
+                    slot_selections: backend::BalancingSlotSelections::TeachersAndTimeSlots,
+                },
+            },
+        )]),
+        students: BTreeMap::from([
+            (
+                alice,
+                backend::Student {
+                    surname: String::from("Martin"),
+                    firstname: String::from("Alice"),
+                    email: None,
+                    phone: None,
+                    no_consecutive_slots: false,
+                },
+            ),
+            (
+                bob,
+                backend::Student {
+                    surname: String::from("O'Neil"),
+                    firstname: String::from("Bob"),
+                    email: None,
+                    phone: None,
+                    no_consecutive_slots: false,
+                },
+            ),
+        ]),
+    }
+}
+
+// Starts on 2024-09-02 with a holiday on the second week
+fn week_mapping() -> WeekMapping {
+    let first_week = WeekStart::new(Date::new(2024, 9, 2).unwrap()).unwrap();
+    WeekMapping::new(
+        first_week,
+        HolidayCalendar::new().add_week(first_week.next()),
+    )
+}
+
+fn unfold(content: &str) -> String {
+    content.replace("\r\n ", "")
+}
+
+#[test]
+fn escape_ical_text() {
+    assert_eq!(escape("a,b;c\\d\r\ne"), "a\\,b\\;c\\\\d\\ne");
+}
+
+#[test]
+fn lines_are_folded_at_75_octets() {
+    // "é" takes two octets and must not be split
+    let line = "é".repeat(100);
+    let mut output = String::new();
+    push_line(&mut output, &line);
+
+    let lines: Vec<_> = output.split("\r\n").collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0].len(), 74);
+    assert!(lines.iter().all(|line| line.len() <= 75));
+    assert!(lines[1..3].iter().all(|line| line.starts_with(' ')));
+    assert_eq!(lines[3], "");
+    assert_eq!(unfold(&output), format!("{}\r\n", line));
+}
+
+#[test]
+fn ical_export_writes_one_calendar_per_person() {
+    let data = data();
+    let directory = std::env::temp_dir().join(format!("ical_test_{}", std::process::id()));
+
+    let names = export_colloscope_to_ical(
+        &data.colloscope,
+        &data.teachers,
+        &data.subjects,
+        &data.students,
+        &week_mapping(),
+        &directory,
+    )
+    .unwrap();
+    let teacher_calendar =
+        std::fs::read_to_string(directory.join("colleur-Dupont-Jean.ics")).unwrap();
+    let student_calendar = std::fs::read_to_string(directory.join("eleve-O_Neil-Bob.ics")).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(
+        names,
+        vec![
+            String::from("colleur-Dupont-Jean.ics"),
+            String::from("eleve-Martin-Alice.ics"),
+            String::from("eleve-O_Neil-Bob.ics"),
+        ]
+    );
+
+    for calendar in [&teacher_calendar, &student_calendar] {
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert!(calendar.split("\r\n").all(|line| line.len() <= 75));
+        // The summary line is long enough to be folded
+        assert!(calendar.contains("\r\n "));
+    }
+
+    let teacher_calendar = unfold(&teacher_calendar);
+    assert_eq!(teacher_calendar.matches("BEGIN:VEVENT").count(), 3);
+    assert!(teacher_calendar.contains(
+        "SUMMARY:Mathématiques approfondies et physique pour la préparation aux concours - Groupe 1\r\n"
+    ));
+    assert!(teacher_calendar.contains("LOCATION:B12\\, bâtiment A\\; 2e étage\r\n"));
+    assert!(teacher_calendar.contains("DESCRIPTION:Alice Martin\\, Bob O'Neil\r\n"));
+    // The holiday week is skipped
+    assert!(teacher_calendar.contains("DTSTART:20240902T080000\r\n"));
+    assert!(teacher_calendar.contains("DTSTART:20240916T080000\r\nDTEND:20240916T090000\r\n"));
+    assert!(!teacher_calendar.contains("DTSTART:20240909"));
+
+    let student_calendar = unfold(&student_calendar);
+    assert!(student_calendar.contains("X-WR-CALNAME:MPSI 1 - Bob O'Neil\r\n"));
+    assert!(student_calendar.contains(
+        "DESCRIPTION:Groupe 1 : Alice Martin\\, Bob O'Neil\\nContact : jean.dupont@example.org\r\n"
+    ));
+}
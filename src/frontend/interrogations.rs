@@ -0,0 +1,52 @@
+use super::state::{StudentHandle, SubjectHandle, TeacherHandle};
+use crate::backend;
+
+// One group interrogated in a time slot for a given week.
+// This is the flat view of a colloscope shared by the exporters and the dataframe API.
+#[derive(Clone, Debug)]
+pub struct Interrogation<'a> {
+    pub subject_handle: SubjectHandle,
+    pub time_slot: &'a backend::ColloscopeTimeSlot<TeacherHandle>,
+    // Position of the time slot in the subject
+    pub time_slot_index: usize,
+    pub week: u32,
+    pub group: usize,
+    // None if the group number is invalid
+    pub group_name: Option<&'a str>,
+    pub students: Vec<StudentHandle>,
+}
+
+// Interrogations are listed by subject, then time slot, week and group
+pub fn flatten(
+    colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+) -> Vec<Interrogation<'_>> {
+    let mut output = Vec::new();
+
+    for (subject_handle, subject) in &colloscope.subjects {
+        for (time_slot_index, time_slot) in subject.time_slots.iter().enumerate() {
+            for (week, groups) in &time_slot.group_assignments {
+                for &group in groups {
+                    let students = subject
+                        .group_list
+                        .students_mapping
+                        .iter()
+                        .filter(|(_, student_group)| **student_group == group)
+                        .map(|(handle, _)| *handle)
+                        .collect();
+
+                    output.push(Interrogation {
+                        subject_handle: *subject_handle,
+                        time_slot,
+                        time_slot_index,
+                        week: week.get(),
+                        group,
+                        group_name: subject.group_list.groups.get(group).map(String::as_str),
+                        students,
+                    });
+                }
+            }
+        }
+    }
+
+    output
+}
@@ -0,0 +1,271 @@
+use super::state::{
+    GroupListHandle, IncompatHandle, StudentHandle, SubjectGroupHandle, SubjectHandle,
+    TeacherHandle,
+};
+use crate::backend;
+use crate::time::{Locale, WeekMapping};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Failed to write file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Error while writing CSV: {0}")]
+    CsvError(#[from] ::csv::Error),
+    #[error("Error while writing JSON: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Colloscope is not compatible with the provided database")]
+    BadColloscope,
+    #[error("Colloscope is inconsistent: a group number is invalid")]
+    InvalidGroupNumber,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    // One line per person, interrogations are summarized in a single multiline field
+    Csv { delimiter: u8 },
+    // One object per person with the full list of interrogations
+    Json,
+}
+
+// Weeks (0-based) whose interrogations are included
+#[derive(Debug, Clone, Copy)]
+pub struct WeekRange {
+    pub first: u32,
+    pub count: Option<u32>,
+}
+
+impl WeekRange {
+    fn contains(&self, week: u32) -> bool {
+        week >= self.first
+            && match self.count {
+                Some(count) => week - self.first < count,
+                None => true,
+            }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct InterrogationRecord {
+    week: u32,
+    date: Option<String>,
+    day: String,
+    start: String,
+    end: String,
+    subject: String,
+    teacher: String,
+    room: String,
+    group: String,
+    students: Vec<String>,
+    #[serde(skip)]
+    summary: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PersonRecord {
+    role: &'static str,
+    surname: String,
+    firstname: String,
+    email: Option<String>,
+    phone: Option<String>,
+    interrogations: Vec<InterrogationRecord>,
+}
+
+const CSV_HEADERS: [&str; 7] = [
+    "role",
+    "surname",
+    "firstname",
+    "email",
+    "phone",
+    "interrogation_count",
+    "interrogations",
+];
+
+fn write_csv(persons: &[PersonRecord], delimiter: u8, file: &std::path::Path) -> Result<()> {
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_path(file)?;
+    writer.write_record(CSV_HEADERS)?;
+
+    for person in persons {
+        let summaries: Vec<_> = person
+            .interrogations
+            .iter()
+            .map(|interrogation| interrogation.summary.as_str())
+            .collect();
+        writer.write_record([
+            person.role.to_string(),
+            person.surname.clone(),
+            person.firstname.clone(),
+            person.email.clone().unwrap_or_default(),
+            person.phone.clone().unwrap_or_default(),
+            person.interrogations.len().to_string(),
+            summaries.join("\n"),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExportOptions<'a> {
+    // Interrogations are given by week number if no week mapping is given
+    pub week_mapping: Option<&'a WeekMapping>,
+    pub weeks: WeekRange,
+    pub format: Format,
+    pub locale: Locale,
+}
+
+// Per teacher and per student: contact information and their interrogations
+// in the given weeks, to be used by mail merge tools for notifications.
+// Every teacher and student gets a record, even without interrogations.
+pub fn export_colloscope_to_mail_merge(
+    colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    teachers: &BTreeMap<TeacherHandle, backend::Teacher>,
+    subjects: &BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    students: &BTreeMap<StudentHandle, backend::Student>,
+    options: &ExportOptions,
+    file: &std::path::Path,
+) -> Result<()> {
+    let locale = options.locale;
+    let mut interrogations: Vec<_> = super::interrogations::flatten(colloscope)
+        .into_iter()
+        .filter(|interrogation| options.weeks.contains(interrogation.week))
+        .collect();
+    interrogations
+        .sort_by(|i1, i2| (i1.week, &i1.time_slot.start).cmp(&(i2.week, &i2.time_slot.start)));
+
+    let mut records = Vec::with_capacity(interrogations.len());
+    for interrogation in &interrogations {
+        let time_slot = interrogation.time_slot;
+        let subject = subjects
+            .get(&interrogation.subject_handle)
+            .ok_or(Error::BadColloscope)?;
+        let teacher = teachers
+            .get(&time_slot.teacher_id)
+            .ok_or(Error::BadColloscope)?;
+        let group_name = interrogation.group_name.ok_or(Error::InvalidGroupNumber)?;
+
+        let date = options.week_mapping.map(|week_mapping| {
+            week_mapping
+                .week_start(interrogation.week)
+                .get_date(time_slot.start.day)
+        });
+        let day = locale.weekday_name(time_slot.start.day).to_string();
+        let start = locale.format_time(&time_slot.start.time);
+        let end = time_slot
+            .start
+            .time
+            .add(subject.duration.get())
+            .map(|time| locale.format_time(&time))
+            .unwrap_or_default();
+        let teacher_name = format!("{} {}", teacher.firstname, teacher.surname);
+        let student_names = interrogation
+            .students
+            .iter()
+            .map(|handle| {
+                students
+                    .get(handle)
+                    .map(|student| format!("{} {}", student.firstname, student.surname))
+                    .ok_or(Error::BadColloscope)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let when = match &date {
+            Some(date) => format!("{} {}", day, locale.format_date(date)),
+            None => format!(
+                "{} {} - {}",
+                locale.week_word(),
+                interrogation.week + 1,
+                day
+            ),
+        };
+        let mut summary = format!(
+            "{} {}-{} : {} ({})",
+            when, start, end, subject.name, teacher_name
+        );
+        if !time_slot.room.is_empty() {
+            summary += &format!(", {}", time_slot.room);
+        }
+
+        records.push(InterrogationRecord {
+            week: interrogation.week + 1,
+            date: date.map(|date| date.to_iso_string()),
+            day,
+            start,
+            end,
+            subject: subject.name.clone(),
+            teacher: teacher_name,
+            room: time_slot.room.clone(),
+            group: group_name.to_string(),
+            students: student_names,
+            summary,
+        });
+    }
+
+    let mut sorted_teachers: Vec<_> = teachers.iter().collect();
+    sorted_teachers.sort_by(|(_, t1), (_, t2)| {
+        (&t1.surname, &t1.firstname).cmp(&(&t2.surname, &t2.firstname))
+    });
+    let mut sorted_students: Vec<_> = students.iter().collect();
+    sorted_students.sort_by(|(_, s1), (_, s2)| {
+        (&s1.surname, &s1.firstname).cmp(&(&s2.surname, &s2.firstname))
+    });
+
+    let mut persons = Vec::with_capacity(sorted_teachers.len() + sorted_students.len());
+    for (handle, teacher) in sorted_teachers {
+        persons.push(PersonRecord {
+            role: "teacher",
+            surname: teacher.surname.clone(),
+            firstname: teacher.firstname.clone(),
+            // Teachers only have a free-form contact field
+            email: Some(teacher.contact.clone()).filter(|contact| !contact.is_empty()),
+            phone: None,
+            interrogations: interrogations
+                .iter()
+                .zip(&records)
+                .filter(|(interrogation, _)| interrogation.time_slot.teacher_id == *handle)
+                .map(|(_, record)| record.clone())
+                .collect(),
+        });
+    }
+    for (handle, student) in sorted_students {
+        persons.push(PersonRecord {
+            role: "student",
+            surname: student.surname.clone(),
+            firstname: student.firstname.clone(),
+            email: student.email.clone(),
+            phone: student.phone.clone(),
+            interrogations: interrogations
+                .iter()
+                .zip(&records)
+                .filter(|(interrogation, _)| interrogation.students.contains(handle))
+                .map(|(_, record)| record.clone())
+                .collect(),
+        });
+    }
+
+    match options.format {
+        Format::Csv { delimiter } => write_csv(&persons, delimiter, file)?,
+        Format::Json => {
+            let mut text = serde_json::to_string_pretty(&persons)?;
+            text.push('\n');
+            std::fs::write(file, text)?;
+        }
+    }
+
+    Ok(())
+}
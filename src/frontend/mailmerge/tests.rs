@@ -0,0 +1,228 @@
+use super::*;
+
+use crate::time::{Date, HolidayCalendar, Time, WeekStart, Weekday};
+
+use std::collections::BTreeSet;
+use std::num::{NonZeroU32, NonZeroUsize};
+
+struct Data {
+    colloscope: backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    teachers: BTreeMap<TeacherHandle, backend::Teacher>,
+    subjects: BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    students: BTreeMap<StudentHandle, backend::Student>,
+}
+
+// One teacher interrogating a group of two students on weeks 1 to 3
+fn data() -> Data {
+    let teacher = TeacherHandle::from_index(0);
+    let subject = SubjectHandle::from_index(0);
+    let alice = StudentHandle::from_index(0);
+    let bob = StudentHandle::from_index(1);
+
+    let colloscope = backend::Colloscope {
+        name: String::from("MPSI 1"),
+        subjects: BTreeMap::from([(
+            subject,
+            backend::ColloscopeSubject {
+                time_slots: vec![backend::ColloscopeTimeSlot {
+                    teacher_id: teacher,
+                    start: backend::SlotStart {
+                        day: Weekday::Monday,
+                        time: Time::from_hm(8, 0).unwrap(),
+                    },
+                    room: String::from("B12"),
+                    group_assignments: (0..3)
+                        .map(|week| (backend::Week::new(week), BTreeSet::from([0])))
+                        .collect(),
+                }],
+                group_list: backend::ColloscopeGroupList {
+                    name: String::from("Groups"),
+                    groups: vec![String::from("1")],
+                    students_mapping: BTreeMap::from([(alice, 0), (bob, 0)]),
+                },
+            },
+        )]),
+    };
+
+    Data {
+        colloscope,
+        teachers: BTreeMap::from([(
+            teacher,
+            backend::Teacher {
+                surname: String::from("Dupont"),
+                firstname: String::from("Jean"),
+                contact: String::from("jean.dupont@example.org"),
+            },
+        )]),
+        subjects: BTreeMap::from([(
+            subject,
+            backend::Subject {
+                name: String::from("Maths"),
+                subject_group_id: SubjectGroupHandle::from_index(0),
+                incompat_id: None,
+                group_list_id: None,
+                duration: NonZeroU32::new(60).unwrap(),
+                students_per_group: NonZeroUsize::new(2).unwrap()..=NonZeroUsize::new(3).unwrap(),
+                period: NonZeroU32::new(1).unwrap(),
+                period_is_strict: false,
+                is_tutorial: false,
+                max_groups_per_slot: NonZeroUsize::new(1).unwrap(),
+                balancing_requirements: backend::BalancingRequirements {
+                    constraints: backend::BalancingConstraints::OptimizeOnly,
+                    slot_selections: backend::BalancingSlotSelections::TeachersAndTimeSlots,
+                },
+            },
+        )]),
+        students: BTreeMap::from([
+            (
+                alice,
+                backend::Student {
+                    surname: String::from("Martin"),
+                    firstname: String::from("Alice"),
+                    email: Some(String::from("alice@example.org")),
+                    phone: None,
+                    no_consecutive_slots: false,
+                },
+            ),
+            (
+                bob,
+                backend::Student {
+                    surname: String::from("O'Neil"),
+                    firstname: String::from("Bob"),
+                    email: Some(String::from("bob@example.org")),
+                    phone: None,
+                    no_consecutive_slots: false,
+                },
+            ),
+        ]),
+    }
+}
+
+// Starts on 2024-09-02 with a holiday on the second week
+fn week_mapping() -> WeekMapping {
+    let first_week = WeekStart::new(Date::new(2024, 9, 2).unwrap()).unwrap();
+    WeekMapping::new(
+        first_week,
+        HolidayCalendar::new().add_week(first_week.next()),
+    )
+}
+
+fn export(data: &Data, weeks: WeekRange, format: Format) -> String {
+    let file = std::env::temp_dir().join(format!("mailmerge_test_{}", std::process::id()));
+    let week_mapping = week_mapping();
+    let options = ExportOptions {
+        week_mapping: Some(&week_mapping),
+        weeks,
+        format,
+        locale: Locale::French,
+    };
+    export_colloscope_to_mail_merge(
+        &data.colloscope,
+        &data.teachers,
+        &data.subjects,
+        &data.students,
+        &options,
+        &file,
+    )
+    .unwrap();
+    let output = std::fs::read_to_string(&file).unwrap();
+    std::fs::remove_file(&file).unwrap();
+    output
+}
+
+#[test]
+fn week_range_contains() {
+    let range = WeekRange {
+        first: 1,
+        count: Some(2),
+    };
+    assert!(!range.contains(0));
+    assert!(range.contains(1));
+    assert!(range.contains(2));
+    assert!(!range.contains(3));
+
+    let open_range = WeekRange {
+        first: 1,
+        count: None,
+    };
+    assert!(!open_range.contains(0));
+    assert!(open_range.contains(100));
+}
+
+#[test]
+fn json_mail_merge_only_includes_the_week_window() {
+    let output = export(
+        &data(),
+        WeekRange {
+            first: 1,
+            count: Some(1),
+        },
+        Format::Json,
+    );
+    let persons: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let persons = persons.as_array().unwrap();
+
+    let names: Vec<_> = persons
+        .iter()
+        .map(|person| {
+            (
+                person["role"].as_str().unwrap(),
+                person["surname"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("teacher", "Dupont"),
+            ("student", "Martin"),
+            ("student", "O'Neil")
+        ]
+    );
+    assert_eq!(persons[0]["email"], "jean.dupont@example.org");
+    assert_eq!(persons[1]["email"], "alice@example.org");
+
+    for person in persons {
+        let interrogations = person["interrogations"].as_array().unwrap();
+        assert_eq!(interrogations.len(), 1);
+        // Second week of the colloscope, after the holiday
+        assert_eq!(interrogations[0]["week"], 2);
+        assert_eq!(interrogations[0]["date"], "2024-09-16");
+        assert_eq!(interrogations[0]["day"], "Lundi");
+        assert_eq!(interrogations[0]["start"], "08h00");
+        assert_eq!(interrogations[0]["end"], "09h00");
+        assert!(interrogations[0].get("summary").is_none());
+    }
+}
+
+#[test]
+fn csv_mail_merge_summarizes_interrogations() {
+    let output = export(
+        &data(),
+        WeekRange {
+            first: 1,
+            count: None,
+        },
+        Format::Csv { delimiter: b';' },
+    );
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .from_reader(output.as_bytes());
+
+    let headers: Vec<_> = reader.headers().unwrap().iter().map(String::from).collect();
+    assert_eq!(headers, CSV_HEADERS);
+
+    let records: Vec<_> = reader.records().map(|record| record.unwrap()).collect();
+    assert_eq!(records.len(), 3);
+    let alice = &records[1];
+    assert_eq!(&alice[2], "Alice");
+    assert_eq!(&alice[3], "alice@example.org");
+    assert_eq!(&alice[5], "2");
+    assert_eq!(
+        &alice[6],
+        "Lundi 16/09/2024 08h00-09h00 : Maths (Jean Dupont), B12\nLundi 23/09/2024 08h00-09h00 : Maths (Jean Dupont), B12"
+    );
+}
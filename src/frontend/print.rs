@@ -0,0 +1,172 @@
+use std::ops::Range;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PaperSize {
+    #[default]
+    A4,
+    A3,
+    Letter,
+}
+
+impl PaperSize {
+    // (width, height) in portrait orientation
+    pub fn dimensions_mm(&self) -> (f64, f64) {
+        match self {
+            PaperSize::A4 => (210., 297.),
+            PaperSize::A3 => (297., 420.),
+            PaperSize::Letter => (215.9, 279.4),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Orientation {
+    Portrait,
+    #[default]
+    Landscape,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageSetup {
+    pub paper_size: PaperSize,
+    pub orientation: Orientation,
+    // Multiplies the size of every cell (1.0 is the natural size)
+    pub font_scale: f64,
+    pub margin_mm: f64,
+    // Repeat the header rows and columns on every page
+    // instead of printing them only on the first row/column of pages
+    pub repeat_headers: bool,
+}
+
+impl Default for PageSetup {
+    fn default() -> Self {
+        PageSetup {
+            paper_size: PaperSize::default(),
+            orientation: Orientation::default(),
+            font_scale: 1.,
+            margin_mm: 10.,
+            repeat_headers: true,
+        }
+    }
+}
+
+impl PageSetup {
+    // (width, height) available for the grid once the margins are removed
+    pub fn printable_size_mm(&self) -> (f64, f64) {
+        let (width, height) = self.paper_size.dimensions_mm();
+        let (width, height) = match self.orientation {
+            Orientation::Portrait => (width, height),
+            Orientation::Landscape => (height, width),
+        };
+        (
+            (width - 2. * self.margin_mm).max(0.),
+            (height - 2. * self.margin_mm).max(0.),
+        )
+    }
+}
+
+// Sizes of a grid at its natural size (font scale 1), in mm.
+// Header rows are on top of every column, header columns on the left of every row.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Grid {
+    pub header_width: f64,
+    pub header_height: f64,
+    pub column_widths: Vec<f64>,
+    pub row_heights: Vec<f64>,
+}
+
+// Part of the grid printed on one page.
+// Ranges are indices in column_widths and row_heights.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page {
+    pub columns: Range<usize>,
+    pub rows: Range<usize>,
+    pub with_header_rows: bool,
+    pub with_header_columns: bool,
+}
+
+// Splits sizes in consecutive chunks fitting in the available space.
+// An element larger than a page still gets a page on its own.
+fn split(sizes: &[f64], available: f64, header: f64, repeat_header: bool) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+
+    let mut start = 0;
+    let mut used = header;
+    for (i, size) in sizes.iter().enumerate() {
+        if i != start && used + size > available {
+            chunks.push(start..i);
+            start = i;
+            used = if repeat_header { header } else { 0. };
+        }
+        used += size;
+    }
+    if start != sizes.len() || chunks.is_empty() {
+        chunks.push(start..sizes.len());
+    }
+
+    chunks
+}
+
+// Pages are ordered row by row: all the columns of the first rows, then the next rows.
+pub fn paginate(grid: &Grid, setup: &PageSetup) -> Vec<Page> {
+    let (width, height) = setup.printable_size_mm();
+    let scale = if setup.font_scale > 0. {
+        setup.font_scale
+    } else {
+        1.
+    };
+    let (width, height) = (width / scale, height / scale);
+
+    let column_chunks = split(
+        &grid.column_widths,
+        width,
+        grid.header_width,
+        setup.repeat_headers,
+    );
+    let row_chunks = split(
+        &grid.row_heights,
+        height,
+        grid.header_height,
+        setup.repeat_headers,
+    );
+
+    let mut pages = Vec::with_capacity(column_chunks.len() * row_chunks.len());
+    for (i, rows) in row_chunks.iter().enumerate() {
+        for (j, columns) in column_chunks.iter().enumerate() {
+            pages.push(Page {
+                columns: columns.clone(),
+                rows: rows.clone(),
+                with_header_rows: i == 0 || setup.repeat_headers,
+                with_header_columns: j == 0 || setup.repeat_headers,
+            });
+        }
+    }
+
+    pages
+}
+
+// Indices at which a new page starts (excluding 0), for outputs working with page breaks
+pub fn column_breaks(pages: &[Page]) -> Vec<usize> {
+    let mut breaks: Vec<_> = pages
+        .iter()
+        .map(|page| page.columns.start)
+        .filter(|start| *start != 0)
+        .collect();
+    breaks.sort();
+    breaks.dedup();
+    breaks
+}
+
+pub fn row_breaks(pages: &[Page]) -> Vec<usize> {
+    let mut breaks: Vec<_> = pages
+        .iter()
+        .map(|page| page.rows.start)
+        .filter(|start| *start != 0)
+        .collect();
+    breaks.sort();
+    breaks.dedup();
+    breaks
+}
@@ -0,0 +1,88 @@
+use super::*;
+
+fn grid(column_count: usize, row_count: usize) -> Grid {
+    Grid {
+        header_width: 100.,
+        header_height: 10.,
+        column_widths: vec![10.; column_count],
+        row_heights: vec![5.; row_count],
+    }
+}
+
+#[test]
+fn printable_size_depends_on_orientation() {
+    let setup = PageSetup::default();
+    assert_eq!(setup.printable_size_mm(), (277., 190.));
+
+    let setup = PageSetup {
+        orientation: Orientation::Portrait,
+        ..PageSetup::default()
+    };
+    assert_eq!(setup.printable_size_mm(), (190., 277.));
+}
+
+#[test]
+fn headers_are_repeated_on_every_page() {
+    let pages = paginate(&grid(20, 10), &PageSetup::default());
+
+    assert_eq!(
+        pages,
+        vec![
+            Page {
+                columns: 0..17,
+                rows: 0..10,
+                with_header_rows: true,
+                with_header_columns: true,
+            },
+            Page {
+                columns: 17..20,
+                rows: 0..10,
+                with_header_rows: true,
+                with_header_columns: true,
+            },
+        ]
+    );
+    assert_eq!(column_breaks(&pages), vec![17]);
+    assert_eq!(row_breaks(&pages), Vec::<usize>::new());
+}
+
+#[test]
+fn headers_only_on_first_page_leave_more_room() {
+    let setup = PageSetup {
+        repeat_headers: false,
+        ..PageSetup::default()
+    };
+    let pages = paginate(&grid(50, 40), &setup);
+
+    let columns: Vec<_> = pages.iter().map(|page| page.columns.clone()).collect();
+    assert_eq!(columns, vec![0..17, 17..44, 44..50, 0..17, 17..44, 44..50]);
+    assert_eq!(row_breaks(&pages), vec![36]);
+    assert!(pages[0].with_header_columns && pages[0].with_header_rows);
+    assert!(!pages[1].with_header_columns && pages[1].with_header_rows);
+    assert!(pages[3].with_header_columns && !pages[3].with_header_rows);
+}
+
+#[test]
+fn font_scale_reduces_page_content() {
+    let setup = PageSetup {
+        font_scale: 2.,
+        ..PageSetup::default()
+    };
+    let pages = paginate(&grid(7, 1), &setup);
+
+    assert_eq!(column_breaks(&pages), vec![3, 6]);
+}
+
+#[test]
+fn oversized_cells_get_their_own_page() {
+    let grid = Grid {
+        header_width: 0.,
+        header_height: 0.,
+        column_widths: vec![10., 500., 10.],
+        row_heights: vec![],
+    };
+    let pages = paginate(&grid, &PageSetup::default());
+
+    assert_eq!(column_breaks(&pages), vec![1, 2]);
+    assert_eq!(pages[0].rows, 0..0);
+}
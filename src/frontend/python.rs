@@ -2,11 +2,20 @@ use std::{collections::BTreeSet, path::PathBuf};
 
 use pyo3::{prelude::*, types::IntoPyDict};
 
+#[cfg(test)]
+mod tests;
+
 mod csv_file;
 mod database;
+mod sandbox;
+
+pub use sandbox::{Sandbox, SandboxedRun};
 
 use super::state;
 
+// Type stubs for the embedded module (checked against the module in tests)
+pub const STUBS: &str = include_str!("python/collomatique.pyi");
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PythonCode {
     code: String,
@@ -35,9 +44,13 @@ fn extract_function_arguments(py: Python, func: &Py<PyAny>) -> PyResult<Vec<Stri
 }
 
 pub fn initialize() {
-    use database::collomatique;
-    pyo3::append_to_inittab!(collomatique);
-    pyo3::prepare_freethreaded_python();
+    // The module can only be added to the inittab before the interpreter starts
+    static INIT: std::sync::Once = std::sync::Once::new();
+    INIT.call_once(|| {
+        use database::collomatique;
+        pyo3::append_to_inittab!(collomatique);
+        pyo3::prepare_freethreaded_python();
+    });
 }
 
 impl PythonCode {
@@ -88,6 +101,70 @@ impl PythonCode {
         self.run_func_internal(manager, func, Some(csv_extract))
     }
 
+    // Runs the whole script (or only func if given) within the limits of the sandbox
+    pub fn run_sandboxed<T: state::Manager>(
+        &self,
+        manager: &mut T,
+        func: Option<&str>,
+        csv_extract: Option<super::csv::Extract>,
+        sandbox: &Sandbox,
+    ) -> SandboxedRun {
+        std::thread::scope(|scope| {
+            let session_connection = database::SessionConnection::new(scope, manager);
+
+            let sandboxed_run = Python::with_gil(|py| {
+                let globals = match sandbox.globals(py) {
+                    Ok(globals) => globals,
+                    Err(e) => {
+                        return SandboxedRun {
+                            stdout: String::new(),
+                            stderr: String::new(),
+                            result: Err(e),
+                        }
+                    }
+                };
+                let db = session_connection.python_database();
+
+                sandbox.run(py, |py| match func {
+                    Some(func) => {
+                        let module_name = self
+                            .file
+                            .as_path()
+                            .file_stem()
+                            .unwrap_or_default()
+                            .to_string_lossy();
+                        globals.set_item("__name__", module_name)?;
+                        py.run_bound(&self.code, Some(&globals), None)?;
+
+                        let func: Py<PyAny> = globals
+                            .get_item(func)?
+                            .ok_or_else(|| {
+                                pyo3::exceptions::PyAttributeError::new_err(format!(
+                                    "Script has no function named \"{}\"",
+                                    func
+                                ))
+                            })?
+                            .into();
+                        Self::call_func(py, &func, csv_extract, db)
+                    }
+                    None => {
+                        if let Some(extract) = csv_extract {
+                            let csv_file = csv_file::CsvFile::from_extract(extract);
+                            globals.set_item("csv", Py::new(py, csv_file)?)?;
+                        }
+                        globals.set_item("db", Py::new(py, db)?)?;
+
+                        py.run_bound(&self.code, Some(&globals), None)
+                    }
+                })
+            });
+
+            session_connection.join();
+
+            sandboxed_run
+        })
+    }
+
     fn run_internal<T: state::Manager>(
         &self,
         manager: &mut T,
@@ -0,0 +1,400 @@
+# Type stubs for the collomatique python module
+# Print them with "collomatique python stubs" and save them as collomatique.pyi
+# next to your scripts to get IDE completion and mypy checking.
+
+from typing import Any, Callable, Literal, Optional
+
+SolveStatus = Literal["queued", "running", "finished", "cancelled"]
+DataFrameLibrary = Literal["pandas", "polars"]
+SolverProfileName = Literal["quick-draft", "balanced", "exhaustive"]
+
+class WeekPatternHandle: ...
+class TeacherHandle: ...
+class StudentHandle: ...
+class SubjectGroupHandle: ...
+class IncompatHandle: ...
+class GroupListHandle: ...
+class SubjectHandle: ...
+class TimeSlotHandle: ...
+class GroupingHandle: ...
+class GroupingIncompatHandle: ...
+class SlotSelectionHandle: ...
+class ColloscopeHandle: ...
+
+class GeneralData:
+    interrogations_per_week_range: Optional[tuple[int, int]]
+    max_interrogations_per_day: Optional[int]
+    week_count: int
+    periodicity_cuts: set[int]
+    max_interrogations_per_day_for_single_student_cost: int
+    max_interrogations_per_day_for_all_students_cost: int
+    interrogations_per_week_range_for_single_student_cost: int
+    interrogations_per_week_range_for_all_students_cost: int
+    balancing_cost: int
+    consecutive_slots_cost: int
+    # Kinds of constraints only penalized in the objective, with a positive weight.
+    # Setting an unknown kind or a kind that cannot be soft raises ValueError
+    soft_constraints: dict[str, int]
+    # Incompatibility slots may cross midnight (they are split into same-day parts)
+    overnight_incompat_slots: bool
+    def __init__(self, week_count: int) -> None: ...
+
+class WeekPattern:
+    name: str
+    weeks: set[int]
+    def __init__(self, name: str) -> None: ...
+
+class Teacher:
+    surname: str
+    firstname: str
+    contact: str
+    def __init__(self, surname: str, firstname: str) -> None: ...
+
+class Student:
+    surname: str
+    firstname: str
+    email: Optional[str]
+    phone: Optional[str]
+    no_consecutive_slots: bool
+    def __init__(self, firstname: str, surname: str) -> None: ...
+
+class SubjectGroup:
+    name: str
+    optional: bool
+    def __init__(self, name: str) -> None: ...
+
+class Weekday:
+    Monday: Weekday
+    Tuesday: Weekday
+    Wednesday: Weekday
+    Thursday: Weekday
+    Friday: Weekday
+    Saturday: Weekday
+    Sunday: Weekday
+
+class Time:
+    @property
+    def hour(self) -> int: ...
+    @property
+    def minute(self) -> int: ...
+    def __init__(self, hour: int, minute: int) -> None: ...
+
+class SlotStart:
+    day: Weekday
+    time: Time
+    def __init__(self, day: Weekday, time: Time) -> None: ...
+
+class IncompatSlot:
+    @property
+    def week_pattern_handle(self) -> WeekPatternHandle: ...
+    @property
+    def start(self) -> SlotStart: ...
+    @property
+    def duration(self) -> int: ...
+    def __init__(
+        self,
+        week_pattern_handle: WeekPatternHandle,
+        start: SlotStart,
+        duration: int | str,
+    ) -> None: ...
+
+class Incompat:
+    name: str
+    max_count: int
+    groups: list[set[IncompatSlot]]
+    def __init__(self, name: str) -> None: ...
+
+class Group:
+    name: str
+    extendable: bool
+    def __init__(self, name: str) -> None: ...
+
+class GroupList:
+    name: str
+    groups: list[Group]
+    students_mapping: dict[StudentHandle, int]
+    def __init__(self, name: str) -> None: ...
+
+class BalancingConstraints:
+    OptimizeOnly: BalancingConstraints
+    OverallOnly: BalancingConstraints
+    StrictWithCuts: BalancingConstraints
+    StrictWithCutsAndOverall: BalancingConstraints
+    Strict: BalancingConstraints
+    OptimizeAndConsecutiveDifferentTeachers: BalancingConstraints
+    OverallAndConsecutiveDifferentTeachers: BalancingConstraints
+    StrictWithCutsAndConsecutiveDifferentTeachers: BalancingConstraints
+    StrictWithCutsAndOverallAndConsecutiveDifferentTeachers: BalancingConstraints
+
+class BalancingSlotSelections:
+    TeachersAndTimeSlots: BalancingSlotSelections
+    Teachers: BalancingSlotSelections
+    TimeSlots: BalancingSlotSelections
+    Manual: BalancingSlotSelections
+
+class Subject:
+    name: str
+    subject_group_handle: SubjectGroupHandle
+    incompat_handle: Optional[IncompatHandle]
+    group_list_handle: Optional[GroupListHandle]
+    duration: int
+    students_per_group_range: tuple[int, int]
+    period: int
+    period_is_strict: bool
+    is_tutorial: bool
+    max_groups_per_slot: int
+    balancing_constraints: BalancingConstraints
+    balancing_slot_selections: BalancingSlotSelections
+    def __init__(self, name: str, subject_group_handle: SubjectGroupHandle) -> None: ...
+
+class TimeSlot:
+    subject_handle: SubjectHandle
+    teacher_handle: TeacherHandle
+    start: SlotStart
+    week_pattern_handle: WeekPatternHandle
+    room: str
+    cost: int
+    def __init__(
+        self,
+        subject_handle: SubjectHandle,
+        teacher_handle: TeacherHandle,
+        week_pattern_handle: WeekPatternHandle,
+    ) -> None: ...
+
+class Grouping:
+    name: str
+    slots: set[TimeSlotHandle]
+    def __init__(self, name: str) -> None: ...
+
+class GroupingIncompat:
+    max_count: int
+    groupings: set[GroupingHandle]
+    def __init__(self, max_count: int) -> None: ...
+
+class SlotGroup:
+    count: int
+    slots: set[TimeSlotHandle]
+    def __init__(self, count: int) -> None: ...
+
+class SlotSelection:
+    subject_handle: SubjectHandle
+    slot_groups: list[SlotGroup]
+    def __init__(self, subject_handle: SubjectHandle) -> None: ...
+
+class ColloscopeTimeSlot:
+    teacher_handle: TeacherHandle
+    start: SlotStart
+    room: str
+    group_assignments: dict[int, set[int]]
+    def __init__(self, teacher_handle: TeacherHandle, start: SlotStart) -> None: ...
+
+class ColloscopeGroupList:
+    name: str
+    groups: list[str]
+    students_mapping: dict[StudentHandle, int]
+    def __init__(self, name: str) -> None: ...
+
+class ColloscopeSubject:
+    time_slots: list[ColloscopeTimeSlot]
+    group_list: ColloscopeGroupList
+    def __init__(self, group_list: ColloscopeGroupList) -> None: ...
+    # Table of weeks x time slots with the interrogated groups (for notebooks)
+    def _repr_html_(self) -> str: ...
+
+class Colloscope:
+    name: str
+    subjects: dict[SubjectHandle, ColloscopeSubject]
+    def __init__(self, name: str) -> None: ...
+    def _repr_html_(self) -> str: ...
+
+class SolveJob:
+    # Called from poll() with a dict describing each new solver event
+    progress_callback: Optional[Callable[[dict[str, Any]], object]]
+    def poll(self) -> SolveStatus: ...
+    def is_done(self) -> bool: ...
+    def cancel(self) -> bool: ...
+    def wait(self, poll_interval: float = 0.1) -> SolveStatus: ...
+    # None until the solve ends: the solver does not report intermediate solutions
+    def objective_value(self) -> Optional[float]: ...
+    def store(self, name: str) -> ColloscopeHandle: ...
+
+class IlpModel:
+    variables: list[str]
+    # (coefs, sign, constant, groups) for sum(coefs[v]*v) + constant <= 0 (or == 0)
+    constraints: list[tuple[dict[str, int], Literal["<=", "=="], int, list[str]]]
+    # Linear part of the objective
+    objective: dict[str, float]
+    # (coef, exprs) adds coef * max(exprs) to the objective, exprs being (coefs, constant)
+    objective_terms: list[tuple[float, list[tuple[dict[str, int], int]]]]
+    hint: dict[str, bool]
+    # (A_ub, b_ub, A_eq, b_eq) with columns in the order of variables (needs scipy)
+    def sparse_matrices(self) -> tuple[Any, list[int], Any, list[int]]: ...
+    def to_json(self) -> str: ...
+
+class LinExpr:
+    def __init__(self, constant: int = 0) -> None: ...
+    # 1 if the group is assigned to the time slot on the given week (0-based)
+    @staticmethod
+    def group_in_slot(time_slot_handle: TimeSlotHandle, week: int, group: int) -> LinExpr: ...
+    # 1 if the student is put in the group of the subject group list
+    @staticmethod
+    def student_in_group(
+        subject_handle: SubjectHandle, student_handle: StudentHandle, group: int
+    ) -> LinExpr: ...
+    def __add__(self, other: LinExpr | int) -> LinExpr: ...
+    def __radd__(self, other: LinExpr | int) -> LinExpr: ...
+    def __sub__(self, other: LinExpr | int) -> LinExpr: ...
+    def __rsub__(self, other: LinExpr | int) -> LinExpr: ...
+    def __mul__(self, factor: int) -> LinExpr: ...
+    def __rmul__(self, factor: int) -> LinExpr: ...
+    def __neg__(self) -> LinExpr: ...
+    def leq(self, rhs: LinExpr | int) -> LinConstraint: ...
+    def geq(self, rhs: LinExpr | int) -> LinConstraint: ...
+    def eq(self, rhs: LinExpr | int) -> LinConstraint: ...
+
+class LinConstraint: ...
+
+class CsvFile:
+    @property
+    def headers(self) -> Optional[list[str]]: ...
+    @property
+    def content(self) -> list[list[str]]: ...
+    @property
+    def map(self) -> Optional[list[dict[str, list[str]]]]: ...
+
+class Database:
+    # Set to False to run operations without calling the registered hooks
+    hooks_enabled: bool
+    # Hooks run by increasing priority, then in registration order.
+    # "before" hooks get (op, args) and "after" hooks get (op, args, result).
+    # A failing hook only emits a RuntimeWarning, and operations done
+    # from inside a hook do not trigger other hooks.
+    def register_hook(
+        self,
+        op: str,
+        callback: Callable[..., object],
+        when: Literal["before", "after"] = "after",
+        priority: int = 0,
+    ) -> int: ...
+    def remove_hook(self, id: int) -> bool: ...
+    # Observers get (op, args) for every operation applied to the database,
+    # with the same op names as hooks. Create operations give (handle, value)
+    # and undo/redo report the operations they actually apply.
+    def subscribe(self, callback: Callable[[str, tuple[Any, ...]], object]) -> int: ...
+    def unsubscribe(self, id: int) -> bool: ...
+    def undo(self) -> None: ...
+    def redo(self) -> None: ...
+    def general_data_get(self) -> GeneralData: ...
+    def general_data_set(self, general_data: GeneralData) -> None: ...
+    def week_patterns_get_all(self) -> dict[WeekPatternHandle, WeekPattern]: ...
+    def week_patterns_get(self, handle: WeekPatternHandle) -> WeekPattern: ...
+    def week_patterns_create(self, pattern: WeekPattern) -> WeekPatternHandle: ...
+    def week_patterns_update(self, handle: WeekPatternHandle, pattern: WeekPattern) -> None: ...
+    def week_patterns_remove(self, handle: WeekPatternHandle) -> None: ...
+    def teachers_get_all(self) -> dict[TeacherHandle, Teacher]: ...
+    def teachers_get(self, handle: TeacherHandle) -> Teacher: ...
+    def teachers_create(self, teacher: Teacher) -> TeacherHandle: ...
+    def teachers_update(self, handle: TeacherHandle, teacher: Teacher) -> None: ...
+    def teachers_remove(self, handle: TeacherHandle) -> None: ...
+    def students_get_all(self) -> dict[StudentHandle, Student]: ...
+    def students_get(self, handle: StudentHandle) -> Student: ...
+    def students_create(self, student: Student) -> StudentHandle: ...
+    def students_update(self, handle: StudentHandle, student: Student) -> None: ...
+    def students_remove(self, handle: StudentHandle) -> None: ...
+    def subject_groups_get_all(self) -> dict[SubjectGroupHandle, SubjectGroup]: ...
+    def subject_groups_get(self, handle: SubjectGroupHandle) -> SubjectGroup: ...
+    def subject_groups_create(self, subject_group: SubjectGroup) -> SubjectGroupHandle: ...
+    def subject_groups_update(
+        self, handle: SubjectGroupHandle, subject_group: SubjectGroup
+    ) -> None: ...
+    def subject_groups_remove(self, handle: SubjectGroupHandle) -> None: ...
+    def incompats_get_all(self) -> dict[IncompatHandle, Incompat]: ...
+    def incompats_get(self, handle: IncompatHandle) -> Incompat: ...
+    def incompats_create(self, incompat: Incompat) -> IncompatHandle: ...
+    def incompats_update(self, handle: IncompatHandle, incompat: Incompat) -> None: ...
+    def incompats_remove(self, handle: IncompatHandle) -> None: ...
+    def group_lists_get_all(self) -> dict[GroupListHandle, GroupList]: ...
+    def group_lists_get(self, handle: GroupListHandle) -> GroupList: ...
+    def group_lists_create(self, group_list: GroupList) -> GroupListHandle: ...
+    def group_lists_update(self, handle: GroupListHandle, group_list: GroupList) -> None: ...
+    def group_lists_remove(self, handle: GroupListHandle) -> None: ...
+    def subjects_get_all(self) -> dict[SubjectHandle, Subject]: ...
+    def subjects_get(self, handle: SubjectHandle) -> Subject: ...
+    def subjects_create(self, subject: Subject) -> SubjectHandle: ...
+    def subjects_update(self, handle: SubjectHandle, subject: Subject) -> None: ...
+    def subjects_remove(self, handle: SubjectHandle) -> None: ...
+    def time_slots_get_all(self) -> dict[TimeSlotHandle, TimeSlot]: ...
+    def time_slots_get(self, handle: TimeSlotHandle) -> TimeSlot: ...
+    def time_slots_create(self, time_slot: TimeSlot) -> TimeSlotHandle: ...
+    def time_slots_update(self, handle: TimeSlotHandle, time_slot: TimeSlot) -> None: ...
+    def time_slots_remove(self, handle: TimeSlotHandle) -> None: ...
+    def groupings_get_all(self) -> dict[GroupingHandle, Grouping]: ...
+    def groupings_get(self, handle: GroupingHandle) -> Grouping: ...
+    def groupings_create(self, grouping: Grouping) -> GroupingHandle: ...
+    def groupings_update(self, handle: GroupingHandle, grouping: Grouping) -> None: ...
+    def groupings_remove(self, handle: GroupingHandle) -> None: ...
+    def grouping_incompats_get_all(self) -> dict[GroupingIncompatHandle, GroupingIncompat]: ...
+    def grouping_incompats_get(self, handle: GroupingIncompatHandle) -> GroupingIncompat: ...
+    def grouping_incompats_create(
+        self, grouping_incompat: GroupingIncompat
+    ) -> GroupingIncompatHandle: ...
+    def grouping_incompats_update(
+        self, handle: GroupingIncompatHandle, grouping_incompat: GroupingIncompat
+    ) -> None: ...
+    def grouping_incompats_remove(self, handle: GroupingIncompatHandle) -> None: ...
+    def subject_group_for_student_get(
+        self, student_handle: StudentHandle, subject_group_handle: SubjectGroupHandle
+    ) -> Optional[SubjectHandle]: ...
+    def subject_group_for_student_set(
+        self,
+        student_handle: StudentHandle,
+        subject_group_handle: SubjectGroupHandle,
+        subject_handle: Optional[SubjectHandle],
+    ) -> None: ...
+    def incompat_for_student_get(
+        self, student_handle: StudentHandle, incompat_handle: IncompatHandle
+    ) -> bool: ...
+    def incompat_for_student_set(
+        self, student_handle: StudentHandle, incompat_handle: IncompatHandle, enabled: bool
+    ) -> None: ...
+    def colloscopes_get_all(self) -> dict[ColloscopeHandle, Colloscope]: ...
+    def colloscopes_get(self, handle: ColloscopeHandle) -> Colloscope: ...
+    def colloscopes_create(self, colloscope: Colloscope) -> ColloscopeHandle: ...
+    def colloscopes_update(self, handle: ColloscopeHandle, colloscope: Colloscope) -> None: ...
+    def colloscopes_remove(self, handle: ColloscopeHandle) -> None: ...
+    # Raises ValueError if a constraint refers to a variable that does not exist
+    def check_constraints(self, constraints: list[LinConstraint]) -> None: ...
+    # minimize_objective and time_limit (in seconds, 0 for no limit) default to the
+    # values of the profile. Raises ValueError for an unknown profile
+    def start_solve(
+        self,
+        minimize_objective: Optional[bool] = None,
+        time_limit: Optional[int] = None,
+        progress: Optional[Callable[[dict[str, Any]], object]] = None,
+        constraints: list[LinConstraint] = [],
+        profile: SolverProfileName = "balanced",
+    ) -> SolveJob: ...
+    # None if a colloscope can be built. Otherwise, for each kind of constraint in a
+    # minimal conflicting set: {"constraint_count": int, "subjects": [SubjectHandle],
+    # "time_slots": [(TimeSlotHandle, week)], "groups": [(SubjectHandle, group)],
+    # "students": [StudentHandle], "groupings": [(GroupingHandle, week)],
+    # "incompats": [(IncompatHandle, week)]} with 0-based weeks
+    def diagnose_infeasability(
+        self, constraints: list[LinConstraint] = []
+    ) -> Optional[dict[str, dict[str, Any]]]: ...
+    def ilp_model(self, constraints: list[LinConstraint] = []) -> IlpModel: ...
+    def students_dataframe(self, library: DataFrameLibrary = "pandas") -> Any: ...
+    def time_slots_dataframe(self, library: DataFrameLibrary = "pandas") -> Any: ...
+    def colloscope_dataframe(
+        self, handle: ColloscopeHandle, library: DataFrameLibrary = "pandas"
+    ) -> Any: ...
+    def slot_selections_get_all(self) -> dict[SlotSelectionHandle, SlotSelection]: ...
+    def slot_selections_get(self, handle: SlotSelectionHandle) -> SlotSelection: ...
+    def slot_selections_create(self, slot_selection: SlotSelection) -> SlotSelectionHandle: ...
+    def slot_selections_update(
+        self, handle: SlotSelectionHandle, grouping_incompat: SlotSelection
+    ) -> None: ...
+    def slot_selections_remove(self, handle: SlotSelectionHandle) -> None: ...
+
+def extract_name_parts(name: str, firstname_first: bool = True) -> tuple[str, str]: ...
+def load_csv(filename: str, has_headers: bool = False, delimiter: str = ";") -> CsvFile: ...
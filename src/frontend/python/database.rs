@@ -6,12 +6,35 @@ use super::*;
 mod classes;
 use classes::*;
 
+mod hooks;
+mod model;
+use model::IlpModel;
+mod observers;
+mod solving;
+use solving::*;
+
 mod utils;
 
 #[pymodule]
 pub fn collomatique(m: &Bound<'_, PyModule>) -> PyResult<()> {
     use utils::*;
 
+    m.add_class::<Database>()?;
+    m.add_class::<super::csv_file::CsvFile>()?;
+
+    m.add_class::<WeekPatternHandle>()?;
+    m.add_class::<TeacherHandle>()?;
+    m.add_class::<StudentHandle>()?;
+    m.add_class::<SubjectGroupHandle>()?;
+    m.add_class::<IncompatHandle>()?;
+    m.add_class::<GroupListHandle>()?;
+    m.add_class::<SubjectHandle>()?;
+    m.add_class::<TimeSlotHandle>()?;
+    m.add_class::<GroupingHandle>()?;
+    m.add_class::<GroupingIncompatHandle>()?;
+    m.add_class::<SlotSelectionHandle>()?;
+    m.add_class::<ColloscopeHandle>()?;
+
     m.add_class::<GeneralData>()?;
     m.add_class::<WeekPattern>()?;
     m.add_class::<Teacher>()?;
@@ -32,6 +55,14 @@ pub fn collomatique(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<SlotSelection>()?;
     m.add_class::<BalancingConstraints>()?;
     m.add_class::<BalancingSlotSelections>()?;
+    m.add_class::<ColloscopeTimeSlot>()?;
+    m.add_class::<ColloscopeGroupList>()?;
+    m.add_class::<ColloscopeSubject>()?;
+    m.add_class::<Colloscope>()?;
+    m.add_class::<SolveJob>()?;
+    m.add_class::<IlpModel>()?;
+    m.add_class::<LinExpr>()?;
+    m.add_class::<LinConstraint>()?;
 
     m.add_function(wrap_pyfunction!(extract_name_parts, m)?)?;
     m.add_function(wrap_pyfunction!(load_csv, m)?)?;
@@ -42,14 +73,15 @@ pub fn collomatique(m: &Bound<'_, PyModule>) -> PyResult<()> {
 #[pyclass]
 pub struct Database {
     sender: Sender<Job>,
+    hooks: std::cell::RefCell<hooks::HookRegistry>,
+    hooks_enabled: std::cell::Cell<bool>,
+    observers: std::cell::RefCell<observers::ObserverRegistry>,
 }
 
 #[pymethods]
 impl Database {
     fn undo(self_: PyRef<'_, Self>) -> PyResult<()> {
-        let Answer::Undo =
-            SessionConnection::send_command(self_.py(), &self_.sender, Command::Undo)?
-        else {
+        let Answer::Undo = Self::send_hooked_command(&self_, Command::Undo)? else {
             panic!("Bad answer type");
         };
 
@@ -57,21 +89,57 @@ impl Database {
     }
 
     fn redo(self_: PyRef<'_, Self>) -> PyResult<()> {
-        let Answer::Redo =
-            SessionConnection::send_command(self_.py(), &self_.sender, Command::Redo)?
-        else {
+        let Answer::Redo = Self::send_hooked_command(&self_, Command::Redo)? else {
             panic!("Bad answer type");
         };
 
         Ok(())
     }
 
+    // callback(op, args) is called before the operation and
+    // callback(op, args, result) after it
+    #[pyo3(signature = (op, callback, when = "after", priority = 0))]
+    fn register_hook(
+        self_: PyRef<'_, Self>,
+        op: String,
+        callback: PyObject,
+        when: &str,
+        priority: i32,
+    ) -> PyResult<usize> {
+        let timing = when.parse()?;
+        self_
+            .hooks
+            .borrow_mut()
+            .register(op, callback, timing, priority)
+    }
+
+    fn remove_hook(self_: PyRef<'_, Self>, id: usize) -> bool {
+        self_.hooks.borrow_mut().remove(id)
+    }
+
+    #[getter]
+    fn get_hooks_enabled(self_: PyRef<'_, Self>) -> bool {
+        self_.hooks_enabled.get()
+    }
+
+    #[setter]
+    fn set_hooks_enabled(self_: PyRef<'_, Self>, enabled: bool) {
+        self_.hooks_enabled.set(enabled);
+    }
+
+    // callback(op, args) is called for every operation applied to the database.
+    // Undo and redo report the operations they actually apply.
+    fn subscribe(self_: PyRef<'_, Self>, callback: PyObject) -> PyResult<usize> {
+        Self::add_observer(&self_, callback)
+    }
+
+    fn unsubscribe(self_: PyRef<'_, Self>, id: usize) -> bool {
+        self_.observers.borrow_mut().remove(id)
+    }
+
     fn general_data_get(self_: PyRef<'_, Self>) -> PyResult<GeneralData> {
-        let Answer::GeneralData(GeneralDataAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::GeneralData(GeneralDataCommand::Get),
-        )?
+        let Answer::GeneralData(GeneralDataAnswer::Get(val)) =
+            Self::send_hooked_command(&self_, Command::GeneralData(GeneralDataCommand::Get))?
         else {
             panic!("Bad answer type");
         };
@@ -80,9 +148,8 @@ impl Database {
     }
 
     fn general_data_set(self_: PyRef<'_, Self>, general_data: GeneralData) -> PyResult<()> {
-        let Answer::GeneralData(GeneralDataAnswer::Set) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::GeneralData(GeneralDataAnswer::Set) = Self::send_hooked_command(
+            &self_,
             Command::GeneralData(GeneralDataCommand::Set(general_data)),
         )?
         else {
@@ -96,11 +163,7 @@ impl Database {
         self_: PyRef<'_, Self>,
     ) -> PyResult<BTreeMap<WeekPatternHandle, WeekPattern>> {
         let Answer::WeekPatterns(WeekPatternsAnswer::GetAll(val)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
-                Command::WeekPatterns(WeekPatternsCommand::GetAll),
-            )?
+            Self::send_hooked_command(&self_, Command::WeekPatterns(WeekPatternsCommand::GetAll))?
         else {
             panic!("Bad answer type");
         };
@@ -112,9 +175,8 @@ impl Database {
         self_: PyRef<'_, Self>,
         handle: WeekPatternHandle,
     ) -> PyResult<WeekPattern> {
-        let Answer::WeekPatterns(WeekPatternsAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::WeekPatterns(WeekPatternsAnswer::Get(val)) = Self::send_hooked_command(
+            &self_,
             Command::WeekPatterns(WeekPatternsCommand::Get(handle)),
         )?
         else {
@@ -128,12 +190,10 @@ impl Database {
         self_: PyRef<'_, Self>,
         pattern: WeekPattern,
     ) -> PyResult<WeekPatternHandle> {
-        let Answer::WeekPatterns(WeekPatternsAnswer::Create(handle)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
-                Command::WeekPatterns(WeekPatternsCommand::Create(pattern)),
-            )?
+        let Answer::WeekPatterns(WeekPatternsAnswer::Create(handle)) = Self::send_hooked_command(
+            &self_,
+            Command::WeekPatterns(WeekPatternsCommand::Create(pattern)),
+        )?
         else {
             panic!("Bad answer type");
         };
@@ -146,9 +206,8 @@ impl Database {
         handle: WeekPatternHandle,
         pattern: WeekPattern,
     ) -> PyResult<()> {
-        let Answer::WeekPatterns(WeekPatternsAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::WeekPatterns(WeekPatternsAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::WeekPatterns(WeekPatternsCommand::Update(handle, pattern)),
         )?
         else {
@@ -159,9 +218,8 @@ impl Database {
     }
 
     fn week_patterns_remove(self_: PyRef<'_, Self>, handle: WeekPatternHandle) -> PyResult<()> {
-        let Answer::WeekPatterns(WeekPatternsAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::WeekPatterns(WeekPatternsAnswer::Remove) = Self::send_hooked_command(
+            &self_,
             Command::WeekPatterns(WeekPatternsCommand::Remove(handle)),
         )?
         else {
@@ -172,11 +230,8 @@ impl Database {
     }
 
     fn teachers_get_all(self_: PyRef<'_, Self>) -> PyResult<BTreeMap<TeacherHandle, Teacher>> {
-        let Answer::Teachers(TeachersAnswer::GetAll(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Teachers(TeachersCommand::GetAll),
-        )?
+        let Answer::Teachers(TeachersAnswer::GetAll(val)) =
+            Self::send_hooked_command(&self_, Command::Teachers(TeachersCommand::GetAll))?
         else {
             panic!("Bad answer type");
         };
@@ -185,11 +240,8 @@ impl Database {
     }
 
     fn teachers_get(self_: PyRef<'_, Self>, handle: TeacherHandle) -> PyResult<Teacher> {
-        let Answer::Teachers(TeachersAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Teachers(TeachersCommand::Get(handle)),
-        )?
+        let Answer::Teachers(TeachersAnswer::Get(val)) =
+            Self::send_hooked_command(&self_, Command::Teachers(TeachersCommand::Get(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -198,11 +250,8 @@ impl Database {
     }
 
     fn teachers_create(self_: PyRef<'_, Self>, teacher: Teacher) -> PyResult<TeacherHandle> {
-        let Answer::Teachers(TeachersAnswer::Create(handle)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Teachers(TeachersCommand::Create(teacher)),
-        )?
+        let Answer::Teachers(TeachersAnswer::Create(handle)) =
+            Self::send_hooked_command(&self_, Command::Teachers(TeachersCommand::Create(teacher)))?
         else {
             panic!("Bad answer type");
         };
@@ -215,9 +264,8 @@ impl Database {
         handle: TeacherHandle,
         teacher: Teacher,
     ) -> PyResult<()> {
-        let Answer::Teachers(TeachersAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::Teachers(TeachersAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::Teachers(TeachersCommand::Update(handle, teacher)),
         )?
         else {
@@ -228,11 +276,8 @@ impl Database {
     }
 
     fn teachers_remove(self_: PyRef<'_, Self>, handle: TeacherHandle) -> PyResult<()> {
-        let Answer::Teachers(TeachersAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Teachers(TeachersCommand::Remove(handle)),
-        )?
+        let Answer::Teachers(TeachersAnswer::Remove) =
+            Self::send_hooked_command(&self_, Command::Teachers(TeachersCommand::Remove(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -241,11 +286,8 @@ impl Database {
     }
 
     fn students_get_all(self_: PyRef<'_, Self>) -> PyResult<BTreeMap<StudentHandle, Student>> {
-        let Answer::Students(StudentsAnswer::GetAll(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Students(StudentsCommand::GetAll),
-        )?
+        let Answer::Students(StudentsAnswer::GetAll(val)) =
+            Self::send_hooked_command(&self_, Command::Students(StudentsCommand::GetAll))?
         else {
             panic!("Bad answer type");
         };
@@ -254,11 +296,8 @@ impl Database {
     }
 
     fn students_get(self_: PyRef<'_, Self>, handle: StudentHandle) -> PyResult<Student> {
-        let Answer::Students(StudentsAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Students(StudentsCommand::Get(handle)),
-        )?
+        let Answer::Students(StudentsAnswer::Get(val)) =
+            Self::send_hooked_command(&self_, Command::Students(StudentsCommand::Get(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -267,11 +306,8 @@ impl Database {
     }
 
     fn students_create(self_: PyRef<'_, Self>, student: Student) -> PyResult<StudentHandle> {
-        let Answer::Students(StudentsAnswer::Create(handle)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Students(StudentsCommand::Create(student)),
-        )?
+        let Answer::Students(StudentsAnswer::Create(handle)) =
+            Self::send_hooked_command(&self_, Command::Students(StudentsCommand::Create(student)))?
         else {
             panic!("Bad answer type");
         };
@@ -284,9 +320,8 @@ impl Database {
         handle: StudentHandle,
         student: Student,
     ) -> PyResult<()> {
-        let Answer::Students(StudentsAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::Students(StudentsAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::Students(StudentsCommand::Update(handle, student)),
         )?
         else {
@@ -297,11 +332,8 @@ impl Database {
     }
 
     fn students_remove(self_: PyRef<'_, Self>, handle: StudentHandle) -> PyResult<()> {
-        let Answer::Students(StudentsAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Students(StudentsCommand::Remove(handle)),
-        )?
+        let Answer::Students(StudentsAnswer::Remove) =
+            Self::send_hooked_command(&self_, Command::Students(StudentsCommand::Remove(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -312,12 +344,10 @@ impl Database {
     fn subject_groups_get_all(
         self_: PyRef<'_, Self>,
     ) -> PyResult<BTreeMap<SubjectGroupHandle, SubjectGroup>> {
-        let Answer::SubjectGroups(SubjectGroupsAnswer::GetAll(val)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
-                Command::SubjectGroups(SubjectGroupsCommand::GetAll),
-            )?
+        let Answer::SubjectGroups(SubjectGroupsAnswer::GetAll(val)) = Self::send_hooked_command(
+            &self_,
+            Command::SubjectGroups(SubjectGroupsCommand::GetAll),
+        )?
         else {
             panic!("Bad answer type");
         };
@@ -329,9 +359,8 @@ impl Database {
         self_: PyRef<'_, Self>,
         handle: SubjectGroupHandle,
     ) -> PyResult<SubjectGroup> {
-        let Answer::SubjectGroups(SubjectGroupsAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::SubjectGroups(SubjectGroupsAnswer::Get(val)) = Self::send_hooked_command(
+            &self_,
             Command::SubjectGroups(SubjectGroupsCommand::Get(handle)),
         )?
         else {
@@ -345,12 +374,10 @@ impl Database {
         self_: PyRef<'_, Self>,
         subject_group: SubjectGroup,
     ) -> PyResult<SubjectGroupHandle> {
-        let Answer::SubjectGroups(SubjectGroupsAnswer::Create(handle)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
-                Command::SubjectGroups(SubjectGroupsCommand::Create(subject_group)),
-            )?
+        let Answer::SubjectGroups(SubjectGroupsAnswer::Create(handle)) = Self::send_hooked_command(
+            &self_,
+            Command::SubjectGroups(SubjectGroupsCommand::Create(subject_group)),
+        )?
         else {
             panic!("Bad answer type");
         };
@@ -363,9 +390,8 @@ impl Database {
         handle: SubjectGroupHandle,
         subject_group: SubjectGroup,
     ) -> PyResult<()> {
-        let Answer::SubjectGroups(SubjectGroupsAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::SubjectGroups(SubjectGroupsAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::SubjectGroups(SubjectGroupsCommand::Update(handle, subject_group)),
         )?
         else {
@@ -376,9 +402,8 @@ impl Database {
     }
 
     fn subject_groups_remove(self_: PyRef<'_, Self>, handle: SubjectGroupHandle) -> PyResult<()> {
-        let Answer::SubjectGroups(SubjectGroupsAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::SubjectGroups(SubjectGroupsAnswer::Remove) = Self::send_hooked_command(
+            &self_,
             Command::SubjectGroups(SubjectGroupsCommand::Remove(handle)),
         )?
         else {
@@ -389,11 +414,8 @@ impl Database {
     }
 
     fn incompats_get_all(self_: PyRef<'_, Self>) -> PyResult<BTreeMap<IncompatHandle, Incompat>> {
-        let Answer::Incompats(IncompatsAnswer::GetAll(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Incompats(IncompatsCommand::GetAll),
-        )?
+        let Answer::Incompats(IncompatsAnswer::GetAll(val)) =
+            Self::send_hooked_command(&self_, Command::Incompats(IncompatsCommand::GetAll))?
         else {
             panic!("Bad answer type");
         };
@@ -402,11 +424,8 @@ impl Database {
     }
 
     fn incompats_get(self_: PyRef<'_, Self>, handle: IncompatHandle) -> PyResult<Incompat> {
-        let Answer::Incompats(IncompatsAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Incompats(IncompatsCommand::Get(handle)),
-        )?
+        let Answer::Incompats(IncompatsAnswer::Get(val)) =
+            Self::send_hooked_command(&self_, Command::Incompats(IncompatsCommand::Get(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -415,9 +434,8 @@ impl Database {
     }
 
     fn incompats_create(self_: PyRef<'_, Self>, incompat: Incompat) -> PyResult<IncompatHandle> {
-        let Answer::Incompats(IncompatsAnswer::Create(handle)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::Incompats(IncompatsAnswer::Create(handle)) = Self::send_hooked_command(
+            &self_,
             Command::Incompats(IncompatsCommand::Create(incompat)),
         )?
         else {
@@ -432,9 +450,8 @@ impl Database {
         handle: IncompatHandle,
         incompat: Incompat,
     ) -> PyResult<()> {
-        let Answer::Incompats(IncompatsAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::Incompats(IncompatsAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::Incompats(IncompatsCommand::Update(handle, incompat)),
         )?
         else {
@@ -445,9 +462,8 @@ impl Database {
     }
 
     fn incompats_remove(self_: PyRef<'_, Self>, handle: IncompatHandle) -> PyResult<()> {
-        let Answer::Incompats(IncompatsAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::Incompats(IncompatsAnswer::Remove) = Self::send_hooked_command(
+            &self_,
             Command::Incompats(IncompatsCommand::Remove(handle)),
         )?
         else {
@@ -460,11 +476,8 @@ impl Database {
     fn group_lists_get_all(
         self_: PyRef<'_, Self>,
     ) -> PyResult<BTreeMap<GroupListHandle, GroupList>> {
-        let Answer::GroupLists(GroupListsAnswer::GetAll(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::GroupLists(GroupListsCommand::GetAll),
-        )?
+        let Answer::GroupLists(GroupListsAnswer::GetAll(val)) =
+            Self::send_hooked_command(&self_, Command::GroupLists(GroupListsCommand::GetAll))?
         else {
             panic!("Bad answer type");
         };
@@ -473,11 +486,8 @@ impl Database {
     }
 
     fn group_lists_get(self_: PyRef<'_, Self>, handle: GroupListHandle) -> PyResult<GroupList> {
-        let Answer::GroupLists(GroupListsAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::GroupLists(GroupListsCommand::Get(handle)),
-        )?
+        let Answer::GroupLists(GroupListsAnswer::Get(val)) =
+            Self::send_hooked_command(&self_, Command::GroupLists(GroupListsCommand::Get(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -489,9 +499,8 @@ impl Database {
         self_: PyRef<'_, Self>,
         group_list: GroupList,
     ) -> PyResult<GroupListHandle> {
-        let Answer::GroupLists(GroupListsAnswer::Create(handle)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::GroupLists(GroupListsAnswer::Create(handle)) = Self::send_hooked_command(
+            &self_,
             Command::GroupLists(GroupListsCommand::Create(group_list)),
         )?
         else {
@@ -506,9 +515,8 @@ impl Database {
         handle: GroupListHandle,
         group_list: GroupList,
     ) -> PyResult<()> {
-        let Answer::GroupLists(GroupListsAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::GroupLists(GroupListsAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::GroupLists(GroupListsCommand::Update(handle, group_list)),
         )?
         else {
@@ -519,9 +527,8 @@ impl Database {
     }
 
     fn group_lists_remove(self_: PyRef<'_, Self>, handle: GroupListHandle) -> PyResult<()> {
-        let Answer::GroupLists(GroupListsAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::GroupLists(GroupListsAnswer::Remove) = Self::send_hooked_command(
+            &self_,
             Command::GroupLists(GroupListsCommand::Remove(handle)),
         )?
         else {
@@ -532,11 +539,8 @@ impl Database {
     }
 
     fn subjects_get_all(self_: PyRef<'_, Self>) -> PyResult<BTreeMap<SubjectHandle, Subject>> {
-        let Answer::Subjects(SubjectsAnswer::GetAll(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Subjects(SubjectsCommand::GetAll),
-        )?
+        let Answer::Subjects(SubjectsAnswer::GetAll(val)) =
+            Self::send_hooked_command(&self_, Command::Subjects(SubjectsCommand::GetAll))?
         else {
             panic!("Bad answer type");
         };
@@ -545,11 +549,8 @@ impl Database {
     }
 
     fn subjects_get(self_: PyRef<'_, Self>, handle: SubjectHandle) -> PyResult<Subject> {
-        let Answer::Subjects(SubjectsAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Subjects(SubjectsCommand::Get(handle)),
-        )?
+        let Answer::Subjects(SubjectsAnswer::Get(val)) =
+            Self::send_hooked_command(&self_, Command::Subjects(SubjectsCommand::Get(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -558,11 +559,8 @@ impl Database {
     }
 
     fn subjects_create(self_: PyRef<'_, Self>, subject: Subject) -> PyResult<SubjectHandle> {
-        let Answer::Subjects(SubjectsAnswer::Create(handle)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Subjects(SubjectsCommand::Create(subject)),
-        )?
+        let Answer::Subjects(SubjectsAnswer::Create(handle)) =
+            Self::send_hooked_command(&self_, Command::Subjects(SubjectsCommand::Create(subject)))?
         else {
             panic!("Bad answer type");
         };
@@ -575,9 +573,8 @@ impl Database {
         handle: SubjectHandle,
         subject: Subject,
     ) -> PyResult<()> {
-        let Answer::Subjects(SubjectsAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::Subjects(SubjectsAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::Subjects(SubjectsCommand::Update(handle, subject)),
         )?
         else {
@@ -588,11 +585,8 @@ impl Database {
     }
 
     fn subjects_remove(self_: PyRef<'_, Self>, handle: SubjectHandle) -> PyResult<()> {
-        let Answer::Subjects(SubjectsAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Subjects(SubjectsCommand::Remove(handle)),
-        )?
+        let Answer::Subjects(SubjectsAnswer::Remove) =
+            Self::send_hooked_command(&self_, Command::Subjects(SubjectsCommand::Remove(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -601,11 +595,8 @@ impl Database {
     }
 
     fn time_slots_get_all(self_: PyRef<'_, Self>) -> PyResult<BTreeMap<TimeSlotHandle, TimeSlot>> {
-        let Answer::TimeSlots(TimeSlotsAnswer::GetAll(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::TimeSlots(TimeSlotsCommand::GetAll),
-        )?
+        let Answer::TimeSlots(TimeSlotsAnswer::GetAll(val)) =
+            Self::send_hooked_command(&self_, Command::TimeSlots(TimeSlotsCommand::GetAll))?
         else {
             panic!("Bad answer type");
         };
@@ -614,11 +605,8 @@ impl Database {
     }
 
     fn time_slots_get(self_: PyRef<'_, Self>, handle: TimeSlotHandle) -> PyResult<TimeSlot> {
-        let Answer::TimeSlots(TimeSlotsAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::TimeSlots(TimeSlotsCommand::Get(handle)),
-        )?
+        let Answer::TimeSlots(TimeSlotsAnswer::Get(val)) =
+            Self::send_hooked_command(&self_, Command::TimeSlots(TimeSlotsCommand::Get(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -627,9 +615,8 @@ impl Database {
     }
 
     fn time_slots_create(self_: PyRef<'_, Self>, time_slot: TimeSlot) -> PyResult<TimeSlotHandle> {
-        let Answer::TimeSlots(TimeSlotsAnswer::Create(handle)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::TimeSlots(TimeSlotsAnswer::Create(handle)) = Self::send_hooked_command(
+            &self_,
             Command::TimeSlots(TimeSlotsCommand::Create(time_slot)),
         )?
         else {
@@ -644,9 +631,8 @@ impl Database {
         handle: TimeSlotHandle,
         time_slot: TimeSlot,
     ) -> PyResult<()> {
-        let Answer::TimeSlots(TimeSlotsAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::TimeSlots(TimeSlotsAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::TimeSlots(TimeSlotsCommand::Update(handle, time_slot)),
         )?
         else {
@@ -657,9 +643,8 @@ impl Database {
     }
 
     fn time_slots_remove(self_: PyRef<'_, Self>, handle: TimeSlotHandle) -> PyResult<()> {
-        let Answer::TimeSlots(TimeSlotsAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::TimeSlots(TimeSlotsAnswer::Remove) = Self::send_hooked_command(
+            &self_,
             Command::TimeSlots(TimeSlotsCommand::Remove(handle)),
         )?
         else {
@@ -670,11 +655,8 @@ impl Database {
     }
 
     fn groupings_get_all(self_: PyRef<'_, Self>) -> PyResult<BTreeMap<GroupingHandle, Grouping>> {
-        let Answer::Groupings(GroupingsAnswer::GetAll(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Groupings(GroupingsCommand::GetAll),
-        )?
+        let Answer::Groupings(GroupingsAnswer::GetAll(val)) =
+            Self::send_hooked_command(&self_, Command::Groupings(GroupingsCommand::GetAll))?
         else {
             panic!("Bad answer type");
         };
@@ -683,11 +665,8 @@ impl Database {
     }
 
     fn groupings_get(self_: PyRef<'_, Self>, handle: GroupingHandle) -> PyResult<Grouping> {
-        let Answer::Groupings(GroupingsAnswer::Get(val)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
-            Command::Groupings(GroupingsCommand::Get(handle)),
-        )?
+        let Answer::Groupings(GroupingsAnswer::Get(val)) =
+            Self::send_hooked_command(&self_, Command::Groupings(GroupingsCommand::Get(handle)))?
         else {
             panic!("Bad answer type");
         };
@@ -696,9 +675,8 @@ impl Database {
     }
 
     fn groupings_create(self_: PyRef<'_, Self>, grouping: Grouping) -> PyResult<GroupingHandle> {
-        let Answer::Groupings(GroupingsAnswer::Create(handle)) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::Groupings(GroupingsAnswer::Create(handle)) = Self::send_hooked_command(
+            &self_,
             Command::Groupings(GroupingsCommand::Create(grouping)),
         )?
         else {
@@ -713,9 +691,8 @@ impl Database {
         handle: GroupingHandle,
         grouping: Grouping,
     ) -> PyResult<()> {
-        let Answer::Groupings(GroupingsAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::Groupings(GroupingsAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::Groupings(GroupingsCommand::Update(handle, grouping)),
         )?
         else {
@@ -726,9 +703,8 @@ impl Database {
     }
 
     fn groupings_remove(self_: PyRef<'_, Self>, handle: GroupingHandle) -> PyResult<()> {
-        let Answer::Groupings(GroupingsAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::Groupings(GroupingsAnswer::Remove) = Self::send_hooked_command(
+            &self_,
             Command::Groupings(GroupingsCommand::Remove(handle)),
         )?
         else {
@@ -742,9 +718,8 @@ impl Database {
         self_: PyRef<'_, Self>,
     ) -> PyResult<BTreeMap<GroupingIncompatHandle, GroupingIncompat>> {
         let Answer::GroupingIncompats(GroupingIncompatsAnswer::GetAll(val)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
+            Self::send_hooked_command(
+                &self_,
                 Command::GroupingIncompats(GroupingIncompatsCommand::GetAll),
             )?
         else {
@@ -759,9 +734,8 @@ impl Database {
         handle: GroupingIncompatHandle,
     ) -> PyResult<GroupingIncompat> {
         let Answer::GroupingIncompats(GroupingIncompatsAnswer::Get(val)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
+            Self::send_hooked_command(
+                &self_,
                 Command::GroupingIncompats(GroupingIncompatsCommand::Get(handle)),
             )?
         else {
@@ -776,9 +750,8 @@ impl Database {
         grouping_incompat: GroupingIncompat,
     ) -> PyResult<GroupingIncompatHandle> {
         let Answer::GroupingIncompats(GroupingIncompatsAnswer::Create(handle)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
+            Self::send_hooked_command(
+                &self_,
                 Command::GroupingIncompats(GroupingIncompatsCommand::Create(grouping_incompat)),
             )?
         else {
@@ -793,15 +766,10 @@ impl Database {
         handle: GroupingIncompatHandle,
         grouping_incompat: GroupingIncompat,
     ) -> PyResult<()> {
-        let Answer::GroupingIncompats(GroupingIncompatsAnswer::Update) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
-                Command::GroupingIncompats(GroupingIncompatsCommand::Update(
-                    handle,
-                    grouping_incompat,
-                )),
-            )?
+        let Answer::GroupingIncompats(GroupingIncompatsAnswer::Update) = Self::send_hooked_command(
+            &self_,
+            Command::GroupingIncompats(GroupingIncompatsCommand::Update(handle, grouping_incompat)),
+        )?
         else {
             panic!("Bad answer type");
         };
@@ -813,12 +781,10 @@ impl Database {
         self_: PyRef<'_, Self>,
         handle: GroupingIncompatHandle,
     ) -> PyResult<()> {
-        let Answer::GroupingIncompats(GroupingIncompatsAnswer::Remove) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
-                Command::GroupingIncompats(GroupingIncompatsCommand::Remove(handle)),
-            )?
+        let Answer::GroupingIncompats(GroupingIncompatsAnswer::Remove) = Self::send_hooked_command(
+            &self_,
+            Command::GroupingIncompats(GroupingIncompatsCommand::Remove(handle)),
+        )?
         else {
             panic!("Bad answer type");
         };
@@ -832,9 +798,8 @@ impl Database {
         subject_group_handle: SubjectGroupHandle,
     ) -> PyResult<Option<SubjectHandle>> {
         let Answer::RegisterStudent(RegisterStudentAnswer::InSubjectGroupGet(val)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
+            Self::send_hooked_command(
+                &self_,
                 Command::RegisterStudent(RegisterStudentCommand::InSubjectGroupGet(
                     student_handle,
                     subject_group_handle,
@@ -855,9 +820,8 @@ impl Database {
         subject_handle: Option<SubjectHandle>,
     ) -> PyResult<()> {
         let Answer::RegisterStudent(RegisterStudentAnswer::InSubjectGroupSet) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
+            Self::send_hooked_command(
+                &self_,
                 Command::RegisterStudent(RegisterStudentCommand::InSubjectGroupSet(
                     student_handle,
                     subject_group_handle,
@@ -877,9 +841,8 @@ impl Database {
         incompat_handle: IncompatHandle,
     ) -> PyResult<bool> {
         let Answer::RegisterStudent(RegisterStudentAnswer::InIncompatGet(val)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
+            Self::send_hooked_command(
+                &self_,
                 Command::RegisterStudent(RegisterStudentCommand::InIncompatGet(
                     student_handle,
                     incompat_handle,
@@ -899,9 +862,8 @@ impl Database {
         enabled: bool,
     ) -> PyResult<()> {
         let Answer::RegisterStudent(RegisterStudentAnswer::InIncompatSet) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
+            Self::send_hooked_command(
+                &self_,
                 Command::RegisterStudent(RegisterStudentCommand::InIncompatSet(
                     student_handle,
                     incompat_handle,
@@ -915,15 +877,212 @@ impl Database {
         Ok(())
     }
 
+    fn colloscopes_get_all(
+        self_: PyRef<'_, Self>,
+    ) -> PyResult<BTreeMap<ColloscopeHandle, Colloscope>> {
+        let Answer::Colloscopes(ColloscopesAnswer::GetAll(val)) =
+            Self::send_hooked_command(&self_, Command::Colloscopes(ColloscopesCommand::GetAll))?
+        else {
+            panic!("Bad answer type");
+        };
+
+        Ok(val)
+    }
+
+    fn colloscopes_get(self_: PyRef<'_, Self>, handle: ColloscopeHandle) -> PyResult<Colloscope> {
+        let Answer::Colloscopes(ColloscopesAnswer::Get(val)) = Self::send_hooked_command(
+            &self_,
+            Command::Colloscopes(ColloscopesCommand::Get(handle)),
+        )?
+        else {
+            panic!("Bad answer type");
+        };
+
+        Ok(val)
+    }
+
+    fn colloscopes_create(
+        self_: PyRef<'_, Self>,
+        colloscope: Colloscope,
+    ) -> PyResult<ColloscopeHandle> {
+        let Answer::Colloscopes(ColloscopesAnswer::Create(handle)) = Self::send_hooked_command(
+            &self_,
+            Command::Colloscopes(ColloscopesCommand::Create(colloscope)),
+        )?
+        else {
+            panic!("Bad answer type");
+        };
+
+        Ok(handle)
+    }
+
+    fn colloscopes_update(
+        self_: PyRef<'_, Self>,
+        handle: ColloscopeHandle,
+        colloscope: Colloscope,
+    ) -> PyResult<()> {
+        let Answer::Colloscopes(ColloscopesAnswer::Update) = Self::send_hooked_command(
+            &self_,
+            Command::Colloscopes(ColloscopesCommand::Update(handle, colloscope)),
+        )?
+        else {
+            panic!("Bad answer type");
+        };
+
+        Ok(())
+    }
+
+    fn colloscopes_remove(self_: PyRef<'_, Self>, handle: ColloscopeHandle) -> PyResult<()> {
+        let Answer::Colloscopes(ColloscopesAnswer::Remove) = Self::send_hooked_command(
+            &self_,
+            Command::Colloscopes(ColloscopesCommand::Remove(handle)),
+        )?
+        else {
+            panic!("Bad answer type");
+        };
+
+        Ok(())
+    }
+
+    // Raises ValueError if a constraint refers to a variable that does not exist
+    fn check_constraints(self_: PyRef<'_, Self>, constraints: Vec<LinConstraint>) -> PyResult<()> {
+        let Answer::Solve(SolveAnswer::Check) =
+            Self::send_hooked_command(&self_, Command::Solve(SolveCommand::Check(constraints)))?
+        else {
+            panic!("Bad answer type");
+        };
+
+        Ok(())
+    }
+
+    // The profile gives the defaults: minimize_objective and time_limit (in seconds,
+    // 0 for no limit) override them when given
+    #[pyo3(signature = (minimize_objective = None, time_limit = None, progress = None, constraints = vec![], profile = "balanced"))]
+    fn start_solve(
+        self_: PyRef<'_, Self>,
+        minimize_objective: Option<bool>,
+        time_limit: Option<u32>,
+        progress: Option<PyObject>,
+        constraints: Vec<LinConstraint>,
+        profile: &str,
+    ) -> PyResult<SolveJob> {
+        use crate::ilp::solvers::SolverProfile;
+
+        let profile = SolverProfile::from_name(profile).ok_or_else(|| {
+            let names: Vec<_> = SolverProfile::ALL.iter().map(|p| p.name()).collect();
+            PyValueError::new_err(format!(
+                "Unknown solver profile \"{}\" (expected one of: {})",
+                profile,
+                names.join(", ")
+            ))
+        })?;
+        let mut options = profile.options();
+        if let Some(time_limit) = time_limit {
+            options.time_limit_in_seconds = if time_limit == 0 {
+                None
+            } else {
+                Some(time_limit)
+            };
+        }
+
+        let Answer::Solve(SolveAnswer::Prepare(problem, hint)) =
+            Self::send_hooked_command(&self_, Command::Solve(SolveCommand::Prepare(constraints)))?
+        else {
+            panic!("Bad answer type");
+        };
+
+        Ok(SolveJob::start(
+            self_.into(),
+            &problem,
+            hint,
+            minimize_objective.unwrap_or(profile.minimize_objective()),
+            options,
+            progress,
+        ))
+    }
+
+    // None if a colloscope can be built. Otherwise, a minimal set of conflicting
+    // constraints grouped by kind, with the objects they involve
+    #[pyo3(signature = (constraints = vec![]))]
+    fn diagnose_infeasability(
+        self_: PyRef<'_, Self>,
+        constraints: Vec<LinConstraint>,
+    ) -> PyResult<Option<PyObject>> {
+        let Answer::Solve(SolveAnswer::Diagnose(explanation)) =
+            Self::send_hooked_command(&self_, Command::Solve(SolveCommand::Diagnose(constraints)))?
+        else {
+            panic!("Bad answer type");
+        };
+
+        explanation
+            .map(|explanation| {
+                Ok(infeasability_to_dict(self_.py(), &explanation)?.into_py(self_.py()))
+            })
+            .transpose()
+    }
+
+    // Model that start_solve would give to the solver
+    #[pyo3(signature = (constraints = vec![]))]
+    fn ilp_model(self_: PyRef<'_, Self>, constraints: Vec<LinConstraint>) -> PyResult<IlpModel> {
+        let Answer::Solve(SolveAnswer::Prepare(problem, hint)) =
+            Self::send_hooked_command(&self_, Command::Solve(SolveCommand::Prepare(constraints)))?
+        else {
+            panic!("Bad answer type");
+        };
+
+        Ok(IlpModel::new(*problem, hint))
+    }
+
+    #[pyo3(signature = (library = "pandas"))]
+    fn students_dataframe(self_: PyRef<'_, Self>, library: &str) -> PyResult<PyObject> {
+        let Answer::Students(StudentsAnswer::GetAll(students)) =
+            Self::send_hooked_command(&self_, Command::Students(StudentsCommand::GetAll))?
+        else {
+            panic!("Bad answer type");
+        };
+
+        students_table(self_.py(), &students).into_dataframe(self_.py(), library)
+    }
+
+    #[pyo3(signature = (library = "pandas"))]
+    fn time_slots_dataframe(self_: PyRef<'_, Self>, library: &str) -> PyResult<PyObject> {
+        let Answer::TimeSlots(TimeSlotsAnswer::GetAll(time_slots)) =
+            Self::send_hooked_command(&self_, Command::TimeSlots(TimeSlotsCommand::GetAll))?
+        else {
+            panic!("Bad answer type");
+        };
+        let (subjects, teachers) = Self::subjects_and_teachers(&self_)?;
+
+        time_slots_table(self_.py(), &time_slots, &subjects, &teachers)
+            .into_dataframe(self_.py(), library)
+    }
+
+    #[pyo3(signature = (handle, library = "pandas"))]
+    fn colloscope_dataframe(
+        self_: PyRef<'_, Self>,
+        handle: ColloscopeHandle,
+        library: &str,
+    ) -> PyResult<PyObject> {
+        let Answer::Colloscopes(ColloscopesAnswer::Get(colloscope)) = Self::send_hooked_command(
+            &self_,
+            Command::Colloscopes(ColloscopesCommand::Get(handle)),
+        )?
+        else {
+            panic!("Bad answer type");
+        };
+        let (subjects, teachers) = Self::subjects_and_teachers(&self_)?;
+
+        colloscope_table(self_.py(), &colloscope, &subjects, &teachers)
+            .into_dataframe(self_.py(), library)
+    }
+
     fn slot_selections_get_all(
         self_: PyRef<'_, Self>,
     ) -> PyResult<BTreeMap<SlotSelectionHandle, SlotSelection>> {
-        let Answer::SlotSelections(SlotSelectionsAnswer::GetAll(val)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
-                Command::SlotSelections(SlotSelectionsCommand::GetAll),
-            )?
+        let Answer::SlotSelections(SlotSelectionsAnswer::GetAll(val)) = Self::send_hooked_command(
+            &self_,
+            Command::SlotSelections(SlotSelectionsCommand::GetAll),
+        )?
         else {
             panic!("Bad answer type");
         };
@@ -935,12 +1094,10 @@ impl Database {
         self_: PyRef<'_, Self>,
         handle: SlotSelectionHandle,
     ) -> PyResult<SlotSelection> {
-        let Answer::SlotSelections(SlotSelectionsAnswer::Get(val)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
-                Command::SlotSelections(SlotSelectionsCommand::Get(handle)),
-            )?
+        let Answer::SlotSelections(SlotSelectionsAnswer::Get(val)) = Self::send_hooked_command(
+            &self_,
+            Command::SlotSelections(SlotSelectionsCommand::Get(handle)),
+        )?
         else {
             panic!("Bad answer type");
         };
@@ -953,9 +1110,8 @@ impl Database {
         slot_selection: SlotSelection,
     ) -> PyResult<SlotSelectionHandle> {
         let Answer::SlotSelections(SlotSelectionsAnswer::Create(handle)) =
-            SessionConnection::send_command(
-                self_.py(),
-                &self_.sender,
+            Self::send_hooked_command(
+                &self_,
                 Command::SlotSelections(SlotSelectionsCommand::Create(slot_selection)),
             )?
         else {
@@ -970,9 +1126,8 @@ impl Database {
         handle: SlotSelectionHandle,
         grouping_incompat: SlotSelection,
     ) -> PyResult<()> {
-        let Answer::SlotSelections(SlotSelectionsAnswer::Update) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::SlotSelections(SlotSelectionsAnswer::Update) = Self::send_hooked_command(
+            &self_,
             Command::SlotSelections(SlotSelectionsCommand::Update(handle, grouping_incompat)),
         )?
         else {
@@ -983,9 +1138,8 @@ impl Database {
     }
 
     fn slot_selections_remove(self_: PyRef<'_, Self>, handle: SlotSelectionHandle) -> PyResult<()> {
-        let Answer::SlotSelections(SlotSelectionsAnswer::Remove) = SessionConnection::send_command(
-            self_.py(),
-            &self_.sender,
+        let Answer::SlotSelections(SlotSelectionsAnswer::Remove) = Self::send_hooked_command(
+            &self_,
             Command::SlotSelections(SlotSelectionsCommand::Remove(handle)),
         )?
         else {
@@ -996,6 +1150,34 @@ impl Database {
     }
 }
 
+impl Database {
+    fn subjects_and_teachers(
+        self_: &PyRef<'_, Self>,
+    ) -> PyResult<(
+        BTreeMap<SubjectHandle, Subject>,
+        BTreeMap<TeacherHandle, Teacher>,
+    )> {
+        let Answer::Subjects(SubjectsAnswer::GetAll(subjects)) = SessionConnection::send_command(
+            self_.py(),
+            &self_.sender,
+            Command::Subjects(SubjectsCommand::GetAll),
+        )?
+        else {
+            panic!("Bad answer type");
+        };
+        let Answer::Teachers(TeachersAnswer::GetAll(teachers)) = SessionConnection::send_command(
+            self_.py(),
+            &self_.sender,
+            Command::Teachers(TeachersCommand::GetAll),
+        )?
+        else {
+            panic!("Bad answer type");
+        };
+
+        Ok((subjects, teachers))
+    }
+}
+
 use std::sync::mpsc::{self, Receiver, Sender};
 
 use crate::backend::{self, Id2Error, IdError};
@@ -1016,9 +1198,12 @@ pub enum Command {
     Groupings(GroupingsCommand),
     GroupingIncompats(GroupingIncompatsCommand),
     RegisterStudent(RegisterStudentCommand),
+    Colloscopes(ColloscopesCommand),
     SlotSelections(SlotSelectionsCommand),
+    Solve(SolveCommand),
     Undo,
     Redo,
+    History,
     Exit,
 }
 
@@ -1126,6 +1311,15 @@ pub enum RegisterStudentCommand {
     InIncompatSet(StudentHandle, IncompatHandle, bool),
 }
 
+#[derive(Debug, Clone)]
+pub enum ColloscopesCommand {
+    GetAll,
+    Get(ColloscopeHandle),
+    Create(Colloscope),
+    Update(ColloscopeHandle, Colloscope),
+    Remove(ColloscopeHandle),
+}
+
 #[derive(Debug, Clone)]
 pub enum SlotSelectionsCommand {
     GetAll,
@@ -1135,6 +1329,14 @@ pub enum SlotSelectionsCommand {
     Remove(SlotSelectionHandle),
 }
 
+#[derive(Debug, Clone)]
+pub enum SolveCommand {
+    Check(Vec<LinConstraint>),
+    Prepare(Vec<LinConstraint>),
+    Diagnose(Vec<LinConstraint>),
+    Store(BTreeMap<crate::gen::colloscope::Variable, bool>, String),
+}
+
 #[derive(Debug)]
 struct PythonError {
     int_err: Box<dyn std::error::Error + Send>,
@@ -1162,9 +1364,12 @@ pub enum Answer {
     Groupings(GroupingsAnswer),
     GroupingIncompats(GroupingIncompatsAnswer),
     RegisterStudent(RegisterStudentAnswer),
+    Colloscopes(ColloscopesAnswer),
     SlotSelections(SlotSelectionsAnswer),
+    Solve(SolveAnswer),
     Undo,
     Redo,
+    History(state::AggregatedOperations),
 }
 
 #[derive(Debug)]
@@ -1271,6 +1476,15 @@ pub enum RegisterStudentAnswer {
     InIncompatSet,
 }
 
+#[derive(Debug)]
+pub enum ColloscopesAnswer {
+    GetAll(BTreeMap<ColloscopeHandle, Colloscope>),
+    Get(Colloscope),
+    Create(ColloscopeHandle),
+    Update,
+    Remove,
+}
+
 #[derive(Debug)]
 pub enum SlotSelectionsAnswer {
     GetAll(BTreeMap<SlotSelectionHandle, SlotSelection>),
@@ -1280,6 +1494,18 @@ pub enum SlotSelectionsAnswer {
     Remove,
 }
 
+#[derive(Debug)]
+pub enum SolveAnswer {
+    Check,
+    // The problem is boxed to keep answers small
+    Prepare(
+        Box<crate::ilp::Problem<crate::gen::colloscope::Variable>>,
+        BTreeMap<crate::gen::colloscope::Variable, bool>,
+    ),
+    Diagnose(Option<crate::frontend::translator::InfeasabilityExplanationRef>),
+    Store(ColloscopeHandle),
+}
+
 #[derive(Debug)]
 pub struct Job {
     command: Command,
@@ -1323,6 +1549,9 @@ impl<'scope> SessionConnection<'scope> {
     pub fn python_database(&self) -> Database {
         Database {
             sender: self.queue_sender.clone(),
+            hooks: Default::default(),
+            hooks_enabled: std::cell::Cell::new(true),
+            observers: Default::default(),
         }
     }
 
@@ -2476,6 +2705,110 @@ impl<'scope> SessionConnection<'scope> {
         }
     }
 
+    async fn execute_colloscopes_job<T: state::Manager>(
+        colloscopes_command: &ColloscopesCommand,
+        manager: &mut T,
+    ) -> PyResult<ColloscopesAnswer> {
+        let bad_reference_error = |e| match e {
+            UpdateError::ColloscopeBadTeacher(teacher_handle) => {
+                Some(PyValueError::new_err(format!(
+                    "Colloscope references a bad teacher handle {:?}",
+                    teacher_handle
+                )))
+            }
+            UpdateError::ColloscopeBadSubject(subject_handle) => {
+                Some(PyValueError::new_err(format!(
+                    "Colloscope references a bad subject handle {:?}",
+                    subject_handle
+                )))
+            }
+            UpdateError::ColloscopeBadStudent(student_handle) => {
+                Some(PyValueError::new_err(format!(
+                    "Colloscope references a bad student handle {:?}",
+                    student_handle
+                )))
+            }
+            _ => None,
+        };
+
+        match colloscopes_command {
+            ColloscopesCommand::GetAll => {
+                let result = manager
+                    .colloscopes_get_all()
+                    .await
+                    .map_err(|e| PyException::new_err(e.to_string()))?
+                    .into_iter()
+                    .map(|(handle, colloscope)| (handle.into(), Colloscope::from(colloscope)))
+                    .collect::<BTreeMap<_, _>>();
+
+                Ok(ColloscopesAnswer::GetAll(result))
+            }
+            ColloscopesCommand::Get(handle) => {
+                let result = manager
+                    .colloscopes_get(handle.handle)
+                    .await
+                    .map_err(|e| match e {
+                        IdError::InternalError(int_err) => {
+                            PyException::new_err(int_err.to_string())
+                        }
+                        IdError::InvalidId(_) => PyValueError::new_err("Invalid handle"),
+                    })?;
+
+                Ok(ColloscopesAnswer::Get(result.into()))
+            }
+            ColloscopesCommand::Create(colloscope) => {
+                let output = manager
+                    .apply(Operation::Colloscopes(state::ColloscopesOperation::Create(
+                        colloscope.into(),
+                    )))
+                    .await
+                    .map_err(|e| match e {
+                        UpdateError::Internal(int_err) => PyException::new_err(int_err.to_string()),
+                        e => bad_reference_error(e).expect("Unexpected error!"),
+                    })?;
+
+                let ReturnHandle::Colloscope(handle) = output else {
+                    panic!("No colloscope handle returned on ColloscopesCommand::Create");
+                };
+
+                Ok(ColloscopesAnswer::Create(handle.into()))
+            }
+            ColloscopesCommand::Update(handle, colloscope) => {
+                manager
+                    .apply(Operation::Colloscopes(state::ColloscopesOperation::Update(
+                        handle.handle,
+                        colloscope.into(),
+                    )))
+                    .await
+                    .map_err(|e| match e {
+                        UpdateError::Internal(int_err) => PyException::new_err(int_err.to_string()),
+                        UpdateError::ColloscopeRemoved(_) => {
+                            PyValueError::new_err("Colloscope was previously removed")
+                        }
+                        e => bad_reference_error(e).expect("Unexpected error!"),
+                    })?;
+
+                Ok(ColloscopesAnswer::Update)
+            }
+            ColloscopesCommand::Remove(handle) => {
+                manager
+                    .apply(Operation::Colloscopes(state::ColloscopesOperation::Remove(
+                        handle.handle,
+                    )))
+                    .await
+                    .map_err(|e| match e {
+                        UpdateError::Internal(int_err) => PyException::new_err(int_err.to_string()),
+                        UpdateError::ColloscopeRemoved(_) => {
+                            PyValueError::new_err("Colloscope was previously removed")
+                        }
+                        _ => panic!("Unexpected error!"),
+                    })?;
+
+                Ok(ColloscopesAnswer::Remove)
+            }
+        }
+    }
+
     async fn execute_slot_selections_job<T: state::Manager>(
         slot_selections_command: &SlotSelectionsCommand,
         manager: &mut T,
@@ -2584,6 +2917,101 @@ impl<'scope> SessionConnection<'scope> {
         }
     }
 
+    async fn execute_solve_job<T: state::Manager>(
+        solve_command: &SolveCommand,
+        manager: &mut T,
+    ) -> PyResult<SolveAnswer> {
+        use crate::frontend::translator::GenColloscopeTranslator;
+
+        let gen_colloscope_translator = GenColloscopeTranslator::new(manager)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let data = gen_colloscope_translator.get_validated_data();
+        let ilp_translator = data.ilp_translator();
+
+        let problem_with_constraints = |constraints: &Vec<LinConstraint>| {
+            let custom_constraints = constraints
+                .iter()
+                .map(|constraint| constraint.resolve(&gen_colloscope_translator))
+                .collect::<PyResult<Vec<_>>>()?;
+            let problem_builder = ilp_translator
+                .problem_builder()
+                .add_grouped_constraints(custom_constraints, "custom")
+                .map_err(|e| {
+                    PyValueError::new_err(format!(
+                        "Custom constraint uses a variable absent from the problem: {}",
+                        e
+                    ))
+                })?;
+            PyResult::Ok(problem_builder.build())
+        };
+
+        match solve_command {
+            SolveCommand::Check(constraints) => {
+                problem_with_constraints(constraints)?;
+                Ok(SolveAnswer::Check)
+            }
+            SolveCommand::Prepare(constraints) => {
+                let problem = problem_with_constraints(constraints)?;
+
+                // Warm start from a greedy construction
+                let hint = ilp_translator
+                    .config_hint_from_colloscope(&problem, &ilp_translator.greedy_colloscope())
+                    .get_bool_vars();
+
+                Ok(SolveAnswer::Prepare(Box::new(problem), hint))
+            }
+            SolveCommand::Diagnose(constraints) => {
+                let problem = problem_with_constraints(constraints)?;
+                let solver = crate::ilp::solvers::coin_cbc::Solver::with_disable_logging(true);
+
+                // Custom constraints have no kind and do not appear in the explanation
+                let explanation = problem
+                    .compute_iis(&solver, &crate::ilp::solvers::SolverOptions::default())
+                    .map(|iis| {
+                        gen_colloscope_translator.translate_infeasability_explanation(
+                            &ilp_translator.explain_infeasability(&iis),
+                        )
+                    });
+
+                Ok(SolveAnswer::Diagnose(explanation))
+            }
+            SolveCommand::Store(bool_vars, name) => {
+                let problem = ilp_translator.problem();
+                let data_changed_error =
+                    || PyValueError::new_err("Data was modified since the solve was started");
+
+                let feasable_config = problem
+                    .config_from(bool_vars.clone())
+                    .map_err(|_| data_changed_error())?
+                    .into_feasable()
+                    .ok_or_else(data_changed_error)?;
+                let ilp_config = ilp_translator
+                    .read_solution(&feasable_config)
+                    .ok_or_else(data_changed_error)?;
+                let colloscope = gen_colloscope_translator
+                    .translate_colloscope(&ilp_config, name)
+                    .map_err(|_| data_changed_error())?;
+
+                let output = manager
+                    .apply(Operation::Colloscopes(state::ColloscopesOperation::Create(
+                        colloscope,
+                    )))
+                    .await
+                    .map_err(|e| match e {
+                        UpdateError::Internal(int_err) => PyException::new_err(int_err.to_string()),
+                        _ => panic!("Unexpected error!"),
+                    })?;
+
+                let ReturnHandle::Colloscope(handle) = output else {
+                    panic!("No colloscope handle returned on SolveCommand::Store");
+                };
+
+                Ok(SolveAnswer::Store(handle.into()))
+            }
+        }
+    }
+
     async fn execute_job<T: state::Manager>(
         command: &Command,
         manager: &mut T,
@@ -2642,11 +3070,19 @@ impl<'scope> SessionConnection<'scope> {
                     Self::execute_register_student_job(register_student_command, manager).await?;
                 Ok(Answer::RegisterStudent(answer))
             }
+            Command::Colloscopes(colloscopes_command) => {
+                let answer = Self::execute_colloscopes_job(colloscopes_command, manager).await?;
+                Ok(Answer::Colloscopes(answer))
+            }
             Command::SlotSelections(slot_selections_command) => {
                 let answer =
                     Self::execute_slot_selections_job(slot_selections_command, manager).await?;
                 Ok(Answer::SlotSelections(answer))
             }
+            Command::Solve(solve_command) => {
+                let answer = Self::execute_solve_job(solve_command, manager).await?;
+                Ok(Answer::Solve(answer))
+            }
             Command::Undo => {
                 manager.undo().await.map_err(|e| match e {
                     UndoError::HistoryDepleted => PyException::new_err("History depleted"),
@@ -2665,6 +3101,7 @@ impl<'scope> SessionConnection<'scope> {
 
                 Ok(Answer::Redo)
             }
+            Command::History => Ok(Answer::History(manager.get_aggregated_history())),
             Command::Exit => panic!("Exit command should be treated on level above"),
         }
     }
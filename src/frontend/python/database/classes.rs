@@ -4,6 +4,12 @@ use pyo3::types::PyString;
 
 use std::num::{NonZeroU32, NonZeroUsize};
 
+mod constraints;
+mod html;
+mod tables;
+pub use constraints::*;
+pub use tables::*;
+
 #[pyclass(eq)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GeneralData {
@@ -27,6 +33,10 @@ pub struct GeneralData {
     balancing_cost: i32,
     #[pyo3(get, set)]
     consecutive_slots_cost: i32,
+    #[pyo3(get)]
+    soft_constraints: BTreeMap<String, i32>,
+    #[pyo3(get, set)]
+    overnight_incompat_slots: bool,
 }
 
 #[pymethods]
@@ -44,6 +54,8 @@ impl GeneralData {
             interrogations_per_week_range_for_all_students_cost: 1,
             balancing_cost: 1,
             consecutive_slots_cost: 1,
+            soft_constraints: BTreeMap::new(),
+            overnight_incompat_slots: false,
         }
     }
 
@@ -54,7 +66,7 @@ impl GeneralData {
             .map(|x| x.to_string())
             .collect();
         let output = format!(
-            "{{ interrogations_per_week_range = {}, max_interrogations_per_day = {}, week_count = {}, periodicity_cuts = [{}], max_interrogations_per_day_for_single_student_cost = {}, max_interrogations_per_day_for_all_students_cost = {}, interrogations_per_week_range_for_single_student_cost = {}, interrogations_per_week_range_for_all_students_cost = {}, balancing_cost = {}, consecutive_slots_cost = {} }}",
+            "{{ interrogations_per_week_range = {}, max_interrogations_per_day = {}, week_count = {}, periodicity_cuts = [{}], max_interrogations_per_day_for_single_student_cost = {}, max_interrogations_per_day_for_all_students_cost = {}, interrogations_per_week_range_for_single_student_cost = {}, interrogations_per_week_range_for_all_students_cost = {}, balancing_cost = {}, consecutive_slots_cost = {}, soft_constraints = {{{}}}, overnight_incompat_slots = {} }}",
             match self_.interrogations_per_week_range {
                 Some(val) => format!("{}..{}", val.0, val.1 as i64),
                 None => String::from("none"),
@@ -71,10 +83,26 @@ impl GeneralData {
             self_.interrogations_per_week_range_for_all_students_cost,
             self_.balancing_cost,
             self_.consecutive_slots_cost,
+            self_
+                .soft_constraints
+                .iter()
+                .map(|(name, weight)| format!("{}: {}", name, weight))
+                .collect::<Vec<_>>()
+                .join(", "),
+            self_.overnight_incompat_slots,
         );
 
         PyString::new_bound(self_.py(), output.as_str())
     }
+
+    // Raises ValueError for unknown names, kinds that cannot be soft and non-positive weights
+    #[setter]
+    fn set_soft_constraints(&mut self, soft_constraints: BTreeMap<String, i32>) -> PyResult<()> {
+        crate::frontend::translator::validate_soft_constraints(&soft_constraints)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.soft_constraints = soft_constraints;
+        Ok(())
+    }
 }
 
 impl From<&backend::GeneralData> for GeneralData {
@@ -101,6 +129,8 @@ impl From<&backend::GeneralData> for GeneralData {
                 .interrogations_per_week_range_for_all_students,
             balancing_cost: value.costs_adjustments.balancing,
             consecutive_slots_cost: value.costs_adjustments.consecutive_slots,
+            soft_constraints: value.costs_adjustments.soft_constraints.clone(),
+            overnight_incompat_slots: value.overnight_incompat_slots,
         }
     }
 }
@@ -131,7 +161,9 @@ impl From<&GeneralData> for backend::GeneralData {
                     .interrogations_per_week_range_for_all_students_cost,
                 balancing: value.balancing_cost,
                 consecutive_slots: value.consecutive_slots_cost,
+                soft_constraints: value.soft_constraints.clone(),
             },
+            overnight_incompat_slots: value.overnight_incompat_slots,
         }
     }
 }
@@ -845,15 +877,40 @@ impl std::fmt::Display for IncompatSlot {
     }
 }
 
+// Durations can be given in minutes or as text ("1h30", "2h", "90")
+#[derive(Debug, Clone, FromPyObject)]
+pub enum DurationOrStr {
+    Minutes(NonZeroU32),
+    Text(String),
+}
+
+impl TryFrom<DurationOrStr> for NonZeroU32 {
+    type Error = PyErr;
+
+    fn try_from(value: DurationOrStr) -> PyResult<Self> {
+        match value {
+            DurationOrStr::Minutes(minutes) => Ok(minutes),
+            DurationOrStr::Text(text) => text
+                .parse::<crate::time::NonZeroDurationInMinutes>()
+                .map(NonZeroU32::from)
+                .map_err(|e| PyValueError::new_err(e.to_string())),
+        }
+    }
+}
+
 #[pymethods]
 impl IncompatSlot {
     #[new]
-    fn new(week_pattern_handle: WeekPatternHandle, start: SlotStart, duration: NonZeroU32) -> Self {
-        IncompatSlot {
+    fn new(
+        week_pattern_handle: WeekPatternHandle,
+        start: SlotStart,
+        duration: DurationOrStr,
+    ) -> PyResult<Self> {
+        Ok(IncompatSlot {
             week_pattern_handle,
             start,
-            duration,
-        }
+            duration: duration.try_into()?,
+        })
     }
 
     fn __repr__(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
@@ -2078,3 +2135,404 @@ impl From<SlotSelection> for backend::SlotSelection<state::SubjectHandle, state:
         backend::SlotSelection::from(&value)
     }
 }
+
+#[pyclass(eq, hash, frozen)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ColloscopeHandle {
+    pub handle: state::ColloscopeHandle,
+}
+
+#[pymethods]
+impl ColloscopeHandle {
+    fn __repr__(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
+        let output = format!("{:?}", *self_);
+        PyString::new_bound(self_.py(), output.as_str())
+    }
+}
+
+impl From<&state::ColloscopeHandle> for ColloscopeHandle {
+    fn from(value: &state::ColloscopeHandle) -> Self {
+        ColloscopeHandle { handle: *value }
+    }
+}
+
+impl From<state::ColloscopeHandle> for ColloscopeHandle {
+    fn from(value: state::ColloscopeHandle) -> Self {
+        ColloscopeHandle::from(&value)
+    }
+}
+
+impl From<&ColloscopeHandle> for state::ColloscopeHandle {
+    fn from(value: &ColloscopeHandle) -> Self {
+        value.handle
+    }
+}
+
+impl From<ColloscopeHandle> for state::ColloscopeHandle {
+    fn from(value: ColloscopeHandle) -> Self {
+        state::ColloscopeHandle::from(&value)
+    }
+}
+
+#[pyclass(eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColloscopeTimeSlot {
+    #[pyo3(set, get)]
+    teacher_handle: TeacherHandle,
+    #[pyo3(set, get)]
+    start: SlotStart,
+    #[pyo3(set, get)]
+    room: String,
+    #[pyo3(set, get)]
+    group_assignments: BTreeMap<u32, BTreeSet<usize>>,
+}
+
+impl std::fmt::Display for ColloscopeTimeSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let group_assignments_strings: Vec<_> = self
+            .group_assignments
+            .iter()
+            .map(|(week, groups)| {
+                let groups_strings: Vec<_> = groups.iter().map(|g| g.to_string()).collect();
+                format!("{}: [{}]", week, groups_strings.join(","))
+            })
+            .collect();
+
+        write!(
+            f,
+            "{{ teacher_handle = {:?}, start = {}, room = {}, group_assignments = {{ {} }} }}",
+            self.teacher_handle,
+            self.start,
+            self.room,
+            group_assignments_strings.join(","),
+        )
+    }
+}
+
+#[pymethods]
+impl ColloscopeTimeSlot {
+    #[new]
+    fn new(teacher_handle: TeacherHandle, start: SlotStart) -> Self {
+        ColloscopeTimeSlot {
+            teacher_handle,
+            start,
+            room: String::new(),
+            group_assignments: BTreeMap::new(),
+        }
+    }
+
+    fn __repr__(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
+        PyString::new_bound(self_.py(), self_.to_string().as_str())
+    }
+}
+
+impl From<&backend::ColloscopeTimeSlot<state::TeacherHandle>> for ColloscopeTimeSlot {
+    fn from(value: &backend::ColloscopeTimeSlot<state::TeacherHandle>) -> Self {
+        ColloscopeTimeSlot {
+            teacher_handle: value.teacher_id.into(),
+            start: (&value.start).into(),
+            room: value.room.clone(),
+            group_assignments: value
+                .group_assignments
+                .iter()
+                .map(|(week, groups)| (week.get(), groups.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl From<backend::ColloscopeTimeSlot<state::TeacherHandle>> for ColloscopeTimeSlot {
+    fn from(value: backend::ColloscopeTimeSlot<state::TeacherHandle>) -> Self {
+        ColloscopeTimeSlot::from(&value)
+    }
+}
+
+impl From<&ColloscopeTimeSlot> for backend::ColloscopeTimeSlot<state::TeacherHandle> {
+    fn from(value: &ColloscopeTimeSlot) -> Self {
+        backend::ColloscopeTimeSlot {
+            teacher_id: (&value.teacher_handle).into(),
+            start: (&value.start).into(),
+            room: value.room.clone(),
+            group_assignments: value
+                .group_assignments
+                .iter()
+                .map(|(week, groups)| (backend::Week::new(*week), groups.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl From<ColloscopeTimeSlot> for backend::ColloscopeTimeSlot<state::TeacherHandle> {
+    fn from(value: ColloscopeTimeSlot) -> Self {
+        backend::ColloscopeTimeSlot::from(&value)
+    }
+}
+
+#[pyclass(eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColloscopeGroupList {
+    #[pyo3(set, get)]
+    name: String,
+    #[pyo3(set, get)]
+    groups: Vec<String>,
+    #[pyo3(set, get)]
+    students_mapping: BTreeMap<StudentHandle, usize>,
+}
+
+impl std::fmt::Display for ColloscopeGroupList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let students_mapping_strings: Vec<_> = self
+            .students_mapping
+            .iter()
+            .map(|(student_handle, group)| format!("{:?}: {}", student_handle, group))
+            .collect();
+
+        write!(
+            f,
+            "{{ name = {}, groups = [{}], students_mapping = {{ {} }} }}",
+            self.name,
+            self.groups.join(","),
+            students_mapping_strings.join(","),
+        )
+    }
+}
+
+#[pymethods]
+impl ColloscopeGroupList {
+    #[new]
+    fn new(name: String) -> Self {
+        ColloscopeGroupList {
+            name,
+            groups: Vec::new(),
+            students_mapping: BTreeMap::new(),
+        }
+    }
+
+    fn __repr__(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
+        PyString::new_bound(self_.py(), self_.to_string().as_str())
+    }
+}
+
+impl From<&backend::ColloscopeGroupList<state::StudentHandle>> for ColloscopeGroupList {
+    fn from(value: &backend::ColloscopeGroupList<state::StudentHandle>) -> Self {
+        ColloscopeGroupList {
+            name: value.name.clone(),
+            groups: value.groups.clone(),
+            students_mapping: value
+                .students_mapping
+                .iter()
+                .map(|(y, z)| (y.into(), *z))
+                .collect(),
+        }
+    }
+}
+
+impl From<backend::ColloscopeGroupList<state::StudentHandle>> for ColloscopeGroupList {
+    fn from(value: backend::ColloscopeGroupList<state::StudentHandle>) -> Self {
+        ColloscopeGroupList::from(&value)
+    }
+}
+
+impl From<&ColloscopeGroupList> for backend::ColloscopeGroupList<state::StudentHandle> {
+    fn from(value: &ColloscopeGroupList) -> Self {
+        backend::ColloscopeGroupList {
+            name: value.name.clone(),
+            groups: value.groups.clone(),
+            students_mapping: value
+                .students_mapping
+                .iter()
+                .map(|(y, z)| (y.into(), *z))
+                .collect(),
+        }
+    }
+}
+
+impl From<ColloscopeGroupList> for backend::ColloscopeGroupList<state::StudentHandle> {
+    fn from(value: ColloscopeGroupList) -> Self {
+        backend::ColloscopeGroupList::from(&value)
+    }
+}
+
+#[pyclass(eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColloscopeSubject {
+    #[pyo3(set, get)]
+    time_slots: Vec<ColloscopeTimeSlot>,
+    #[pyo3(set, get)]
+    group_list: ColloscopeGroupList,
+}
+
+impl std::fmt::Display for ColloscopeSubject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let time_slots_strings: Vec<_> = self.time_slots.iter().map(|x| x.to_string()).collect();
+
+        write!(
+            f,
+            "{{ time_slots = [{}], group_list = {} }}",
+            time_slots_strings.join(","),
+            self.group_list,
+        )
+    }
+}
+
+#[pymethods]
+impl ColloscopeSubject {
+    #[new]
+    fn new(group_list: ColloscopeGroupList) -> Self {
+        ColloscopeSubject {
+            time_slots: Vec::new(),
+            group_list,
+        }
+    }
+
+    fn __repr__(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
+        PyString::new_bound(self_.py(), self_.to_string().as_str())
+    }
+
+    fn _repr_html_(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
+        PyString::new_bound(self_.py(), self_.to_html().as_str())
+    }
+}
+
+impl From<&backend::ColloscopeSubject<state::TeacherHandle, state::StudentHandle>>
+    for ColloscopeSubject
+{
+    fn from(
+        value: &backend::ColloscopeSubject<state::TeacherHandle, state::StudentHandle>,
+    ) -> Self {
+        ColloscopeSubject {
+            time_slots: value.time_slots.iter().map(|x| x.into()).collect(),
+            group_list: (&value.group_list).into(),
+        }
+    }
+}
+
+impl From<backend::ColloscopeSubject<state::TeacherHandle, state::StudentHandle>>
+    for ColloscopeSubject
+{
+    fn from(value: backend::ColloscopeSubject<state::TeacherHandle, state::StudentHandle>) -> Self {
+        ColloscopeSubject::from(&value)
+    }
+}
+
+impl From<&ColloscopeSubject>
+    for backend::ColloscopeSubject<state::TeacherHandle, state::StudentHandle>
+{
+    fn from(value: &ColloscopeSubject) -> Self {
+        backend::ColloscopeSubject {
+            time_slots: value.time_slots.iter().map(|x| x.into()).collect(),
+            group_list: (&value.group_list).into(),
+        }
+    }
+}
+
+impl From<ColloscopeSubject>
+    for backend::ColloscopeSubject<state::TeacherHandle, state::StudentHandle>
+{
+    fn from(value: ColloscopeSubject) -> Self {
+        backend::ColloscopeSubject::from(&value)
+    }
+}
+
+#[pyclass(eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Colloscope {
+    #[pyo3(set, get)]
+    name: String,
+    #[pyo3(set, get)]
+    subjects: BTreeMap<SubjectHandle, ColloscopeSubject>,
+}
+
+impl std::fmt::Display for Colloscope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let subjects_strings: Vec<_> = self
+            .subjects
+            .iter()
+            .map(|(subject_handle, subject)| format!("{:?}: {}", subject_handle, subject))
+            .collect();
+
+        write!(
+            f,
+            "{{ name = {}, subjects = {{ {} }} }}",
+            self.name,
+            subjects_strings.join(","),
+        )
+    }
+}
+
+#[pymethods]
+impl Colloscope {
+    #[new]
+    fn new(name: String) -> Self {
+        Colloscope {
+            name,
+            subjects: BTreeMap::new(),
+        }
+    }
+
+    fn __repr__(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
+        PyString::new_bound(self_.py(), self_.to_string().as_str())
+    }
+
+    fn _repr_html_(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
+        PyString::new_bound(self_.py(), self_.to_html().as_str())
+    }
+}
+
+impl From<&backend::Colloscope<state::TeacherHandle, state::SubjectHandle, state::StudentHandle>>
+    for Colloscope
+{
+    fn from(
+        value: &backend::Colloscope<
+            state::TeacherHandle,
+            state::SubjectHandle,
+            state::StudentHandle,
+        >,
+    ) -> Self {
+        Colloscope {
+            name: value.name.clone(),
+            subjects: value
+                .subjects
+                .iter()
+                .map(|(subject_id, subject)| (subject_id.into(), subject.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<backend::Colloscope<state::TeacherHandle, state::SubjectHandle, state::StudentHandle>>
+    for Colloscope
+{
+    fn from(
+        value: backend::Colloscope<
+            state::TeacherHandle,
+            state::SubjectHandle,
+            state::StudentHandle,
+        >,
+    ) -> Self {
+        Colloscope::from(&value)
+    }
+}
+
+impl From<&Colloscope>
+    for backend::Colloscope<state::TeacherHandle, state::SubjectHandle, state::StudentHandle>
+{
+    fn from(value: &Colloscope) -> Self {
+        backend::Colloscope {
+            name: value.name.clone(),
+            subjects: value
+                .subjects
+                .iter()
+                .map(|(subject_handle, subject)| (subject_handle.into(), subject.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<Colloscope>
+    for backend::Colloscope<state::TeacherHandle, state::SubjectHandle, state::StudentHandle>
+{
+    fn from(value: Colloscope) -> Self {
+        backend::Colloscope::from(&value)
+    }
+}
@@ -0,0 +1,256 @@
+use super::*;
+
+use crate::frontend::translator::{GenColloscopeTranslator, VariableRef};
+use crate::gen::colloscope::Variable;
+use crate::ilp::linexpr;
+
+// Linear expression over solver variables, used to add custom constraints to a solve
+#[pyclass(eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LinExpr {
+    coefs: BTreeMap<VariableRef, i32>,
+    constant: i32,
+}
+
+#[derive(Debug, Clone, FromPyObject)]
+pub enum LinExprOrInt {
+    Expr(LinExpr),
+    Int(i32),
+}
+
+impl From<LinExprOrInt> for LinExpr {
+    fn from(value: LinExprOrInt) -> Self {
+        match value {
+            LinExprOrInt::Expr(expr) => expr,
+            LinExprOrInt::Int(constant) => LinExpr {
+                coefs: BTreeMap::new(),
+                constant,
+            },
+        }
+    }
+}
+
+impl LinExpr {
+    fn var(variable: VariableRef) -> Self {
+        LinExpr {
+            coefs: BTreeMap::from([(variable, 1)]),
+            constant: 0,
+        }
+    }
+
+    fn scaled(&self, factor: i32) -> Self {
+        LinExpr {
+            coefs: self
+                .coefs
+                .iter()
+                .filter(|_| factor != 0)
+                .map(|(var, coef)| (var.clone(), coef * factor))
+                .collect(),
+            constant: self.constant * factor,
+        }
+    }
+
+    fn added(&self, other: &LinExpr) -> Self {
+        let mut output = self.clone();
+        for (var, coef) in &other.coefs {
+            *output.coefs.entry(var.clone()).or_default() += coef;
+        }
+        output.coefs.retain(|_var, coef| *coef != 0);
+        output.constant += other.constant;
+        output
+    }
+
+    fn constraint(&self, rhs: LinExprOrInt, sign: linexpr::Sign) -> LinConstraint {
+        LinConstraint {
+            expr: self.added(&LinExpr::from(rhs).scaled(-1)),
+            sign,
+        }
+    }
+
+    pub fn resolve(
+        &self,
+        translator: &GenColloscopeTranslator,
+    ) -> PyResult<linexpr::Expr<Variable>> {
+        let mut output = linexpr::Expr::constant(self.constant);
+        for (var, coef) in &self.coefs {
+            let resolved = translator
+                .resolve_variable(var)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            output = output + *coef * linexpr::Expr::var(resolved);
+        }
+        Ok(output)
+    }
+}
+
+#[pymethods]
+impl LinExpr {
+    #[new]
+    #[pyo3(signature = (constant = 0))]
+    fn new(constant: i32) -> Self {
+        LinExpr {
+            coefs: BTreeMap::new(),
+            constant,
+        }
+    }
+
+    // 1 if the group is assigned to the time slot on the given week (0-based)
+    #[staticmethod]
+    fn group_in_slot(time_slot_handle: TimeSlotHandle, week: u32, group: usize) -> Self {
+        Self::var(VariableRef::GroupInSlot {
+            time_slot: time_slot_handle.handle,
+            week: crate::backend::Week::new(week),
+            group,
+        })
+    }
+
+    // 1 if the student is put in the group of the subject group list
+    #[staticmethod]
+    fn student_in_group(
+        subject_handle: SubjectHandle,
+        student_handle: StudentHandle,
+        group: usize,
+    ) -> Self {
+        Self::var(VariableRef::StudentInGroup {
+            subject: subject_handle.handle,
+            student: student_handle.handle,
+            group,
+        })
+    }
+
+    fn __add__(&self, other: LinExprOrInt) -> Self {
+        self.added(&other.into())
+    }
+
+    fn __radd__(&self, other: LinExprOrInt) -> Self {
+        self.added(&other.into())
+    }
+
+    fn __sub__(&self, other: LinExprOrInt) -> Self {
+        self.added(&LinExpr::from(other).scaled(-1))
+    }
+
+    fn __rsub__(&self, other: LinExprOrInt) -> Self {
+        LinExpr::from(other).added(&self.scaled(-1))
+    }
+
+    fn __mul__(&self, factor: i32) -> Self {
+        self.scaled(factor)
+    }
+
+    fn __rmul__(&self, factor: i32) -> Self {
+        self.scaled(factor)
+    }
+
+    fn __neg__(&self) -> Self {
+        self.scaled(-1)
+    }
+
+    fn leq(&self, rhs: LinExprOrInt) -> LinConstraint {
+        self.constraint(rhs, linexpr::Sign::LessThan)
+    }
+
+    fn geq(&self, rhs: LinExprOrInt) -> LinConstraint {
+        LinExpr::from(rhs).constraint(self.clone().into(), linexpr::Sign::LessThan)
+    }
+
+    fn eq(&self, rhs: LinExprOrInt) -> LinConstraint {
+        self.constraint(rhs, linexpr::Sign::Equals)
+    }
+
+    fn __repr__(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
+        let output = format!("{}", *self_);
+        PyString::new_bound(self_.py(), output.as_str())
+    }
+}
+
+impl From<LinExpr> for LinExprOrInt {
+    fn from(value: LinExpr) -> Self {
+        LinExprOrInt::Expr(value)
+    }
+}
+
+impl std::fmt::Display for LinExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let write_sign =
+            |f: &mut std::fmt::Formatter<'_>, first: bool, value: i32| match (first, value < 0) {
+                (true, false) => Ok(()),
+                (true, true) => write!(f, "-"),
+                (false, false) => write!(f, " + "),
+                (false, true) => write!(f, " - "),
+            };
+
+        for (i, (var, coef)) in self.coefs.iter().enumerate() {
+            write_sign(f, i == 0, *coef)?;
+            if coef.abs() != 1 {
+                write!(f, "{}*", coef.abs())?;
+            }
+            match var {
+                VariableRef::GroupInSlot {
+                    time_slot,
+                    week,
+                    group,
+                } => write!(
+                    f,
+                    "group_in_slot({:?}, {}, {})",
+                    time_slot,
+                    week.get(),
+                    group
+                )?,
+                VariableRef::StudentInGroup {
+                    subject,
+                    student,
+                    group,
+                } => write!(
+                    f,
+                    "student_in_group({:?}, {:?}, {})",
+                    subject, student, group
+                )?,
+            }
+        }
+        if self.coefs.is_empty() || self.constant != 0 {
+            write_sign(f, self.coefs.is_empty(), self.constant)?;
+            write!(f, "{}", self.constant.abs())?;
+        }
+        Ok(())
+    }
+}
+
+// Constraint "expr <= 0" or "expr == 0" built with LinExpr.leq/geq/eq
+#[pyclass(eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinConstraint {
+    expr: LinExpr,
+    sign: linexpr::Sign,
+}
+
+impl LinConstraint {
+    pub fn resolve(
+        &self,
+        translator: &GenColloscopeTranslator,
+    ) -> PyResult<linexpr::Constraint<Variable>> {
+        let lhs = self.expr.resolve(translator)?;
+        let zero = linexpr::Expr::constant(0);
+        Ok(match self.sign {
+            linexpr::Sign::LessThan => lhs.leq(&zero),
+            linexpr::Sign::Equals => lhs.eq(&zero),
+        })
+    }
+}
+
+#[pymethods]
+impl LinConstraint {
+    fn __repr__(self_: PyRef<'_, Self>) -> Bound<'_, PyString> {
+        let output = format!("{}", *self_);
+        PyString::new_bound(self_.py(), output.as_str())
+    }
+}
+
+impl std::fmt::Display for LinConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = match self.sign {
+            linexpr::Sign::LessThan => "<=",
+            linexpr::Sign::Equals => "==",
+        };
+        write!(f, "{} {} 0", self.expr, sign)
+    }
+}
@@ -0,0 +1,109 @@
+use super::*;
+
+// HTML tables returned by _repr_html_ so that colloscopes display nicely in notebooks.
+// Styles are inlined as Jupyter may strip <style> blocks.
+
+const TABLE_STYLE: &str = "border-collapse: collapse; font-size: 0.9em;";
+const HEADER_STYLE: &str =
+    "border: 1px solid #999; padding: 2px 6px; background-color: #e8e8e8; text-align: center;";
+const CELL_STYLE: &str = "border: 1px solid #999; padding: 2px 6px; text-align: center;";
+const EMPTY_CELL_STYLE: &str =
+    "border: 1px solid #999; padding: 2px 6px; background-color: #f6f6f6;";
+
+fn escape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+fn week_count(subjects: &[&ColloscopeSubject]) -> u32 {
+    subjects
+        .iter()
+        .flat_map(|subject| subject.time_slots.iter())
+        .flat_map(|time_slot| time_slot.group_assignments.keys())
+        .map(|week| week + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+impl ColloscopeSubject {
+    // One row per time slot and one column per week (numbered from 1)
+    fn html_table(&self, week_count: u32) -> String {
+        let mut output = format!("<table style=\"{}\"><tr>", TABLE_STYLE);
+        for title in ["Slot", "Teacher", "Room"] {
+            output += &format!("<th style=\"{}\">{}</th>", HEADER_STYLE, title);
+        }
+        for week in 0..week_count {
+            output += &format!("<th style=\"{}\">{}</th>", HEADER_STYLE, week + 1);
+        }
+        output += "</tr>";
+
+        for time_slot in &self.time_slots {
+            output += &format!(
+                "<tr><td style=\"{}\">{} {}</td><td style=\"{}\">{}</td><td style=\"{}\">{}</td>",
+                CELL_STYLE,
+                time_slot.start.day,
+                time_slot.start.time,
+                CELL_STYLE,
+                escape(&format!("{:?}", time_slot.teacher_handle.handle)),
+                CELL_STYLE,
+                escape(&time_slot.room),
+            );
+            for week in 0..week_count {
+                let groups: Vec<_> = time_slot
+                    .group_assignments
+                    .get(&week)
+                    .into_iter()
+                    .flatten()
+                    .map(|group| match self.group_list.groups.get(*group) {
+                        Some(name) => escape(name),
+                        None => format!("#{}", group),
+                    })
+                    .collect();
+                if groups.is_empty() {
+                    output += &format!("<td style=\"{}\"></td>", EMPTY_CELL_STYLE);
+                } else {
+                    output += &format!("<td style=\"{}\">{}</td>", CELL_STYLE, groups.join(", "));
+                }
+            }
+            output += "</tr>";
+        }
+
+        output += "</table>";
+        output
+    }
+
+    pub fn to_html(&self) -> String {
+        format!(
+            "<p><b>{}</b></p>{}",
+            escape(&self.group_list.name),
+            self.html_table(week_count(&[self])),
+        )
+    }
+}
+
+impl Colloscope {
+    pub fn to_html(&self) -> String {
+        let subjects: Vec<_> = self.subjects.values().collect();
+        let week_count = week_count(&subjects);
+
+        let mut output = format!("<h3>{}</h3>", escape(&self.name));
+        for (subject_handle, subject) in &self.subjects {
+            output += &format!(
+                "<p><b>{}</b> (group list: {})</p>",
+                escape(&format!("{:?}", subject_handle.handle)),
+                escape(&subject.group_list.name),
+            );
+            output += &subject.html_table(week_count);
+        }
+        output
+    }
+}
@@ -0,0 +1,163 @@
+use super::*;
+
+use pyo3::types::PyDict;
+
+// Column-oriented table, convertible to a dict of lists which both
+// pandas and polars accept to build a DataFrame
+pub struct Table {
+    columns: Vec<(&'static str, Vec<PyObject>)>,
+}
+
+impl Table {
+    fn new(names: &[&'static str]) -> Self {
+        Table {
+            columns: names.iter().map(|name| (*name, Vec::new())).collect(),
+        }
+    }
+
+    fn push_row(&mut self, row: Vec<PyObject>) {
+        assert_eq!(row.len(), self.columns.len());
+        for ((_name, column), value) in self.columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+    }
+
+    pub fn into_dict(self, py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        let dict = PyDict::new_bound(py);
+        for (name, column) in self.columns {
+            dict.set_item(name, column)?;
+        }
+        Ok(dict)
+    }
+
+    pub fn into_dataframe(self, py: Python<'_>, library: &str) -> PyResult<PyObject> {
+        if library != "pandas" && library != "polars" {
+            return Err(PyValueError::new_err(format!(
+                "Unsupported dataframe library \"{}\" (expected \"pandas\" or \"polars\")",
+                library
+            )));
+        }
+
+        let module = py.import_bound(library)?;
+        let dict = self.into_dict(py)?;
+        let dataframe = module.getattr("DataFrame")?.call1((dict,))?;
+        Ok(dataframe.unbind())
+    }
+}
+
+pub fn students_table(py: Python<'_>, students: &BTreeMap<StudentHandle, Student>) -> Table {
+    let mut table = Table::new(&[
+        "handle",
+        "surname",
+        "firstname",
+        "email",
+        "phone",
+        "no_consecutive_slots",
+    ]);
+
+    for (handle, student) in students {
+        table.push_row(vec![
+            handle.clone().into_py(py),
+            student.surname.clone().into_py(py),
+            student.firstname.clone().into_py(py),
+            student.email.clone().into_py(py),
+            student.phone.clone().into_py(py),
+            student.no_consecutive_slots.into_py(py),
+        ]);
+    }
+
+    table
+}
+
+fn teacher_name(teachers: &BTreeMap<TeacherHandle, Teacher>, handle: &TeacherHandle) -> String {
+    teachers
+        .get(handle)
+        .map(|teacher| format!("{} {}", teacher.firstname, teacher.surname))
+        .unwrap_or_default()
+}
+
+fn subject_name(subjects: &BTreeMap<SubjectHandle, Subject>, handle: &SubjectHandle) -> String {
+    subjects
+        .get(handle)
+        .map(|subject| subject.name.clone())
+        .unwrap_or_default()
+}
+
+pub fn time_slots_table(
+    py: Python<'_>,
+    time_slots: &BTreeMap<TimeSlotHandle, TimeSlot>,
+    subjects: &BTreeMap<SubjectHandle, Subject>,
+    teachers: &BTreeMap<TeacherHandle, Teacher>,
+) -> Table {
+    let mut table = Table::new(&[
+        "handle",
+        "subject_handle",
+        "subject",
+        "teacher_handle",
+        "teacher",
+        "day",
+        "time",
+        "week_pattern_handle",
+        "room",
+        "cost",
+    ]);
+
+    for (handle, time_slot) in time_slots {
+        table.push_row(vec![
+            handle.clone().into_py(py),
+            time_slot.subject_handle.clone().into_py(py),
+            subject_name(subjects, &time_slot.subject_handle).into_py(py),
+            time_slot.teacher_handle.clone().into_py(py),
+            teacher_name(teachers, &time_slot.teacher_handle).into_py(py),
+            time_slot.start.day.to_string().into_py(py),
+            time_slot.start.time.to_string().into_py(py),
+            time_slot.week_pattern_handle.clone().into_py(py),
+            time_slot.room.clone().into_py(py),
+            time_slot.cost.into_py(py),
+        ]);
+    }
+
+    table
+}
+
+// One row per group interrogated in a time slot for a given week
+pub fn colloscope_table(
+    py: Python<'_>,
+    colloscope: &Colloscope,
+    subjects: &BTreeMap<SubjectHandle, Subject>,
+    teachers: &BTreeMap<TeacherHandle, Teacher>,
+) -> Table {
+    let mut table = Table::new(&[
+        "subject_handle",
+        "subject",
+        "teacher_handle",
+        "teacher",
+        "day",
+        "time",
+        "room",
+        "week",
+        "group",
+        "group_name",
+    ]);
+
+    let colloscope = backend::Colloscope::from(colloscope);
+    for interrogation in crate::frontend::interrogations::flatten(&colloscope) {
+        let subject_handle = SubjectHandle::from(interrogation.subject_handle);
+        let teacher_handle = TeacherHandle::from(interrogation.time_slot.teacher_id);
+        let start = SlotStart::from(&interrogation.time_slot.start);
+        table.push_row(vec![
+            subject_handle.clone().into_py(py),
+            subject_name(subjects, &subject_handle).into_py(py),
+            teacher_handle.clone().into_py(py),
+            teacher_name(teachers, &teacher_handle).into_py(py),
+            start.day.to_string().into_py(py),
+            start.time.to_string().into_py(py),
+            interrogation.time_slot.room.clone().into_py(py),
+            interrogation.week.into_py(py),
+            interrogation.group.into_py(py),
+            interrogation.group_name.into_py(py),
+        ]);
+    }
+
+    table
+}
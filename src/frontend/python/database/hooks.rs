@@ -0,0 +1,323 @@
+use super::*;
+
+use pyo3::types::PyTuple;
+
+// Names accepted by Database.register_hook ("*" matches every operation)
+pub const HOOKABLE_OPS: &[&str] = &[
+    "undo",
+    "redo",
+    "general_data_set",
+    "week_patterns_create",
+    "week_patterns_update",
+    "week_patterns_remove",
+    "teachers_create",
+    "teachers_update",
+    "teachers_remove",
+    "students_create",
+    "students_update",
+    "students_remove",
+    "subject_groups_create",
+    "subject_groups_update",
+    "subject_groups_remove",
+    "incompats_create",
+    "incompats_update",
+    "incompats_remove",
+    "group_lists_create",
+    "group_lists_update",
+    "group_lists_remove",
+    "subjects_create",
+    "subjects_update",
+    "subjects_remove",
+    "time_slots_create",
+    "time_slots_update",
+    "time_slots_remove",
+    "groupings_create",
+    "groupings_update",
+    "groupings_remove",
+    "grouping_incompats_create",
+    "grouping_incompats_update",
+    "grouping_incompats_remove",
+    "subject_group_for_student_set",
+    "incompat_for_student_set",
+    "colloscopes_create",
+    "colloscopes_update",
+    "colloscopes_remove",
+    "slot_selections_create",
+    "slot_selections_update",
+    "slot_selections_remove",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookTiming {
+    Before,
+    After,
+}
+
+impl std::str::FromStr for HookTiming {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> PyResult<Self> {
+        match s {
+            "before" => Ok(HookTiming::Before),
+            "after" => Ok(HookTiming::After),
+            _ => Err(PyValueError::new_err(format!(
+                "Hook timing must be \"before\" or \"after\" (got \"{}\")",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Hook {
+    id: usize,
+    op: String,
+    timing: HookTiming,
+    priority: i32,
+    callback: PyObject,
+}
+
+// Hooks registered by the running script.
+// They are called by increasing priority, then in registration order.
+#[derive(Debug, Default)]
+pub struct HookRegistry {
+    hooks: Vec<Hook>,
+    next_id: usize,
+    // Operations done from a hook do not trigger other hooks
+    running: bool,
+}
+
+impl HookRegistry {
+    pub fn register(
+        &mut self,
+        op: String,
+        callback: PyObject,
+        timing: HookTiming,
+        priority: i32,
+    ) -> PyResult<usize> {
+        if op != "*" && !HOOKABLE_OPS.contains(&op.as_str()) {
+            return Err(PyValueError::new_err(format!(
+                "Unknown operation \"{}\" for hook",
+                op
+            )));
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let pos = self.hooks.partition_point(|hook| hook.priority <= priority);
+        self.hooks.insert(
+            pos,
+            Hook {
+                id,
+                op,
+                timing,
+                priority,
+                callback,
+            },
+        );
+
+        Ok(id)
+    }
+
+    pub fn remove(&mut self, id: usize) -> bool {
+        let len = self.hooks.len();
+        self.hooks.retain(|hook| hook.id != id);
+        self.hooks.len() != len
+    }
+
+    fn matching(&self, py: Python<'_>, op: &str, timing: HookTiming) -> Vec<(usize, PyObject)> {
+        if self.running {
+            return vec![];
+        }
+        self.hooks
+            .iter()
+            .filter(|hook| hook.timing == timing && (hook.op == "*" || hook.op == op))
+            .map(|hook| (hook.id, hook.callback.clone_ref(py)))
+            .collect()
+    }
+}
+
+fn op_description(py: Python<'_>, command: &Command) -> Option<(&'static str, Py<PyTuple>)> {
+    macro_rules! op {
+        ($name:literal) => {
+            Some(($name, PyTuple::empty_bound(py).unbind()))
+        };
+        ($name:literal, $($arg:expr),+) => {
+            Some(($name, PyTuple::new_bound(py, [$($arg.clone().into_py(py)),+]).unbind()))
+        };
+    }
+
+    match command {
+        Command::Undo => op!("undo"),
+        Command::Redo => op!("redo"),
+        Command::GeneralData(GeneralDataCommand::Set(d)) => op!("general_data_set", d),
+        Command::WeekPatterns(c) => match c {
+            WeekPatternsCommand::Create(v) => op!("week_patterns_create", v),
+            WeekPatternsCommand::Update(h, v) => op!("week_patterns_update", h, v),
+            WeekPatternsCommand::Remove(h) => op!("week_patterns_remove", h),
+            _ => None,
+        },
+        Command::Teachers(c) => match c {
+            TeachersCommand::Create(v) => op!("teachers_create", v),
+            TeachersCommand::Update(h, v) => op!("teachers_update", h, v),
+            TeachersCommand::Remove(h) => op!("teachers_remove", h),
+            _ => None,
+        },
+        Command::Students(c) => match c {
+            StudentsCommand::Create(v) => op!("students_create", v),
+            StudentsCommand::Update(h, v) => op!("students_update", h, v),
+            StudentsCommand::Remove(h) => op!("students_remove", h),
+            _ => None,
+        },
+        Command::SubjectGroups(c) => match c {
+            SubjectGroupsCommand::Create(v) => op!("subject_groups_create", v),
+            SubjectGroupsCommand::Update(h, v) => op!("subject_groups_update", h, v),
+            SubjectGroupsCommand::Remove(h) => op!("subject_groups_remove", h),
+            _ => None,
+        },
+        Command::Incompats(c) => match c {
+            IncompatsCommand::Create(v) => op!("incompats_create", v),
+            IncompatsCommand::Update(h, v) => op!("incompats_update", h, v),
+            IncompatsCommand::Remove(h) => op!("incompats_remove", h),
+            _ => None,
+        },
+        Command::GroupLists(c) => match c {
+            GroupListsCommand::Create(v) => op!("group_lists_create", v),
+            GroupListsCommand::Update(h, v) => op!("group_lists_update", h, v),
+            GroupListsCommand::Remove(h) => op!("group_lists_remove", h),
+            _ => None,
+        },
+        Command::Subjects(c) => match c {
+            SubjectsCommand::Create(v) => op!("subjects_create", v),
+            SubjectsCommand::Update(h, v) => op!("subjects_update", h, v),
+            SubjectsCommand::Remove(h) => op!("subjects_remove", h),
+            _ => None,
+        },
+        Command::TimeSlots(c) => match c {
+            TimeSlotsCommand::Create(v) => op!("time_slots_create", v),
+            TimeSlotsCommand::Update(h, v) => op!("time_slots_update", h, v),
+            TimeSlotsCommand::Remove(h) => op!("time_slots_remove", h),
+            _ => None,
+        },
+        Command::Groupings(c) => match c {
+            GroupingsCommand::Create(v) => op!("groupings_create", v),
+            GroupingsCommand::Update(h, v) => op!("groupings_update", h, v),
+            GroupingsCommand::Remove(h) => op!("groupings_remove", h),
+            _ => None,
+        },
+        Command::GroupingIncompats(c) => match c {
+            GroupingIncompatsCommand::Create(v) => op!("grouping_incompats_create", v),
+            GroupingIncompatsCommand::Update(h, v) => op!("grouping_incompats_update", h, v),
+            GroupingIncompatsCommand::Remove(h) => op!("grouping_incompats_remove", h),
+            _ => None,
+        },
+        Command::RegisterStudent(c) => match c {
+            RegisterStudentCommand::InSubjectGroupSet(s, g, h) => {
+                op!("subject_group_for_student_set", s, g, h)
+            }
+            RegisterStudentCommand::InIncompatSet(s, i, e) => {
+                op!("incompat_for_student_set", s, i, e)
+            }
+            _ => None,
+        },
+        Command::Colloscopes(c) => match c {
+            ColloscopesCommand::Create(v) => op!("colloscopes_create", v),
+            ColloscopesCommand::Update(h, v) => op!("colloscopes_update", h, v),
+            ColloscopesCommand::Remove(h) => op!("colloscopes_remove", h),
+            _ => None,
+        },
+        Command::SlotSelections(c) => match c {
+            SlotSelectionsCommand::Create(v) => op!("slot_selections_create", v),
+            SlotSelectionsCommand::Update(h, v) => op!("slot_selections_update", h, v),
+            SlotSelectionsCommand::Remove(h) => op!("slot_selections_remove", h),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Handle returned by create operations (None for the others)
+fn op_result(py: Python<'_>, answer: &Answer) -> PyObject {
+    match answer {
+        Answer::WeekPatterns(WeekPatternsAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::Teachers(TeachersAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::Students(StudentsAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::SubjectGroups(SubjectGroupsAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::Incompats(IncompatsAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::GroupLists(GroupListsAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::Subjects(SubjectsAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::TimeSlots(TimeSlotsAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::Groupings(GroupingsAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::GroupingIncompats(GroupingIncompatsAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::Colloscopes(ColloscopesAnswer::Create(h)) => h.clone().into_py(py),
+        Answer::SlotSelections(SlotSelectionsAnswer::Create(h)) => h.clone().into_py(py),
+        _ => py.None(),
+    }
+}
+
+impl Database {
+    // call_args is (op, args) for "before" hooks and (op, args, result) for "after" hooks
+    fn run_hooks(
+        self_: &PyRef<'_, Self>,
+        op: &str,
+        timing: HookTiming,
+        call_args: &Bound<'_, PyTuple>,
+    ) -> PyResult<()> {
+        let py = self_.py();
+        let hooks = self_.hooks.borrow().matching(py, op, timing);
+        if hooks.is_empty() {
+            return Ok(());
+        }
+
+        self_.hooks.borrow_mut().running = true;
+        let mut result = Ok(());
+        for (id, callback) in hooks {
+            // A failing hook is reported but neither stops the other hooks nor the operation
+            if let Err(e) = callback.call1(py, call_args) {
+                let warning = py.get_type_bound::<pyo3::exceptions::PyRuntimeWarning>();
+                let message = format!("Hook {} for \"{}\" failed: {}", id, op, e);
+                result = PyErr::warn_bound(py, &warning, &message, 1);
+                if result.is_err() {
+                    break;
+                }
+            }
+        }
+        self_.hooks.borrow_mut().running = false;
+
+        result
+    }
+
+    pub(super) fn send_hooked_command(
+        self_: &PyRef<'_, Self>,
+        command: Command,
+    ) -> PyResult<Answer> {
+        let py = self_.py();
+
+        // Commands without description do not modify the database
+        let Some((op, args)) = op_description(py, &command) else {
+            return SessionConnection::send_command(py, &self_.sender, command);
+        };
+        let hooks_enabled = self_.hooks_enabled.get();
+
+        if hooks_enabled {
+            let before_args =
+                PyTuple::new_bound(py, [op.into_py(py), args.clone_ref(py).into_any()]);
+            Self::run_hooks(self_, op, HookTiming::Before, &before_args)?;
+        }
+
+        let answer = SessionConnection::send_command(py, &self_.sender, command)?;
+
+        if hooks_enabled {
+            let after_args = PyTuple::new_bound(
+                py,
+                [op.into_py(py), args.into_any(), op_result(py, &answer)],
+            );
+            Self::run_hooks(self_, op, HookTiming::After, &after_args)?;
+        }
+
+        Self::notify_observers(self_)?;
+
+        Ok(answer)
+    }
+}
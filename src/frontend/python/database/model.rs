@@ -0,0 +1,180 @@
+use super::*;
+
+use crate::gen::colloscope::Variable;
+use crate::ilp::linexpr;
+use crate::ilp::Problem;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IlpModelError {
+    #[error("scipy is needed to build sparse matrices: {0}")]
+    NoScipy(PyErr),
+    #[error(transparent)]
+    Python(#[from] PyErr),
+}
+
+impl From<IlpModelError> for PyErr {
+    fn from(value: IlpModelError) -> Self {
+        match value {
+            IlpModelError::NoScipy(_) => {
+                pyo3::exceptions::PyImportError::new_err(value.to_string())
+            }
+            IlpModelError::Python(err) => err,
+        }
+    }
+}
+
+// Raw ILP model built from the database, for experiments outside of collomatique.
+// Columns of the matrices follow the order of the variables list.
+#[pyclass]
+#[derive(Debug)]
+pub struct IlpModel {
+    problem: Problem<Variable>,
+    hint: BTreeMap<Variable, bool>,
+}
+
+type PyExpr = (BTreeMap<String, i32>, i32);
+type PyConstraint = (BTreeMap<String, i32>, &'static str, i32, Vec<String>);
+
+fn expr_to_py(expr: &linexpr::Expr<Variable>) -> PyExpr {
+    let coefs = expr
+        .coefs()
+        .iter()
+        .map(|(var, coef)| (var.to_string(), *coef))
+        .collect();
+    (coefs, expr.get_constant())
+}
+
+fn sign_name(sign: linexpr::Sign) -> &'static str {
+    match sign {
+        linexpr::Sign::LessThan => "<=",
+        linexpr::Sign::Equals => "==",
+    }
+}
+
+impl IlpModel {
+    pub fn new(problem: Problem<Variable>, hint: BTreeMap<Variable, bool>) -> Self {
+        IlpModel { problem, hint }
+    }
+}
+
+#[pymethods]
+impl IlpModel {
+    #[getter]
+    fn variables(&self) -> Vec<String> {
+        self.problem
+            .get_variables()
+            .iter()
+            .map(|var| var.to_string())
+            .collect()
+    }
+
+    // Each constraint is (coefs, sign, constant, groups) and reads
+    // sum(coefs[v]*v) + constant <= 0 (or == 0)
+    #[getter]
+    fn constraints(&self) -> Vec<PyConstraint> {
+        let groups = self.problem.get_constraint_groups();
+        self.problem
+            .get_constraints()
+            .iter()
+            .map(|constraint| {
+                let (coefs, constant) = expr_to_py(constraint.get_lhs());
+                let constraint_groups = groups
+                    .get(constraint)
+                    .map(|names| names.iter().cloned().collect())
+                    .unwrap_or_default();
+                (
+                    coefs,
+                    sign_name(constraint.get_sign()),
+                    constant,
+                    constraint_groups,
+                )
+            })
+            .collect()
+    }
+
+    // Linear part of the objective
+    #[getter]
+    fn objective(&self) -> BTreeMap<String, f64> {
+        self.problem
+            .get_objective_contribs()
+            .iter()
+            .map(|(var, coef)| (var.to_string(), *coef))
+            .collect()
+    }
+
+    // Each term is (coef, exprs) and adds coef * max(exprs) to the objective
+    #[getter]
+    fn objective_terms(&self) -> Vec<(f64, Vec<PyExpr>)> {
+        self.problem
+            .get_objective_terms()
+            .iter()
+            .map(|term| (term.coef, term.exprs.iter().map(expr_to_py).collect()))
+            .collect()
+    }
+
+    // Starting point used by collomatique (a valid but usually poor solution)
+    #[getter]
+    fn hint(&self) -> BTreeMap<String, bool> {
+        self.hint
+            .iter()
+            .map(|(var, value)| (var.to_string(), *value))
+            .collect()
+    }
+
+    // Constraints as scipy.sparse matrices (A_ub, b_ub, A_eq, b_eq)
+    // with A_ub @ x <= b_ub and A_eq @ x == b_eq (as for scipy.optimize.milp)
+    fn sparse_matrices(&self, py: Python<'_>) -> Result<PyObject, IlpModelError> {
+        let sparse = py
+            .import_bound("scipy.sparse")
+            .map_err(IlpModelError::NoScipy)?;
+        let variables = self.problem.get_variables();
+        let columns: BTreeMap<_, _> = variables
+            .iter()
+            .enumerate()
+            .map(|(i, var)| (var, i))
+            .collect();
+
+        let mut matrices = Vec::new();
+        for sign in [linexpr::Sign::LessThan, linexpr::Sign::Equals] {
+            let mut data = Vec::new();
+            let mut rows = Vec::new();
+            let mut cols = Vec::new();
+            let mut rhs = Vec::new();
+
+            let constraints = self
+                .problem
+                .get_constraints()
+                .iter()
+                .filter(|constraint| constraint.get_sign() == sign);
+            for (row, constraint) in constraints.enumerate() {
+                for (var, coef) in constraint.coefs() {
+                    data.push(*coef);
+                    rows.push(row);
+                    cols.push(columns[var]);
+                }
+                rhs.push(-constraint.get_constant());
+            }
+
+            let shape = (rhs.len(), variables.len());
+            let matrix = sparse
+                .getattr("csr_matrix")?
+                .call1(((data, (rows, cols)), shape))?;
+            matrices.push(matrix.into_py(py));
+            matrices.push(rhs.into_py(py));
+        }
+
+        Ok(pyo3::types::PyTuple::new_bound(py, matrices).into_py(py))
+    }
+
+    // Whole problem serialized as JSON (it can be read back with serde)
+    fn to_json(&self) -> String {
+        // Maps are stored as lists so serialization can't fail on non-string keys
+        serde_json::to_string(&self.problem).expect("ILP problem should serialize to JSON")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("IlpModel {{\n{}\n}}", self.problem.stats())
+    }
+}
@@ -0,0 +1,322 @@
+use super::*;
+
+use pyo3::types::PyTuple;
+
+use crate::frontend::state::{
+    AggregatedOperations, AnnotatedColloscopesOperation, AnnotatedGroupListsOperation,
+    AnnotatedGroupingIncompatsOperation, AnnotatedGroupingsOperation, AnnotatedIncompatsOperation,
+    AnnotatedOperation, AnnotatedRegisterStudentOperation, AnnotatedSlotSelectionsOperation,
+    AnnotatedStudentsOperation, AnnotatedSubjectGroupsOperation, AnnotatedSubjectsOperation,
+    AnnotatedTeachersOperation, AnnotatedTimeSlotsOperation, AnnotatedWeekPatternsOperation,
+};
+
+// Observers subscribed by the running script
+#[derive(Debug, Default)]
+pub struct ObserverRegistry {
+    observers: Vec<(usize, PyObject)>,
+    next_id: usize,
+    // Session history when the observers were last notified
+    last_history: Option<AggregatedOperations>,
+}
+
+impl ObserverRegistry {
+    pub fn remove(&mut self, id: usize) -> bool {
+        let len = self.observers.len();
+        self.observers.retain(|(observer_id, _)| *observer_id != id);
+        if self.observers.is_empty() {
+            self.last_history = None;
+        }
+        self.observers.len() != len
+    }
+}
+
+// Operations applied to go from one state of the history to the other.
+// Undone operations are reported with their backward operation.
+fn applied_ops(
+    old_history: &AggregatedOperations,
+    new_history: &AggregatedOperations,
+) -> Vec<AnnotatedOperation> {
+    let common_len = old_history
+        .inner()
+        .iter()
+        .zip(new_history.inner().iter())
+        .take_while(|(old_op, new_op)| old_op == new_op)
+        .count();
+
+    let undone = old_history.inner()[common_len..]
+        .iter()
+        .rev()
+        .map(|op| op.backward.clone());
+    let done = new_history.inner()[common_len..]
+        .iter()
+        .map(|op| op.forward.clone());
+
+    undone.chain(done).collect()
+}
+
+// Same operation names as hooks but create operations also give the new handle
+fn op_description(py: Python<'_>, op: AnnotatedOperation) -> (&'static str, Py<PyTuple>) {
+    macro_rules! op {
+        ($name:literal, $($arg:expr),+) => {
+            ($name, PyTuple::new_bound(py, [$($arg.into_py(py)),+]).unbind())
+        };
+    }
+
+    match op {
+        AnnotatedOperation::GeneralData(d) => op!("general_data_set", GeneralData::from(d)),
+        AnnotatedOperation::WeekPatterns(o) => match o {
+            AnnotatedWeekPatternsOperation::Create(h, v) => op!(
+                "week_patterns_create",
+                WeekPatternHandle::from(h),
+                WeekPattern::from(v)
+            ),
+            AnnotatedWeekPatternsOperation::Update(h, v) => op!(
+                "week_patterns_update",
+                WeekPatternHandle::from(h),
+                WeekPattern::from(v)
+            ),
+            AnnotatedWeekPatternsOperation::Remove(h) => {
+                op!("week_patterns_remove", WeekPatternHandle::from(h))
+            }
+        },
+        AnnotatedOperation::Teachers(o) => match o {
+            AnnotatedTeachersOperation::Create(h, v) => {
+                op!("teachers_create", TeacherHandle::from(h), Teacher::from(v))
+            }
+            AnnotatedTeachersOperation::Update(h, v) => {
+                op!("teachers_update", TeacherHandle::from(h), Teacher::from(v))
+            }
+            AnnotatedTeachersOperation::Remove(h) => {
+                op!("teachers_remove", TeacherHandle::from(h))
+            }
+        },
+        AnnotatedOperation::Students(o) => match o {
+            AnnotatedStudentsOperation::Create(h, v) => {
+                op!("students_create", StudentHandle::from(h), Student::from(v))
+            }
+            AnnotatedStudentsOperation::Update(h, v) => {
+                op!("students_update", StudentHandle::from(h), Student::from(v))
+            }
+            AnnotatedStudentsOperation::Remove(h) => {
+                op!("students_remove", StudentHandle::from(h))
+            }
+        },
+        AnnotatedOperation::SubjectGroups(o) => match o {
+            AnnotatedSubjectGroupsOperation::Create(h, v) => op!(
+                "subject_groups_create",
+                SubjectGroupHandle::from(h),
+                SubjectGroup::from(v)
+            ),
+            AnnotatedSubjectGroupsOperation::Update(h, v) => op!(
+                "subject_groups_update",
+                SubjectGroupHandle::from(h),
+                SubjectGroup::from(v)
+            ),
+            AnnotatedSubjectGroupsOperation::Remove(h) => {
+                op!("subject_groups_remove", SubjectGroupHandle::from(h))
+            }
+        },
+        AnnotatedOperation::Incompats(o) => match o {
+            AnnotatedIncompatsOperation::Create(h, v) => {
+                op!(
+                    "incompats_create",
+                    IncompatHandle::from(h),
+                    Incompat::from(v)
+                )
+            }
+            AnnotatedIncompatsOperation::Update(h, v) => {
+                op!(
+                    "incompats_update",
+                    IncompatHandle::from(h),
+                    Incompat::from(v)
+                )
+            }
+            AnnotatedIncompatsOperation::Remove(h) => {
+                op!("incompats_remove", IncompatHandle::from(h))
+            }
+        },
+        AnnotatedOperation::GroupLists(o) => match o {
+            AnnotatedGroupListsOperation::Create(h, v) => op!(
+                "group_lists_create",
+                GroupListHandle::from(h),
+                GroupList::from(v)
+            ),
+            AnnotatedGroupListsOperation::Update(h, v) => op!(
+                "group_lists_update",
+                GroupListHandle::from(h),
+                GroupList::from(v)
+            ),
+            AnnotatedGroupListsOperation::Remove(h) => {
+                op!("group_lists_remove", GroupListHandle::from(h))
+            }
+        },
+        AnnotatedOperation::Subjects(o) => match o {
+            AnnotatedSubjectsOperation::Create(h, v) => {
+                op!("subjects_create", SubjectHandle::from(h), Subject::from(v))
+            }
+            AnnotatedSubjectsOperation::Update(h, v) => {
+                op!("subjects_update", SubjectHandle::from(h), Subject::from(v))
+            }
+            AnnotatedSubjectsOperation::Remove(h) => {
+                op!("subjects_remove", SubjectHandle::from(h))
+            }
+        },
+        AnnotatedOperation::TimeSlots(o) => match o {
+            AnnotatedTimeSlotsOperation::Create(h, v) => {
+                op!(
+                    "time_slots_create",
+                    TimeSlotHandle::from(h),
+                    TimeSlot::from(v)
+                )
+            }
+            AnnotatedTimeSlotsOperation::Update(h, v) => {
+                op!(
+                    "time_slots_update",
+                    TimeSlotHandle::from(h),
+                    TimeSlot::from(v)
+                )
+            }
+            AnnotatedTimeSlotsOperation::Remove(h) => {
+                op!("time_slots_remove", TimeSlotHandle::from(h))
+            }
+        },
+        AnnotatedOperation::Groupings(o) => match o {
+            AnnotatedGroupingsOperation::Create(h, v) => {
+                op!(
+                    "groupings_create",
+                    GroupingHandle::from(h),
+                    Grouping::from(v)
+                )
+            }
+            AnnotatedGroupingsOperation::Update(h, v) => {
+                op!(
+                    "groupings_update",
+                    GroupingHandle::from(h),
+                    Grouping::from(v)
+                )
+            }
+            AnnotatedGroupingsOperation::Remove(h) => {
+                op!("groupings_remove", GroupingHandle::from(h))
+            }
+        },
+        AnnotatedOperation::GroupingIncompats(o) => match o {
+            AnnotatedGroupingIncompatsOperation::Create(h, v) => op!(
+                "grouping_incompats_create",
+                GroupingIncompatHandle::from(h),
+                GroupingIncompat::from(v)
+            ),
+            AnnotatedGroupingIncompatsOperation::Update(h, v) => op!(
+                "grouping_incompats_update",
+                GroupingIncompatHandle::from(h),
+                GroupingIncompat::from(v)
+            ),
+            AnnotatedGroupingIncompatsOperation::Remove(h) => {
+                op!("grouping_incompats_remove", GroupingIncompatHandle::from(h))
+            }
+        },
+        AnnotatedOperation::RegisterStudent(o) => match o {
+            AnnotatedRegisterStudentOperation::InSubjectGroup(s, g, h) => op!(
+                "subject_group_for_student_set",
+                StudentHandle::from(s),
+                SubjectGroupHandle::from(g),
+                h.map(SubjectHandle::from)
+            ),
+            AnnotatedRegisterStudentOperation::InIncompat(s, i, e) => op!(
+                "incompat_for_student_set",
+                StudentHandle::from(s),
+                IncompatHandle::from(i),
+                e
+            ),
+        },
+        AnnotatedOperation::Colloscopes(o) => match o {
+            AnnotatedColloscopesOperation::Create(h, v) => op!(
+                "colloscopes_create",
+                ColloscopeHandle::from(h),
+                Colloscope::from(v)
+            ),
+            AnnotatedColloscopesOperation::Update(h, v) => op!(
+                "colloscopes_update",
+                ColloscopeHandle::from(h),
+                Colloscope::from(v)
+            ),
+            AnnotatedColloscopesOperation::Remove(h) => {
+                op!("colloscopes_remove", ColloscopeHandle::from(h))
+            }
+        },
+        AnnotatedOperation::SlotSelections(o) => match o {
+            AnnotatedSlotSelectionsOperation::Create(h, v) => op!(
+                "slot_selections_create",
+                SlotSelectionHandle::from(h),
+                SlotSelection::from(v)
+            ),
+            AnnotatedSlotSelectionsOperation::Update(h, v) => op!(
+                "slot_selections_update",
+                SlotSelectionHandle::from(h),
+                SlotSelection::from(v)
+            ),
+            AnnotatedSlotSelectionsOperation::Remove(h) => {
+                op!("slot_selections_remove", SlotSelectionHandle::from(h))
+            }
+        },
+    }
+}
+
+impl Database {
+    fn current_history(self_: &PyRef<'_, Self>) -> PyResult<AggregatedOperations> {
+        let Answer::History(history) =
+            SessionConnection::send_command(self_.py(), &self_.sender, Command::History)?
+        else {
+            panic!("Bad answer type");
+        };
+        Ok(history)
+    }
+
+    pub(super) fn add_observer(self_: &PyRef<'_, Self>, callback: PyObject) -> PyResult<usize> {
+        // Only operations applied from now on are reported
+        if self_.observers.borrow().last_history.is_none() {
+            let history = Self::current_history(self_)?;
+            self_.observers.borrow_mut().last_history = Some(history);
+        }
+
+        let mut observers = self_.observers.borrow_mut();
+        let id = observers.next_id;
+        observers.next_id += 1;
+        observers.observers.push((id, callback));
+
+        Ok(id)
+    }
+
+    // Reports the operations applied since the last notification.
+    // Operations applied by an observer are reported from a nested notification.
+    pub(super) fn notify_observers(self_: &PyRef<'_, Self>) -> PyResult<()> {
+        let py = self_.py();
+        let Some(old_history) = self_.observers.borrow_mut().last_history.take() else {
+            return Ok(());
+        };
+        let new_history = Self::current_history(self_)?;
+        let ops = applied_ops(&old_history, &new_history);
+        self_.observers.borrow_mut().last_history = Some(new_history);
+
+        for op in ops {
+            let (name, args) = op_description(py, op);
+            let observers: Vec<_> = self_
+                .observers
+                .borrow()
+                .observers
+                .iter()
+                .map(|(id, callback)| (*id, callback.clone_ref(py)))
+                .collect();
+
+            for (id, callback) in observers {
+                // Like hooks, a failing observer is only reported
+                if let Err(e) = callback.call1(py, (name, args.clone_ref(py))) {
+                    let warning = py.get_type_bound::<pyo3::exceptions::PyRuntimeWarning>();
+                    let message = format!("Observer {} for \"{}\" failed: {}", id, name, e);
+                    PyErr::warn_bound(py, &warning, &message, 1)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
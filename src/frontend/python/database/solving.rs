@@ -0,0 +1,320 @@
+use super::*;
+
+use pyo3::types::PyDict;
+
+use crate::gen::colloscope::Variable;
+use crate::ilp::solvers::jobs::{JobId, JobManager, JobResult, JobStatus};
+use crate::ilp::solvers::{SolveProgress, SolverOptions};
+use crate::ilp::{DefaultRepr, Problem};
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SolveJobError {
+    #[error("Invalid poll interval: {0}")]
+    InvalidPollInterval(#[from] std::time::TryFromFloatSecsError),
+    #[error("Solve is still running")]
+    StillRunning,
+    #[error("No solution was found")]
+    NoSolution,
+    #[error(transparent)]
+    Python(#[from] PyErr),
+}
+
+impl From<SolveJobError> for PyErr {
+    fn from(value: SolveJobError) -> Self {
+        match value {
+            SolveJobError::Python(err) => err,
+            SolveJobError::InvalidPollInterval(_) => PyValueError::new_err(value.to_string()),
+            _ => PyException::new_err(value.to_string()),
+        }
+    }
+}
+
+// Background solve started from python.
+// Progress callbacks are called from poll() (so from the interpreter thread)
+// whenever a new progress event was reported by the solver.
+#[pyclass]
+pub struct SolveJob {
+    database: Py<Database>,
+    manager: JobManager<Variable, DefaultRepr<Variable>>,
+    id: JobId,
+    #[pyo3(get, set)]
+    progress_callback: Option<PyObject>,
+    last_progress: Option<SolveProgress>,
+    // Filled once the job is done (the manager forgets about it afterwards)
+    outcome: Option<(JobStatus, JobResult<Variable>)>,
+}
+
+impl std::fmt::Debug for SolveJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SolveJob")
+            .field("id", &self.id)
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+impl SolveJob {
+    // The job runs on its own worker thread and is cancelled when dropped
+    pub fn start(
+        database: Py<Database>,
+        problem: &Problem<Variable>,
+        hint: BTreeMap<Variable, bool>,
+        minimize_objective: bool,
+        options: SolverOptions,
+        progress_callback: Option<PyObject>,
+    ) -> Self {
+        let config_hint = problem
+            .config_from(hint)
+            .expect("Hint should be built from the same problem");
+
+        let manager = JobManager::new(1);
+        let id = manager.submit(
+            crate::ilp::solvers::coin_cbc::Solver::with_disable_logging(true),
+            &config_hint,
+            minimize_objective,
+            options,
+        );
+
+        SolveJob {
+            database,
+            manager,
+            id,
+            progress_callback,
+            last_progress: None,
+            outcome: None,
+        }
+    }
+
+    fn status(&self) -> JobStatus {
+        if let Some((status, _result)) = &self.outcome {
+            return status.clone();
+        }
+        self.manager
+            .status(self.id)
+            .expect("Job should exist until its result is taken")
+    }
+
+    fn fetch_outcome(&mut self) {
+        if self.outcome.is_some() {
+            return;
+        }
+        let status = self.status();
+        if !status.is_done() {
+            return;
+        }
+        let result = self
+            .manager
+            .take_result(self.id)
+            .expect("Result should be available for a finished job");
+        self.outcome = Some((status, result));
+    }
+
+    fn update_progress(&mut self, py: Python<'_>) -> PyResult<JobStatus> {
+        self.fetch_outcome();
+        let status = self.status();
+
+        if let JobStatus::Running {
+            last_progress: Some(progress),
+        } = &status
+        {
+            if self.last_progress.as_ref() != Some(progress) {
+                self.last_progress = Some(progress.clone());
+                if let Some(callback) = &self.progress_callback {
+                    callback.call1(py, (Self::progress_to_dict(py, progress)?,))?;
+                }
+            }
+        }
+
+        Ok(status)
+    }
+
+    fn status_name(status: &JobStatus) -> &'static str {
+        match status {
+            JobStatus::Queued => "queued",
+            JobStatus::Running { .. } => "running",
+            JobStatus::Finished(_) => "finished",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    fn progress_to_dict<'py>(
+        py: Python<'py>,
+        progress: &SolveProgress,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new_bound(py);
+        match progress {
+            SolveProgress::Started => {
+                dict.set_item("kind", "started")?;
+            }
+            SolveProgress::TimeElapsed {
+                elapsed,
+                time_limit_fraction,
+            } => {
+                dict.set_item("kind", "time_elapsed")?;
+                dict.set_item("elapsed", elapsed.as_secs_f64())?;
+                dict.set_item("time_limit_fraction", *time_limit_fraction)?;
+            }
+            SolveProgress::IncumbentFound { objective_value } => {
+                dict.set_item("kind", "incumbent_found")?;
+                dict.set_item("objective_value", *objective_value)?;
+            }
+            SolveProgress::BoundImproved { best_bound } => {
+                dict.set_item("kind", "bound_improved")?;
+                dict.set_item("best_bound", *best_bound)?;
+            }
+            SolveProgress::Finished(stats) => {
+                dict.set_item("kind", "finished")?;
+                dict.set_item("objective_value", stats.objective_value)?;
+                dict.set_item("best_bound", stats.best_bound)?;
+            }
+        }
+        Ok(dict)
+    }
+}
+
+#[pymethods]
+impl SolveJob {
+    // Returns "queued", "running", "finished" or "cancelled"
+    fn poll(mut self_: PyRefMut<'_, Self>) -> Result<&'static str, SolveJobError> {
+        let py = self_.py();
+        let status = self_.update_progress(py)?;
+        Ok(Self::status_name(&status))
+    }
+
+    fn is_done(self_: PyRef<'_, Self>) -> bool {
+        self_.status().is_done()
+    }
+
+    // Returns False if the job was already done.
+    // The job is cancelled right away, the solver finishes in the background
+    fn cancel(self_: PyRef<'_, Self>) -> bool {
+        self_.manager.cancel(self_.id)
+    }
+
+    #[pyo3(signature = (poll_interval = 0.1))]
+    fn wait(
+        mut self_: PyRefMut<'_, Self>,
+        poll_interval: f64,
+    ) -> Result<&'static str, SolveJobError> {
+        let interval = std::time::Duration::try_from_secs_f64(poll_interval)?;
+
+        let py = self_.py();
+        loop {
+            let status = self_.update_progress(py)?;
+            if status.is_done() {
+                return Ok(Self::status_name(&status));
+            }
+            py.check_signals()?;
+            py.allow_threads(|| std::thread::sleep(interval));
+        }
+    }
+
+    // Objective value of the best solution found so far (if any).
+    // cbc does not report intermediate solutions: this stays None until the solve ends
+    // and a cancelled solve has no solution to store
+    fn objective_value(self_: PyRef<'_, Self>) -> Option<f64> {
+        match self_.status() {
+            JobStatus::Finished(stats) => stats.objective_value,
+            JobStatus::Running {
+                last_progress: Some(SolveProgress::IncumbentFound { objective_value }),
+            } => objective_value,
+            _ => None,
+        }
+    }
+
+    // Stores the solution as a new colloscope
+    fn store(
+        mut self_: PyRefMut<'_, Self>,
+        name: String,
+    ) -> Result<ColloscopeHandle, SolveJobError> {
+        self_.fetch_outcome();
+        let Some((_status, result)) = &self_.outcome else {
+            return Err(SolveJobError::StillRunning);
+        };
+        let bool_vars = result.bool_vars.clone().ok_or(SolveJobError::NoSolution)?;
+
+        let database = self_.database.bind(self_.py()).borrow();
+        let Answer::Solve(SolveAnswer::Store(handle)) = SessionConnection::send_command(
+            self_.py(),
+            &database.sender,
+            Command::Solve(SolveCommand::Store(bool_vars, name)),
+        )?
+        else {
+            panic!("Bad answer type");
+        };
+        Database::notify_observers(&database)?;
+
+        Ok(handle)
+    }
+
+    fn __repr__(self_: PyRef<'_, Self>) -> String {
+        format!(
+            "SolveJob {{ status = {} }}",
+            Self::status_name(&self_.status())
+        )
+    }
+}
+
+// {kind name: {"constraint_count": int, "subjects": [...], ...}} with weeks 0-based
+pub fn infeasability_to_dict<'py>(
+    py: Python<'py>,
+    explanation: &crate::frontend::translator::InfeasabilityExplanationRef,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    for (kind, involvement) in &explanation.conflicts {
+        let details = PyDict::new_bound(py);
+        details.set_item("constraint_count", involvement.constraint_count)?;
+        details.set_item(
+            "subjects",
+            involvement
+                .subjects
+                .iter()
+                .map(|handle| SubjectHandle::from(handle).into_py(py))
+                .collect::<Vec<PyObject>>(),
+        )?;
+        details.set_item(
+            "time_slots",
+            involvement
+                .time_slots
+                .iter()
+                .map(|(handle, week)| (TimeSlotHandle::from(handle), week.get()).into_py(py))
+                .collect::<Vec<PyObject>>(),
+        )?;
+        details.set_item(
+            "groups",
+            involvement
+                .groups
+                .iter()
+                .map(|(handle, group)| (SubjectHandle::from(handle), *group).into_py(py))
+                .collect::<Vec<PyObject>>(),
+        )?;
+        details.set_item(
+            "students",
+            involvement
+                .students
+                .iter()
+                .map(|handle| StudentHandle::from(handle).into_py(py))
+                .collect::<Vec<PyObject>>(),
+        )?;
+        details.set_item(
+            "groupings",
+            involvement
+                .groupings
+                .iter()
+                .map(|(handle, week)| (GroupingHandle::from(handle), week.get()).into_py(py))
+                .collect::<Vec<PyObject>>(),
+        )?;
+        details.set_item(
+            "incompats",
+            involvement
+                .incompats
+                .iter()
+                .map(|(handle, week)| (IncompatHandle::from(handle), week.get()).into_py(py))
+                .collect::<Vec<PyObject>>(),
+        )?;
+        dict.set_item(kind.name(), details)?;
+    }
+    Ok(dict)
+}
@@ -0,0 +1,243 @@
+use super::*;
+
+use pyo3::types::{PyDict, PyFrozenSet};
+use std::sync::mpsc;
+use std::time::Duration;
+
+// Limits applied to scripts that are not trusted (third-party scripts for instance).
+// Restricting builtins makes accidents unlikely but is not a security boundary:
+// a determined script can still reach the real builtins through introspection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sandbox {
+    pub timeout: Option<Duration>,
+    pub restrict_builtins: bool,
+}
+
+// Result of a sandboxed run. Output is captured even if the script fails.
+#[derive(Debug)]
+pub struct SandboxedRun {
+    pub stdout: String,
+    pub stderr: String,
+    pub result: PyResult<()>,
+}
+
+const FORBIDDEN_BUILTINS: &[&str] = &[
+    "open",
+    "exec",
+    "eval",
+    "compile",
+    "input",
+    "breakpoint",
+    "exit",
+    "quit",
+    "help",
+    "globals",
+    "locals",
+    "vars",
+    "memoryview",
+];
+
+// Top-level modules that sandboxed scripts may import (with their submodules)
+const ALLOWED_MODULES: &[&str] = &[
+    "collomatique",
+    "collections",
+    "copy",
+    "dataclasses",
+    "datetime",
+    "decimal",
+    "enum",
+    "fractions",
+    "functools",
+    "itertools",
+    "json",
+    "math",
+    "operator",
+    "random",
+    "re",
+    "statistics",
+    "string",
+    "typing",
+];
+
+const RESTRICTED_IMPORT_CODE: &str = r#"
+def make_import(original_import, allowed):
+    def restricted_import(name, globals=None, locals=None, fromlist=(), level=0):
+        if level != 0 or name.split(".")[0] not in allowed:
+            raise ImportError(f"import of module '{name}' is not allowed in sandboxed scripts")
+        return original_import(name, globals, locals, fromlist, level)
+    return restricted_import
+"#;
+
+fn restricted_builtins(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let builtins = py.import_bound("builtins")?.dict().copy()?;
+    for name in FORBIDDEN_BUILTINS {
+        if builtins.contains(*name)? {
+            builtins.del_item(*name)?;
+        }
+    }
+
+    let scope = PyDict::new_bound(py);
+    py.run_bound(RESTRICTED_IMPORT_CODE, Some(&scope), None)?;
+    let make_import = scope
+        .get_item("make_import")?
+        .expect("make_import should be defined");
+
+    let original_import = builtins
+        .get_item("__import__")?
+        .expect("__import__ should be a builtin");
+    let allowed = PyFrozenSet::new_bound(py, ALLOWED_MODULES)?;
+    builtins.set_item("__import__", make_import.call1((original_import, allowed))?)?;
+
+    Ok(builtins)
+}
+
+// Replaces sys.stdout and sys.stderr with StringIO objects until finish is called
+struct OutputCapture<'py> {
+    sys: Bound<'py, PyModule>,
+    old_stdout: Bound<'py, PyAny>,
+    old_stderr: Bound<'py, PyAny>,
+    stdout: Bound<'py, PyAny>,
+    stderr: Bound<'py, PyAny>,
+}
+
+impl<'py> OutputCapture<'py> {
+    fn start(py: Python<'py>) -> PyResult<Self> {
+        let io = py.import_bound("io")?;
+        let sys = py.import_bound("sys")?;
+
+        let capture = OutputCapture {
+            old_stdout: sys.getattr("stdout")?,
+            old_stderr: sys.getattr("stderr")?,
+            stdout: io.call_method0("StringIO")?,
+            stderr: io.call_method0("StringIO")?,
+            sys,
+        };
+
+        capture.sys.setattr("stdout", &capture.stdout)?;
+        capture.sys.setattr("stderr", &capture.stderr)?;
+
+        Ok(capture)
+    }
+
+    fn finish(self) -> PyResult<(String, String)> {
+        self.sys.setattr("stdout", &self.old_stdout)?;
+        self.sys.setattr("stderr", &self.old_stderr)?;
+
+        let stdout = self.stdout.call_method0("getvalue")?.extract()?;
+        let stderr = self.stderr.call_method0("getvalue")?.extract()?;
+
+        Ok((stdout, stderr))
+    }
+}
+
+// Raises TimeoutError in the Python thread that started it once the timeout expires
+struct Watchdog {
+    cancel: mpsc::Sender<()>,
+    thread: std::thread::JoinHandle<bool>,
+    thread_id: std::os::raw::c_long,
+}
+
+impl Watchdog {
+    fn start(py: Python<'_>, timeout: Duration) -> PyResult<Self> {
+        let ident: u64 = py
+            .import_bound("threading")?
+            .call_method0("get_ident")?
+            .extract()?;
+        let thread_id = ident as std::os::raw::c_long;
+
+        let (cancel, receiver) = mpsc::channel::<()>();
+        let thread = std::thread::spawn(move || {
+            if receiver.recv_timeout(timeout) != Err(mpsc::RecvTimeoutError::Timeout) {
+                return false;
+            }
+            // The exception is only delivered when the script runs Python bytecode again
+            Python::with_gil(|_py| unsafe {
+                pyo3::ffi::PyThreadState_SetAsyncExc(thread_id, pyo3::ffi::PyExc_TimeoutError);
+            });
+            true
+        });
+
+        Ok(Watchdog {
+            cancel,
+            thread,
+            thread_id,
+        })
+    }
+
+    // Returns true if the timeout expired
+    fn stop(self, py: Python<'_>) -> bool {
+        let _ = self.cancel.send(());
+        let thread = self.thread;
+        // The watchdog might be waiting for the GIL
+        let expired = py.allow_threads(move || thread.join()).unwrap_or(false);
+        if expired {
+            // Clear the exception if the script ended before it was delivered
+            unsafe {
+                pyo3::ffi::PyThreadState_SetAsyncExc(self.thread_id, std::ptr::null_mut());
+            }
+        }
+        expired
+    }
+}
+
+impl Sandbox {
+    // Prepares the global variables for the script
+    pub(super) fn globals<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let globals = PyDict::new_bound(py);
+        if self.restrict_builtins {
+            globals.set_item("__builtins__", restricted_builtins(py)?)?;
+        }
+        Ok(globals)
+    }
+
+    pub(super) fn run<'py, F>(&self, py: Python<'py>, f: F) -> SandboxedRun
+    where
+        F: FnOnce(Python<'py>) -> PyResult<()>,
+    {
+        let capture = match OutputCapture::start(py) {
+            Ok(capture) => capture,
+            Err(e) => {
+                return SandboxedRun {
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    result: Err(e),
+                }
+            }
+        };
+
+        let mut expired = false;
+        let mut result = (|| {
+            let watchdog = self
+                .timeout
+                .map(|timeout| Watchdog::start(py, timeout))
+                .transpose()?;
+            let result = f(py);
+            if let Some(watchdog) = watchdog {
+                expired = watchdog.stop(py);
+            }
+            result
+        })();
+
+        if let (true, Some(timeout), Err(e)) = (expired, self.timeout, &result) {
+            if e.is_instance_of::<pyo3::exceptions::PyTimeoutError>(py) {
+                result = Err(pyo3::exceptions::PyTimeoutError::new_err(format!(
+                    "Script did not finish within {} seconds",
+                    timeout.as_secs_f64()
+                )));
+            }
+        }
+
+        match capture.finish() {
+            Ok((stdout, stderr)) => SandboxedRun {
+                stdout,
+                stderr,
+                result,
+            },
+            Err(e) => SandboxedRun {
+                stdout: String::new(),
+                stderr: String::new(),
+                result: result.and(Err(e)),
+            },
+        }
+    }
+}
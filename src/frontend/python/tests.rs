@@ -0,0 +1,106 @@
+use super::*;
+
+#[test]
+fn stubs_match_module() {
+    initialize();
+
+    let check_code = r#"
+import ast
+import collomatique
+
+def public_names(names):
+    return {name for name in names if not name.startswith("_")}
+
+stub_tree = ast.parse(stubs)
+stub_classes = {}
+stub_functions = set()
+for node in stub_tree.body:
+    if isinstance(node, ast.ClassDef):
+        members = set()
+        for item in node.body:
+            if isinstance(item, ast.FunctionDef):
+                members.add(item.name)
+            elif isinstance(item, ast.AnnAssign):
+                members.add(item.target.id)
+        stub_classes[node.name] = public_names(members)
+    elif isinstance(node, ast.FunctionDef):
+        stub_functions.add(node.name)
+
+module_names = public_names(dir(collomatique))
+assert module_names == set(stub_classes) | stub_functions, module_names ^ (set(stub_classes) | stub_functions)
+
+for name, members in stub_classes.items():
+    actual = public_names(vars(getattr(collomatique, name)))
+    assert actual == members, (name, actual ^ members)
+"#;
+
+    Python::with_gil(|py| {
+        let locals = [("stubs", STUBS)].into_py_dict_bound(py);
+        py.run_bound(check_code, None, Some(&locals))
+    })
+    .unwrap();
+}
+
+#[test]
+fn sandbox_limits_script() {
+    initialize();
+
+    let sandbox = Sandbox {
+        timeout: Some(std::time::Duration::from_millis(200)),
+        restrict_builtins: true,
+    };
+
+    let run_code = |code: &str| {
+        Python::with_gil(|py| {
+            let globals = sandbox.globals(py).unwrap();
+            sandbox.run(py, |py| py.run_bound(code, Some(&globals), None))
+        })
+    };
+
+    let sandboxed_run = run_code("import math\nprint(math.floor(2.5))");
+    assert!(sandboxed_run.result.is_ok());
+    assert_eq!(sandboxed_run.stdout, "2\n");
+
+    let sandboxed_run = run_code("print('before')\nwhile True:\n    pass");
+    assert_eq!(sandboxed_run.stdout, "before\n");
+    Python::with_gil(|py| {
+        let err = sandboxed_run.result.unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyTimeoutError>(py));
+    });
+
+    let sandboxed_run = run_code("import os");
+    Python::with_gil(|py| {
+        let err = sandboxed_run.result.unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyImportError>(py));
+    });
+
+    let sandboxed_run = run_code("open('file.txt', 'w')");
+    Python::with_gil(|py| {
+        let err = sandboxed_run.result.unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyNameError>(py));
+    });
+}
+
+#[test]
+fn soft_constraints_are_validated() {
+    initialize();
+
+    let check_code = r#"
+import collomatique
+
+general_data = collomatique.GeneralData(10)
+general_data.soft_constraints = {"balancing": 3}
+assert general_data.soft_constraints == {"balancing": 3}
+
+for invalid in [{"balancin": 3}, {"max_groups_per_slot": 3}, {"balancing": 0}]:
+    try:
+        general_data.soft_constraints = invalid
+    except ValueError:
+        pass
+    else:
+        raise AssertionError(invalid)
+assert general_data.soft_constraints == {"balancing": 3}
+"#;
+
+    Python::with_gil(|py| py.run_bound(check_code, None, None)).unwrap();
+}
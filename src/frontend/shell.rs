@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Result};
-use clap::{Subcommand, ValueEnum};
-use std::collections::BTreeSet;
+use clap::{Args, Subcommand, ValueEnum};
+use std::collections::{BTreeMap, BTreeSet};
 use std::num::{NonZeroU32, NonZeroUsize};
 use std::path::PathBuf;
 
@@ -32,20 +32,29 @@ pub enum CliCommand {
         /// Verbose resolution output
         #[arg(short, long, default_value_t = false)]
         verbose: bool,
-        /// Quick resolution: do not optimize the colloscope.
-        /// This is useful to see if the constraints are compatible as a solution
-        /// can usually be found in a few minutes. However, the resulting colloscope
-        /// is usually unusable.
-        #[arg(short, long, default_value_t = false)]
-        quick: bool,
-        /// Maximum time for resolution in minutes
-        /// 0 means no limit
-        #[arg(short, long, default_value_t = 60)]
-        max_time: u32,
-        /// EXPERIMENTAL: use HiGHS solver
-        #[cfg(feature = "highs")]
+        #[command(flatten)]
+        solver: SolverArgs,
+        /// If no colloscope can be built, list conflicting constraints
+        /// and look for the cheapest kinds of constraints to relax
+        #[arg(long, default_value_t = false)]
+        advise: bool,
+        #[command(flatten)]
+        base: BaseColloscopeArgs,
+    },
+    /// Run the solver on every problem file (.json) of a directory and write a CSV report
+    Benchmark {
+        /// Directory containing the problem files
+        dir: PathBuf,
+        /// CSV file to write (the report is printed if not given)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Solver profiles to compare (all profiles with a time limit if none is given:
+        /// exhaustive must be asked for explicitly)
+        #[arg(short, long, value_enum)]
+        profiles: Vec<SolverProfileArg>,
+        /// Also benchmark the problem built from the current database
         #[arg(long, default_value_t = false)]
-        highs: bool,
+        add_current: bool,
     },
     /// Create, remove or run python script
     Python {
@@ -71,6 +80,11 @@ pub enum GeneralCommand {
         #[command(subcommand)]
         command: InterrogationsPerWeekRangeCommand,
     },
+    /// Show or modify whether incompatibility slots may cross midnight
+    OvernightIncompatSlots {
+        #[command(subcommand)]
+        command: OvernightIncompatSlotsCommand,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -118,6 +132,16 @@ pub enum InterrogationsPerWeekRangeCommand {
     Print,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum OvernightIncompatSlotsCommand {
+    /// Allow incompatibility slots to cross midnight (they are split into same-day parts)
+    Enable,
+    /// Reject incompatibility slots crossing midnight
+    Disable,
+    /// Show whether incompatibility slots may cross midnight
+    Print,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum WeekPatternCommand {
     /// Create a new week pattern
@@ -129,6 +153,9 @@ pub enum WeekPatternCommand {
         /// Force creating a new week pattern with an existing name
         #[arg(short, long, default_value_t = false)]
         force: bool,
+        /// Needed for week-a and week-b
+        #[command(flatten)]
+        calendar: CalendarArgs,
     },
     /// Remove an existing week pattern
     Remove {
@@ -181,6 +208,9 @@ pub enum WeekPatternCommand {
         week_pattern_number: Option<NonZeroUsize>,
         /// Possible predefined patterns
         pattern: WeekPatternFilling,
+        /// Needed for week-a and week-b
+        #[command(flatten)]
+        calendar: CalendarArgs,
     },
     /// Clear existing week pattern to make it empty
     Clear {
@@ -218,6 +248,255 @@ pub enum WeekPatternCommand {
     },
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum LocaleArg {
+    /// French
+    Fr,
+    /// English
+    En,
+}
+
+impl From<LocaleArg> for crate::time::Locale {
+    fn from(value: LocaleArg) -> Self {
+        use crate::time::Locale;
+        match value {
+            LocaleArg::Fr => Locale::French,
+            LocaleArg::En => Locale::English,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PaperArg {
+    A4,
+    A3,
+    Letter,
+}
+
+impl From<PaperArg> for crate::frontend::print::PaperSize {
+    fn from(value: PaperArg) -> Self {
+        use crate::frontend::print::PaperSize;
+        match value {
+            PaperArg::A4 => PaperSize::A4,
+            PaperArg::A3 => PaperSize::A3,
+            PaperArg::Letter => PaperSize::Letter,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum HolidayZoneArg {
+    A,
+    B,
+    C,
+}
+
+impl From<HolidayZoneArg> for crate::time::HolidayZone {
+    fn from(value: HolidayZoneArg) -> Self {
+        use crate::time::HolidayZone;
+        match value {
+            HolidayZoneArg::A => HolidayZone::A,
+            HolidayZoneArg::B => HolidayZone::B,
+            HolidayZoneArg::C => HolidayZone::C,
+        }
+    }
+}
+
+// Actual dates of the colloscope weeks
+#[derive(Debug, Clone, Args)]
+pub struct CalendarArgs {
+    /// Date of the first week of the colloscope (YYYY-MM-DD, any day of the week)
+    #[arg(long, conflicts_with = "school_year")]
+    first_week: Option<crate::time::Date>,
+    /// Use the official french calendar of the school year starting in september of the given year
+    /// (first week and holidays)
+    #[arg(long)]
+    school_year: Option<i32>,
+    /// Holiday zone for --school-year
+    #[arg(long, value_enum, requires = "school_year", default_value_t = HolidayZoneArg::A)]
+    zone: HolidayZoneArg,
+    /// Weeks without colles (holidays for instance), given by any of their days (YYYY-MM-DD).
+    /// They are not counted as weeks of the colloscope.
+    #[arg(long)]
+    skip_week: Vec<crate::time::Date>,
+}
+
+impl CalendarArgs {
+    // None if no calendar was given
+    fn week_mapping(&self) -> Result<Option<crate::time::WeekMapping>> {
+        use crate::time::{AcademicYear, HolidayCalendar, WeekMapping, WeekStart};
+
+        let (first_week, calendar) = match (&self.first_week, self.school_year) {
+            (Some(first_week), _) => (WeekStart::containing(first_week), HolidayCalendar::new()),
+            (None, Some(school_year)) => {
+                let year = AcademicYear::french(school_year, self.zone.into()).ok_or(anyhow!(
+                    "No calendar is known for school year {}-{}",
+                    school_year,
+                    school_year + 1
+                ))?;
+                (
+                    WeekStart::containing(&year.get_start()),
+                    year.get_calendar().clone(),
+                )
+            }
+            (None, None) => {
+                if !self.skip_week.is_empty() {
+                    return Err(anyhow!(
+                        "--skip-week needs either --first-week or --school-year"
+                    ));
+                }
+                return Ok(None);
+            }
+        };
+
+        let calendar = self.skip_week.iter().fold(calendar, |calendar, date| {
+            calendar.add_week(WeekStart::containing(date))
+        });
+        Ok(Some(WeekMapping::new(first_week, calendar)))
+    }
+}
+
+// How the solver is run
+#[derive(Debug, Clone, Args)]
+pub struct SolverArgs {
+    /// Solver profile: sets the time limit and whether the colloscope is optimized
+    #[arg(short, long, value_enum, default_value_t = SolverProfileArg::Balanced)]
+    profile: SolverProfileArg,
+    /// Quick resolution: do not optimize the colloscope.
+    /// This is useful to see if the constraints are compatible as a solution
+    /// can usually be found in a few minutes. However, the resulting colloscope
+    /// is usually unusable.
+    #[arg(short, long, default_value_t = false)]
+    quick: bool,
+    /// Maximum time for resolution in minutes (overrides the profile)
+    /// 0 means no limit
+    #[arg(short, long)]
+    max_time: Option<u32>,
+    /// Time budget in seconds for local search post-optimization of the solution
+    /// 0 disables it. With --seed, the budget is converted to 100 steps per second
+    #[arg(long, default_value_t = 0)]
+    local_search: u32,
+    /// Deterministic resolution: fix random seeds and use a single thread
+    /// so that the same data always gives the same colloscope (up to time limits)
+    #[arg(long)]
+    seed: Option<u32>,
+    /// EXPERIMENTAL: use HiGHS solver
+    #[cfg(feature = "highs")]
+    #[arg(long, default_value_t = false)]
+    highs: bool,
+}
+
+// Existing colloscope a new resolution starts from
+#[derive(Debug, Clone, Args)]
+pub struct BaseColloscopeArgs {
+    /// Name of the existing colloscope to start from (see --stability, --subject and --from-week)
+    #[arg(long)]
+    base: Option<String>,
+    /// If multiple colloscopes have the same name as --base, select which one to use (1 for the first one, etc...)
+    #[arg(long, requires = "base")]
+    base_number: Option<NonZeroUsize>,
+    /// Cost of each interrogation that differs from the base colloscope,
+    /// to get a colloscope as close as possible to it
+    #[arg(long, requires = "base")]
+    stability: Option<f64>,
+    /// Only rebuild the given subject (can be repeated).
+    /// Everything else is kept as in the base colloscope
+    #[arg(long, requires = "base")]
+    subject: Vec<String>,
+    /// Only rebuild interrogations from this week on (starting from 1).
+    /// Everything else (including groups) is kept as in the base colloscope
+    #[arg(long, requires = "base")]
+    from_week: Option<NonZeroU32>,
+    /// Number of weeks to rebuild. If not given, all remaining weeks are rebuilt
+    #[arg(long, requires = "from_week")]
+    weeks: Option<NonZeroU32>,
+}
+
+impl BaseColloscopeArgs {
+    async fn colloscope(
+        &self,
+        app_state: &mut AppState<sqlite::Store>,
+        translator: &crate::frontend::translator::GenColloscopeTranslator,
+    ) -> Result<Option<crate::gen::colloscope::Colloscope>> {
+        let Some(name) = &self.base else {
+            return Ok(None);
+        };
+        let (_handle, colloscope) = get_colloscope(app_state, name, self.base_number).await?;
+        let colloscope = translator.read_colloscope(&colloscope).map_err(|_| {
+            anyhow!(
+                "Colloscope \"{}\" does not match the current data and cannot be used as a base",
+                name
+            )
+        })?;
+        Ok(Some(colloscope))
+    }
+
+    // None if the whole colloscope should be rebuilt
+    async fn scope(
+        &self,
+        app_state: &mut AppState<sqlite::Store>,
+        translator: &crate::frontend::translator::GenColloscopeTranslator,
+    ) -> Result<Option<crate::gen::colloscope::SolveScope>> {
+        use crate::frontend::state::Manager;
+
+        if self.subject.is_empty() && self.from_week.is_none() {
+            return Ok(None);
+        }
+
+        let subjects = if self.subject.is_empty() {
+            None
+        } else {
+            let all_subjects = app_state.subjects_get_all().await?;
+            let mut handles = BTreeSet::new();
+            for name in &self.subject {
+                let matching: Vec<_> = all_subjects
+                    .iter()
+                    .filter(|(_handle, subject)| subject.name == *name)
+                    .map(|(handle, _subject)| *handle)
+                    .collect();
+                match matching.as_slice() {
+                    [handle] => {
+                        handles.insert(*handle);
+                    }
+                    [] => return Err(anyhow!("No subject has the name \"{}\".", name)),
+                    _ => return Err(anyhow!("Several subjects have the name \"{}\".", name)),
+                }
+            }
+            Some(handles)
+        };
+        let weeks = self.from_week.map(|from_week| {
+            let first = from_week.get() - 1;
+            match self.weeks {
+                Some(weeks) => first..(first + weeks.get()),
+                None => first..u32::MAX,
+            }
+        });
+
+        Ok(Some(translator.solve_scope(subjects.as_ref(), weeks)?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SolverProfileArg {
+    /// Only look for a valid colloscope (10 minutes at most)
+    QuickDraft,
+    /// Optimize the colloscope for at most an hour
+    Balanced,
+    /// Optimize the colloscope until optimality is proven (no time limit)
+    Exhaustive,
+}
+
+impl From<SolverProfileArg> for crate::ilp::solvers::SolverProfile {
+    fn from(value: SolverProfileArg) -> Self {
+        use crate::ilp::solvers::SolverProfile;
+        match value {
+            SolverProfileArg::QuickDraft => SolverProfile::QuickDraft,
+            SolverProfileArg::Balanced => SolverProfile::Balanced,
+            SolverProfileArg::Exhaustive => SolverProfile::Exhaustive,
+        }
+    }
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 pub enum WeekPatternFilling {
     /// Fill the week pattern with every week for 1 to week_count
@@ -226,6 +505,11 @@ pub enum WeekPatternFilling {
     Even,
     /// Fill the week pattern with every odd week for 1 to week_count
     Odd,
+    /// Fill the week pattern with the weeks A: one calendar week out of two, starting with
+    /// the first week and counting holidays (unlike odd which only counts colloscope weeks)
+    WeekA,
+    /// Fill the week pattern with the weeks B (see week-a)
+    WeekB,
 }
 
 #[derive(Debug, Subcommand)]
@@ -257,7 +541,7 @@ pub enum ColloscopeCommand {
     },
     /// Show all colloscopes
     PrintAll,
-    /// Export a colloscope to xlsx
+    /// Export a colloscope to xlsx (one worksheet per period)
     Export {
         /// Name of the colloscope to export
         name: String,
@@ -269,6 +553,160 @@ pub enum ColloscopeCommand {
         /// Name of the output xlsx file.
         /// If the file already exists, it will be overwritten.
         output: std::path::PathBuf,
+        /// Language used for days and times
+        #[arg(short, long, value_enum, default_value_t = LocaleArg::Fr)]
+        locale: LocaleArg,
+        /// Paper size used when printing
+        #[arg(long, value_enum, default_value_t = PaperArg::A4)]
+        paper: PaperArg,
+        /// Print in portrait orientation (landscape by default)
+        #[arg(long)]
+        portrait: bool,
+        /// Scale of the printed text (1.0 is the natural size)
+        #[arg(long, default_value_t = 1.)]
+        font_scale: f64,
+        /// Only print the headers on the first page instead of every page
+        #[arg(long)]
+        no_repeat_headers: bool,
+    },
+    /// Export a colloscope to a standalone html page
+    /// (with filters by student, teacher, subject and week)
+    ExportHtml {
+        /// Name of the colloscope to export
+        name: String,
+        /// If multiple colloscopes have the same name, select which one to use.
+        /// So if there are 3 colloscopes with the same name, 1 would refer to the first one, 2 to the second, etc...
+        /// Be careful the order might change between databases update (even when using undo/redo)
+        #[arg(short = 'n')]
+        colloscope_number: Option<NonZeroUsize>,
+        /// Name of the output html file.
+        /// If the file already exists, it will be overwritten.
+        output: std::path::PathBuf,
+        /// Language used for days and times
+        #[arg(short, long, value_enum, default_value_t = LocaleArg::Fr)]
+        locale: LocaleArg,
+    },
+    /// Export a colloscope to iCalendar files (one per teacher and one per student)
+    ExportIcal {
+        /// Name of the colloscope to export
+        name: String,
+        /// If multiple colloscopes have the same name, select which one to use.
+        /// So if there are 3 colloscopes with the same name, 1 would refer to the first one, 2 to the second, etc...
+        /// Be careful the order might change between databases update (even when using undo/redo)
+        #[arg(short = 'n')]
+        colloscope_number: Option<NonZeroUsize>,
+        /// Directory in which the ics files are written.
+        /// Existing files with the same names will be overwritten.
+        output: std::path::PathBuf,
+        /// Either --first-week or --school-year is needed
+        #[command(flatten)]
+        calendar: CalendarArgs,
+    },
+    /// Export a colloscope to csv with one line per interrogation
+    ExportCsv {
+        /// Name of the colloscope to export
+        name: String,
+        /// If multiple colloscopes have the same name, select which one to use.
+        /// So if there are 3 colloscopes with the same name, 1 would refer to the first one, 2 to the second, etc...
+        /// Be careful the order might change between databases update (even when using undo/redo)
+        #[arg(short = 'n')]
+        colloscope_number: Option<NonZeroUsize>,
+        /// Name of the output csv file.
+        /// If the file already exists, it will be overwritten.
+        output: std::path::PathBuf,
+        /// Do not write the headers line
+        #[arg(long)]
+        no_headers: bool,
+        /// Delimiter for the csv file
+        #[arg(short, long, default_value_t = ';')]
+        delimiter: char,
+        /// If no calendar is given, the date column is left empty
+        #[command(flatten)]
+        calendar: CalendarArgs,
+        /// Language used for days and times
+        #[arg(short, long, value_enum, default_value_t = LocaleArg::Fr)]
+        locale: LocaleArg,
+    },
+    /// List the differences between a colloscope and another one (for instance a new resolution)
+    Diff {
+        /// Name of the current colloscope
+        name: String,
+        /// If multiple colloscopes have the same name, select which one to use.
+        /// So if there are 3 colloscopes with the same name, 1 would refer to the first one, 2 to the second, etc...
+        /// Be careful the order might change between databases update (even when using undo/redo)
+        #[arg(short = 'n')]
+        colloscope_number: Option<NonZeroUsize>,
+        /// Name of the proposed colloscope
+        proposed: String,
+        /// If multiple colloscopes have the same name as the proposed one, select which one to use
+        #[arg(short = 'm')]
+        proposed_number: Option<NonZeroUsize>,
+        /// Language for days
+        #[arg(short, long, value_enum, default_value_t = LocaleArg::Fr)]
+        locale: LocaleArg,
+    },
+    /// Apply some of the differences listed by "diff" to the current colloscope
+    ApplyChanges {
+        /// Name of the colloscope to update
+        name: String,
+        /// If multiple colloscopes have the same name, select which one to use.
+        /// So if there are 3 colloscopes with the same name, 1 would refer to the first one, 2 to the second, etc...
+        /// Be careful the order might change between databases update (even when using undo/redo)
+        #[arg(short = 'n')]
+        colloscope_number: Option<NonZeroUsize>,
+        /// Name of the proposed colloscope
+        proposed: String,
+        /// If multiple colloscopes have the same name as the proposed one, select which one to use
+        #[arg(short = 'm')]
+        proposed_number: Option<NonZeroUsize>,
+        /// Number of a change to apply, as listed by "diff" (can be repeated).
+        /// Every change is applied if none is given
+        #[arg(short, long)]
+        change: Vec<NonZeroUsize>,
+    },
+    /// List double bookings and incompatibilities not respected by a colloscope
+    Check {
+        /// Name of the colloscope to check
+        name: String,
+        /// If multiple colloscopes have the same name, select which one to use.
+        /// So if there are 3 colloscopes with the same name, 1 would refer to the first one, 2 to the second, etc...
+        /// Be careful the order might change between databases update (even when using undo/redo)
+        #[arg(short = 'n')]
+        colloscope_number: Option<NonZeroUsize>,
+        /// Language used for days and times
+        #[arg(short, long, value_enum, default_value_t = LocaleArg::Fr)]
+        locale: LocaleArg,
+    },
+    /// Export, for each teacher and student, their contact information and interrogations (for mail merge)
+    ExportMailMerge {
+        /// Name of the colloscope to export
+        name: String,
+        /// If multiple colloscopes have the same name, select which one to use.
+        /// So if there are 3 colloscopes with the same name, 1 would refer to the first one, 2 to the second, etc...
+        /// Be careful the order might change between databases update (even when using undo/redo)
+        #[arg(short = 'n')]
+        colloscope_number: Option<NonZeroUsize>,
+        /// Name of the output file.
+        /// If the file already exists, it will be overwritten.
+        output: std::path::PathBuf,
+        /// First week to include (starting from 1)
+        #[arg(long, default_value_t = NonZeroU32::new(1).unwrap())]
+        from_week: NonZeroU32,
+        /// Number of weeks to include. If not given, all remaining weeks are included.
+        #[arg(long)]
+        weeks: Option<NonZeroU32>,
+        /// Write json instead of csv
+        #[arg(long)]
+        json: bool,
+        /// Delimiter for the csv file
+        #[arg(short, long, default_value_t = ';', conflicts_with = "json")]
+        delimiter: char,
+        /// If no calendar is given, interrogations are not dated
+        #[command(flatten)]
+        calendar: CalendarArgs,
+        /// Language used for days and times
+        #[arg(short, long, value_enum, default_value_t = LocaleArg::Fr)]
+        locale: LocaleArg,
     },
 }
 
@@ -332,6 +770,18 @@ pub enum PythonCommand {
         /// Delimiter for the csv file (default is adjusted for pronote files)
         #[arg(short, long, default_value_t = ';')]
         delimiter: char,
+        /// Stop the script if it runs for longer than the given number of seconds
+        #[arg(long)]
+        timeout: Option<f64>,
+        /// Run the script with restricted builtins and imports (for untrusted scripts)
+        #[arg(long)]
+        sandbox: bool,
+    },
+    /// Write type stubs (.pyi) for the python module to use with an IDE or mypy
+    Stubs {
+        /// File to write (the stubs are printed if not given)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 }
 
@@ -379,19 +829,97 @@ fn find_colloscope_name(
     }
 }
 
+fn describe_solve_stats(stats: &crate::ilp::solvers::SolveStats) -> String {
+    use crate::ilp::solvers::SolveStatus;
+
+    let time = format!("{:.1}s", stats.solve_time.as_secs_f64());
+    let gap = match stats.gap() {
+        Some(gap) if gap.is_finite() => format!(", gap {:.2}%", 100. * gap),
+        _ => String::new(),
+    };
+
+    match stats.status {
+        SolveStatus::Optimal => format!("optimal, {}", time),
+        SolveStatus::TimeLimitReached => {
+            format!("best found within time limit{}, {}", gap, time)
+        }
+        _ => format!("not proven optimal{}, {}", gap, time),
+    }
+}
+
+async fn benchmark_command(
+    dir: PathBuf,
+    output: Option<PathBuf>,
+    profiles: Vec<SolverProfileArg>,
+    add_current: bool,
+    app_state: &mut AppState<sqlite::Store>,
+) -> Result<Option<String>> {
+    use crate::frontend::translator::GenColloscopeTranslator;
+    use crate::ilp::solvers::{benchmark, SolverProfile};
+
+    let mut instances = benchmark::load_problems_from_dir(&dir)?;
+    if add_current {
+        let gen_colloscope_translator = GenColloscopeTranslator::new(app_state).await?;
+        let data = gen_colloscope_translator.get_validated_data();
+        instances.push((String::from("current"), data.ilp_translator().problem()));
+    }
+
+    let configs: Vec<_> = if profiles.is_empty() {
+        SolverProfile::ALL
+            .into_iter()
+            .filter(|profile| profile.options().time_limit_in_seconds.is_some())
+            .map(From::from)
+            .collect()
+    } else {
+        profiles
+            .into_iter()
+            .map(|profile| SolverProfile::from(profile).into())
+            .collect()
+    };
+
+    let solver = crate::ilp::solvers::coin_cbc::Solver::with_disable_logging(true);
+    let records = benchmark::run(&solver, instances, &configs);
+
+    match output {
+        Some(path) => {
+            let file = std::fs::File::create(&path)?;
+            benchmark::write_csv(&records, file)?;
+            Ok(Some(format!(
+                "Benchmark report written to \"{}\".",
+                path.display()
+            )))
+        }
+        None => {
+            let mut report = Vec::new();
+            benchmark::write_csv(&records, &mut report)?;
+            Ok(Some(String::from_utf8(report)?))
+        }
+    }
+}
+
 async fn solve_command(
     name: Option<String>,
     force: bool,
     verbose: bool,
-    quick: bool,
-    max_time: u32,
-    #[cfg(feature = "highs")] highs: bool,
+    solver: SolverArgs,
+    advise: bool,
+    base: BaseColloscopeArgs,
     app_state: &mut AppState<sqlite::Store>,
 ) -> Result<Option<String>> {
     use crate::frontend::{state::update::Manager, translator::GenColloscopeTranslator};
     use indicatif::{ProgressBar, ProgressStyle};
     use std::time::Duration;
 
+    let SolverArgs {
+        profile,
+        quick,
+        max_time,
+        local_search,
+        seed,
+        #[cfg(feature = "highs")]
+        highs,
+    } = solver;
+
     let colloscopes = app_state.colloscopes_get_all().await?;
 
     let colloscope_name = match name {
@@ -417,52 +945,187 @@ async fn solve_command(
     let data = gen_colloscope_translator.get_validated_data();
 
     let ilp_translator = data.ilp_translator();
+    let base_colloscope = base
+        .colloscope(app_state, &gen_colloscope_translator)
+        .await?;
+    let scope = base.scope(app_state, &gen_colloscope_translator).await?;
 
     let pb = ProgressBar::new_spinner().with_style(style.clone());
     pb.set_message("Generating ILP problem...");
     pb.enable_steady_tick(Duration::from_millis(20));
-    let problem = ilp_translator.problem();
+    let mut pb_builder = match (&base_colloscope, &scope) {
+        (Some(base_colloscope), Some(scope)) => ilp_translator
+            .problem_builder_for_scope(base_colloscope, scope)
+            .ok_or(anyhow!("Base colloscope does not match the current data"))?,
+        _ => ilp_translator.problem_builder(),
+    };
+    if let (Some(base_colloscope), Some(weight)) = (&base_colloscope, base.stability) {
+        pb_builder = ilp_translator
+            .add_stability_objective(pb_builder, base_colloscope, weight)
+            .ok_or(anyhow!("Base colloscope does not match the current data"))?;
+    }
+    let problem = pb_builder.build();
     pb.finish();
 
+    if verbose {
+        println!("{}", problem.stats());
+    }
+
     let pb = ProgressBar::new_spinner().with_style(style.clone());
 
     pb.set_message("Building colloscope... (this can take a few minutes)");
     pb.enable_steady_tick(Duration::from_millis(100));
 
     use crate::ilp::solvers::FeasabilitySolver;
-    let minimize_objective = !quick;
+    let profile = crate::ilp::solvers::SolverProfile::from(profile);
+    let minimize_objective = profile.minimize_objective() && !quick;
 
-    let config_hint = problem.default_config();
-    let time_limit_in_seconds = if max_time == 0 {
-        None
-    } else {
-        Some(max_time * 60)
+    // Warm start from the base colloscope or from a greedy construction
+    let config_hint = match &base_colloscope {
+        Some(base_colloscope) => {
+            ilp_translator.config_hint_from_colloscope(&problem, base_colloscope)
+        }
+        None => ilp_translator
+            .config_hint_from_colloscope(&problem, &ilp_translator.greedy_colloscope()),
+    };
+    let mut solver_options = profile.options();
+    if let Some(max_time) = max_time {
+        solver_options.time_limit_in_seconds = if max_time == 0 {
+            None
+        } else {
+            Some(max_time * 60)
+        };
+    }
+    if let Some(seed) = seed {
+        solver_options = solver_options.with_determinism(seed);
+    }
+
+    let cancellation_token = crate::ilp::solvers::CancellationToken::new();
+    let progress = |progress: crate::ilp::solvers::SolveProgress| {
+        if let crate::ilp::solvers::SolveProgress::TimeElapsed {
+            time_limit_fraction: Some(fraction),
+            ..
+        } = progress
+        {
+            pb.set_message(format!(
+                "Building colloscope... (this can take a few minutes - {:.0}% of time limit)",
+                100. * fraction
+            ));
+        }
     };
 
     #[cfg(feature = "highs")]
-    let config_opt = if highs {
+    let solve_result = if highs {
         let solver = crate::ilp::solvers::highs::Solver::with_disable_logging(!verbose);
-        solver.solve(&config_hint, minimize_objective, time_limit_in_seconds)
+        solver.solve_with_progress(
+            &config_hint,
+            minimize_objective,
+            &solver_options,
+            &cancellation_token,
+            &progress,
+        )
     } else {
         let solver = crate::ilp::solvers::coin_cbc::Solver::with_disable_logging(!verbose);
-        solver.solve(&config_hint, minimize_objective, time_limit_in_seconds)
+        solver.solve_with_progress(
+            &config_hint,
+            minimize_objective,
+            &solver_options,
+            &cancellation_token,
+            &progress,
+        )
     };
 
     #[cfg(not(feature = "highs"))]
-    let config_opt = {
+    let solve_result = {
         let solver = crate::ilp::solvers::coin_cbc::Solver::with_disable_logging(!verbose);
-        solver.solve(&config_hint, minimize_objective, time_limit_in_seconds)
+        solver.solve_with_progress(
+            &config_hint,
+            minimize_objective,
+            &solver_options,
+            &cancellation_token,
+            &progress,
+        )
     };
 
-    pb.finish_with_message(if config_opt.is_some() {
-        "Done. Found valid colloscope"
+    pb.finish_with_message(if solve_result.config.is_some() {
+        format!(
+            "Done. Found valid colloscope ({})",
+            describe_solve_stats(&solve_result.stats)
+        )
     } else {
-        "Done"
+        String::from("Done")
     });
 
-    let config = match config_opt {
+    let config = match solve_result.config {
         Some(value) => value,
-        None => return Err(anyhow!("No solution found, colloscope is unfeasable!\nThis means the constraints are incompatible and no colloscope can be built that follows all of them. Relax some constraints and try again.")),
+        None => {
+            if advise {
+                let pb = ProgressBar::new_spinner().with_style(style.clone());
+                pb.set_message("Looking for conflicting constraints...");
+                pb.enable_steady_tick(Duration::from_millis(100));
+                let solver = crate::ilp::solvers::coin_cbc::Solver::with_disable_logging(!verbose);
+                let explanation =
+                    ilp_translator.diagnose_infeasability(&problem, &solver, &solver_options);
+                pb.finish();
+
+                if let Some(explanation) = explanation {
+                    let explanation =
+                        gen_colloscope_translator.translate_infeasability_explanation(&explanation);
+                    println!("The following constraints cannot be satisfied together:");
+                    println!(
+                        "{}",
+                        gen_colloscope_translator.describe_infeasability(
+                            &explanation,
+                            &app_state.subjects_get_all().await?,
+                            &app_state.students_get_all().await?,
+                            &app_state.incompats_get_all().await?,
+                            &app_state.groupings_get_all().await?,
+                            crate::time::Locale::English,
+                        )
+                    );
+                }
+
+                let pb = ProgressBar::new_spinner().with_style(style.clone());
+                pb.set_message("Looking for constraints to relax...");
+                pb.enable_steady_tick(Duration::from_millis(100));
+                let suggestions =
+                    ilp_translator.suggest_relaxations(&problem, &solver, 3, &solver_options);
+                pb.finish();
+
+                if suggestions.is_empty() {
+                    println!("No combination of constraints to relax was found.");
+                } else {
+                    println!("Relaxing one of the following would make the colloscope feasable:");
+                    for suggestion in &suggestions {
+                        let kinds: Vec<_> =
+                            suggestion.kinds.iter().map(|k| k.to_string()).collect();
+                        println!("- {}", kinds.join(" + "));
+                    }
+                }
+            }
+            return Err(anyhow!("No solution found, colloscope is unfeasable!\nThis means the constraints are incompatible and no colloscope can be built that follows all of them. Relax some constraints and try again."));
+        }
+    };
+
+    let config = if local_search != 0 && minimize_objective {
+        let pb = ProgressBar::new_spinner().with_style(style.clone());
+        pb.set_message("Improving colloscope with local search...");
+        pb.enable_steady_tick(Duration::from_millis(100));
+
+        let improved = crate::ilp::solvers::local_search::improve_within_budget(
+            &config,
+            local_search,
+            seed.map(u64::from),
+        );
+
+        pb.finish_with_message(format!(
+            "Done. Objective went from {} to {}",
+            config.objective_value(),
+            improved.objective_value()
+        ));
+        improved
+    } else {
+        config
     };
 
     let ilp_config = ilp_translator
@@ -715,6 +1378,38 @@ async fn interrogations_per_week_range_command(
     }
 }
 
+async fn overnight_incompat_slots_command(
+    command: OvernightIncompatSlotsCommand,
+    app_state: &mut AppState<sqlite::Store>,
+) -> Result<Option<String>> {
+    use crate::frontend::state::{Manager, Operation, UpdateError};
+
+    let overnight_incompat_slots = match command {
+        OvernightIncompatSlotsCommand::Enable => true,
+        OvernightIncompatSlotsCommand::Disable => false,
+        OvernightIncompatSlotsCommand::Print => {
+            let general_data = app_state.general_data_get().await?;
+            let output = if general_data.overnight_incompat_slots {
+                "enabled"
+            } else {
+                "disabled"
+            };
+            return Ok(Some(String::from(output)));
+        }
+    };
+
+    let mut general_data = app_state.general_data_get().await?;
+    general_data.overnight_incompat_slots = overnight_incompat_slots;
+    if let Err(e) = app_state.apply(Operation::GeneralData(general_data)).await {
+        let err = match e {
+            UpdateError::Internal(int_err) => anyhow::Error::from(int_err),
+            _ => panic!("/!\\ Unexpected error ! {:?}", e),
+        };
+        return Err(err);
+    }
+    Ok(None)
+}
+
 async fn general_command(
     command: GeneralCommand,
     app_state: &mut AppState<sqlite::Store>,
@@ -727,6 +1422,9 @@ async fn general_command(
         GeneralCommand::InterrogationsPerWeekRange { command } => {
             interrogations_per_week_range_command(command, app_state).await
         }
+        GeneralCommand::OvernightIncompatSlots { command } => {
+            overnight_incompat_slots_command(command, app_state).await
+        }
     }
 }
 
@@ -784,14 +1482,35 @@ async fn get_week_pattern(
 fn predefined_week_pattern_weeks(
     filling: WeekPatternFilling,
     week_count: NonZeroU32,
-) -> BTreeSet<crate::backend::Week> {
+    week_mapping: Option<&crate::time::WeekMapping>,
+) -> Result<BTreeSet<crate::backend::Week>> {
     use crate::backend::Week;
-    let weeks = (0..week_count.get()).into_iter();
-    match filling {
-        WeekPatternFilling::All => weeks.map(|w| Week::new(w)).collect(),
-        WeekPatternFilling::Odd => weeks.step_by(2).map(|w| Week::new(w)).collect(),
-        WeekPatternFilling::Even => weeks.skip(1).step_by(2).map(|w| Week::new(w)).collect(),
-    }
+    use crate::time::{ParityCalendar, Recurrence, WeekParity};
+    // Weeks are displayed starting from 1 so odd weeks have offset 0
+    let recurrence = match filling {
+        WeekPatternFilling::All => Recurrence::Weekly,
+        WeekPatternFilling::Odd => Recurrence::Biweekly { offset: 0 },
+        WeekPatternFilling::Even => Recurrence::Biweekly { offset: 1 },
+        WeekPatternFilling::WeekA | WeekPatternFilling::WeekB => {
+            let week_mapping = week_mapping.ok_or(anyhow!(
+                "Weeks A and B need dates: use --first-week or --school-year"
+            ))?;
+            let parity = match filling {
+                WeekPatternFilling::WeekA => WeekParity::A,
+                _ => WeekParity::B,
+            };
+            ParityCalendar::new(week_mapping.week_start(0)).recurrence(
+                week_mapping,
+                week_count.get(),
+                parity,
+            )
+        }
+    };
+    Ok(recurrence
+        .weeks(week_count.get())
+        .into_iter()
+        .map(Week::new)
+        .collect())
 }
 
 async fn week_patterns_check_existing_names(
@@ -824,18 +1543,22 @@ async fn week_pattern_command(
             name,
             pattern,
             force,
+            calendar,
         } => {
             if !force {
                 week_patterns_check_existing_names(app_state, &name).await?;
             }
             let general_data = app_state.general_data_get().await?;
+            let week_mapping = calendar.week_mapping()?;
 
             let pattern = WeekPattern {
                 name,
                 weeks: match pattern {
-                    Some(filling) => {
-                        predefined_week_pattern_weeks(filling, general_data.week_count)
-                    }
+                    Some(filling) => predefined_week_pattern_weeks(
+                        filling,
+                        general_data.week_count,
+                        week_mapping.as_ref(),
+                    )?,
                     None => BTreeSet::new(),
                 },
             };
@@ -970,14 +1693,20 @@ async fn week_pattern_command(
             name,
             week_pattern_number,
             pattern,
+            calendar,
         } => {
+            let week_mapping = calendar.week_mapping()?;
             let (handle, _week_pattern) =
                 get_week_pattern(app_state, &name, week_pattern_number).await?;
 
             let general_data = app_state.general_data_get().await?;
             let new_week_pattern = WeekPattern {
                 name,
-                weeks: predefined_week_pattern_weeks(pattern, general_data.week_count),
+                weeks: predefined_week_pattern_weeks(
+                    pattern,
+                    general_data.week_count,
+                    week_mapping.as_ref(),
+                )?,
             };
 
             if let Err(e) = app_state
@@ -1254,6 +1983,11 @@ async fn colloscope_command(
             name,
             colloscope_number,
             output,
+            locale,
+            paper,
+            portrait,
+            font_scale,
+            no_repeat_headers,
         } => {
             let (_handle, colloscope) = get_colloscope(app_state, &name, colloscope_number).await?;
 
@@ -1261,6 +1995,28 @@ async fn colloscope_command(
             let subjects = app_state.subjects_get_all().await?;
             let subject_groups = app_state.subject_groups_get_all().await?;
             let students = app_state.students_get_all().await?;
+            let general_data = app_state.general_data_get().await?;
+
+            if font_scale.is_nan() || font_scale <= 0. {
+                return Err(anyhow!("Font scale must be positive"));
+            }
+            let page_setup = super::print::PageSetup {
+                paper_size: paper.into(),
+                orientation: if portrait {
+                    super::print::Orientation::Portrait
+                } else {
+                    super::print::Orientation::Landscape
+                },
+                font_scale,
+                repeat_headers: !no_repeat_headers,
+                ..super::print::PageSetup::default()
+            };
+
+            let options = super::xlsx::ExportOptions {
+                periodicity_cuts: &general_data.periodicity_cuts,
+                page_setup: &page_setup,
+                locale: locale.into(),
+            };
 
             super::xlsx::export_colloscope_to_xlsx(
                 &colloscope,
@@ -1268,6 +2024,278 @@ async fn colloscope_command(
                 &subjects,
                 &subject_groups,
                 &students,
+                &options,
+                &output,
+            )?;
+
+            Ok(None)
+        }
+        ColloscopeCommand::ExportHtml {
+            name,
+            colloscope_number,
+            output,
+            locale,
+        } => {
+            let (_handle, colloscope) = get_colloscope(app_state, &name, colloscope_number).await?;
+
+            let teachers = app_state.teachers_get_all().await?;
+            let subjects = app_state.subjects_get_all().await?;
+            let students = app_state.students_get_all().await?;
+
+            super::html::export_colloscope_to_html(
+                &colloscope,
+                &teachers,
+                &subjects,
+                &students,
+                &output,
+                locale.into(),
+            )?;
+
+            Ok(None)
+        }
+        ColloscopeCommand::ExportIcal {
+            name,
+            colloscope_number,
+            output,
+            calendar,
+        } => {
+            let week_mapping = calendar.week_mapping()?.ok_or(anyhow!(
+                "Dates are needed for iCalendar files: use --first-week or --school-year"
+            ))?;
+            let (_handle, colloscope) = get_colloscope(app_state, &name, colloscope_number).await?;
+
+            let teachers = app_state.teachers_get_all().await?;
+            let subjects = app_state.subjects_get_all().await?;
+            let students = app_state.students_get_all().await?;
+
+            let files = super::ical::export_colloscope_to_ical(
+                &colloscope,
+                &teachers,
+                &subjects,
+                &students,
+                &week_mapping,
+                &output,
+            )?;
+
+            Ok(Some(format!("{} calendar(s) written", files.len())))
+        }
+        ColloscopeCommand::ExportCsv {
+            name,
+            colloscope_number,
+            output,
+            no_headers,
+            delimiter,
+            calendar,
+            locale,
+        } => {
+            let week_mapping = calendar.week_mapping()?;
+            let (_handle, colloscope) = get_colloscope(app_state, &name, colloscope_number).await?;
+
+            let teachers = app_state.teachers_get_all().await?;
+            let subjects = app_state.subjects_get_all().await?;
+            let students = app_state.students_get_all().await?;
+
+            if !delimiter.is_ascii() {
+                return Err(anyhow!(
+                    "Csv delimiter must be encoded as a single byte  ASCII character"
+                ));
+            }
+            let options = super::csv::ExportOptions {
+                week_mapping: week_mapping.as_ref(),
+                params: crate::frontend::csv::Params {
+                    has_headers: !no_headers,
+                    delimiter: delimiter as u8,
+                },
+                locale: locale.into(),
+            };
+
+            super::csv::export_colloscope_to_csv(
+                &colloscope,
+                &teachers,
+                &subjects,
+                &students,
+                &options,
+                &output,
+            )?;
+
+            Ok(None)
+        }
+        ColloscopeCommand::Diff {
+            name,
+            colloscope_number,
+            proposed,
+            proposed_number,
+            locale,
+        } => {
+            let (_handle, colloscope) = get_colloscope(app_state, &name, colloscope_number).await?;
+            let (_handle, proposed_colloscope) =
+                get_colloscope(app_state, &proposed, proposed_number).await?;
+
+            let translator =
+                crate::frontend::translator::GenColloscopeTranslator::new(app_state).await?;
+            let changes = translator.diff_colloscopes(&colloscope, &proposed_colloscope)?;
+            if changes.is_empty() {
+                return Ok(Some(String::from("No difference found")));
+            }
+
+            let subjects = app_state.subjects_get_all().await?;
+            let students = app_state.students_get_all().await?;
+            let lines: Vec<_> = changes
+                .iter()
+                .enumerate()
+                .map(|(i, change)| {
+                    format!(
+                        "{}. {}",
+                        i + 1,
+                        translator.describe_colloscope_change(
+                            change,
+                            &subjects,
+                            &students,
+                            locale.into()
+                        )
+                    )
+                })
+                .collect();
+            Ok(Some(lines.join("\n")))
+        }
+        ColloscopeCommand::ApplyChanges {
+            name,
+            colloscope_number,
+            proposed,
+            proposed_number,
+            change,
+        } => {
+            let (handle, colloscope) = get_colloscope(app_state, &name, colloscope_number).await?;
+            let (_handle, proposed_colloscope) =
+                get_colloscope(app_state, &proposed, proposed_number).await?;
+
+            let translator =
+                crate::frontend::translator::GenColloscopeTranslator::new(app_state).await?;
+            let changes = translator.diff_colloscopes(&colloscope, &proposed_colloscope)?;
+            let selected_changes = if change.is_empty() {
+                changes
+            } else {
+                change
+                    .iter()
+                    .map(|num| {
+                        changes.get(num.get() - 1).cloned().ok_or(anyhow!(
+                            "There is no change number {} ({} changes found)",
+                            num,
+                            changes.len()
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            };
+
+            let op = translator.apply_changes_operation(handle, &colloscope, &selected_changes)?;
+            if let Err(e) = app_state.apply(Operation::Colloscopes(op)).await {
+                let err = match e {
+                    UpdateError::Internal(int_err) => anyhow::Error::from(int_err),
+                    _ => panic!("/!\\ Unexpected error ! {:?}", e),
+                };
+                return Err(err);
+            }
+            Ok(None)
+        }
+        ColloscopeCommand::Check {
+            name,
+            colloscope_number,
+            locale,
+        } => {
+            let (_handle, colloscope) = get_colloscope(app_state, &name, colloscope_number).await?;
+
+            let teachers = app_state.teachers_get_all().await?;
+            let subjects = app_state.subjects_get_all().await?;
+            let students = app_state.students_get_all().await?;
+            let incompats = app_state.incompats_get_all().await?;
+            let week_patterns = app_state.week_patterns_get_all().await?;
+
+            let mut registrations = BTreeMap::new();
+            for student_handle in students.keys() {
+                let mut student_incompats = BTreeSet::new();
+                for incompat_handle in incompats.keys() {
+                    if app_state
+                        .incompat_for_student_get(*student_handle, *incompat_handle)
+                        .await?
+                    {
+                        student_incompats.insert(*incompat_handle);
+                    }
+                }
+                registrations.insert(*student_handle, student_incompats);
+            }
+
+            let conflicts = super::conflicts::find_conflicts(
+                &colloscope,
+                &subjects,
+                &incompats,
+                &week_patterns,
+                &registrations,
+            );
+            if conflicts.is_empty() {
+                return Ok(Some(String::from("No conflict found")));
+            }
+
+            let lines: Vec<_> = conflicts
+                .iter()
+                .map(|conflict| {
+                    conflict.describe(
+                        &colloscope,
+                        &teachers,
+                        &subjects,
+                        &students,
+                        &incompats,
+                        locale.into(),
+                    )
+                })
+                .collect();
+            Ok(Some(lines.join("\n")))
+        }
+        ColloscopeCommand::ExportMailMerge {
+            name,
+            colloscope_number,
+            output,
+            from_week,
+            weeks,
+            json,
+            delimiter,
+            calendar,
+            locale,
+        } => {
+            let week_mapping = calendar.week_mapping()?;
+            let (_handle, colloscope) = get_colloscope(app_state, &name, colloscope_number).await?;
+
+            let teachers = app_state.teachers_get_all().await?;
+            let subjects = app_state.subjects_get_all().await?;
+            let students = app_state.students_get_all().await?;
+
+            let format = if json {
+                super::mailmerge::Format::Json
+            } else {
+                if !delimiter.is_ascii() {
+                    return Err(anyhow!(
+                        "Csv delimiter must be encoded as a single byte  ASCII character"
+                    ));
+                }
+                super::mailmerge::Format::Csv {
+                    delimiter: delimiter as u8,
+                }
+            };
+            let options = super::mailmerge::ExportOptions {
+                week_mapping: week_mapping.as_ref(),
+                weeks: super::mailmerge::WeekRange {
+                    first: from_week.get() - 1,
+                    count: weeks.map(|weeks| weeks.get()),
+                },
+                format,
+                locale: locale.into(),
+            };
+
+            super::mailmerge::export_colloscope_to_mail_merge(
+                &colloscope,
+                &teachers,
+                &subjects,
+                &students,
+                &options,
                 &output,
             )?;
 
@@ -1276,6 +2304,28 @@ async fn colloscope_command(
     }
 }
 
+fn read_csv_extract(
+    path: &std::path::Path,
+    no_headers: bool,
+    delimiter: char,
+) -> Result<crate::frontend::csv::Extract> {
+    let csv_content = crate::frontend::csv::Content::from_csv_file(path)?;
+
+    if !delimiter.is_ascii() {
+        return Err(anyhow!(
+            "Csv delimiter must be encoded as a single byte  ASCII character"
+        ));
+    }
+    let delimiter_str = delimiter.to_string();
+
+    let params = crate::frontend::csv::Params {
+        has_headers: !no_headers,
+        delimiter: delimiter_str.as_bytes()[0],
+    };
+
+    Ok(csv_content.extract(&params)?)
+}
+
 async fn python_command(
     command: PythonCommand,
     app_state: &mut AppState<sqlite::Store>,
@@ -1304,24 +2354,44 @@ async fn python_command(
             csv,
             no_headers,
             delimiter,
+            timeout,
+            sandbox,
         } => {
-            if let Some(path) = csv {
+            if timeout.is_some() || sandbox {
+                let timeout = timeout
+                    .map(std::time::Duration::try_from_secs_f64)
+                    .transpose()
+                    .map_err(|_| anyhow!("Timeout must be a non-negative number of seconds"))?;
+                let sandbox = crate::frontend::python::Sandbox {
+                    timeout,
+                    restrict_builtins: sandbox,
+                };
+
                 let python_code = crate::frontend::python::PythonCode::from_file(&script)?;
-                let csv_content = crate::frontend::csv::Content::from_csv_file(&path)?;
+                let csv_extract = match csv {
+                    Some(path) => Some(read_csv_extract(&path, no_headers, delimiter)?),
+                    None => None,
+                };
 
-                if !delimiter.is_ascii() {
-                    return Err(anyhow!(
-                        "Csv delimiter must be encoded as a single byte  ASCII character"
-                    ));
+                let mut app_session = AppSession::new(app_state);
+                let sandboxed_run = python_code.run_sandboxed(
+                    &mut app_session,
+                    func.as_deref(),
+                    csv_extract,
+                    &sandbox,
+                );
+                print!("{}", sandboxed_run.stdout);
+                eprint!("{}", sandboxed_run.stderr);
+                if let Err(e) = sandboxed_run.result {
+                    app_session.cancel().await;
+                    return Err(e.into());
                 }
-                let delimiter_str = delimiter.to_string();
+                app_session.commit();
 
-                let params = crate::frontend::csv::Params {
-                    has_headers: !no_headers,
-                    delimiter: delimiter_str.as_bytes()[0],
-                };
-
-                let csv_extract = csv_content.extract(&params)?;
+                Ok(None)
+            } else if let Some(path) = csv {
+                let python_code = crate::frontend::python::PythonCode::from_file(&script)?;
+                let csv_extract = read_csv_extract(&path, no_headers, delimiter)?;
 
                 {
                     let mut app_session = AppSession::new(app_state);
@@ -1374,6 +2444,16 @@ async fn python_command(
                 Ok(None)
             }
         }
+        PythonCommand::Stubs { output } => match output {
+            Some(path) => {
+                std::fs::write(&path, crate::frontend::python::STUBS)?;
+                Ok(Some(format!(
+                    "Type stubs written to \"{}\".",
+                    path.display()
+                )))
+            }
+            None => Ok(Some(String::from(crate::frontend::python::STUBS))),
+        },
     }
 }
 
@@ -1389,23 +2469,16 @@ pub async fn execute_cli_command(
             name,
             force,
             verbose,
-            quick,
-            max_time,
-            #[cfg(feature = "highs")]
-            highs,
-        } => {
-            solve_command(
-                name,
-                force,
-                verbose,
-                quick,
-                max_time,
-                #[cfg(feature = "highs")]
-                highs,
-                app_state,
-            )
-            .await
-        }
+            solver,
+            advise,
+            base,
+        } => solve_command(name, force, verbose, solver, advise, base, app_state).await,
+        CliCommand::Benchmark {
+            dir,
+            output,
+            profiles,
+            add_current,
+        } => benchmark_command(dir, output, profiles, add_current, app_state).await,
         CliCommand::Python { command } => python_command(command, app_state).await,
     }
 }
@@ -5,14 +5,14 @@ mod history;
 pub mod update;
 
 use crate::backend;
-use history::{
-    AnnotatedColloscopesOperation, AnnotatedGroupListsOperation,
+pub use history::{
+    AggregatedOperations, AnnotatedColloscopesOperation, AnnotatedGroupListsOperation,
     AnnotatedGroupingIncompatsOperation, AnnotatedGroupingsOperation, AnnotatedIncompatsOperation,
     AnnotatedOperation, AnnotatedRegisterStudentOperation, AnnotatedSlotSelectionsOperation,
     AnnotatedStudentsOperation, AnnotatedSubjectGroupsOperation, AnnotatedSubjectsOperation,
     AnnotatedTeachersOperation, AnnotatedTimeSlotsOperation, AnnotatedWeekPatternsOperation,
-    ModificationHistory, ReversibleOperation,
 };
+use history::{ModificationHistory, ReversibleOperation};
 use update::private::ManagerInternal;
 
 pub use handles::{
@@ -22,8 +22,6 @@ pub use handles::{
 };
 pub use update::{Manager, UpdateError};
 
-use self::history::AggregatedOperations;
-
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Operation {
     GeneralData(backend::GeneralData),
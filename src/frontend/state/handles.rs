@@ -52,6 +52,20 @@ macro_rules! impl_handle {
     };
 }
 
+// Handles are normally only given by the state, tests build them directly
+#[cfg(test)]
+macro_rules! impl_from_index {
+    ($($HandleType:ident),*) => {
+        $(
+            impl $HandleType {
+                pub(crate) fn from_index(value: usize) -> Self {
+                    $HandleType(value)
+                }
+            }
+        )*
+    };
+}
+
 impl_handle!(WeekPatternHandle);
 impl_handle!(TeacherHandle);
 impl_handle!(StudentHandle);
@@ -65,6 +79,16 @@ impl_handle!(GroupingIncompatHandle);
 impl_handle!(ColloscopeHandle);
 impl_handle!(SlotSelectionHandle);
 
+#[cfg(test)]
+impl_from_index!(
+    WeekPatternHandle,
+    TeacherHandle,
+    StudentHandle,
+    SubjectGroupHandle,
+    IncompatHandle,
+    SubjectHandle
+);
+
 use crate::backend;
 use std::collections::BTreeMap;
 
@@ -3,8 +3,12 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::num::{NonZeroU32, NonZeroUsize};
 use thiserror::Error;
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Clone, Debug)]
 struct GenColloCacheTimeSlot<TeacherId: OrdId> {
+    time_slot_id: TimeSlotHandle,
     teacher_id: TeacherId,
     start: SlotStart,
     room: String,
@@ -23,13 +27,16 @@ struct GenColloCacheSubject<SubjectId: OrdId, TeacherId: OrdId> {
 struct GenColloscopeCache<StudentId: OrdId, SubjectId: OrdId, TeacherId: OrdId> {
     student_ids: Vec<StudentId>,
     subjects: Vec<GenColloCacheSubject<SubjectId, TeacherId>>,
+    // Indexed by slot grouping and incompatibility group numbers
+    grouping_ids: Vec<(GroupingHandle, Week)>,
+    incompat_group_ids: Vec<(IncompatHandle, Week)>,
     validated_data: crate::gen::colloscope::ValidatedData,
 }
 
 use super::state::{
-    update::Manager, GroupListHandle, GroupingHandle, GroupingIncompatHandle, IncompatHandle,
-    StudentHandle, SubjectGroupHandle, SubjectHandle, TeacherHandle, TimeSlotHandle,
-    WeekPatternHandle,
+    update::Manager, ColloscopeHandle, ColloscopesOperation, GroupListHandle, GroupingHandle,
+    GroupingIncompatHandle, IncompatHandle, StudentHandle, SubjectGroupHandle, SubjectHandle,
+    TeacherHandle, TimeSlotHandle, WeekPatternHandle,
 };
 
 #[derive(Debug)]
@@ -73,6 +80,41 @@ pub enum GenColloscopeError<StorageError: std::fmt::Debug + std::error::Error> {
     BadTimeSlotId(TimeSlotHandle),
     #[error("Inconsistent data: bad grouping id ({0:?})")]
     BadGroupingId(GroupingHandle),
+    #[error("Invalid soft constraint: {0}")]
+    BadSoftConstraint(SoftConstraintError),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum SoftConstraintError {
+    #[error("unknown kind of constraint \"{0}\"")]
+    UnknownKind(String),
+    #[error("constraints of kind \"{0}\" cannot be made soft")]
+    CannotBeSoft(String),
+    #[error("weight for \"{0}\" must be positive (got {1})")]
+    NonPositiveWeight(String, i32),
+}
+
+// Soft constraints are stored by name: every name must be a kind that can be made soft
+pub fn validate_soft_constraints(
+    soft_constraints: &BTreeMap<String, i32>,
+) -> Result<BTreeMap<crate::gen::colloscope::ConstraintKind, i32>, SoftConstraintError> {
+    soft_constraints
+        .iter()
+        .map(|(name, weight)| {
+            let kind = crate::gen::colloscope::ConstraintKind::from_name(name)
+                .ok_or(SoftConstraintError::UnknownKind(name.clone()))?;
+            if !kind.can_be_soft() {
+                return Err(SoftConstraintError::CannotBeSoft(name.clone()));
+            }
+            if *weight <= 0 {
+                return Err(SoftConstraintError::NonPositiveWeight(
+                    name.clone(),
+                    *weight,
+                ));
+            }
+            Ok((kind, *weight))
+        })
+        .collect()
 }
 
 #[derive(Debug, Error)]
@@ -81,6 +123,53 @@ pub enum TranslateColloscopeError {
     BadColloscope,
 }
 
+// ILP variables described with database handles rather than internal indices
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VariableRef {
+    GroupInSlot {
+        time_slot: TimeSlotHandle,
+        week: Week,
+        group: usize,
+    },
+    StudentInGroup {
+        subject: SubjectHandle,
+        student: StudentHandle,
+        group: usize,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum ResolveVariableError {
+    #[error("Time slot {0:?} is not used in the colloscope")]
+    BadTimeSlot(TimeSlotHandle),
+    #[error("Time slot {0:?} does not occur on week {1}")]
+    TimeSlotNotOnWeek(TimeSlotHandle, u32),
+    #[error("Subject {0:?} is not used in the colloscope")]
+    BadSubject(SubjectHandle),
+    #[error("Student {0:?} is not used in the colloscope")]
+    BadStudent(StudentHandle),
+    #[error("Group index {0} is out of range for this subject")]
+    BadGroupIndex(usize),
+}
+
+// Part of an infeasability explanation described with database handles
+// rather than internal indices. Groups are indices in the subject group list.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ConflictInvolvementRef {
+    pub constraint_count: usize,
+    pub subjects: BTreeSet<SubjectHandle>,
+    pub time_slots: BTreeSet<(TimeSlotHandle, Week)>,
+    pub groups: BTreeSet<(SubjectHandle, usize)>,
+    pub students: BTreeSet<StudentHandle>,
+    pub groupings: BTreeSet<(GroupingHandle, Week)>,
+    pub incompats: BTreeSet<(IncompatHandle, Week)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct InfeasabilityExplanationRef {
+    pub conflicts: BTreeMap<crate::gen::colloscope::ConstraintKind, ConflictInvolvementRef>,
+}
+
 impl<StorageError: std::fmt::Debug + std::error::Error> GenColloscopeError<StorageError> {
     fn from_validation(validation_error: crate::gen::colloscope::Error) -> Self {
         GenColloscopeError::ValidationError(validation_error)
@@ -183,22 +272,24 @@ impl GenColloscopeTranslator {
         }
     }
 
-    fn build_general_data(data: &GenColloscopeData) -> crate::gen::colloscope::GeneralData {
-        crate::gen::colloscope::GeneralData {
+    fn build_general_data(
+        data: &GenColloscopeData,
+    ) -> Result<crate::gen::colloscope::GeneralData, SoftConstraintError> {
+        Ok(crate::gen::colloscope::GeneralData {
             teacher_count: data.teachers.len(),
             week_count: data.general_data.week_count,
             interrogations_per_week: data.general_data.interrogations_per_week.clone(),
             max_interrogations_per_day: data.general_data.max_interrogations_per_day,
             periodicity_cuts: data.general_data.periodicity_cuts.clone(),
-            costs_adjustments: Self::build_costs_adjustments(data),
-        }
+            costs_adjustments: Self::build_costs_adjustments(data)?,
+        })
     }
 
     fn build_costs_adjustments(
         data: &GenColloscopeData,
-    ) -> crate::gen::colloscope::CostsAdjustments {
+    ) -> Result<crate::gen::colloscope::CostsAdjustments, SoftConstraintError> {
         let costs_adjustments = &data.general_data.costs_adjustments;
-        crate::gen::colloscope::CostsAdjustments {
+        Ok(crate::gen::colloscope::CostsAdjustments {
             max_interrogations_per_day_for_single_student: costs_adjustments
                 .max_interrogations_per_day_for_single_student,
             max_interrogations_per_day_for_all_students: costs_adjustments
@@ -209,7 +300,8 @@ impl GenColloscopeTranslator {
                 .interrogations_per_week_range_for_all_students,
             balancing: costs_adjustments.balancing,
             consecutive_slots: costs_adjustments.consecutive_slots,
-        }
+            soft_constraints: validate_soft_constraints(&costs_adjustments.soft_constraints)?,
+        })
     }
 }
 
@@ -218,6 +310,7 @@ struct IncompatibilitiesData {
     incompat_list: crate::gen::colloscope::IncompatibilityList,
     incompat_group_list: crate::gen::colloscope::IncompatibilityGroupList,
     id_map: BTreeMap<IncompatHandle, BTreeSet<usize>>,
+    group_ids: Vec<(IncompatHandle, Week)>,
 }
 
 impl GenColloscopeTranslator {
@@ -238,12 +331,15 @@ impl GenColloscopeTranslator {
         data: &GenColloscopeData,
         week_count: NonZeroU32,
     ) -> IncompatibilitiesData {
-        use crate::gen::colloscope::{Incompatibility, IncompatibilityGroup, SlotWithDuration};
+        use crate::gen::colloscope::{
+            Incompatibility, IncompatibilityGroup, OvernightSlot, SlotStart, SlotWithDuration,
+        };
 
         let mut output = IncompatibilitiesData {
             incompat_list: vec![],
             incompat_group_list: vec![],
             id_map: BTreeMap::new(),
+            group_ids: vec![],
         };
 
         for (&incompat_id, incompat) in &data.incompats {
@@ -265,19 +361,31 @@ impl GenColloscopeTranslator {
                     let slots = group
                         .slots
                         .iter()
-                        .map(|s| SlotWithDuration {
-                            start: crate::gen::colloscope::SlotStart {
-                                week: week.get(),
-                                weekday: s.start.day,
-                                start_time: s.start.time.clone(),
-                            },
-                            duration: s.duration,
+                        .flat_map(|s| {
+                            let slot = SlotWithDuration {
+                                start: SlotStart {
+                                    week: week.get(),
+                                    weekday: s.start.day,
+                                    start_time: s.start.time.clone(),
+                                },
+                                duration: s.duration,
+                            };
+                            // Without the setting, slots crossing midnight are left
+                            // as is and rejected during validation
+                            if data.general_data.overnight_incompat_slots {
+                                OvernightSlot::from(slot).split_at_midnight()
+                            } else {
+                                vec![slot]
+                            }
                         })
+                        // The end of an overnight slot can fall after the last week
+                        .filter(|slot| slot.start.week < week_count.get())
                         .collect();
                     let new_group = IncompatibilityGroup { slots };
 
                     new_incompat.groups.insert(output.incompat_group_list.len());
                     output.incompat_group_list.push(new_group);
+                    output.group_ids.push((incompat_id, week));
                 }
 
                 if !new_incompat.groups.is_empty() {
@@ -458,6 +566,7 @@ impl GenColloscopeTranslator {
                 subject.slots_information.slots.push(new_slot);
             }
             let rev_time_slot = GenColloCacheTimeSlot {
+                time_slot_id,
                 teacher_id: time_slot.teacher_id,
                 start: time_slot.start.clone(),
                 room: time_slot.room.clone(),
@@ -878,7 +987,8 @@ impl GenColloscopeTranslator {
     {
         let data = Self::extract_data(manager).await?;
 
-        let general = Self::build_general_data(&data);
+        let general =
+            Self::build_general_data(&data).map_err(GenColloscopeError::BadSoftConstraint)?;
         let incompatibility_data = Self::build_incompatibility_data(&data, general.week_count);
         let student_data = Self::build_student_data(&data, &incompatibility_data.id_map);
         let subject_data = Self::build_subject_data::<T>(&data, &student_data.id_map)?;
@@ -921,9 +1031,21 @@ impl GenColloscopeTranslator {
 
         let subjects = subject_data.subject_reverse_data;
 
+        let reverse_groupings_map: BTreeMap<_, _> = slot_grouping_data
+            .id_map
+            .iter()
+            .flat_map(|(&grouping_id, ids)| {
+                ids.iter()
+                    .map(move |(&week, &num)| (num, (grouping_id, week)))
+            })
+            .collect();
+        let grouping_ids = reverse_groupings_map.into_values().collect();
+
         Ok(GenColloscopeCache {
             student_ids,
             subjects,
+            grouping_ids,
+            incompat_group_ids: incompatibility_data.group_ids,
             validated_data,
         })
     }
@@ -934,6 +1056,299 @@ impl GenColloscopeTranslator {
         self.data_cache.validated_data.clone()
     }
 
+    pub fn resolve_variable(
+        &self,
+        variable: &VariableRef,
+    ) -> Result<crate::gen::colloscope::Variable, ResolveVariableError> {
+        use crate::gen::colloscope::Variable;
+
+        match variable {
+            VariableRef::GroupInSlot {
+                time_slot,
+                week,
+                group,
+            } => {
+                let (subject_index, subject, slot) = self
+                    .data_cache
+                    .subjects
+                    .iter()
+                    .enumerate()
+                    .find_map(|(i, subject)| {
+                        subject
+                            .slots
+                            .iter()
+                            .find(|slot| slot.time_slot_id == *time_slot)
+                            .map(|slot| (i, subject, slot))
+                    })
+                    .ok_or(ResolveVariableError::BadTimeSlot(*time_slot))?;
+                let slot_index =
+                    *slot
+                        .week_map
+                        .get(week)
+                        .ok_or(ResolveVariableError::TimeSlotNotOnWeek(
+                            *time_slot,
+                            week.get(),
+                        ))?;
+                if *group >= subject.groups.len() {
+                    return Err(ResolveVariableError::BadGroupIndex(*group));
+                }
+
+                Ok(Variable::GroupInSlot {
+                    subject: subject_index,
+                    slot: slot_index,
+                    group: *group,
+                })
+            }
+            VariableRef::StudentInGroup {
+                subject,
+                student,
+                group,
+            } => {
+                let (subject_index, subject_data) = self
+                    .data_cache
+                    .subjects
+                    .iter()
+                    .enumerate()
+                    .find(|(_i, s)| s.id == *subject)
+                    .ok_or(ResolveVariableError::BadSubject(*subject))?;
+                let student_index = self
+                    .data_cache
+                    .student_ids
+                    .iter()
+                    .position(|id| id == student)
+                    .ok_or(ResolveVariableError::BadStudent(*student))?;
+                if *group >= subject_data.groups.len() {
+                    return Err(ResolveVariableError::BadGroupIndex(*group));
+                }
+
+                Ok(Variable::StudentInGroup {
+                    subject: subject_index,
+                    student: student_index,
+                    group: *group,
+                })
+            }
+        }
+    }
+
+    // Weeks are 0-based. None means no restriction
+    pub fn solve_scope(
+        &self,
+        subjects: Option<&BTreeSet<SubjectHandle>>,
+        weeks: Option<std::ops::Range<u32>>,
+    ) -> Result<crate::gen::colloscope::SolveScope, ResolveVariableError> {
+        let subjects = subjects
+            .map(|subjects| {
+                subjects
+                    .iter()
+                    .map(|handle| {
+                        self.data_cache
+                            .subjects
+                            .iter()
+                            .position(|subject| subject.id == *handle)
+                            .ok_or(ResolveVariableError::BadSubject(*handle))
+                    })
+                    .collect::<Result<BTreeSet<_>, _>>()
+            })
+            .transpose()?;
+
+        Ok(crate::gen::colloscope::SolveScope { subjects, weeks })
+    }
+
+    pub fn translate_infeasability_explanation(
+        &self,
+        explanation: &crate::gen::colloscope::InfeasabilityExplanation,
+    ) -> InfeasabilityExplanationRef {
+        let subjects = &self.data_cache.subjects;
+
+        let conflicts = explanation
+            .conflicts
+            .iter()
+            .map(|(kind, involvement)| {
+                let time_slots = involvement
+                    .slots
+                    .iter()
+                    .filter_map(|slot_ref| {
+                        subjects
+                            .get(slot_ref.subject)?
+                            .slots
+                            .iter()
+                            .find_map(|slot| {
+                                slot.week_map
+                                    .iter()
+                                    .find(|(_week, num)| **num == slot_ref.slot)
+                                    .map(|(week, _num)| (slot.time_slot_id, *week))
+                            })
+                    })
+                    .collect();
+
+                let output = ConflictInvolvementRef {
+                    constraint_count: involvement.constraint_count,
+                    subjects: involvement
+                        .subjects
+                        .iter()
+                        .filter_map(|i| subjects.get(*i).map(|subject| subject.id))
+                        .collect(),
+                    time_slots,
+                    groups: involvement
+                        .groups
+                        .iter()
+                        .filter_map(|group_ref| {
+                            subjects
+                                .get(group_ref.subject)
+                                .map(|subject| (subject.id, group_ref.group))
+                        })
+                        .collect(),
+                    students: involvement
+                        .students
+                        .iter()
+                        .filter_map(|i| self.data_cache.student_ids.get(*i).copied())
+                        .collect(),
+                    groupings: involvement
+                        .slot_groupings
+                        .iter()
+                        .filter_map(|i| self.data_cache.grouping_ids.get(*i).copied())
+                        .collect(),
+                    incompats: involvement
+                        .incompatibility_groups
+                        .iter()
+                        .filter_map(|i| self.data_cache.incompat_group_ids.get(*i).copied())
+                        .collect(),
+                };
+                (*kind, output)
+            })
+            .collect();
+
+        InfeasabilityExplanationRef { conflicts }
+    }
+
+    // One line per kind of constraint, with the names of the objects involved
+    pub fn describe_infeasability(
+        &self,
+        explanation: &InfeasabilityExplanationRef,
+        subjects: &BTreeMap<
+            SubjectHandle,
+            Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+        >,
+        students: &BTreeMap<StudentHandle, Student>,
+        incompats: &BTreeMap<IncompatHandle, Incompat<WeekPatternHandle>>,
+        groupings: &BTreeMap<GroupingHandle, Grouping<TimeSlotHandle>>,
+        locale: crate::time::Locale,
+    ) -> String {
+        fn join<T, F: Fn(&T) -> String>(elems: &BTreeSet<T>, func: F) -> String {
+            elems.iter().map(func).collect::<Vec<_>>().join(", ")
+        }
+
+        let subject_name = |handle: &SubjectHandle| {
+            subjects
+                .get(handle)
+                .map(|subject| subject.name.clone())
+                .unwrap_or_else(|| String::from("?"))
+        };
+        let cache_subject = |handle: &SubjectHandle| {
+            self.data_cache
+                .subjects
+                .iter()
+                .find(|subject| subject.id == *handle)
+        };
+        let time_slot = |(handle, week): &(TimeSlotHandle, Week)| {
+            let found = self.data_cache.subjects.iter().find_map(|subject| {
+                subject
+                    .slots
+                    .iter()
+                    .find(|slot| slot.time_slot_id == *handle)
+                    .map(|slot| (subject.id, slot))
+            });
+            match found {
+                Some((subject_id, slot)) => format!(
+                    "{} {} {} ({} {})",
+                    subject_name(&subject_id),
+                    locale.weekday_name(slot.start.day),
+                    locale.format_time(&slot.start.time),
+                    locale.week_word(),
+                    week.get() + 1
+                ),
+                None => String::from("?"),
+            }
+        };
+        let group = |(handle, group): &(SubjectHandle, usize)| {
+            let group_name = cache_subject(handle)
+                .and_then(|subject| subject.groups.get(*group))
+                .map(String::as_str)
+                .unwrap_or("?");
+            format!("{} {}", subject_name(handle), group_name)
+        };
+        let student = |handle: &StudentHandle| {
+            students
+                .get(handle)
+                .map(|student| format!("{} {}", student.firstname, student.surname))
+                .unwrap_or_else(|| String::from("?"))
+        };
+        let grouping = |(handle, week): &(GroupingHandle, Week)| {
+            format!(
+                "{} ({} {})",
+                groupings
+                    .get(handle)
+                    .map(|grouping| grouping.name.as_str())
+                    .unwrap_or("?"),
+                locale.week_word(),
+                week.get() + 1
+            )
+        };
+        let incompat = |(handle, week): &(IncompatHandle, Week)| {
+            format!(
+                "{} ({} {})",
+                incompats
+                    .get(handle)
+                    .map(|incompat| incompat.name.as_str())
+                    .unwrap_or("?"),
+                locale.week_word(),
+                week.get() + 1
+            )
+        };
+
+        let mut lines = vec![];
+        for (kind, involvement) in &explanation.conflicts {
+            let mut details = vec![];
+            if !involvement.subjects.is_empty() {
+                details.push(format!(
+                    "subjects {}",
+                    join(&involvement.subjects, subject_name)
+                ));
+            }
+            if !involvement.time_slots.is_empty() {
+                details.push(format!(
+                    "slots {}",
+                    join(&involvement.time_slots, time_slot)
+                ));
+            }
+            if !involvement.groups.is_empty() {
+                details.push(format!("groups {}", join(&involvement.groups, group)));
+            }
+            if !involvement.students.is_empty() {
+                details.push(format!("students {}", join(&involvement.students, student)));
+            }
+            if !involvement.groupings.is_empty() {
+                details.push(format!(
+                    "groupings {}",
+                    join(&involvement.groupings, grouping)
+                ));
+            }
+            if !involvement.incompats.is_empty() {
+                details.push(format!(
+                    "incompatibilities {}",
+                    join(&involvement.incompats, incompat)
+                ));
+            }
+
+            if details.is_empty() {
+                lines.push(format!("- {}", kind));
+            } else {
+                lines.push(format!("- {} ({})", kind, details.join("; ")));
+            }
+        }
+        lines.join("\n")
+    }
+
     pub fn translate_colloscope(
         &self,
         colloscope: &crate::gen::colloscope::Colloscope,
@@ -1017,4 +1432,208 @@ impl GenColloscopeTranslator {
 
         Ok(output)
     }
+
+    pub fn read_colloscope(
+        &self,
+        colloscope: &Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    ) -> Result<crate::gen::colloscope::Colloscope, TranslateColloscopeError> {
+        let subjects = self
+            .data_cache
+            .subjects
+            .iter()
+            .map(|subject_data| {
+                let subject = colloscope
+                    .subjects
+                    .get(&subject_data.id)
+                    .ok_or(TranslateColloscopeError::BadColloscope)?;
+                if subject.time_slots.len() != subject_data.slots.len() {
+                    return Err(TranslateColloscopeError::BadColloscope);
+                }
+
+                let mut groups = vec![BTreeSet::new(); subject_data.groups.len()];
+                for (student_id, &group) in &subject.group_list.students_mapping {
+                    let student = self
+                        .data_cache
+                        .student_ids
+                        .iter()
+                        .position(|id| id == student_id)
+                        .ok_or(TranslateColloscopeError::BadColloscope)?;
+                    groups
+                        .get_mut(group)
+                        .ok_or(TranslateColloscopeError::BadColloscope)?
+                        .insert(student);
+                }
+
+                let slot_count = subject_data
+                    .slots
+                    .iter()
+                    .map(|time_slot_data| time_slot_data.week_map.len())
+                    .sum();
+                let mut slots = vec![BTreeSet::new(); slot_count];
+                for (time_slot, time_slot_data) in
+                    subject.time_slots.iter().zip(subject_data.slots.iter())
+                {
+                    for (week, &slot_num) in &time_slot_data.week_map {
+                        let assigned_groups = time_slot
+                            .group_assignments
+                            .get(week)
+                            .cloned()
+                            .unwrap_or_default();
+                        *slots
+                            .get_mut(slot_num)
+                            .ok_or(TranslateColloscopeError::BadColloscope)? = assigned_groups;
+                    }
+                }
+
+                Ok(crate::gen::colloscope::ColloscopeSubject { groups, slots })
+            })
+            .collect::<Result<_, TranslateColloscopeError>>()?;
+
+        Ok(crate::gen::colloscope::Colloscope { subjects })
+    }
+
+    // Builds the operation applying only the selected changes to a stored colloscope
+    pub fn apply_changes_operation(
+        &self,
+        handle: ColloscopeHandle,
+        colloscope: &Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+        changes: &[crate::gen::colloscope::ColloscopeChange],
+    ) -> Result<ColloscopesOperation, TranslateColloscopeError> {
+        let current = self.read_colloscope(colloscope)?;
+        let updated = current
+            .apply_changes(changes)
+            .ok_or(TranslateColloscopeError::BadColloscope)?;
+        let new_colloscope = self.translate_colloscope(&updated, &colloscope.name)?;
+
+        Ok(ColloscopesOperation::Update(handle, new_colloscope))
+    }
+
+    // Changes turning the current colloscope into the proposed one
+    pub fn diff_colloscopes(
+        &self,
+        current: &Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+        proposed: &Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
+    ) -> Result<Vec<crate::gen::colloscope::ColloscopeChange>, TranslateColloscopeError> {
+        let current = self.read_colloscope(current)?;
+        let proposed = self.read_colloscope(proposed)?;
+
+        self.data_cache
+            .validated_data
+            .diff_colloscopes(&current, &proposed)
+            .ok_or(TranslateColloscopeError::BadColloscope)
+    }
+
+    pub fn describe_colloscope_change(
+        &self,
+        change: &crate::gen::colloscope::ColloscopeChange,
+        subjects: &BTreeMap<
+            SubjectHandle,
+            Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+        >,
+        students: &BTreeMap<StudentHandle, Student>,
+        locale: crate::time::Locale,
+    ) -> String {
+        use crate::gen::colloscope::ColloscopeChange;
+
+        let subject_name = |subject: usize| {
+            self.data_cache
+                .subjects
+                .get(subject)
+                .and_then(|subject| subjects.get(&subject.id))
+                .map(|subject| subject.name.as_str())
+                .unwrap_or("?")
+        };
+        let group_name = |subject: usize, group: usize| {
+            self.data_cache
+                .subjects
+                .get(subject)
+                .and_then(|subject| subject.groups.get(group))
+                .map(String::as_str)
+                .unwrap_or("?")
+        };
+        let slot = |subject: usize, slot: usize| {
+            self.data_cache
+                .subjects
+                .get(subject)
+                .and_then(|subject| {
+                    subject.slots.iter().find_map(|time_slot| {
+                        time_slot
+                            .week_map
+                            .iter()
+                            .find(|(_week, num)| **num == slot)
+                            .map(|(week, _num)| (time_slot, week))
+                    })
+                })
+                .map(|(time_slot, week)| {
+                    format!(
+                        "{} {}, {} {}",
+                        locale.week_word(),
+                        week.get() + 1,
+                        locale.weekday_name(time_slot.start.day),
+                        locale.format_time(&time_slot.start.time)
+                    )
+                })
+                .unwrap_or_else(|| String::from("?"))
+        };
+
+        match change {
+            ColloscopeChange::InterrogationAdded {
+                subject,
+                slot: slot_num,
+                group,
+            } => format!(
+                "{}, group {}: new interrogation on {}",
+                subject_name(*subject),
+                group_name(*subject, *group),
+                slot(*subject, *slot_num)
+            ),
+            ColloscopeChange::InterrogationRemoved {
+                subject,
+                slot: slot_num,
+                group,
+            } => format!(
+                "{}, group {}: no more interrogation on {}",
+                subject_name(*subject),
+                group_name(*subject, *group),
+                slot(*subject, *slot_num)
+            ),
+            ColloscopeChange::InterrogationMoved {
+                subject,
+                group,
+                from_slot,
+                to_slot,
+            } => format!(
+                "{}, group {}: interrogation moved from {} to {}",
+                subject_name(*subject),
+                group_name(*subject, *group),
+                slot(*subject, *from_slot),
+                slot(*subject, *to_slot)
+            ),
+            ColloscopeChange::StudentMoved {
+                subject,
+                student,
+                from_group,
+                to_group,
+            } => {
+                let student_name = self
+                    .data_cache
+                    .student_ids
+                    .get(*student)
+                    .and_then(|handle| students.get(handle))
+                    .map(|student| format!("{} {}", student.firstname, student.surname))
+                    .unwrap_or_else(|| String::from("?"));
+                let group = |group: &Option<usize>| match group {
+                    Some(group) => format!("group {}", group_name(*subject, *group)),
+                    None => String::from("no group"),
+                };
+                format!(
+                    "{}: {} moved from {} to {}",
+                    subject_name(*subject),
+                    student_name,
+                    group(from_group),
+                    group(to_group)
+                )
+            }
+        }
+    }
 }
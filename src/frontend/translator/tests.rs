@@ -0,0 +1,109 @@
+use super::*;
+
+use crate::gen::colloscope::{SlotStart as GenSlotStart, SlotWithDuration};
+use crate::time::{Time, Weekday};
+
+// Incompatibility from sunday 23h to monday 1h on both weeks
+fn data(overnight_incompat_slots: bool) -> GenColloscopeData {
+    let week_pattern = WeekPatternHandle::from_index(0);
+
+    GenColloscopeData {
+        general_data: GeneralData {
+            interrogations_per_week: None,
+            max_interrogations_per_day: None,
+            week_count: NonZeroU32::new(2).unwrap(),
+            periodicity_cuts: BTreeSet::new(),
+            costs_adjustments: CostsAdjustments::default(),
+            overnight_incompat_slots,
+        },
+        week_patterns: BTreeMap::from([(
+            week_pattern,
+            WeekPattern {
+                name: String::from("All"),
+                weeks: BTreeSet::from([Week::new(0), Week::new(1)]),
+            },
+        )]),
+        teachers: BTreeMap::new(),
+        incompats: BTreeMap::from([(
+            IncompatHandle::from_index(0),
+            Incompat {
+                name: String::from("Study"),
+                max_count: 0,
+                groups: BTreeSet::from([IncompatGroup {
+                    slots: BTreeSet::from([IncompatSlot {
+                        week_pattern_id: week_pattern,
+                        start: SlotStart {
+                            day: Weekday::Sunday,
+                            time: Time::from_hm(23, 0).unwrap(),
+                        },
+                        duration: NonZeroU32::new(120).unwrap(),
+                    }]),
+                }]),
+            },
+        )]),
+        students: BTreeMap::new(),
+        incompat_for_student_data: BTreeSet::new(),
+        subjects: BTreeMap::new(),
+        subject_for_student_data: BTreeSet::new(),
+        time_slots: BTreeMap::new(),
+        group_lists: BTreeMap::new(),
+        groupings: BTreeMap::new(),
+        grouping_incompats: BTreeMap::new(),
+    }
+}
+
+fn slot(week: u32, weekday: Weekday, hour: u32, duration: u32) -> SlotWithDuration {
+    SlotWithDuration {
+        start: GenSlotStart {
+            week,
+            weekday,
+            start_time: Time::from_hm(hour, 0).unwrap(),
+        },
+        duration: NonZeroU32::new(duration).unwrap(),
+    }
+}
+
+#[test]
+fn overnight_incompat_slots_are_split_at_midnight() {
+    let data = data(true);
+    let incompatibility_data =
+        GenColloscopeTranslator::build_incompatibility_data(&data, data.general_data.week_count);
+
+    let groups: Vec<_> = incompatibility_data
+        .incompat_group_list
+        .iter()
+        .map(|group| group.slots.clone())
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            BTreeSet::from([
+                slot(0, Weekday::Sunday, 23, 60),
+                slot(1, Weekday::Monday, 0, 60)
+            ]),
+            // The monday after the last week is not part of the colloscope
+            BTreeSet::from([slot(1, Weekday::Sunday, 23, 60)]),
+        ]
+    );
+}
+
+#[test]
+fn overnight_incompat_slots_are_kept_whole_by_default() {
+    let data = data(false);
+    let incompatibility_data =
+        GenColloscopeTranslator::build_incompatibility_data(&data, data.general_data.week_count);
+
+    let groups: Vec<_> = incompatibility_data
+        .incompat_group_list
+        .iter()
+        .map(|group| group.slots.clone())
+        .collect();
+    // Validation rejects them later on
+    assert_eq!(
+        groups,
+        vec![
+            BTreeSet::from([slot(0, Weekday::Sunday, 23, 120)]),
+            BTreeSet::from([slot(1, Weekday::Sunday, 23, 120)]),
+        ]
+    );
+}
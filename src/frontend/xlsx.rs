@@ -1,13 +1,17 @@
+use super::print::{Grid, Orientation, PageSetup, PaperSize};
 use super::state::{
     GroupListHandle, IncompatHandle, StudentHandle, SubjectGroupHandle, SubjectHandle,
     TeacherHandle,
 };
 use crate::backend;
+use crate::time::Locale;
 
 use rust_xlsxwriter::*;
 use thiserror::Error;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::num::NonZeroU32;
+use std::ops::Range;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -27,6 +31,35 @@ pub enum Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+#[derive(Debug, Clone)]
+pub struct ExportOptions<'a> {
+    // One colloscope worksheet is written for each period
+    pub periodicity_cuts: &'a BTreeSet<NonZeroU32>,
+    pub page_setup: &'a PageSetup,
+    pub locale: Locale,
+}
+
+// Database data and settings used by all worksheets
+struct ExportData<'a> {
+    teachers: &'a BTreeMap<TeacherHandle, backend::Teacher>,
+    subjects: &'a BTreeMap<
+        SubjectHandle,
+        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
+    >,
+    subject_groups: &'a BTreeMap<SubjectGroupHandle, backend::SubjectGroup>,
+    students: &'a BTreeMap<StudentHandle, backend::Student>,
+    subject_colors: BTreeMap<SubjectHandle, Color>,
+    page_setup: &'a PageSetup,
+    locale: Locale,
+}
+
+// Weeks shown on a colloscope worksheet
+struct MainWorksheetContext<'a> {
+    data: &'a ExportData<'a>,
+    first_week: u32,
+    week_count: u16,
+}
+
 fn sort_with<T, I, K, F>(data: I, mut func: F) -> Result<BTreeMap<K, Vec<T>>>
 where
     I: IntoIterator<Item = T>,
@@ -84,10 +117,54 @@ const COL_FIRST_WEEK: u16 = 7;
 const BORDER_BIG: FormatBorder = FormatBorder::Medium;
 const BORDER_SMALL: FormatBorder = FormatBorder::Thin;
 
-fn build_main_worksheet_first_line(
+// Light colors so that the text stays readable when printed
+const SUBJECT_COLORS: [Color; 8] = [
+    Color::RGB(0xDCE6F1),
+    Color::RGB(0xF2DCDB),
+    Color::RGB(0xEBF1DE),
+    Color::RGB(0xE4DFEC),
+    Color::RGB(0xDAEEF3),
+    Color::RGB(0xFDE9D9),
+    Color::RGB(0xFFF2CC),
+    Color::RGB(0xEDEDED),
+];
+
+// Estimated sizes used to place page breaks (actual widths come from autofit)
+const PRINT_ROW_HEIGHT_MM: f64 = 5.3;
+const PRINT_HEADER_COLUMN_WIDTH_MM: f64 = 22.;
+const PRINT_WEEK_WIDTH_MM: f64 = 9.;
+
+fn apply_page_setup(
     worksheet: &mut Worksheet,
+    page_setup: &PageSetup,
+    header_rows: Range<u32>,
+    header_columns: Range<u16>,
+) -> Result<()> {
+    worksheet.set_paper_size(match page_setup.paper_size {
+        PaperSize::Letter => 1,
+        PaperSize::A3 => 8,
+        PaperSize::A4 => 9,
+    });
+    match page_setup.orientation {
+        Orientation::Portrait => worksheet.set_portrait(),
+        Orientation::Landscape => worksheet.set_landscape(),
+    };
+    let margin = page_setup.margin_mm / 25.4;
+    worksheet.set_margins(margin, margin, margin, margin, 0.3, 0.3);
+    let scale = (page_setup.font_scale * 100.).round().clamp(10., 400.) as u16;
+    worksheet.set_print_scale(scale);
+
+    if page_setup.repeat_headers {
+        worksheet.set_repeat_rows(header_rows.start, header_rows.end - 1)?;
+        worksheet.set_repeat_columns(header_columns.start, header_columns.end - 1)?;
+    }
+
+    Ok(())
+}
+
+fn colloscope_week_count(
     colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
-) -> Result<u16> {
+) -> Result<u32> {
     let week_count = colloscope
         .subjects
         .iter()
@@ -113,7 +190,30 @@ fn build_main_worksheet_first_line(
         return Err(Error::NoWeeks);
     }
 
-    let week_count: u16 = week_count.try_into().map_err(|_| Error::TooManyWeeks)?;
+    Ok(week_count)
+}
+
+// Periods are delimited by the periodicity cuts (as when generating the colloscope)
+fn build_periods(week_count: u32, periodicity_cuts: &BTreeSet<NonZeroU32>) -> Vec<Range<u32>> {
+    let mut periods = Vec::new();
+
+    let mut start = 0;
+    for cut in periodicity_cuts {
+        if cut.get() >= week_count {
+            break;
+        }
+        periods.push(start..cut.get());
+        start = cut.get();
+    }
+    periods.push(start..week_count);
+
+    periods
+}
+
+fn build_main_worksheet_first_line(worksheet: &mut Worksheet, weeks: &Range<u32>) -> Result<u16> {
+    let week_count: u16 = (weeks.end - weeks.start)
+        .try_into()
+        .map_err(|_| Error::TooManyWeeks)?;
 
     let format = Format::new()
         .set_align(FormatAlign::VerticalCenter)
@@ -171,25 +271,26 @@ fn build_main_worksheet_first_line(
         .set_border_right(BORDER_BIG);
 
     for i in 0..week_count {
+        let week_number = weeks.start + u32::from(i) + 1;
         if i == 0 {
             worksheet.write_with_format(
                 ROW_COLLOSCOPE_TITLES,
                 i + COL_FIRST_WEEK,
-                i + 1,
+                week_number,
                 &format_first,
             )?;
         } else if i == week_count - 1 {
             worksheet.write_with_format(
                 ROW_COLLOSCOPE_TITLES,
                 i + COL_FIRST_WEEK,
-                i + 1,
+                week_number,
                 &format_last,
             )?;
         } else {
             worksheet.write_with_format(
                 ROW_COLLOSCOPE_TITLES,
                 i + COL_FIRST_WEEK,
-                i + 1,
+                week_number,
                 &format,
             )?;
         }
@@ -253,29 +354,40 @@ impl VerticalPosition {
 
 fn build_main_worksheet_timeslot(
     worksheet: &mut Worksheet,
+    context: &MainWorksheetContext,
     start_line: u32,
     time_slot: backend::ColloscopeTimeSlot<TeacherHandle>,
     group_list: &backend::ColloscopeGroupList<StudentHandle>,
-    week_count: u16,
+    color: Color,
     position: VerticalPosition,
 ) -> Result<u32> {
+    let locale = context.data.locale;
+    let week_count = context.week_count;
     let format = Format::new()
         .set_align(FormatAlign::VerticalCenter)
         .set_align(FormatAlign::Center)
+        .set_background_color(color)
         .set_border_left(BORDER_BIG)
         .set_border_right(BORDER_BIG);
     let format = position.apply(format);
 
     let slot = format!(
-        "{} {:02}h{:02}",
-        time_slot.start.day,
-        time_slot.start.time.get_hour(),
-        time_slot.start.time.get_min()
+        "{} {}",
+        locale.weekday_name(time_slot.start.day),
+        locale.format_time(&time_slot.start.time)
     );
     worksheet.write_with_format(start_line, COL_SLOT, &slot, &format)?;
     worksheet.write_with_format(start_line, COL_ROOM, &time_slot.room, &format)?;
 
     for (week, groups) in time_slot.group_assignments {
+        // Weeks of other periods are on other worksheets
+        let Some(week_offset) = week.get().checked_sub(context.first_week) else {
+            continue;
+        };
+        if week_offset >= u32::from(week_count) {
+            continue;
+        }
+
         let group_names = groups
             .into_iter()
             .map(|group_num| {
@@ -288,27 +400,28 @@ fn build_main_worksheet_timeslot(
             .collect::<Result<Vec<_>>>()?;
 
         let column = COL_FIRST_WEEK
-            + u16::try_from(week.get()).expect(
-                "Week numbers should have already been checked when constructing first line",
-            );
+            + u16::try_from(week_offset).expect("Week offset should be less than week_count");
         worksheet.write(start_line, column, group_names.join(","))?;
     }
 
     let format = Format::new()
         .set_align(FormatAlign::VerticalCenter)
         .set_align(FormatAlign::Center)
+        .set_background_color(color)
         .set_border_left(BORDER_SMALL)
         .set_border_right(BORDER_SMALL);
     let format = position.apply(format);
     let format_first = Format::new()
         .set_align(FormatAlign::VerticalCenter)
         .set_align(FormatAlign::Center)
+        .set_background_color(color)
         .set_border_left(BORDER_BIG)
         .set_border_right(BORDER_SMALL);
     let format_first = position.apply(format_first);
     let format_last = Format::new()
         .set_align(FormatAlign::VerticalCenter)
         .set_align(FormatAlign::Center)
+        .set_background_color(color)
         .set_border_left(BORDER_SMALL)
         .set_border_right(BORDER_BIG);
     let format_last = position.apply(format_last);
@@ -328,12 +441,12 @@ fn build_main_worksheet_timeslot(
 
 fn build_main_worksheet_teacher(
     worksheet: &mut Worksheet,
+    context: &MainWorksheetContext,
     start_line: u32,
     time_slots: Vec<backend::ColloscopeTimeSlot<TeacherHandle>>,
     group_list: &backend::ColloscopeGroupList<StudentHandle>,
     teacher_handle: TeacherHandle,
-    teachers: &BTreeMap<TeacherHandle, backend::Teacher>,
-    week_count: u16,
+    color: Color,
     position: VerticalPosition,
 ) -> Result<u32> {
     let mut current_line = start_line;
@@ -342,19 +455,25 @@ fn build_main_worksheet_teacher(
         let ts_position = position.propagate(i, count);
         current_line = build_main_worksheet_timeslot(
             worksheet,
+            context,
             current_line,
             time_slot,
             group_list,
-            week_count,
+            color,
             ts_position,
         )?;
     }
 
-    let teacher = teachers.get(&teacher_handle).ok_or(Error::BadColloscope)?;
+    let teacher = context
+        .data
+        .teachers
+        .get(&teacher_handle)
+        .ok_or(Error::BadColloscope)?;
     let name = format!("{} {}", teacher.firstname, teacher.surname,);
     let format = Format::new()
         .set_align(FormatAlign::VerticalCenter)
         .set_align(FormatAlign::Center)
+        .set_background_color(color)
         .set_border_left(BORDER_BIG)
         .set_border_right(BORDER_BIG);
     let format = position.apply(format);
@@ -382,16 +501,16 @@ fn build_main_worksheet_teacher(
 
 fn build_main_worksheet_subject(
     worksheet: &mut Worksheet,
+    context: &MainWorksheetContext,
     start_line: u32,
     subject: backend::ColloscopeSubject<TeacherHandle, StudentHandle>,
     subject_handle: SubjectHandle,
-    teachers: &BTreeMap<TeacherHandle, backend::Teacher>,
-    subjects: &BTreeMap<
-        SubjectHandle,
-        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
-    >,
-    week_count: u16,
 ) -> Result<u32> {
+    let color = *context
+        .data
+        .subject_colors
+        .get(&subject_handle)
+        .ok_or(Error::BadColloscope)?;
     let sorted_time_slots = sort_with(subject.time_slots, |time_slot| Ok(time_slot.teacher_id))?;
 
     let mut current_line = start_line;
@@ -406,12 +525,12 @@ fn build_main_worksheet_subject(
 
         current_line = build_main_worksheet_teacher(
             worksheet,
+            context,
             current_line,
             time_slots,
             &subject.group_list,
             teacher_handle,
-            teachers,
-            week_count,
+            color,
             position,
         )?;
     }
@@ -419,9 +538,12 @@ fn build_main_worksheet_subject(
     let format = Format::new()
         .set_align(FormatAlign::VerticalCenter)
         .set_align(FormatAlign::Center)
+        .set_background_color(color)
         .set_border(BORDER_BIG);
 
-    let subject_name = &subjects
+    let subject_name = &context
+        .data
+        .subjects
         .get(&subject_handle)
         .ok_or(Error::BadColloscope)?
         .name;
@@ -449,34 +571,28 @@ fn build_main_worksheet_subject(
 
 fn build_main_worksheet_subject_group(
     worksheet: &mut Worksheet,
+    context: &MainWorksheetContext,
     start_line: u32,
     selected_subjects: Vec<(
         SubjectHandle,
         backend::ColloscopeSubject<TeacherHandle, StudentHandle>,
     )>,
     subject_group_handle: SubjectGroupHandle,
-    teachers: &BTreeMap<TeacherHandle, backend::Teacher>,
-    subjects: &BTreeMap<
-        SubjectHandle,
-        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
-    >,
-    subject_groups: &BTreeMap<SubjectGroupHandle, backend::SubjectGroup>,
-    week_count: u16,
 ) -> Result<u32> {
     let mut current_line = start_line;
     for (subject_handle, subject) in selected_subjects {
         current_line = build_main_worksheet_subject(
             worksheet,
+            context,
             current_line,
             subject,
             subject_handle,
-            teachers,
-            subjects,
-            week_count,
         )?;
     }
 
-    let name = &subject_groups
+    let name = &context
+        .data
+        .subject_groups
         .get(&subject_group_handle)
         .ok_or(Error::BadColloscope)?
         .name;
@@ -547,21 +663,29 @@ fn build_empty_line(worksheet: &mut Worksheet, start_line: u32, week_count: u16)
 
 fn build_main_worksheet(
     worksheet: &mut Worksheet,
+    data: &ExportData,
     colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
-    teachers: &BTreeMap<TeacherHandle, backend::Teacher>,
-    subjects: &BTreeMap<
-        SubjectHandle,
-        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
-    >,
-    subject_groups: &BTreeMap<SubjectGroupHandle, backend::SubjectGroup>,
+    weeks: &Range<u32>,
+    name: &str,
 ) -> Result<()> {
-    worksheet.set_name("Colloscope")?;
-    worksheet.set_landscape();
+    worksheet.set_name(name)?;
+    worksheet.set_freeze_panes(ROW_FIRST_TIME_SLOT, COL_FIRST_WEEK)?;
+    apply_page_setup(
+        worksheet,
+        data.page_setup,
+        ROW_COLLOSCOPE_CATEGORIES..ROW_FIRST_TIME_SLOT,
+        COL_SUBJECT_GROUP..COL_FIRST_WEEK,
+    )?;
 
-    let week_count = build_main_worksheet_first_line(worksheet, colloscope)?;
+    let week_count = build_main_worksheet_first_line(worksheet, weeks)?;
+    let context = MainWorksheetContext {
+        data,
+        first_week: weeks.start,
+        week_count,
+    };
 
     let sorted_subjects = sort_with(colloscope.subjects.clone(), |(subject_id, _subject)| {
-        subjects
+        data.subjects
             .get(subject_id)
             .map(|s| s.subject_group_id)
             .ok_or(Error::BadColloscope)
@@ -575,18 +699,34 @@ fn build_main_worksheet(
 
         start_line = build_main_worksheet_subject_group(
             worksheet,
+            &context,
             start_line,
             selected_subjects,
             subject_group_handle,
-            teachers,
-            subjects,
-            subject_groups,
-            week_count,
         )?;
     }
 
     worksheet.autofit();
 
+    let grid = Grid {
+        header_width: f64::from(COL_FIRST_WEEK - COL_SUBJECT_GROUP) * PRINT_HEADER_COLUMN_WIDTH_MM,
+        header_height: f64::from(ROW_FIRST_TIME_SLOT - ROW_COLLOSCOPE_CATEGORIES)
+            * PRINT_ROW_HEIGHT_MM,
+        column_widths: vec![PRINT_WEEK_WIDTH_MM; usize::from(week_count)],
+        row_heights: vec![PRINT_ROW_HEIGHT_MM; (start_line - ROW_FIRST_TIME_SLOT) as usize],
+    };
+    let pages = super::print::paginate(&grid, data.page_setup);
+    let column_breaks: Vec<_> = super::print::column_breaks(&pages)
+        .into_iter()
+        .map(|i| u32::from(COL_FIRST_WEEK) + i as u32)
+        .collect();
+    let row_breaks: Vec<_> = super::print::row_breaks(&pages)
+        .into_iter()
+        .map(|i| ROW_FIRST_TIME_SLOT + i as u32)
+        .collect();
+    worksheet.set_vertical_page_breaks(&column_breaks)?;
+    worksheet.set_page_breaks(&row_breaks)?;
+
     Ok(())
 }
 
@@ -700,24 +840,27 @@ fn build_groups_worksheet_first_columns(
 
 fn build_groups_worksheet_subject(
     worksheet: &mut Worksheet,
+    data: &ExportData,
     start_col: u16,
     subject: backend::ColloscopeSubject<TeacherHandle, StudentHandle>,
     subject_handle: SubjectHandle,
     student_line_map: &BTreeMap<StudentHandle, u32>,
-    subjects: &BTreeMap<
-        SubjectHandle,
-        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
-    >,
     position: HorizontalPosition,
 ) -> Result<u16> {
+    let color = *data
+        .subject_colors
+        .get(&subject_handle)
+        .ok_or(Error::BadColloscope)?;
     let format = Format::new()
         .set_align(FormatAlign::VerticalCenter)
         .set_align(FormatAlign::Center)
+        .set_background_color(color)
         .set_border_top(BORDER_BIG)
         .set_border_bottom(BORDER_BIG);
     let format = position.apply(format);
 
-    let subject_name = &subjects
+    let subject_name = &data
+        .subjects
         .get(&subject_handle)
         .ok_or(Error::BadColloscope)?
         .name;
@@ -755,7 +898,8 @@ fn build_groups_worksheet_subject(
     for (_student_handle, &line) in student_line_map.iter() {
         let format = Format::new()
             .set_align(FormatAlign::VerticalCenter)
-            .set_align(FormatAlign::Center);
+            .set_align(FormatAlign::Center)
+            .set_background_color(color);
         let format = match line {
             x if x == first && first == last => format
                 .set_border_top(BORDER_BIG)
@@ -779,6 +923,7 @@ fn build_groups_worksheet_subject(
 
 fn build_groups_worksheet_subject_group(
     worksheet: &mut Worksheet,
+    data: &ExportData,
     start_col: u16,
     selected_subjects: Vec<(
         SubjectHandle,
@@ -786,11 +931,6 @@ fn build_groups_worksheet_subject_group(
     )>,
     subject_group_handle: SubjectGroupHandle,
     student_line_map: &BTreeMap<StudentHandle, u32>,
-    subjects: &BTreeMap<
-        SubjectHandle,
-        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
-    >,
-    subject_groups: &BTreeMap<SubjectGroupHandle, backend::SubjectGroup>,
 ) -> Result<u16> {
     let mut current_col = start_col;
     let count = selected_subjects.len();
@@ -804,16 +944,17 @@ fn build_groups_worksheet_subject_group(
 
         current_col = build_groups_worksheet_subject(
             worksheet,
+            data,
             current_col,
             subject,
             subject_handle,
             student_line_map,
-            subjects,
             position,
         )?;
     }
 
-    let name = &subject_groups
+    let name = &data
+        .subject_groups
         .get(&subject_group_handle)
         .ok_or(Error::BadColloscope)?
         .name;
@@ -836,20 +977,22 @@ fn build_groups_worksheet_subject_group(
 
 fn build_groups_worksheet(
     worksheet: &mut Worksheet,
+    data: &ExportData,
     colloscope: &backend::Colloscope<TeacherHandle, SubjectHandle, StudentHandle>,
-    subjects: &BTreeMap<
-        SubjectHandle,
-        backend::Subject<SubjectGroupHandle, IncompatHandle, GroupListHandle>,
-    >,
-    subject_groups: &BTreeMap<SubjectGroupHandle, backend::SubjectGroup>,
-    students: &BTreeMap<StudentHandle, backend::Student>,
 ) -> Result<()> {
     worksheet.set_name("Groupes")?;
+    worksheet.set_freeze_panes(ROW_FIRST_STUDENT, COL_FIRST_LIST)?;
+    apply_page_setup(
+        worksheet,
+        data.page_setup,
+        ROW_SUBJECT_GROUP_NAME..ROW_FIRST_STUDENT,
+        COL_SURNAME..COL_FIRST_LIST,
+    )?;
 
-    let student_line_map = build_groups_worksheet_first_columns(worksheet, students)?;
+    let student_line_map = build_groups_worksheet_first_columns(worksheet, data.students)?;
 
     let sorted_subjects = sort_with(colloscope.subjects.clone(), |(subject_id, _subject)| {
-        subjects
+        data.subjects
             .get(subject_id)
             .map(|s| s.subject_group_id)
             .ok_or(Error::BadColloscope)
@@ -859,12 +1002,11 @@ fn build_groups_worksheet(
     for (subject_group_handle, selected_subjects) in sorted_subjects {
         start_col = build_groups_worksheet_subject_group(
             worksheet,
+            data,
             start_col,
             selected_subjects,
             subject_group_handle,
             &student_line_map,
-            subjects,
-            subject_groups,
         )?;
     }
 
@@ -882,24 +1024,37 @@ pub fn export_colloscope_to_xlsx(
     >,
     subject_groups: &BTreeMap<SubjectGroupHandle, backend::SubjectGroup>,
     students: &BTreeMap<StudentHandle, backend::Student>,
+    options: &ExportOptions,
     file: &std::path::Path,
 ) -> Result<()> {
     let mut workbook = Workbook::new();
 
-    build_main_worksheet(
-        workbook.add_worksheet(),
-        colloscope,
+    let data = ExportData {
         teachers,
         subjects,
         subject_groups,
-    )?;
-    build_groups_worksheet(
-        workbook.add_worksheet(),
-        colloscope,
-        subjects,
-        subject_groups,
         students,
-    )?;
+        // Colors follow the order of all subjects so that they don't depend on the colloscope
+        subject_colors: subjects
+            .keys()
+            .enumerate()
+            .map(|(i, handle)| (*handle, SUBJECT_COLORS[i % SUBJECT_COLORS.len()]))
+            .collect(),
+        page_setup: options.page_setup,
+        locale: options.locale,
+    };
+
+    let week_count = colloscope_week_count(colloscope)?;
+    let periods = build_periods(week_count, options.periodicity_cuts);
+    for weeks in &periods {
+        let name = if periods.len() == 1 {
+            String::from("Colloscope")
+        } else {
+            format!("Semaines {}-{}", weeks.start + 1, weeks.end)
+        };
+        build_main_worksheet(workbook.add_worksheet(), &data, colloscope, weeks, &name)?;
+    }
+    build_groups_worksheet(workbook.add_worksheet(), &data, colloscope)?;
 
     workbook.save(file)?;
 
@@ -8,6 +8,7 @@ use std::ops::RangeInclusive;
 
 use crate::time;
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -93,13 +94,44 @@ pub struct SlotStart {
     pub start_time: time::Time,
 }
 
+impl SlotStart {
+    // One slot start per week of the recurrence
+    pub fn occurrences(
+        weekday: time::Weekday,
+        start_time: &time::Time,
+        recurrence: &time::Recurrence,
+        week_count: u32,
+    ) -> Vec<SlotStart> {
+        recurrence
+            .weeks(week_count)
+            .into_iter()
+            .map(|week| SlotStart {
+                week,
+                weekday,
+                start_time: start_time.clone(),
+            })
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SlotWithDuration {
     pub start: SlotStart,
     pub duration: NonZeroU32,
 }
 
+// Days are identified by (week, weekday)
+pub type WeekSlotSet = time::SlotSet<(u32, time::Weekday)>;
+
 impl SlotWithDuration {
+    pub fn to_slot_set(&self) -> WeekSlotSet {
+        WeekSlotSet::new().with_slot(
+            (self.start.week, self.start.weekday),
+            &self.start.start_time,
+            self.duration.get(),
+        )
+    }
+
     pub fn end_time(&self) -> time::Time {
         self.start.start_time.add(self.duration.get() - 1).unwrap()
     }
@@ -120,6 +152,78 @@ impl SlotWithDuration {
     }
 }
 
+// Slot that is allowed to cross midnight (for instance 22h30-0h30).
+// The solver only deals with SlotWithDuration: overnight slots must be
+// explicitly converted with split_at_midnight before being used there.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OvernightSlot {
+    pub start: SlotStart,
+    pub duration: NonZeroU32,
+}
+
+impl From<SlotWithDuration> for OvernightSlot {
+    fn from(value: SlotWithDuration) -> Self {
+        OvernightSlot {
+            start: value.start,
+            duration: value.duration,
+        }
+    }
+}
+
+impl OvernightSlot {
+    pub fn crosses_midnight(&self) -> bool {
+        !self.start.start_time.fit_in_day(self.duration.get())
+    }
+
+    // Same-day parts of the slot. The part after sunday midnight
+    // belongs to the monday of the next week.
+    pub fn split_at_midnight(&self) -> Vec<SlotWithDuration> {
+        let mut output = Vec::new();
+
+        let mut start = self.start.clone();
+        let mut remaining = self.duration.get();
+        loop {
+            let duration = remaining.min(start.start_time.minutes_until_end_of_day());
+            output.push(SlotWithDuration {
+                start: start.clone(),
+                duration: NonZeroU32::new(duration).expect("Slot parts should not be empty"),
+            });
+
+            remaining -= duration;
+            if remaining == 0 {
+                return output;
+            }
+
+            start = SlotStart {
+                week: if start.weekday == time::Weekday::Sunday {
+                    start.week + 1
+                } else {
+                    start.week
+                },
+                weekday: start.weekday.next(),
+                start_time: time::Time::default(),
+            };
+        }
+    }
+
+    pub fn to_slot_set(&self) -> WeekSlotSet {
+        self.split_at_midnight()
+            .iter()
+            .fold(WeekSlotSet::new(), |set, part| {
+                set.union(&part.to_slot_set())
+            })
+    }
+
+    // Two slots overlap if they share at least one minute, including after midnight
+    pub fn overlap_with(&self, other: &OvernightSlot) -> bool {
+        self.to_slot_set().overlaps_set(&other.to_slot_set())
+    }
+
+    pub fn overlap_with_slot(&self, other: &SlotWithDuration) -> bool {
+        self.to_slot_set().overlaps_set(&other.to_slot_set())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SlotWithTeacher {
     pub teacher: usize,
@@ -495,6 +599,9 @@ pub struct CostsAdjustments {
     pub interrogations_per_week_range_for_all_students: i32,
     pub balancing: i32,
     pub consecutive_slots: i32,
+    // Kinds of constraints turned into penalties with the given weight
+    // (only used for kinds that can be soft)
+    pub soft_constraints: BTreeMap<ConstraintKind, i32>,
 }
 
 impl Default for CostsAdjustments {
@@ -506,6 +613,7 @@ impl Default for CostsAdjustments {
             interrogations_per_week_range_for_all_students: 1,
             balancing: 1,
             consecutive_slots: 1,
+            soft_constraints: BTreeMap::new(),
         }
     }
 }
@@ -814,7 +922,7 @@ impl ValidatedData {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Variable {
     GroupInSlot {
         subject: usize,
@@ -917,6 +1025,137 @@ impl std::fmt::Display for Variable {
 }
 
 impl ValidatedData {
+    fn colloscope_fits(&self, colloscope: &Colloscope) -> bool {
+        colloscope.subjects.len() == self.subjects.len()
+            && colloscope.subjects.iter().zip(self.subjects.iter()).all(
+                |(collo_subject, subject)| {
+                    collo_subject.slots.len() == subject.slots_information.slots.len()
+                        && collo_subject.groups.len() == subject.groups.prefilled_groups.len()
+                },
+            )
+    }
+
+    fn diff_subject_groups(
+        i: usize,
+        current: &ColloscopeSubject,
+        proposed: &ColloscopeSubject,
+    ) -> Vec<ColloscopeChange> {
+        let find_group = |collo_subject: &ColloscopeSubject, student: usize| {
+            collo_subject
+                .groups
+                .iter()
+                .position(|group| group.contains(&student))
+        };
+
+        let students: BTreeSet<_> = current
+            .groups
+            .iter()
+            .chain(proposed.groups.iter())
+            .flatten()
+            .copied()
+            .collect();
+
+        students
+            .into_iter()
+            .filter_map(|student| {
+                let from_group = find_group(current, student);
+                let to_group = find_group(proposed, student);
+                (from_group != to_group).then_some(ColloscopeChange::StudentMoved {
+                    subject: i,
+                    student,
+                    from_group,
+                    to_group,
+                })
+            })
+            .collect()
+    }
+
+    fn diff_subject_slots(
+        &self,
+        i: usize,
+        current: &ColloscopeSubject,
+        proposed: &ColloscopeSubject,
+    ) -> Vec<ColloscopeChange> {
+        let interrogations = |collo_subject: &ColloscopeSubject| -> BTreeSet<(usize, usize)> {
+            collo_subject
+                .slots
+                .iter()
+                .enumerate()
+                .flat_map(|(slot, groups)| groups.iter().map(move |group| (slot, *group)))
+                .collect()
+        };
+        let week = |slot: usize| self.subjects[i].slots_information.slots[slot].start.week;
+
+        let current_interrogations = interrogations(current);
+        let proposed_interrogations = interrogations(proposed);
+
+        let mut added: BTreeSet<_> = proposed_interrogations
+            .difference(&current_interrogations)
+            .copied()
+            .collect();
+
+        let mut output = vec![];
+        for &(slot, group) in current_interrogations.difference(&proposed_interrogations) {
+            let moved_to = added
+                .iter()
+                .find(|(new_slot, new_group)| *new_group == group && week(*new_slot) == week(slot))
+                .copied();
+
+            match moved_to {
+                Some((to_slot, _)) => {
+                    added.remove(&(to_slot, group));
+                    output.push(ColloscopeChange::InterrogationMoved {
+                        subject: i,
+                        group,
+                        from_slot: slot,
+                        to_slot,
+                    });
+                }
+                None => output.push(ColloscopeChange::InterrogationRemoved {
+                    subject: i,
+                    slot,
+                    group,
+                }),
+            }
+        }
+        output.extend(added.into_iter().map(|(slot, group)| {
+            ColloscopeChange::InterrogationAdded {
+                subject: i,
+                slot,
+                group,
+            }
+        }));
+
+        output
+    }
+
+    pub fn diff_colloscopes(
+        &self,
+        current: &Colloscope,
+        proposed: &Colloscope,
+    ) -> Option<Vec<ColloscopeChange>> {
+        if !self.colloscope_fits(current) || !self.colloscope_fits(proposed) {
+            return None;
+        }
+
+        let mut output = vec![];
+        for (i, (current_subject, proposed_subject)) in current
+            .subjects
+            .iter()
+            .zip(proposed.subjects.iter())
+            .enumerate()
+        {
+            output.extend(Self::diff_subject_groups(
+                i,
+                current_subject,
+                proposed_subject,
+            ));
+            output.extend(self.diff_subject_slots(i, current_subject, proposed_subject));
+        }
+
+        Some(output)
+    }
+
     pub fn ilp_translator<'a>(&'a self) -> IlpTranslator<'a> {
         IlpTranslator {
             data: self,
@@ -932,7 +1171,8 @@ pub struct IlpTranslator<'a> {
 }
 
 use crate::ilp::linexpr::{Constraint, Expr};
-use crate::ilp::{FeasableConfig, Problem, ProblemBuilder};
+use crate::ilp::solvers::{FeasabilitySolver, SolverOptions};
+use crate::ilp::{DefaultRepr, ElasticVariable, FeasableConfig, Problem, ProblemBuilder};
 
 enum StudentStatus {
     Assigned(usize),
@@ -1907,14 +2147,14 @@ impl<'a> IlpTranslator<'a> {
         slot: &SlotRef,
         k: usize,
     ) -> Constraint<Variable> {
-        let lhs = Expr::var(Variable::GroupInSlot {
-            subject: slot.subject,
-            slot: slot.slot,
-            group: k,
-        });
-        let rhs = Expr::var(Variable::UseGrouping(i));
-
-        lhs.leq(&rhs)
+        crate::ilp::linexpr::implies(
+            Variable::GroupInSlot {
+                subject: slot.subject,
+                slot: slot.slot,
+                group: k,
+            },
+            Variable::UseGrouping(i),
+        )
     }
 
     fn build_grouping_constraints(&self) -> BTreeSet<Constraint<Variable>> {
@@ -2504,16 +2744,17 @@ impl<'a> IlpTranslator<'a> {
         slot: usize,
         group: usize,
     ) -> Constraint<Variable> {
-        let expr = Expr::var(Variable::GroupInSlot {
-            subject,
-            slot,
-            group,
-        });
-
-        expr.leq(&Expr::var(Variable::IncompatGroupForStudent {
-            incompat_group,
-            student,
-        }))
+        crate::ilp::linexpr::implies(
+            Variable::GroupInSlot {
+                subject,
+                slot,
+                group,
+            },
+            Variable::IncompatGroupForStudent {
+                incompat_group,
+                student,
+            },
+        )
     }
 
     fn build_incompat_group_for_student_constraint_for_student_and_incompat_group_and_slot_dynamic_version(
@@ -2524,17 +2765,18 @@ impl<'a> IlpTranslator<'a> {
         slot: usize,
         group: usize,
     ) -> Constraint<Variable> {
-        let expr = Expr::var(Variable::DynamicGroupAssignment {
-            subject,
-            slot,
-            group,
-            student,
-        });
-
-        expr.leq(&Expr::var(Variable::IncompatGroupForStudent {
-            incompat_group,
-            student,
-        }))
+        crate::ilp::linexpr::implies(
+            Variable::DynamicGroupAssignment {
+                subject,
+                slot,
+                group,
+                student,
+            },
+            Variable::IncompatGroupForStudent {
+                incompat_group,
+                student,
+            },
+        )
     }
 
     fn need_building_for_slot_and_incompat_group(
@@ -2543,16 +2785,18 @@ impl<'a> IlpTranslator<'a> {
         duration: NonZeroU32,
         incompat_group: &IncompatibilityGroup,
     ) -> bool {
-        for slot in &incompat_group.slots {
-            if slot.overlap_with(&SlotWithDuration {
-                start: slot_start.clone(),
-                duration,
-            }) {
-                return true;
-            }
-        }
+        let incompat_slots = incompat_group
+            .slots
+            .iter()
+            .fold(WeekSlotSet::new(), |set, slot| {
+                set.union(&slot.to_slot_set())
+            });
 
-        false
+        incompat_slots.overlaps(
+            &(slot_start.week, slot_start.weekday),
+            &slot_start.start_time,
+            duration.get(),
+        )
     }
 
     fn build_incompat_group_for_student_constraints_for_subject_slot_student_and_incompat_group(
@@ -3053,6 +3297,7 @@ impl<'a> IlpTranslator<'a> {
         for (student_num, _student) in self.data.students.iter().enumerate() {
             let mut obj_term = crate::ilp::ObjectiveTerm {
                 coef: cost,
+                desc: None,
                 exprs: BTreeSet::new(),
             };
 
@@ -3133,6 +3378,7 @@ impl<'a> IlpTranslator<'a> {
         for (student_num, _student) in self.data.students.iter().enumerate() {
             let mut obj_term = crate::ilp::ObjectiveTerm {
                 coef: cost,
+                desc: None,
                 exprs: BTreeSet::new(),
             };
 
@@ -3194,35 +3440,6 @@ impl<'a> IlpTranslator<'a> {
         output
     }
 
-    fn constraints_to_objective_terms(
-        &self,
-        constraints: BTreeSet<Constraint<Variable>>,
-        cost: f64,
-    ) -> Vec<crate::ilp::ObjectiveTerm<Variable>> {
-        let mut output = Vec::new();
-
-        for constraint in constraints.into_iter() {
-            let exprs = match constraint.get_sign() {
-                crate::ilp::linexpr::Sign::Equals => {
-                    let expr = constraint.get_lhs().clone();
-
-                    BTreeSet::from([expr.clone(), -expr])
-                }
-                crate::ilp::linexpr::Sign::LessThan => {
-                    let expr = constraint.get_lhs().clone();
-
-                    BTreeSet::from([expr, Expr::constant(0)])
-                }
-            };
-
-            let obj_term = crate::ilp::ObjectiveTerm { coef: cost, exprs };
-
-            output.push(obj_term);
-        }
-
-        output
-    }
-
     fn build_objective_terms(&self) -> Vec<crate::ilp::ObjectiveTerm<Variable>> {
         let student_per_day_cost = f64::from(
             self.data
@@ -3248,9 +3465,6 @@ impl<'a> IlpTranslator<'a> {
                 .costs_adjustments
                 .interrogations_per_week_range_for_all_students,
         );
-        let balancing_cost = f64::from(self.data.general.costs_adjustments.balancing);
-        let consecutive_cost = f64::from(self.data.general.costs_adjustments.consecutive_slots);
-
         let student_count_f64 = self.data.students.len() as f64;
 
         let mut output = Vec::new();
@@ -3263,6 +3477,7 @@ impl<'a> IlpTranslator<'a> {
         // Global number of interrogations per day
         output.push(crate::ilp::ObjectiveTerm {
             coef: global_per_day_cost * student_count_f64,
+            desc: None,
             exprs: interrogations_per_day_objective_terms
                 .into_iter()
                 .flat_map(|obj_term| obj_term.exprs)
@@ -3279,6 +3494,7 @@ impl<'a> IlpTranslator<'a> {
                 .iter()
                 .map(|obj_term| crate::ilp::ObjectiveTerm {
                     coef: obj_term.coef,
+                    desc: None,
                     exprs: obj_term.exprs.iter().map(|e| -e).collect(),
                 })
                 .collect();
@@ -3287,6 +3503,7 @@ impl<'a> IlpTranslator<'a> {
         // Global stable number of interrogations per week
         output.push(crate::ilp::ObjectiveTerm {
             coef: global_per_week_cost * student_count_f64,
+            desc: None,
             exprs: upper_bound_interrogations_per_week_objective_terms
                 .into_iter()
                 .flat_map(|obj_term| obj_term.exprs)
@@ -3295,49 +3512,89 @@ impl<'a> IlpTranslator<'a> {
 
         output.push(crate::ilp::ObjectiveTerm {
             coef: global_per_week_cost * student_count_f64,
+            desc: None,
             exprs: lower_bound_interrogations_per_week_objective_terms
                 .into_iter()
                 .flat_map(|obj_term| obj_term.exprs)
                 .collect(),
         });
 
-        // Soft constraints
-        let balancing_optimizer_constraints = self.build_balancing_optimizer();
-        output.extend(
-            self.constraints_to_objective_terms(balancing_optimizer_constraints, balancing_cost),
-        );
-
-        let not_consecutive_constraints = self.build_not_consecutive_for_students_optimizer();
-        output.extend(
-            self.constraints_to_objective_terms(not_consecutive_constraints, consecutive_cost),
-        );
-
         output
     }
 
-    fn build_hard_constraints(&self) -> BTreeSet<Constraint<Variable>> {
-        let mut output = BTreeSet::new();
-
-        output.extend(self.build_at_most_max_groups_per_slot_constraints());
-        output.extend(self.build_at_most_one_interrogation_per_time_unit_constraints());
-        output.extend(self.build_one_interrogation_per_period_constraints());
-        output
-            .extend(self.build_at_most_one_interrogation_per_period_for_empty_groups_contraints());
-        output.extend(self.build_students_per_group_count_constraints());
-        output.extend(self.build_student_in_single_group_constraints());
-        output.extend(self.build_dynamic_groups_student_in_group_constraints());
-        output.extend(self.build_dynamic_groups_group_in_slot_constraints());
-        output.extend(self.build_interrogations_per_week_constraints());
-        output.extend(self.build_max_interrogations_per_day_constraints());
-        output.extend(self.build_grouping_constraints());
-        output.extend(self.build_grouping_incompats_constraints());
-        output.extend(self.build_incompat_group_for_student_constraints());
-        output.extend(self.build_student_incompat_max_count_constraints());
-        output.extend(self.build_group_on_slot_selection_constraints());
-        output.extend(self.build_balancing_constraints());
-        output.extend(self.build_not_consecutive_for_students_constraints());
-
-        output
+    fn build_hard_constraints_by_kind(
+        &self,
+    ) -> Vec<(ConstraintKind, BTreeSet<Constraint<Variable>>)> {
+        vec![
+            (
+                ConstraintKind::MaxGroupsPerSlot,
+                self.build_at_most_max_groups_per_slot_constraints(),
+            ),
+            (
+                ConstraintKind::OneInterrogationPerTimeUnit,
+                self.build_at_most_one_interrogation_per_time_unit_constraints(),
+            ),
+            (
+                ConstraintKind::OneInterrogationPerPeriod,
+                self.build_one_interrogation_per_period_constraints(),
+            ),
+            (
+                ConstraintKind::EmptyGroupsPerPeriod,
+                self.build_at_most_one_interrogation_per_period_for_empty_groups_contraints(),
+            ),
+            (
+                ConstraintKind::StudentsPerGroup,
+                self.build_students_per_group_count_constraints(),
+            ),
+            (
+                ConstraintKind::StudentInSingleGroup,
+                self.build_student_in_single_group_constraints(),
+            ),
+            (
+                ConstraintKind::DynamicGroups,
+                self.build_dynamic_groups_student_in_group_constraints(),
+            ),
+            (
+                ConstraintKind::DynamicGroups,
+                self.build_dynamic_groups_group_in_slot_constraints(),
+            ),
+            (
+                ConstraintKind::InterrogationsPerWeek,
+                self.build_interrogations_per_week_constraints(),
+            ),
+            (
+                ConstraintKind::MaxInterrogationsPerDay,
+                self.build_max_interrogations_per_day_constraints(),
+            ),
+            (
+                ConstraintKind::SlotGrouping,
+                self.build_grouping_constraints(),
+            ),
+            (
+                ConstraintKind::SlotGroupingIncompat,
+                self.build_grouping_incompats_constraints(),
+            ),
+            (
+                ConstraintKind::StudentIncompatibility,
+                self.build_incompat_group_for_student_constraints(),
+            ),
+            (
+                ConstraintKind::StudentIncompatibility,
+                self.build_student_incompat_max_count_constraints(),
+            ),
+            (
+                ConstraintKind::SlotSelection,
+                self.build_group_on_slot_selection_constraints(),
+            ),
+            (
+                ConstraintKind::Balancing,
+                self.build_balancing_constraints(),
+            ),
+            (
+                ConstraintKind::NonConsecutiveInterrogations,
+                self.build_not_consecutive_for_students_constraints(),
+            ),
+        ]
     }
 
     fn problem_builder_internal(&self) -> ProblemBuilder<Variable> {
@@ -3347,12 +3604,39 @@ impl<'a> IlpTranslator<'a> {
         let hard_problem_builder = ProblemBuilder::new()
             .add_bool_variables(self.build_variables())
             .expect("Should not have duplicates")
-            .add_constraints(self.build_hard_constraints())
-            .expect("Variables should be defined")
             .add_objective_terms(self.build_objective_terms())
             .expect("Variables should be defined")
             .set_objective_contribs(self.build_objective_contribs())
             .expect("Variables should be defined");
+        // Soft constraints are named so that their penalties can be reported
+        let costs_adjustments = &self.data.general.costs_adjustments;
+        let hard_problem_builder = hard_problem_builder
+            .add_soft_constraints(
+                self.build_balancing_optimizer(),
+                f64::from(costs_adjustments.balancing),
+                "balancing_optimizer",
+            )
+            .expect("Variables should be defined")
+            .add_soft_constraints(
+                self.build_not_consecutive_for_students_optimizer(),
+                f64::from(costs_adjustments.consecutive_slots),
+                "not_consecutive_optimizer",
+            )
+            .expect("Variables should be defined");
+        // Hard constraints are grouped by kind so that infeasabilities can be explained
+        // Soft kinds are only penalized in the objective
+        let soft_constraints = &costs_adjustments.soft_constraints;
+        let hard_problem_builder = self.build_hard_constraints_by_kind().into_iter().fold(
+            hard_problem_builder,
+            |builder, (kind, constraints)| match soft_constraints.get(&kind) {
+                Some(weight) if kind.can_be_soft() => builder
+                    .add_soft_constraints(constraints, f64::from(*weight), kind.name())
+                    .expect("Variables should be defined"),
+                _ => builder
+                    .add_grouped_constraints(constraints, kind.name())
+                    .expect("Variables should be defined"),
+            },
+        );
         // Comment out for now, we are going to need this code for the linear optimization
         /*      .eval_fn(crate::debuggable!(move |x| {
             let bool_vars = x.get_bool_vars();
@@ -3468,6 +3752,463 @@ impl<'a> IlpTranslator<'a> {
 
         Some(Colloscope { subjects })
     }
+
+    fn is_variable_in_scope(&self, var: &Variable, scope: &SolveScope) -> bool {
+        let Some(subject) = var.subject() else {
+            // Auxiliary variables are determined by the others
+            return true;
+        };
+        if let Some(subjects) = &scope.subjects {
+            if !subjects.contains(&subject) {
+                return false;
+            }
+        }
+        let Some(weeks) = &scope.weeks else {
+            return true;
+        };
+
+        match var {
+            Variable::GroupInSlot { slot, .. } | Variable::DynamicGroupAssignment { slot, .. } => {
+                let week = self.data.subjects[subject].slots_information.slots[*slot]
+                    .start
+                    .week;
+                weeks.contains(&week)
+            }
+            // Groups span the whole year so they are fixed when solving a few weeks only
+            _ => false,
+        }
+    }
+
+    fn current_value_for_variable(&self, var: &Variable, colloscope: &Colloscope) -> Option<bool> {
+        match var {
+            Variable::GroupInSlot {
+                subject,
+                slot,
+                group,
+            } => Some(
+                colloscope
+                    .subjects
+                    .get(*subject)?
+                    .slots
+                    .get(*slot)?
+                    .contains(group),
+            ),
+            Variable::StudentInGroup {
+                subject,
+                student,
+                group,
+            } => Some(
+                colloscope
+                    .subjects
+                    .get(*subject)?
+                    .groups
+                    .get(*group)?
+                    .contains(student),
+            ),
+            // Other variables are determined by the two above
+            _ => None,
+        }
+    }
+
+    // A student is interrogated in a slot through an extendable group
+    fn current_dynamic_group_assignment(
+        &self,
+        var: &Variable,
+        colloscope: &Colloscope,
+    ) -> Option<bool> {
+        let Variable::DynamicGroupAssignment {
+            subject,
+            slot,
+            group,
+            student,
+        } = var
+        else {
+            return None;
+        };
+        let collo_subject = colloscope.subjects.get(*subject)?;
+        Some(
+            collo_subject.slots.get(*slot)?.contains(group)
+                && collo_subject.groups.get(*group)?.contains(student),
+        )
+    }
+
+    pub fn problem_builder_for_scope(
+        &self,
+        current: &Colloscope,
+        scope: &SolveScope,
+    ) -> Option<ProblemBuilder<Variable>> {
+        if !self.data.colloscope_fits(current) {
+            return None;
+        }
+
+        let pb_builder = self.problem_builder();
+
+        let mut fixed_constraints = BTreeSet::new();
+        for var in pb_builder.get_variables() {
+            if self.is_variable_in_scope(var, scope) {
+                continue;
+            }
+            let Some(value) = self.current_value_for_variable(var, current) else {
+                continue;
+            };
+            let value = if value { 1 } else { 0 };
+            fixed_constraints.insert(Expr::var(var.clone()).eq(&Expr::constant(value)));
+        }
+
+        Some(
+            pb_builder
+                .add_grouped_constraints(fixed_constraints, "fixed_outside_scope")
+                .expect("Variables should be defined"),
+        )
+    }
+
+    pub fn problem_for_scope(
+        &self,
+        current: &Colloscope,
+        scope: &SolveScope,
+    ) -> Option<Problem<Variable>> {
+        Some(self.problem_builder_for_scope(current, scope)?.build())
+    }
+
+    fn greedy_groups(subject: &Subject) -> Vec<BTreeSet<usize>> {
+        let mut groups: Vec<_> = subject
+            .groups
+            .prefilled_groups
+            .iter()
+            .map(|group| group.students.clone())
+            .collect();
+        let max_size = subject.students_per_group.end().get();
+
+        for student in subject.groups.not_assigned.iter().copied() {
+            // Fill the smallest extendable group first
+            let best_group = subject
+                .groups
+                .prefilled_groups
+                .iter()
+                .enumerate()
+                .filter(|(k, group)| {
+                    !Self::is_group_fixed(group, subject) && groups[*k].len() < max_size
+                })
+                .min_by_key(|(k, _group)| groups[*k].len())
+                .map(|(k, _group)| k);
+
+            if let Some(k) = best_group {
+                groups[k].insert(student);
+            }
+        }
+
+        groups
+    }
+
+    // Fast greedy construction, usually nearly feasable.
+    // Groups are filled first, then each group is given, period after period,
+    // the least used slot that does not clash with its students' interrogations.
+    pub fn greedy_colloscope(&self) -> Colloscope {
+        let mut student_slots = vec![WeekSlotSet::new(); self.data.students.len()];
+
+        let mut subjects = Vec::with_capacity(self.data.subjects.len());
+        for subject in &self.data.subjects {
+            let groups = Self::greedy_groups(subject);
+            let slots_data = &subject.slots_information.slots;
+            let slot_selections = &subject
+                .slots_information
+                .balancing_requirements
+                .slot_selections;
+            let mut slots = vec![BTreeSet::new(); slots_data.len()];
+            // Each group must stay within a single slot selection
+            let mut group_selections: Vec<Option<usize>> = vec![None; groups.len()];
+
+            for period in self.generate_period_list(subject, subject.period_is_strict) {
+                for (k, students) in groups.iter().enumerate() {
+                    if students.is_empty() {
+                        continue;
+                    }
+
+                    let already_interrogated =
+                        slots.iter().zip(slots_data.iter()).any(|(assigned, slot)| {
+                            assigned.contains(&k) && period.contains(&slot.start.week)
+                        });
+                    if already_interrogated {
+                        continue;
+                    }
+
+                    let best_slot = slots_data
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, slot)| {
+                            if !period.contains(&slot.start.week)
+                                || slots[*j].len() >= subject.max_groups_per_slot.get()
+                            {
+                                return false;
+                            }
+                            if let Some(selection) = group_selections[k] {
+                                if !slot_selections[selection].contains_slot(*j) {
+                                    return false;
+                                }
+                            }
+                            students.iter().all(|student| {
+                                !student_slots[*student].overlaps(
+                                    &(slot.start.week, slot.start.weekday),
+                                    &slot.start.start_time,
+                                    subject.duration.get(),
+                                )
+                            })
+                        })
+                        .min_by_key(|(j, slot)| (slots[*j].len(), slot.cost))
+                        .map(|(j, _slot)| j);
+
+                    if let Some(j) = best_slot {
+                        slots[j].insert(k);
+                        if group_selections[k].is_none() {
+                            group_selections[k] = slot_selections
+                                .iter()
+                                .position(|selection| selection.contains_slot(j));
+                        }
+                        let start = &slots_data[j].start;
+                        for student in students {
+                            student_slots[*student].insert(
+                                (start.week, start.weekday),
+                                &start.start_time,
+                                subject.duration.get(),
+                            );
+                        }
+                    }
+                }
+            }
+
+            subjects.push(ColloscopeSubject { groups, slots });
+        }
+
+        Colloscope { subjects }
+    }
+
+    // Builds a config (typically used as a warm start) from a colloscope.
+    // Auxiliary variables that can't be deduced directly are left to false.
+    pub fn config_hint_from_colloscope<'p>(
+        &self,
+        problem: &'p Problem<Variable>,
+        colloscope: &Colloscope,
+    ) -> crate::ilp::Config<'p, Variable> {
+        let bool_vars = problem.get_variables().iter().filter_map(|var| {
+            let value = match var {
+                Variable::DynamicGroupAssignment { .. } => {
+                    self.current_dynamic_group_assignment(var, colloscope)?
+                }
+                Variable::GroupOnSlotSelection {
+                    subject,
+                    slot_selection,
+                    group,
+                } => {
+                    let collo_subject = colloscope.subjects.get(*subject)?;
+                    let used_slots: BTreeSet<_> = collo_subject
+                        .slots
+                        .iter()
+                        .enumerate()
+                        .filter(|(_j, groups)| groups.contains(group))
+                        .map(|(j, _groups)| j)
+                        .collect();
+                    let slot_selections = &self
+                        .data
+                        .subjects
+                        .get(*subject)?
+                        .slots_information
+                        .balancing_requirements
+                        .slot_selections;
+                    // The first selection containing every slot used by the group
+                    let selected = slot_selections
+                        .iter()
+                        .position(|selection| selection.extract_slots().is_superset(&used_slots));
+                    selected == Some(*slot_selection)
+                }
+                _ => self.current_value_for_variable(var, colloscope)?,
+            };
+            Some((var.clone(), value))
+        });
+
+        problem
+            .config_from(bool_vars)
+            .expect("Variables should be valid")
+    }
+
+    // Penalizes every interrogation that differs from the current colloscope
+    // (a group added to or removed from a slot costs "weight", and so does
+    // a student moved in or out of a slot through an extendable group)
+    pub fn add_stability_objective(
+        &self,
+        pb_builder: ProblemBuilder<Variable>,
+        current: &Colloscope,
+        weight: f64,
+    ) -> Option<ProblemBuilder<Variable>> {
+        if !self.data.colloscope_fits(current) {
+            return None;
+        }
+
+        let mut contribs = BTreeMap::new();
+        for var in pb_builder.get_variables() {
+            let value = match var {
+                Variable::GroupInSlot { .. } => self.current_value_for_variable(var, current),
+                Variable::DynamicGroupAssignment { .. } => {
+                    self.current_dynamic_group_assignment(var, current)
+                }
+                _ => None,
+            };
+            let Some(value) = value else {
+                continue;
+            };
+
+            // Up to a constant, |x - value| is x if value is false and -x if value is true
+            let delta = if value { -weight } else { weight };
+            let base = pb_builder
+                .get_objective_contribs()
+                .get(var)
+                .copied()
+                .unwrap_or(0.);
+            contribs.insert(var.clone(), base + delta);
+        }
+
+        Some(
+            pb_builder
+                .set_objective_contribs(contribs)
+                .expect("Variables should be defined"),
+        )
+    }
+
+    fn involvement_for_variable(involvement: &mut ConflictInvolvement, var: &Variable) {
+        match var {
+            Variable::GroupInSlot {
+                subject,
+                slot,
+                group,
+            } => {
+                involvement.subjects.insert(*subject);
+                involvement.slots.insert(SlotRef {
+                    subject: *subject,
+                    slot: *slot,
+                });
+                involvement.groups.insert(GroupRef {
+                    subject: *subject,
+                    group: *group,
+                });
+            }
+            Variable::GroupOnSlotSelection {
+                subject,
+                slot_selection: _,
+                group,
+            } => {
+                involvement.subjects.insert(*subject);
+                involvement.groups.insert(GroupRef {
+                    subject: *subject,
+                    group: *group,
+                });
+            }
+            Variable::DynamicGroupAssignment {
+                subject,
+                slot,
+                group,
+                student,
+            } => {
+                involvement.subjects.insert(*subject);
+                involvement.slots.insert(SlotRef {
+                    subject: *subject,
+                    slot: *slot,
+                });
+                involvement.groups.insert(GroupRef {
+                    subject: *subject,
+                    group: *group,
+                });
+                involvement.students.insert(*student);
+            }
+            Variable::StudentInGroup {
+                subject,
+                student,
+                group,
+            } => {
+                involvement.subjects.insert(*subject);
+                involvement.groups.insert(GroupRef {
+                    subject: *subject,
+                    group: *group,
+                });
+                involvement.students.insert(*student);
+            }
+            Variable::UseGrouping(num) => {
+                involvement.slot_groupings.insert(*num);
+            }
+            Variable::IncompatGroupForStudent {
+                incompat_group,
+                student,
+            } => {
+                involvement.incompatibility_groups.insert(*incompat_group);
+                involvement.students.insert(*student);
+            }
+        }
+    }
+
+    pub fn explain_infeasability(
+        &self,
+        constraints: &BTreeSet<Constraint<Variable>>,
+    ) -> InfeasabilityExplanation {
+        let pb_builder = self.problem_builder();
+        let constraint_groups = pb_builder.get_constraint_groups();
+
+        let mut conflicts = BTreeMap::<ConstraintKind, ConflictInvolvement>::new();
+        for constraint in constraints {
+            let Some(groups) = constraint_groups.get(constraint) else {
+                continue;
+            };
+            for kind in groups.iter().filter_map(|g| ConstraintKind::from_name(g)) {
+                let involvement = conflicts.entry(kind).or_default();
+                involvement.constraint_count += 1;
+                for var in constraint.variables() {
+                    Self::involvement_for_variable(involvement, &var);
+                }
+            }
+        }
+
+        InfeasabilityExplanation { conflicts }
+    }
+
+    // The problem should be the one that was actually solved (scoped and with
+    // the stability objective) so that fixed variables are taken into account
+    pub fn diagnose_infeasability<S: FeasabilitySolver<Variable, DefaultRepr<Variable>>>(
+        &self,
+        problem: &Problem<Variable>,
+        solver: &S,
+        options: &SolverOptions,
+    ) -> Option<InfeasabilityExplanation> {
+        let iis = problem.compute_iis(solver, options)?;
+        Some(self.explain_infeasability(&iis))
+    }
+
+    // Only the user-facing rules (the kinds that can be soft) are offered
+    // for relaxation and each of them costs the same to relax
+    pub fn suggest_relaxations<
+        S: FeasabilitySolver<ElasticVariable<Variable>, DefaultRepr<ElasticVariable<Variable>>>,
+    >(
+        &self,
+        problem: &Problem<Variable>,
+        solver: &S,
+        max_suggestions: usize,
+        options: &SolverOptions,
+    ) -> Vec<RelaxationAdvice> {
+        let relaxable_groups: BTreeMap<_, _> = ConstraintKind::ALL
+            .into_iter()
+            .filter(|kind| kind.can_be_soft())
+            .map(|kind| (kind.name().to_string(), 1.))
+            .collect();
+
+        problem
+            .suggest_relaxations(solver, &relaxable_groups, max_suggestions, options)
+            .into_iter()
+            .map(|suggestion| RelaxationAdvice {
+                kinds: suggestion
+                    .groups
+                    .iter()
+                    .filter_map(|group| ConstraintKind::from_name(group))
+                    .collect(),
+                cost: suggestion.cost,
+            })
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -3480,3 +4221,302 @@ pub struct ColloscopeSubject {
     pub groups: Vec<BTreeSet<usize>>,
     pub slots: Vec<BTreeSet<usize>>,
 }
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColloscopeChange {
+    InterrogationAdded {
+        subject: usize,
+        slot: usize,
+        group: usize,
+    },
+    InterrogationRemoved {
+        subject: usize,
+        slot: usize,
+        group: usize,
+    },
+    // Same group, same week but in another slot
+    InterrogationMoved {
+        subject: usize,
+        group: usize,
+        from_slot: usize,
+        to_slot: usize,
+    },
+    StudentMoved {
+        subject: usize,
+        student: usize,
+        from_group: Option<usize>,
+        to_group: Option<usize>,
+    },
+}
+
+impl Colloscope {
+    pub fn apply_changes(&self, changes: &[ColloscopeChange]) -> Option<Colloscope> {
+        let mut output = self.clone();
+
+        for change in changes {
+            match change {
+                ColloscopeChange::InterrogationAdded {
+                    subject,
+                    slot,
+                    group,
+                } => {
+                    let subject = output.subjects.get_mut(*subject)?;
+                    subject.slots.get_mut(*slot)?.insert(*group);
+                }
+                ColloscopeChange::InterrogationRemoved {
+                    subject,
+                    slot,
+                    group,
+                } => {
+                    let subject = output.subjects.get_mut(*subject)?;
+                    subject.slots.get_mut(*slot)?.remove(group);
+                }
+                ColloscopeChange::InterrogationMoved {
+                    subject,
+                    group,
+                    from_slot,
+                    to_slot,
+                } => {
+                    let subject = output.subjects.get_mut(*subject)?;
+                    subject.slots.get_mut(*from_slot)?.remove(group);
+                    subject.slots.get_mut(*to_slot)?.insert(*group);
+                }
+                ColloscopeChange::StudentMoved {
+                    subject,
+                    student,
+                    from_group,
+                    to_group,
+                } => {
+                    let subject = output.subjects.get_mut(*subject)?;
+                    if let Some(from_group) = from_group {
+                        subject.groups.get_mut(*from_group)?.remove(student);
+                    }
+                    if let Some(to_group) = to_group {
+                        subject.groups.get_mut(*to_group)?.insert(*student);
+                    }
+                }
+            }
+        }
+
+        Some(output)
+    }
+}
+
+// Part of the colloscope that can be changed when solving.
+// Everything outside of it is fixed to its current value.
+// None means no restriction.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SolveScope {
+    pub subjects: Option<BTreeSet<usize>>,
+    pub weeks: Option<std::ops::Range<u32>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConstraintKind {
+    MaxGroupsPerSlot,
+    OneInterrogationPerTimeUnit,
+    OneInterrogationPerPeriod,
+    EmptyGroupsPerPeriod,
+    StudentsPerGroup,
+    StudentInSingleGroup,
+    DynamicGroups,
+    InterrogationsPerWeek,
+    MaxInterrogationsPerDay,
+    SlotGrouping,
+    SlotGroupingIncompat,
+    StudentIncompatibility,
+    SlotSelection,
+    Balancing,
+    NonConsecutiveInterrogations,
+}
+
+impl ConstraintKind {
+    const ALL: [ConstraintKind; 15] = [
+        ConstraintKind::MaxGroupsPerSlot,
+        ConstraintKind::OneInterrogationPerTimeUnit,
+        ConstraintKind::OneInterrogationPerPeriod,
+        ConstraintKind::EmptyGroupsPerPeriod,
+        ConstraintKind::StudentsPerGroup,
+        ConstraintKind::StudentInSingleGroup,
+        ConstraintKind::DynamicGroups,
+        ConstraintKind::InterrogationsPerWeek,
+        ConstraintKind::MaxInterrogationsPerDay,
+        ConstraintKind::SlotGrouping,
+        ConstraintKind::SlotGroupingIncompat,
+        ConstraintKind::StudentIncompatibility,
+        ConstraintKind::SlotSelection,
+        ConstraintKind::Balancing,
+        ConstraintKind::NonConsecutiveInterrogations,
+    ];
+
+    // Name of the corresponding constraint group in the ILP problem
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConstraintKind::MaxGroupsPerSlot => "max_groups_per_slot",
+            ConstraintKind::OneInterrogationPerTimeUnit => "one_interrogation_per_time_unit",
+            ConstraintKind::OneInterrogationPerPeriod => "one_interrogation_per_period",
+            ConstraintKind::EmptyGroupsPerPeriod => "empty_groups_per_period",
+            ConstraintKind::StudentsPerGroup => "students_per_group",
+            ConstraintKind::StudentInSingleGroup => "student_in_single_group",
+            ConstraintKind::DynamicGroups => "dynamic_groups",
+            ConstraintKind::InterrogationsPerWeek => "interrogations_per_week",
+            ConstraintKind::MaxInterrogationsPerDay => "max_interrogations_per_day",
+            ConstraintKind::SlotGrouping => "slot_grouping",
+            ConstraintKind::SlotGroupingIncompat => "slot_grouping_incompat",
+            ConstraintKind::StudentIncompatibility => "student_incompatibility",
+            ConstraintKind::SlotSelection => "slot_selection",
+            ConstraintKind::Balancing => "balancing",
+            ConstraintKind::NonConsecutiveInterrogations => "non_consecutive_interrogations",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ConstraintKind> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    // Structural constraints must stay hard otherwise the colloscope is meaningless
+    pub fn can_be_soft(&self) -> bool {
+        match self {
+            ConstraintKind::InterrogationsPerWeek
+            | ConstraintKind::MaxInterrogationsPerDay
+            | ConstraintKind::SlotGroupingIncompat
+            | ConstraintKind::StudentIncompatibility
+            | ConstraintKind::Balancing
+            | ConstraintKind::NonConsecutiveInterrogations => true,
+            ConstraintKind::MaxGroupsPerSlot
+            | ConstraintKind::OneInterrogationPerTimeUnit
+            | ConstraintKind::OneInterrogationPerPeriod
+            | ConstraintKind::EmptyGroupsPerPeriod
+            | ConstraintKind::StudentsPerGroup
+            | ConstraintKind::StudentInSingleGroup
+            | ConstraintKind::DynamicGroups
+            | ConstraintKind::SlotGrouping
+            | ConstraintKind::SlotSelection => false,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ConstraintKind::MaxGroupsPerSlot => "too many groups in a single slot",
+            ConstraintKind::OneInterrogationPerTimeUnit => {
+                "a student cannot have two interrogations at the same time"
+            }
+            ConstraintKind::OneInterrogationPerPeriod => {
+                "each group needs exactly one interrogation per period"
+            }
+            ConstraintKind::EmptyGroupsPerPeriod => {
+                "empty groups can have at most one interrogation per period"
+            }
+            ConstraintKind::StudentsPerGroup => "group sizes must stay in the allowed range",
+            ConstraintKind::StudentInSingleGroup => "each student belongs to exactly one group",
+            ConstraintKind::DynamicGroups => "students follow the group they are assigned to",
+            ConstraintKind::InterrogationsPerWeek => {
+                "interrogations per week must stay in the allowed range"
+            }
+            ConstraintKind::MaxInterrogationsPerDay => "too many interrogations in a single day",
+            ConstraintKind::SlotGrouping => "grouped slots must be used together",
+            ConstraintKind::SlotGroupingIncompat => "incompatible slot groupings used together",
+            ConstraintKind::StudentIncompatibility => {
+                "students cannot have interrogations during their incompatibilities"
+            }
+            ConstraintKind::SlotSelection => "groups must keep to a single slot selection",
+            ConstraintKind::Balancing => "slots must be balanced between groups",
+            ConstraintKind::NonConsecutiveInterrogations => {
+                "a student cannot have consecutive interrogations"
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ConstraintKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GroupRef {
+    pub subject: usize,
+    pub group: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ConflictInvolvement {
+    pub constraint_count: usize,
+    pub subjects: BTreeSet<usize>,
+    pub slots: BTreeSet<SlotRef>,
+    pub groups: BTreeSet<GroupRef>,
+    pub students: BTreeSet<usize>,
+    pub slot_groupings: BTreeSet<usize>,
+    pub incompatibility_groups: BTreeSet<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelaxationAdvice {
+    pub kinds: BTreeSet<ConstraintKind>,
+    pub cost: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct InfeasabilityExplanation {
+    pub conflicts: BTreeMap<ConstraintKind, ConflictInvolvement>,
+}
+
+impl std::fmt::Display for InfeasabilityExplanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn join<T, F: Fn(&T) -> String>(elems: &BTreeSet<T>, func: F) -> String {
+            elems.iter().map(func).collect::<Vec<_>>().join(", ")
+        }
+
+        for (kind, involvement) in &self.conflicts {
+            write!(f, "- {}", kind)?;
+
+            let mut details = vec![];
+            if !involvement.subjects.is_empty() {
+                details.push(format!(
+                    "subjects {}",
+                    join(&involvement.subjects, |x| x.to_string())
+                ));
+            }
+            if !involvement.slots.is_empty() {
+                details.push(format!(
+                    "slots {}",
+                    join(&involvement.slots, |x| format!("{}:{}", x.subject, x.slot))
+                ));
+            }
+            if !involvement.groups.is_empty() {
+                details.push(format!(
+                    "groups {}",
+                    join(&involvement.groups, |x| format!(
+                        "{}:{}",
+                        x.subject, x.group
+                    ))
+                ));
+            }
+            if !involvement.students.is_empty() {
+                details.push(format!(
+                    "students {}",
+                    join(&involvement.students, |x| x.to_string())
+                ));
+            }
+            if !involvement.slot_groupings.is_empty() {
+                details.push(format!(
+                    "slot groupings {}",
+                    join(&involvement.slot_groupings, |x| x.to_string())
+                ));
+            }
+            if !involvement.incompatibility_groups.is_empty() {
+                details.push(format!(
+                    "incompatibilities {}",
+                    join(&involvement.incompatibility_groups, |x| x.to_string())
+                ));
+            }
+            if !details.is_empty() {
+                write!(f, " ({})", details.join("; "))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -10475,3 +10475,760 @@ fn group_on_slot_selection_constraints() {
 
     assert_eq!(group_on_slot_selection_constraints, expected_result);
 }
+
+#[test]
+fn variable_serde_roundtrip() {
+    let variables = vec![
+        Variable::GroupInSlot {
+            subject: 0,
+            slot: 1,
+            group: 2,
+        },
+        Variable::DynamicGroupAssignment {
+            subject: 1,
+            slot: 2,
+            group: 3,
+            student: 4,
+        },
+        Variable::UseGrouping(5),
+        Variable::IncompatGroupForStudent {
+            incompat_group: 6,
+            student: 7,
+        },
+    ];
+
+    let json = serde_json::to_string(&variables).unwrap();
+    let variables2: Vec<Variable> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(variables, variables2);
+}
+
+#[test]
+fn constraint_kind_name_roundtrip() {
+    for kind in ConstraintKind::ALL {
+        assert_eq!(ConstraintKind::from_name(kind.name()), Some(kind));
+    }
+    assert_eq!(ConstraintKind::from_name("unknown"), None);
+}
+
+#[test]
+fn explain_infeasability_maps_constraints_to_domain() {
+    let general = GeneralData {
+        periodicity_cuts: BTreeSet::new(),
+        teacher_count: 1,
+        week_count: NonZeroU32::new(2).unwrap(),
+        interrogations_per_week: None,
+        max_interrogations_per_day: None,
+        costs_adjustments: crate::gen::colloscope::CostsAdjustments::default(),
+    };
+
+    let subjects = vec![Subject {
+        students_per_group: NonZeroUsize::new(2).unwrap()..=NonZeroUsize::new(3).unwrap(),
+        period: NonZeroU32::new(2).unwrap(),
+        slots_information: SlotsInformation::from_slots(vec![SlotWithTeacher {
+            cost: 0,
+            teacher: 0,
+            start: SlotStart {
+                week: 0,
+                weekday: time::Weekday::Monday,
+                start_time: time::Time::from_hm(8, 0).unwrap(),
+            },
+        }]),
+        groups: GroupsDesc {
+            prefilled_groups: vec![GroupDesc {
+                students: BTreeSet::from([0]),
+                can_be_extended: true,
+            }],
+            not_assigned: BTreeSet::from([1]),
+        },
+        ..Subject::default()
+    }];
+    let students = vec![
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+    ];
+
+    let data = ValidatedData::new(
+        general,
+        subjects,
+        IncompatibilityGroupList::new(),
+        vec![],
+        students,
+        vec![],
+        SlotGroupingIncompatSet::new(),
+    )
+    .unwrap();
+
+    let ilp_translator = data.ilp_translator();
+    let constraints = ilp_translator.build_students_per_group_count_constraints();
+    let explanation = ilp_translator.explain_infeasability(&constraints);
+
+    let involvement = &explanation.conflicts[&ConstraintKind::StudentsPerGroup];
+    assert_eq!(involvement.constraint_count, constraints.len());
+    assert_eq!(involvement.subjects, BTreeSet::from([0]));
+    assert_eq!(
+        involvement.groups,
+        BTreeSet::from([GroupRef {
+            subject: 0,
+            group: 0
+        }])
+    );
+    assert_eq!(involvement.students, BTreeSet::from([1]));
+    assert!(explanation.to_string().contains("students 1"));
+}
+
+#[test]
+fn problem_for_scope_fixes_variables_outside_scope() {
+    let general = GeneralData {
+        periodicity_cuts: BTreeSet::new(),
+        teacher_count: 1,
+        week_count: NonZeroU32::new(2).unwrap(),
+        interrogations_per_week: None,
+        max_interrogations_per_day: None,
+        costs_adjustments: crate::gen::colloscope::CostsAdjustments::default(),
+    };
+
+    let subjects = vec![Subject {
+        students_per_group: NonZeroUsize::new(2).unwrap()..=NonZeroUsize::new(3).unwrap(),
+        period: NonZeroU32::new(1).unwrap(),
+        slots_information: SlotsInformation::from_slots(vec![
+            SlotWithTeacher {
+                cost: 0,
+                teacher: 0,
+                start: SlotStart {
+                    week: 0,
+                    weekday: time::Weekday::Monday,
+                    start_time: time::Time::from_hm(8, 0).unwrap(),
+                },
+            },
+            SlotWithTeacher {
+                cost: 0,
+                teacher: 0,
+                start: SlotStart {
+                    week: 1,
+                    weekday: time::Weekday::Monday,
+                    start_time: time::Time::from_hm(8, 0).unwrap(),
+                },
+            },
+        ]),
+        groups: GroupsDesc {
+            prefilled_groups: vec![GroupDesc {
+                students: BTreeSet::from([0]),
+                can_be_extended: true,
+            }],
+            not_assigned: BTreeSet::from([1]),
+        },
+        ..Subject::default()
+    }];
+    let students = vec![
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+    ];
+
+    let data = ValidatedData::new(
+        general,
+        subjects,
+        IncompatibilityGroupList::new(),
+        vec![],
+        students,
+        vec![],
+        SlotGroupingIncompatSet::new(),
+    )
+    .unwrap();
+
+    let ilp_translator = data.ilp_translator();
+    let current = Colloscope {
+        subjects: vec![ColloscopeSubject {
+            groups: vec![BTreeSet::from([0, 1])],
+            slots: vec![BTreeSet::from([0]), BTreeSet::from([0])],
+        }],
+    };
+    let scope = SolveScope {
+        subjects: None,
+        weeks: Some(1..2),
+    };
+
+    let pb_builder = ilp_translator
+        .problem_builder_for_scope(&current, &scope)
+        .unwrap();
+    let fixed: BTreeSet<_> = pb_builder
+        .get_constraint_groups()
+        .iter()
+        .filter(|(_c, groups)| groups.contains("fixed_outside_scope"))
+        .map(|(c, _groups)| c.clone())
+        .collect();
+
+    use crate::ilp::linexpr::Expr;
+
+    #[rustfmt::skip]
+    let gis_0_0_0 = Expr::<Variable>::var(Variable::GroupInSlot { subject: 0, slot: 0, group: 0 });
+    #[rustfmt::skip]
+    let sig_0_1_0 = Expr::<Variable>::var(Variable::StudentInGroup { subject: 0, student: 1, group: 0 });
+
+    let expected_result = BTreeSet::from([
+        gis_0_0_0.eq(&Expr::constant(1)).cleaned(),
+        sig_0_1_0.eq(&Expr::constant(1)).cleaned(),
+    ]);
+
+    assert_eq!(fixed, expected_result);
+
+    let wrong_colloscope = Colloscope { subjects: vec![] };
+    assert!(ilp_translator
+        .problem_builder_for_scope(&wrong_colloscope, &scope)
+        .is_none());
+
+    // Same subjects but a missing slot
+    let mut wrong_colloscope = current.clone();
+    wrong_colloscope.subjects[0].slots.pop();
+    assert!(ilp_translator
+        .problem_builder_for_scope(&wrong_colloscope, &scope)
+        .is_none());
+}
+
+#[test]
+fn stability_objective_penalizes_changes() {
+    let general = GeneralData {
+        periodicity_cuts: BTreeSet::new(),
+        teacher_count: 1,
+        week_count: NonZeroU32::new(2).unwrap(),
+        interrogations_per_week: None,
+        max_interrogations_per_day: None,
+        costs_adjustments: crate::gen::colloscope::CostsAdjustments::default(),
+    };
+
+    let subjects = vec![Subject {
+        students_per_group: NonZeroUsize::new(2).unwrap()..=NonZeroUsize::new(3).unwrap(),
+        period: NonZeroU32::new(1).unwrap(),
+        slots_information: SlotsInformation::from_slots(vec![
+            SlotWithTeacher {
+                cost: 0,
+                teacher: 0,
+                start: SlotStart {
+                    week: 0,
+                    weekday: time::Weekday::Monday,
+                    start_time: time::Time::from_hm(8, 0).unwrap(),
+                },
+            },
+            SlotWithTeacher {
+                cost: 0,
+                teacher: 0,
+                start: SlotStart {
+                    week: 1,
+                    weekday: time::Weekday::Monday,
+                    start_time: time::Time::from_hm(8, 0).unwrap(),
+                },
+            },
+        ]),
+        groups: GroupsDesc {
+            prefilled_groups: vec![GroupDesc {
+                students: BTreeSet::from([0]),
+                can_be_extended: true,
+            }],
+            not_assigned: BTreeSet::from([1]),
+        },
+        ..Subject::default()
+    }];
+    let students = vec![
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+    ];
+
+    let data = ValidatedData::new(
+        general,
+        subjects,
+        IncompatibilityGroupList::new(),
+        vec![],
+        students,
+        vec![],
+        SlotGroupingIncompatSet::new(),
+    )
+    .unwrap();
+
+    let ilp_translator = data.ilp_translator();
+    let current = Colloscope {
+        subjects: vec![ColloscopeSubject {
+            groups: vec![BTreeSet::from([0, 1])],
+            slots: vec![BTreeSet::from([0]), BTreeSet::new()],
+        }],
+    };
+
+    let pb_builder = ilp_translator.problem_builder();
+    let base = pb_builder.get_objective_contribs().clone();
+    let pb_builder = ilp_translator
+        .add_stability_objective(pb_builder, &current, 2.)
+        .unwrap();
+
+    let gis_0_0_0 = Variable::GroupInSlot {
+        subject: 0,
+        slot: 0,
+        group: 0,
+    };
+    let gis_0_1_0 = Variable::GroupInSlot {
+        subject: 0,
+        slot: 1,
+        group: 0,
+    };
+    let base_0 = base.get(&gis_0_0_0).copied().unwrap_or(0.);
+    let base_1 = base.get(&gis_0_1_0).copied().unwrap_or(0.);
+
+    assert_eq!(pb_builder.get_objective_contribs()[&gis_0_0_0], base_0 - 2.);
+    assert_eq!(pb_builder.get_objective_contribs()[&gis_0_1_0], base_1 + 2.);
+
+    // Student 1 is only in group 0 through dynamic assignment
+    let dga_0_0_0_1 = Variable::DynamicGroupAssignment {
+        subject: 0,
+        slot: 0,
+        group: 0,
+        student: 1,
+    };
+    let dga_0_1_0_1 = Variable::DynamicGroupAssignment {
+        subject: 0,
+        slot: 1,
+        group: 0,
+        student: 1,
+    };
+    let base_dga_0 = base.get(&dga_0_0_0_1).copied().unwrap_or(0.);
+    let base_dga_1 = base.get(&dga_0_1_0_1).copied().unwrap_or(0.);
+
+    assert_eq!(
+        pb_builder.get_objective_contribs()[&dga_0_0_0_1],
+        base_dga_0 - 2.
+    );
+    assert_eq!(
+        pb_builder.get_objective_contribs()[&dga_0_1_0_1],
+        base_dga_1 + 2.
+    );
+}
+
+#[test]
+fn diff_colloscopes_and_apply_changes() {
+    let general = GeneralData {
+        periodicity_cuts: BTreeSet::new(),
+        teacher_count: 1,
+        week_count: NonZeroU32::new(2).unwrap(),
+        interrogations_per_week: None,
+        max_interrogations_per_day: None,
+        costs_adjustments: crate::gen::colloscope::CostsAdjustments::default(),
+    };
+
+    let subjects = vec![Subject {
+        students_per_group: NonZeroUsize::new(1).unwrap()..=NonZeroUsize::new(3).unwrap(),
+        period: NonZeroU32::new(1).unwrap(),
+        slots_information: SlotsInformation::from_slots(vec![
+            SlotWithTeacher {
+                cost: 0,
+                teacher: 0,
+                start: SlotStart {
+                    week: 0,
+                    weekday: time::Weekday::Monday,
+                    start_time: time::Time::from_hm(8, 0).unwrap(),
+                },
+            },
+            SlotWithTeacher {
+                cost: 0,
+                teacher: 0,
+                start: SlotStart {
+                    week: 0,
+                    weekday: time::Weekday::Tuesday,
+                    start_time: time::Time::from_hm(8, 0).unwrap(),
+                },
+            },
+            SlotWithTeacher {
+                cost: 0,
+                teacher: 0,
+                start: SlotStart {
+                    week: 1,
+                    weekday: time::Weekday::Monday,
+                    start_time: time::Time::from_hm(8, 0).unwrap(),
+                },
+            },
+        ]),
+        groups: GroupsDesc {
+            prefilled_groups: vec![
+                GroupDesc {
+                    students: BTreeSet::from([0]),
+                    can_be_extended: true,
+                },
+                GroupDesc {
+                    students: BTreeSet::from([1]),
+                    can_be_extended: true,
+                },
+            ],
+            not_assigned: BTreeSet::from([2]),
+        },
+        ..Subject::default()
+    }];
+    let students = vec![
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+    ];
+
+    let data = ValidatedData::new(
+        general,
+        subjects,
+        IncompatibilityGroupList::new(),
+        vec![],
+        students,
+        vec![],
+        SlotGroupingIncompatSet::new(),
+    )
+    .unwrap();
+
+    let current = Colloscope {
+        subjects: vec![ColloscopeSubject {
+            groups: vec![BTreeSet::from([0, 2]), BTreeSet::from([1])],
+            slots: vec![BTreeSet::from([0]), BTreeSet::from([1]), BTreeSet::new()],
+        }],
+    };
+    let proposed = Colloscope {
+        subjects: vec![ColloscopeSubject {
+            groups: vec![BTreeSet::from([0]), BTreeSet::from([1, 2])],
+            slots: vec![BTreeSet::new(), BTreeSet::from([0, 1]), BTreeSet::from([1])],
+        }],
+    };
+
+    let changes = data.diff_colloscopes(&current, &proposed).unwrap();
+
+    let expected_result = vec![
+        ColloscopeChange::StudentMoved {
+            subject: 0,
+            student: 2,
+            from_group: Some(0),
+            to_group: Some(1),
+        },
+        ColloscopeChange::InterrogationMoved {
+            subject: 0,
+            group: 0,
+            from_slot: 0,
+            to_slot: 1,
+        },
+        ColloscopeChange::InterrogationAdded {
+            subject: 0,
+            slot: 2,
+            group: 1,
+        },
+    ];
+
+    assert_eq!(changes, expected_result);
+    assert_eq!(current.apply_changes(&changes), Some(proposed.clone()));
+
+    let partial = current.apply_changes(&changes[2..]).unwrap();
+    assert_eq!(
+        partial.subjects[0].slots,
+        vec![
+            BTreeSet::from([0]),
+            BTreeSet::from([1]),
+            BTreeSet::from([1])
+        ]
+    );
+
+    let wrong_colloscope = Colloscope { subjects: vec![] };
+    assert_eq!(data.diff_colloscopes(&current, &wrong_colloscope), None);
+}
+
+#[test]
+fn greedy_colloscope_gives_feasable_hint_on_simple_data() {
+    let general = GeneralData {
+        periodicity_cuts: BTreeSet::new(),
+        teacher_count: 1,
+        week_count: NonZeroU32::new(2).unwrap(),
+        interrogations_per_week: None,
+        max_interrogations_per_day: None,
+        costs_adjustments: crate::gen::colloscope::CostsAdjustments::default(),
+    };
+
+    let subjects = vec![Subject {
+        students_per_group: NonZeroUsize::new(2).unwrap()..=NonZeroUsize::new(3).unwrap(),
+        period: NonZeroU32::new(1).unwrap(),
+        slots_information: SlotsInformation::from_slots(vec![
+            SlotWithTeacher {
+                cost: 0,
+                teacher: 0,
+                start: SlotStart {
+                    week: 0,
+                    weekday: time::Weekday::Monday,
+                    start_time: time::Time::from_hm(8, 0).unwrap(),
+                },
+            },
+            SlotWithTeacher {
+                cost: 0,
+                teacher: 0,
+                start: SlotStart {
+                    week: 1,
+                    weekday: time::Weekday::Monday,
+                    start_time: time::Time::from_hm(8, 0).unwrap(),
+                },
+            },
+        ]),
+        groups: GroupsDesc {
+            prefilled_groups: vec![GroupDesc {
+                students: BTreeSet::from([0]),
+                can_be_extended: true,
+            }],
+            not_assigned: BTreeSet::from([1]),
+        },
+        ..Subject::default()
+    }];
+    let students = vec![
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+        Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        },
+    ];
+
+    let data = ValidatedData::new(
+        general,
+        subjects,
+        IncompatibilityGroupList::new(),
+        vec![],
+        students,
+        vec![],
+        SlotGroupingIncompatSet::new(),
+    )
+    .unwrap();
+
+    let ilp_translator = data.ilp_translator();
+    let colloscope = ilp_translator.greedy_colloscope();
+
+    let expected_result = Colloscope {
+        subjects: vec![ColloscopeSubject {
+            groups: vec![BTreeSet::from([0, 1])],
+            slots: vec![BTreeSet::from([0]), BTreeSet::from([0])],
+        }],
+    };
+    assert_eq!(colloscope, expected_result);
+
+    let problem = ilp_translator.problem();
+    let config = ilp_translator.config_hint_from_colloscope(&problem, &colloscope);
+    assert!(config.is_feasable());
+}
+
+#[test]
+fn soft_constraint_kinds_are_moved_to_objective() {
+    let build_data = |costs_adjustments| {
+        let general = GeneralData {
+            periodicity_cuts: BTreeSet::new(),
+            teacher_count: 1,
+            week_count: NonZeroU32::new(2).unwrap(),
+            interrogations_per_week: Some(2..3),
+            max_interrogations_per_day: None,
+            costs_adjustments,
+        };
+
+        let subjects = vec![Subject {
+            students_per_group: NonZeroUsize::new(1).unwrap()..=NonZeroUsize::new(2).unwrap(),
+            period: NonZeroU32::new(1).unwrap(),
+            slots_information: SlotsInformation::from_slots(vec![
+                SlotWithTeacher {
+                    cost: 0,
+                    teacher: 0,
+                    start: SlotStart {
+                        week: 0,
+                        weekday: time::Weekday::Monday,
+                        start_time: time::Time::from_hm(8, 0).unwrap(),
+                    },
+                },
+                SlotWithTeacher {
+                    cost: 0,
+                    teacher: 0,
+                    start: SlotStart {
+                        week: 1,
+                        weekday: time::Weekday::Monday,
+                        start_time: time::Time::from_hm(8, 0).unwrap(),
+                    },
+                },
+            ]),
+            groups: GroupsDesc {
+                prefilled_groups: vec![GroupDesc {
+                    students: BTreeSet::from([0]),
+                    can_be_extended: false,
+                }],
+                not_assigned: BTreeSet::new(),
+            },
+            ..Subject::default()
+        }];
+        let students = vec![Student {
+            non_consecutive_interrogations: false,
+            incompatibilities: BTreeSet::new(),
+        }];
+
+        ValidatedData::new(
+            general,
+            subjects,
+            IncompatibilityGroupList::new(),
+            vec![],
+            students,
+            vec![],
+            SlotGroupingIncompatSet::new(),
+        )
+        .unwrap()
+    };
+
+    let hard_data = build_data(crate::gen::colloscope::CostsAdjustments::default());
+    let hard_problem = hard_data.ilp_translator().problem();
+    assert!(hard_problem
+        .get_groups()
+        .contains(ConstraintKind::InterrogationsPerWeek.name()));
+
+    let soft_data = build_data(crate::gen::colloscope::CostsAdjustments {
+        soft_constraints: BTreeMap::from([
+            (ConstraintKind::InterrogationsPerWeek, 5),
+            // Structural constraints stay hard
+            (ConstraintKind::OneInterrogationPerPeriod, 5),
+        ]),
+        ..crate::gen::colloscope::CostsAdjustments::default()
+    });
+    let soft_problem = soft_data.ilp_translator().problem();
+    assert!(!soft_problem
+        .get_groups()
+        .contains(ConstraintKind::InterrogationsPerWeek.name()));
+    assert!(soft_problem
+        .get_groups()
+        .contains(ConstraintKind::OneInterrogationPerPeriod.name()));
+    assert!(soft_problem.get_objective_terms().len() > hard_problem.get_objective_terms().len());
+    assert_eq!(
+        hard_problem.get_soft_constraint_descs(),
+        BTreeSet::from([String::from("balancing_optimizer")])
+    );
+    assert!(soft_problem
+        .get_soft_constraint_descs()
+        .contains(ConstraintKind::InterrogationsPerWeek.name()));
+    assert!(
+        soft_problem.stats().soft_constraint_count > hard_problem.stats().soft_constraint_count
+    );
+}
+
+#[test]
+fn slot_start_occurrences() {
+    let start_time = time::Time::from_hm(14, 0).unwrap();
+    let occurrences = SlotStart::occurrences(
+        time::Weekday::Tuesday,
+        &start_time,
+        &time::Recurrence::Biweekly { offset: 1 },
+        5,
+    );
+
+    assert_eq!(
+        occurrences,
+        vec![
+            SlotStart {
+                week: 1,
+                weekday: time::Weekday::Tuesday,
+                start_time: start_time.clone(),
+            },
+            SlotStart {
+                week: 3,
+                weekday: time::Weekday::Tuesday,
+                start_time,
+            },
+        ]
+    );
+}
+
+#[test]
+fn overnight_slot_split() {
+    let slot = OvernightSlot {
+        start: SlotStart {
+            week: 2,
+            weekday: time::Weekday::Sunday,
+            start_time: time::Time::from_hm(22, 30).unwrap(),
+        },
+        duration: NonZeroU32::new(120).unwrap(),
+    };
+
+    assert!(slot.crosses_midnight());
+    assert_eq!(
+        slot.split_at_midnight(),
+        vec![
+            SlotWithDuration {
+                start: slot.start.clone(),
+                duration: NonZeroU32::new(90).unwrap(),
+            },
+            SlotWithDuration {
+                start: SlotStart {
+                    week: 3,
+                    weekday: time::Weekday::Monday,
+                    start_time: time::Time::from_hm(0, 0).unwrap(),
+                },
+                duration: NonZeroU32::new(30).unwrap(),
+            },
+        ]
+    );
+
+    let regular = SlotWithDuration {
+        start: SlotStart {
+            week: 0,
+            weekday: time::Weekday::Monday,
+            start_time: time::Time::from_hm(21, 30).unwrap(),
+        },
+        duration: NonZeroU32::new(150).unwrap(),
+    };
+    assert!(!OvernightSlot::from(regular.clone()).crosses_midnight());
+    assert_eq!(
+        OvernightSlot::from(regular.clone()).split_at_midnight(),
+        vec![regular]
+    );
+}
+
+#[test]
+fn overnight_slot_overlap() {
+    let slot = |weekday, hour, min, duration| OvernightSlot {
+        start: SlotStart {
+            week: 0,
+            weekday,
+            start_time: time::Time::from_hm(hour, min).unwrap(),
+        },
+        duration: NonZeroU32::new(duration).unwrap(),
+    };
+
+    let overnight = slot(time::Weekday::Tuesday, 23, 0, 120);
+
+    assert!(overnight.overlap_with(&slot(time::Weekday::Wednesday, 0, 30, 60)));
+    assert!(overnight.overlap_with(&slot(time::Weekday::Tuesday, 22, 0, 61)));
+    // Slots only touching at their ends do not overlap
+    assert!(!overnight.overlap_with(&slot(time::Weekday::Wednesday, 1, 0, 60)));
+    assert!(!overnight.overlap_with(&slot(time::Weekday::Tuesday, 22, 0, 60)));
+    assert!(!overnight.overlap_with(&slot(time::Weekday::Thursday, 0, 30, 60)));
+
+    let next_morning = SlotWithDuration {
+        start: SlotStart {
+            week: 0,
+            weekday: time::Weekday::Wednesday,
+            start_time: time::Time::from_hm(0, 0).unwrap(),
+        },
+        duration: NonZeroU32::new(30).unwrap(),
+    };
+    assert!(overnight.overlap_with_slot(&next_morning));
+}
@@ -9,6 +9,7 @@ mod tests;
 
 use std::sync::RwLock;
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use linexpr::VariableName;
@@ -23,19 +24,151 @@ pub enum Error<V: VariableName> {
 pub type Result<T, V> = std::result::Result<T, Error<V>>;
 
 pub type DefaultRepr<V> = mat_repr::sparse::SprsProblem<V>;
+// The ndarray representation is dense: it is faster on small problems
+pub type DenseRepr<V> = mat_repr::nd::NdProblem<V>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(serialize = "V: Serialize", deserialize = "V: Deserialize<'de>"))]
 pub struct ObjectiveTerm<V: VariableName> {
     pub coef: f64,
     pub exprs: BTreeSet<linexpr::Expr<V>>,
+    // Name of the soft constraint this term comes from, if any
+    #[serde(default)]
+    pub desc: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+impl<V: VariableName> ObjectiveTerm<V> {
+    pub fn from_soft_constraint(constraint: &linexpr::Constraint<V>, coef: f64) -> Self {
+        let expr = constraint.get_lhs().clone();
+
+        // The penalty is coef * max(exprs) which is exactly coef * (amount of violation)
+        let exprs = match constraint.get_sign() {
+            linexpr::Sign::Equals => BTreeSet::from([-&expr, expr]),
+            linexpr::Sign::LessThan => BTreeSet::from([expr, linexpr::Expr::constant(0)]),
+        };
+
+        ObjectiveTerm {
+            coef,
+            exprs,
+            desc: None,
+        }
+    }
+}
+
+// Variables of the elastic version of a problem: each relaxable group of
+// constraints gets its own variable that switches the group off.
+// Constraints belonging to several groups are switched off by a RelaxAll
+// variable that can only be set when every one of their groups is relaxed
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ElasticVariable<V: VariableName> {
+    Original(V),
+    Relax(String),
+    RelaxAll(BTreeSet<String>),
+}
+
+impl<V: VariableName> From<&ElasticVariable<V>> for ElasticVariable<V> {
+    fn from(value: &ElasticVariable<V>) -> Self {
+        value.clone()
+    }
+}
+
+impl<V: VariableName> std::fmt::Display for ElasticVariable<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElasticVariable::Original(var) => write!(f, "{}", var),
+            ElasticVariable::Relax(group) => write!(f, "Relax({})", group),
+            ElasticVariable::RelaxAll(groups) => {
+                let groups: Vec<_> = groups.iter().map(String::as_str).collect();
+                write!(f, "RelaxAll({})", groups.join(","))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelaxationSuggestion {
+    pub groups: BTreeSet<String>,
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    try_from = "ProblemData<V>",
+    into = "ProblemData<V>",
+    bound(serialize = "V: Serialize", deserialize = "V: Deserialize<'de>")
+)]
 pub struct ProblemBuilder<V: VariableName> {
     constraints: BTreeSet<linexpr::Constraint<V>>,
+    constraint_groups: BTreeMap<linexpr::Constraint<V>, BTreeSet<String>>,
     variables: BTreeSet<V>,
     objective_terms: Vec<ObjectiveTerm<V>>,
     objective_contribs: BTreeMap<V, f64>,
+    tolerance: f64,
+}
+
+// Threshold used when reading back (floating point) variable values from solvers
+pub const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+fn default_tolerance() -> f64 {
+    DEFAULT_TOLERANCE
+}
+
+// Maps keyed by constraints or variables can't be serialized as maps
+// in most formats so they are stored as lists
+#[derive(Serialize, Deserialize)]
+struct ProblemData<V: VariableName> {
+    variables: Vec<V>,
+    constraints: Vec<linexpr::Constraint<V>>,
+    constraint_groups: Vec<(linexpr::Constraint<V>, BTreeSet<String>)>,
+    objective_terms: Vec<ObjectiveTerm<V>>,
+    objective_contribs: Vec<(V, f64)>,
+    #[serde(default = "default_tolerance")]
+    tolerance: f64,
+}
+
+impl<V: VariableName> From<ProblemBuilder<V>> for ProblemData<V> {
+    fn from(value: ProblemBuilder<V>) -> Self {
+        ProblemData {
+            variables: value.variables.into_iter().collect(),
+            constraints: value.constraints.into_iter().collect(),
+            constraint_groups: value.constraint_groups.into_iter().collect(),
+            objective_terms: value.objective_terms,
+            objective_contribs: value.objective_contribs.into_iter().collect(),
+            tolerance: value.tolerance,
+        }
+    }
+}
+
+// Deserialized data goes through the builder so that a file
+// referencing undeclared variables is rejected
+impl<V: VariableName> TryFrom<ProblemData<V>> for ProblemBuilder<V> {
+    type Error = ProblemDataError<V>;
+
+    fn try_from(value: ProblemData<V>) -> std::result::Result<Self, Self::Error> {
+        let mut constraint_groups: BTreeMap<_, _> = value.constraint_groups.into_iter().collect();
+        let mut builder = ProblemBuilder::new()
+            .with_tolerance(value.tolerance)?
+            .add_bool_variables(value.variables)?;
+
+        for constraint in value.constraints {
+            let groups = constraint_groups.remove(&constraint).unwrap_or_default();
+            if groups.is_empty() {
+                builder = builder.add_constraint(constraint)?;
+                continue;
+            }
+            for group in groups {
+                builder = builder.add_grouped_constraint(constraint.clone(), group)?;
+            }
+        }
+        if !constraint_groups.is_empty() {
+            return Err(ProblemDataError::GroupsForUnknownConstraint);
+        }
+
+        let builder = builder
+            .add_objective_terms(value.objective_terms)?
+            .set_objective_contribs(value.objective_contribs)?;
+        Ok(builder)
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -54,13 +187,35 @@ pub enum ConstraintError<V: VariableName> {
 
 pub type ConstraintResult<T, V> = std::result::Result<T, ConstraintError<V>>;
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ToleranceError {
+    #[error("Tolerance should be a non-negative number")]
+    InvalidTolerance,
+}
+
+pub type ToleranceResult<T> = std::result::Result<T, ToleranceError>;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ProblemDataError<V: VariableName> {
+    #[error(transparent)]
+    Var(#[from] VarError<V>),
+    #[error(transparent)]
+    Constraint(#[from] ConstraintError<V>),
+    #[error("Constraint groups are given for a constraint which is not in the problem")]
+    GroupsForUnknownConstraint,
+    #[error(transparent)]
+    Tolerance(#[from] ToleranceError),
+}
+
 impl<V: VariableName> Default for ProblemBuilder<V> {
     fn default() -> Self {
         ProblemBuilder {
             constraints: BTreeSet::new(),
+            constraint_groups: BTreeMap::new(),
             variables: BTreeSet::new(),
             objective_terms: Vec::new(),
             objective_contribs: BTreeMap::new(),
+            tolerance: DEFAULT_TOLERANCE,
         }
     }
 }
@@ -81,7 +236,51 @@ impl<V: VariableName> ProblemBuilder<V> {
             }
         }
 
-        self.constraints.insert(constraint.cleaned());
+        let cleaned = constraint.cleaned();
+        // A constraint added without a group can never be disabled
+        self.constraint_groups.remove(&cleaned);
+        self.constraints.insert(cleaned);
+        Ok(self)
+    }
+
+    pub fn add_grouped_constraint<T: Into<String>>(
+        mut self,
+        constraint: linexpr::Constraint<V>,
+        group: T,
+    ) -> ConstraintResult<Self, V> {
+        let constraint_vars = constraint.variables();
+        for var in constraint_vars {
+            if !self.variables.contains(&var) {
+                return Err(ConstraintError::UndeclaredVariable(var));
+            }
+        }
+
+        let cleaned = constraint.cleaned();
+        let already_ungrouped =
+            self.constraints.contains(&cleaned) && !self.constraint_groups.contains_key(&cleaned);
+        if !already_ungrouped {
+            self.constraint_groups
+                .entry(cleaned.clone())
+                .or_default()
+                .insert(group.into());
+        }
+        self.constraints.insert(cleaned);
+        Ok(self)
+    }
+
+    pub fn add_grouped_constraints<T, I>(
+        mut self,
+        constraints: I,
+        group: T,
+    ) -> ConstraintResult<Self, V>
+    where
+        T: Into<String>,
+        I: IntoIterator<Item = linexpr::Constraint<V>>,
+    {
+        let group = group.into();
+        for constraint in constraints {
+            self = self.add_grouped_constraint(constraint, group.clone())?;
+        }
         Ok(self)
     }
 
@@ -95,29 +294,58 @@ impl<V: VariableName> ProblemBuilder<V> {
         Ok(self)
     }
 
-    pub fn add_objective_term<I>(mut self, coef: f64, exprs: I) -> ConstraintResult<Self, V>
+    pub fn add_objective_term<I>(self, coef: f64, exprs: I) -> ConstraintResult<Self, V>
     where
         I: IntoIterator<Item = linexpr::Expr<V>>,
     {
-        let exprs = BTreeSet::from_iter(exprs);
-        for expr in &exprs {
-            for var in expr.variables() {
-                if !self.variables.contains(&var) {
-                    return Err(ConstraintError::UndeclaredVariable(var));
+        self.add_objective_terms([ObjectiveTerm {
+            coef,
+            exprs: BTreeSet::from_iter(exprs),
+            desc: None,
+        }])
+    }
+
+    pub fn add_objective_terms<I>(mut self, obj_terms: I) -> ConstraintResult<Self, V>
+    where
+        I: IntoIterator<Item = ObjectiveTerm<V>>,
+    {
+        for obj_term in obj_terms {
+            for expr in &obj_term.exprs {
+                for var in expr.variables() {
+                    if !self.variables.contains(&var) {
+                        return Err(ConstraintError::UndeclaredVariable(var));
+                    }
                 }
             }
+            self.objective_terms.push(obj_term);
         }
-
-        self.objective_terms.push(ObjectiveTerm { coef, exprs });
         Ok(self)
     }
 
-    pub fn add_objective_terms<I>(mut self, obj_terms: I) -> ConstraintResult<Self, V>
+    pub fn add_soft_constraint<T: Into<String>>(
+        self,
+        constraint: linexpr::Constraint<V>,
+        weight: f64,
+        desc: T,
+    ) -> ConstraintResult<Self, V> {
+        let mut obj_term = ObjectiveTerm::from_soft_constraint(&constraint.cleaned(), weight);
+        obj_term.desc = Some(desc.into());
+        self.add_objective_terms([obj_term])
+    }
+
+    pub fn add_soft_constraints<T, I>(
+        mut self,
+        constraints: I,
+        weight: f64,
+        desc: T,
+    ) -> ConstraintResult<Self, V>
     where
-        I: IntoIterator<Item = ObjectiveTerm<V>>,
+        T: Into<String>,
+        I: IntoIterator<Item = linexpr::Constraint<V>>,
     {
-        for obj_term in obj_terms {
-            self = self.add_objective_term(obj_term.coef, obj_term.exprs)?;
+        let desc = desc.into();
+        for constraint in constraints {
+            self = self.add_soft_constraint(constraint, weight, desc.clone())?;
         }
         Ok(self)
     }
@@ -173,24 +401,68 @@ impl<V: VariableName> ProblemBuilder<V> {
         &self.variables
     }
 
-    pub fn build<P: ProblemRepr<V>>(self) -> Problem<V, P> {
-        let variables_vec: Vec<_> = self.variables.iter().cloned().collect();
-        let mut variables_lookup = BTreeMap::new();
-        for (i, var) in variables_vec.iter().enumerate() {
-            variables_lookup.insert(var.clone(), i);
+    pub fn get_constraint_groups(&self) -> &BTreeMap<linexpr::Constraint<V>, BTreeSet<String>> {
+        &self.constraint_groups
+    }
+
+    pub fn get_objective_contribs(&self) -> &BTreeMap<V, f64> {
+        &self.objective_contribs
+    }
+
+    pub fn disable_groups<T, I>(mut self, groups: I) -> ProblemBuilder<V>
+    where
+        T: Into<String>,
+        I: IntoIterator<Item = T>,
+    {
+        let disabled: BTreeSet<String> = groups.into_iter().map(|g| g.into()).collect();
+
+        let mut removed = Vec::new();
+        for (constraint, constraint_groups) in &self.constraint_groups {
+            if constraint_groups.is_subset(&disabled) {
+                removed.push(constraint.clone());
+            }
         }
 
-        let pb_repr = P::new(&variables_vec, &self.constraints);
+        for constraint in removed {
+            self.constraints.remove(&constraint);
+            self.constraint_groups.remove(&constraint);
+        }
+
+        self
+    }
+
+    pub fn build<P: ProblemRepr<V>>(self) -> Problem<V, P> {
+        // The lookup table is only needed to build the representation
+        let variables_lookup: BTreeMap<_, _> = self
+            .variables
+            .iter()
+            .enumerate()
+            .map(|(i, var)| (var.clone(), i))
+            .collect();
+        let pb_repr = P::new(&variables_lookup, &self.constraints);
+        drop(variables_lookup);
 
         Problem {
-            variables: self.variables,
-            variables_vec,
-            variables_lookup,
+            variables: self.variables.into_iter().collect(),
             constraints: self.constraints,
+            constraint_groups: self.constraint_groups,
             pb_repr,
             objective_terms: self.objective_terms,
             objective_contribs: self.objective_contribs,
+            tolerance: self.tolerance,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> ToleranceResult<Self> {
+        if tolerance.is_nan() || tolerance < 0. {
+            return Err(ToleranceError::InvalidTolerance);
         }
+        self.tolerance = tolerance;
+        Ok(self)
+    }
+
+    pub fn get_tolerance(&self) -> f64 {
+        self.tolerance
     }
 
     pub fn filter_variables<F>(self, mut predicate: F) -> ProblemBuilder<V>
@@ -203,6 +475,11 @@ impl<V: VariableName> ProblemBuilder<V> {
             .into_iter()
             .filter(|c: &Constraint<V>| c.variables().iter().all(&mut predicate))
             .collect();
+        let constraint_groups = self
+            .constraint_groups
+            .into_iter()
+            .filter(|(c, _g)| c.variables().iter().all(&mut predicate))
+            .collect();
         let variables = self.variables.into_iter().filter(&mut predicate).collect();
         let objective_terms = self
             .objective_terms
@@ -221,6 +498,7 @@ impl<V: VariableName> ProblemBuilder<V> {
                 Some(ObjectiveTerm {
                     coef: obj_term.coef,
                     exprs,
+                    desc: obj_term.desc,
                 })
             })
             .collect();
@@ -232,9 +510,11 @@ impl<V: VariableName> ProblemBuilder<V> {
 
         ProblemBuilder {
             constraints,
+            constraint_groups,
             variables,
             objective_terms,
             objective_contribs,
+            tolerance: self.tolerance,
         }
     }
 }
@@ -243,13 +523,74 @@ use std::collections::BTreeSet;
 
 #[derive(Debug, Clone)]
 pub struct Problem<V: VariableName, P: ProblemRepr<V> = DefaultRepr<V>> {
-    variables: BTreeSet<V>,
-    variables_vec: Vec<V>,
-    variables_lookup: BTreeMap<V, usize>,
+    // Sorted, so the index of a variable is found by binary search
+    variables: Vec<V>,
     constraints: BTreeSet<linexpr::Constraint<V>>,
+    constraint_groups: BTreeMap<linexpr::Constraint<V>, BTreeSet<String>>,
     pb_repr: P,
     objective_terms: Vec<ObjectiveTerm<V>>,
     objective_contribs: BTreeMap<V, f64>,
+    tolerance: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ProblemStats {
+    pub bool_variable_count: usize,
+    pub constraint_count: usize,
+    pub equality_count: usize,
+    pub inequality_count: usize,
+    pub objective_term_count: usize,
+    pub soft_constraint_count: usize,
+    pub objective_contrib_count: usize,
+    pub nonzero_count: usize,
+    pub density: f64,
+    pub coefficient_range: Option<(i32, i32)>,
+}
+
+impl std::fmt::Display for ProblemStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "variables: {} (boolean)", self.bool_variable_count)?;
+        writeln!(
+            f,
+            "constraints: {} ({} equalities, {} inequalities)",
+            self.constraint_count, self.equality_count, self.inequality_count
+        )?;
+        writeln!(
+            f,
+            "objective: {} terms ({} from soft constraints), {} linear contributions",
+            self.objective_term_count, self.soft_constraint_count, self.objective_contrib_count
+        )?;
+        writeln!(
+            f,
+            "nonzeros: {} (density {:.4}%)",
+            self.nonzero_count,
+            100. * self.density
+        )?;
+        match self.coefficient_range {
+            Some((min, max)) => write!(f, "coefficient range: [{}, {}]", min, max),
+            None => write!(f, "coefficient range: none"),
+        }
+    }
+}
+
+impl<V: VariableName + Serialize, P: ProblemRepr<V>> Serialize for Problem<V, P> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        self.clone().into_builder().serialize(serializer)
+    }
+}
+
+impl<'de, V: VariableName + Deserialize<'de>, P: ProblemRepr<V>> Deserialize<'de>
+    for Problem<V, P>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let builder = ProblemBuilder::deserialize(deserializer)?;
+        Ok(builder.build())
+    }
 }
 
 impl<V: VariableName, P: ProblemRepr<V>> std::fmt::Display for Problem<V, P> {
@@ -275,6 +616,9 @@ impl<V: VariableName, P: ProblemRepr<V>> std::fmt::Display for Problem<V, P> {
                 o.coef,
                 strings.join(", ")
             )?;
+            if let Some(desc) = &o.desc {
+                write!(f, " ({})", desc)?;
+            }
         }
 
         Ok(())
@@ -285,12 +629,24 @@ impl<V: VariableName, P: ProblemRepr<V>> Problem<V, P> {
     pub fn into_builder(self) -> ProblemBuilder<V> {
         ProblemBuilder {
             constraints: self.constraints,
-            variables: self.variables,
+            constraint_groups: self.constraint_groups,
+            variables: self.variables.into_iter().collect(),
             objective_terms: self.objective_terms,
             objective_contribs: self.objective_contribs,
+            tolerance: self.tolerance,
         }
     }
 
+    fn variable_index<T>(&self, var: &T) -> Option<usize>
+    where
+        V: std::borrow::Borrow<T>,
+        T: Ord + ?Sized,
+    {
+        self.variables
+            .binary_search_by(|v| v.borrow().cmp(var))
+            .ok()
+    }
+
     pub fn default_config<'a>(&'a self) -> Config<'a, V, P> {
         self.config_from::<V, _>([])
             .expect("Valid variables as no variables are used")
@@ -307,9 +663,7 @@ impl<V: VariableName, P: ProblemRepr<V>> Problem<V, P> {
             let v = var.into();
 
             let num = self
-                .variables_lookup
-                .get(&v)
-                .copied()
+                .variable_index(&v)
                 .ok_or(Error::InvalidVariable(v.clone()))?;
 
             vars_repr.insert(num, if value { 1 } else { 0 });
@@ -326,7 +680,281 @@ impl<V: VariableName, P: ProblemRepr<V>> Problem<V, P> {
         &self.constraints
     }
 
-    pub fn get_variables(&self) -> &BTreeSet<V> {
+    pub fn stats(&self) -> ProblemStats {
+        let mut equality_count = 0;
+        let mut inequality_count = 0;
+        let mut nonzero_count = 0;
+        let mut coefficient_range: Option<(i32, i32)> = None;
+
+        for constraint in &self.constraints {
+            match constraint.get_sign() {
+                linexpr::Sign::Equals => equality_count += 1,
+                linexpr::Sign::LessThan => inequality_count += 1,
+            }
+
+            for coef in constraint.coefs().values() {
+                if *coef == 0 {
+                    continue;
+                }
+                nonzero_count += 1;
+
+                let abs = coef.abs();
+                coefficient_range = Some(match coefficient_range {
+                    Some((min, max)) => (min.min(abs), max.max(abs)),
+                    None => (abs, abs),
+                });
+            }
+        }
+
+        let cell_count = self.variables.len() * self.constraints.len();
+        let density = if cell_count == 0 {
+            0.
+        } else {
+            nonzero_count as f64 / cell_count as f64
+        };
+
+        ProblemStats {
+            bool_variable_count: self.variables.len(),
+            constraint_count: self.constraints.len(),
+            equality_count,
+            inequality_count,
+            objective_term_count: self.objective_terms.len(),
+            soft_constraint_count: self
+                .objective_terms
+                .iter()
+                .filter(|obj_term| obj_term.desc.is_some())
+                .count(),
+            objective_contrib_count: self.objective_contribs.len(),
+            nonzero_count,
+            density,
+            coefficient_range,
+        }
+    }
+
+    pub fn get_constraint_groups(&self) -> &BTreeMap<linexpr::Constraint<V>, BTreeSet<String>> {
+        &self.constraint_groups
+    }
+
+    pub fn get_groups(&self) -> BTreeSet<String> {
+        self.constraint_groups.values().flatten().cloned().collect()
+    }
+
+    pub fn get_soft_constraint_descs(&self) -> BTreeSet<String> {
+        self.objective_terms
+            .iter()
+            .filter_map(|obj_term| obj_term.desc.clone())
+            .collect()
+    }
+
+    fn subproblem_status<S: solvers::FeasabilitySolver<V, P>>(
+        &self,
+        solver: &S,
+        options: &solvers::SolverOptions,
+        constraints: &BTreeSet<linexpr::Constraint<V>>,
+    ) -> solvers::SolveStatus {
+        let sub_problem = ProblemBuilder {
+            constraints: constraints.clone(),
+            constraint_groups: BTreeMap::new(),
+            variables: self.variables.iter().cloned().collect(),
+            objective_terms: Vec::new(),
+            objective_contribs: BTreeMap::new(),
+            tolerance: self.tolerance,
+        }
+        .build::<P>();
+
+        let config = sub_problem.default_config();
+        solver
+            .solve_with_stats(&config, false, options)
+            .stats
+            .status
+    }
+
+    pub fn compute_iis<S: solvers::FeasabilitySolver<V, P>>(
+        &self,
+        solver: &S,
+        options: &solvers::SolverOptions,
+    ) -> Option<BTreeSet<linexpr::Constraint<V>>> {
+        let infeasible = solvers::SolveStatus::Infeasible;
+        if self.subproblem_status(solver, options, &self.constraints) != infeasible {
+            return None;
+        }
+
+        // Deletion filter: drop every constraint that is not needed
+        // for the remaining system to stay infeasible. A sub-solve that
+        // times out proves nothing so the constraint is kept.
+        let mut iis = self.constraints.clone();
+        for constraint in &self.constraints {
+            iis.remove(constraint);
+            if self.subproblem_status(solver, options, &iis) != infeasible {
+                iis.insert(constraint.clone());
+            }
+        }
+
+        Some(iis)
+    }
+
+    fn elastic_expr(expr: &linexpr::Expr<V>) -> linexpr::Expr<ElasticVariable<V>> {
+        let mut output = linexpr::Expr::constant(expr.get_constant());
+        for (var, coef) in expr.coefs() {
+            output = output + *coef * linexpr::Expr::var(ElasticVariable::Original(var.clone()));
+        }
+        output
+    }
+
+    // Constraints in relaxable groups are deactivated (big-M) when the group
+    // variable is set. Minimizing the cost of the set group variables gives
+    // the cheapest set of groups to relax.
+    pub fn elastic_problem(
+        &self,
+        relaxable_groups: &BTreeMap<String, f64>,
+    ) -> ProblemBuilder<ElasticVariable<V>> {
+        let mut builder = ProblemBuilder::new()
+            .add_bool_variables(
+                self.variables
+                    .iter()
+                    .map(|var| ElasticVariable::Original(var.clone())),
+            )
+            .expect("Variables should not be duplicated")
+            .add_bool_variables(
+                relaxable_groups
+                    .keys()
+                    .map(|group| ElasticVariable::Relax(group.clone())),
+            )
+            .expect("Variables should not be duplicated")
+            .set_objective_contribs(
+                relaxable_groups
+                    .iter()
+                    .map(|(group, cost)| (ElasticVariable::Relax(group.clone()), *cost)),
+            )
+            .expect("Variables should be valid")
+            .with_tolerance(self.tolerance)
+            .expect("Tolerance should be valid");
+
+        for constraint in &self.constraints {
+            // Like disable_groups, a constraint is only switched off
+            // when all of its groups are relaxed
+            let relaxable = self.constraint_groups.get(constraint).filter(|groups| {
+                !groups.is_empty()
+                    && groups
+                        .iter()
+                        .all(|group| relaxable_groups.contains_key(group))
+            });
+            let relax_var = match relaxable {
+                None => None,
+                Some(groups) if groups.len() == 1 => {
+                    let group = groups.first().expect("Group set should not be empty");
+                    Some(ElasticVariable::Relax(group.clone()))
+                }
+                Some(groups) => {
+                    let relax_all = ElasticVariable::RelaxAll(groups.clone());
+                    if !builder.get_variables().contains(&relax_all) {
+                        builder = builder
+                            .add_bool_variable(relax_all.clone())
+                            .expect("Variables should not be duplicated")
+                            .add_constraints(groups.iter().map(|group| {
+                                linexpr::Expr::var(relax_all.clone())
+                                    .leq(&linexpr::Expr::var(ElasticVariable::Relax(group.clone())))
+                            }))
+                            .expect("Variables should be valid");
+                    }
+                    Some(relax_all)
+                }
+            };
+
+            builder = builder
+                .add_constraints(Self::elastic_constraint(constraint, relax_var))
+                .expect("Variables should be valid");
+        }
+
+        builder
+    }
+
+    // Big-M version of a constraint: it is switched off when relax_var is set
+    fn elastic_constraint(
+        constraint: &linexpr::Constraint<V>,
+        relax_var: Option<ElasticVariable<V>>,
+    ) -> Vec<linexpr::Constraint<ElasticVariable<V>>> {
+        let expr = Self::elastic_expr(constraint.get_lhs());
+        let relax = |big_m: i32| match &relax_var {
+            Some(var) => big_m * linexpr::Expr::var(var.clone()),
+            None => linexpr::Expr::constant(0),
+        };
+
+        match constraint.get_sign() {
+            linexpr::Sign::LessThan => {
+                vec![(&expr - &relax(expr.max_value().max(0))).leq(&linexpr::Expr::constant(0))]
+            }
+            linexpr::Sign::Equals => vec![
+                (&expr - &relax(expr.max_value().max(0))).leq(&linexpr::Expr::constant(0)),
+                (&expr + &relax((-expr.min_value()).max(0))).geq(&linexpr::Expr::constant(0)),
+            ],
+        }
+    }
+
+    // Ranked list of (at most max_suggestions) minimal sets of groups whose
+    // relaxation makes the problem feasable. The list is empty if the problem is
+    // already feasable or if it stays infeasable even with every group relaxed.
+    pub fn suggest_relaxations<
+        S: solvers::FeasabilitySolver<ElasticVariable<V>, DefaultRepr<ElasticVariable<V>>>,
+    >(
+        &self,
+        solver: &S,
+        relaxable_groups: &BTreeMap<String, f64>,
+        max_suggestions: usize,
+        options: &solvers::SolverOptions,
+    ) -> Vec<RelaxationSuggestion> {
+        let mut builder = self.elastic_problem(relaxable_groups);
+
+        let mut suggestions = Vec::new();
+        while suggestions.len() < max_suggestions {
+            let elastic_problem = builder.clone().build::<DefaultRepr<ElasticVariable<V>>>();
+            let Some(config) = solver.solve(&elastic_problem.default_config(), true, options)
+            else {
+                break;
+            };
+
+            let groups: BTreeSet<String> = relaxable_groups
+                .keys()
+                .filter(|group| {
+                    config
+                        .get_bool(&ElasticVariable::Relax((*group).clone()))
+                        .expect("Variable should be valid")
+                })
+                .cloned()
+                .collect();
+            if groups.is_empty() {
+                break;
+            }
+
+            // Forbid this set (and its supersets) to get the next suggestion
+            let exclusion = groups
+                .iter()
+                .fold(linexpr::Expr::constant(0), |acc, group| {
+                    acc + linexpr::Expr::var(ElasticVariable::Relax(group.clone()))
+                })
+                .leq(&linexpr::Expr::constant(
+                    i32::try_from(groups.len()).expect("Group count should fit in i32") - 1,
+                ));
+            builder = builder
+                .add_constraint(exclusion)
+                .expect("Variables should be valid");
+
+            let cost = groups.iter().map(|group| relaxable_groups[group]).sum();
+            suggestions.push(RelaxationSuggestion { groups, cost });
+        }
+
+        suggestions
+    }
+
+    pub fn with_groups_disabled<T, I>(&self, groups: I) -> Problem<V, P>
+    where
+        T: Into<String>,
+        I: IntoIterator<Item = T>,
+    {
+        self.clone().into_builder().disable_groups(groups).build()
+    }
+
+    pub fn get_variables(&self) -> &[V] {
         &self.variables
     }
 
@@ -337,6 +965,10 @@ impl<V: VariableName, P: ProblemRepr<V>> Problem<V, P> {
     pub fn get_objective_contribs(&self) -> &BTreeMap<V, f64> {
         &self.objective_contribs
     }
+
+    pub fn get_tolerance(&self) -> f64 {
+        self.tolerance
+    }
 }
 
 use std::collections::BTreeMap;
@@ -384,16 +1016,15 @@ impl<'a, V: VariableName, P: ProblemRepr<V>> Config<'a, V, P> {
     {
         let i = self
             .problem
-            .variables_lookup
-            .get(var)
+            .variable_index(var)
             .ok_or(Error::InvalidVariable(var.into()))?;
-        Ok(unsafe { self.cfg_repr.get_unchecked(*i) == 1 })
+        Ok(unsafe { self.cfg_repr.get_unchecked(i) == 1 })
     }
 
     pub fn get_bool_vars(&self) -> BTreeMap<V, bool> {
         let mut output = BTreeMap::new();
-        for (var, i) in &self.problem.variables_lookup {
-            let is_true = unsafe { self.cfg_repr.get_unchecked(*i) == 1 };
+        for (i, var) in self.problem.variables.iter().enumerate() {
+            let is_true = unsafe { self.cfg_repr.get_unchecked(i) == 1 };
             output.insert(var.clone(), is_true);
         }
         output
@@ -407,20 +1038,22 @@ impl<'a, V: VariableName, P: ProblemRepr<V>> Config<'a, V, P> {
     {
         let i = self
             .problem
-            .variables_lookup
-            .get(var)
+            .variable_index(var)
             .ok_or(Error::InvalidVariable(var.into()))?;
         unsafe {
-            self.cfg_repr.set_unchecked(*i, if val { 1 } else { 0 });
+            self.cfg_repr.set_unchecked(i, if val { 1 } else { 0 });
         }
-        self.invalidate_precomputation(*i);
+        self.invalidate_precomputation(i);
         Ok(())
     }
 
     pub fn compute_lhs(&self) -> BTreeMap<linexpr::Constraint<V>, i32> {
         let precomputation = self.get_precomputation();
-        self.cfg_repr
-            .compute_lhs(&self.problem.pb_repr, &*precomputation)
+        let lhs = self
+            .cfg_repr
+            .compute_lhs(&self.problem.pb_repr, &*precomputation);
+
+        self.problem.constraints.iter().cloned().zip(lhs).collect()
     }
 
     pub fn compute_lhs_sq_norm2(&self) -> f64 {
@@ -432,6 +1065,63 @@ impl<'a, V: VariableName, P: ProblemRepr<V>> Config<'a, V, P> {
         tot
     }
 
+    pub fn constraint_violations(&self) -> BTreeMap<linexpr::Constraint<V>, i32> {
+        self.compute_lhs()
+            .into_iter()
+            .filter_map(|(constraint, lhs)| {
+                let violation = match constraint.get_sign() {
+                    linexpr::Sign::Equals => lhs.abs(),
+                    linexpr::Sign::LessThan => lhs.max(0),
+                };
+                (violation != 0).then_some((constraint, violation))
+            })
+            .collect()
+    }
+
+    pub fn objective_value(&self) -> f64 {
+        let bool_vars = self.get_bool_vars();
+
+        let mut total = 0.;
+        for (var, coef) in &self.problem.objective_contribs {
+            if bool_vars[var] {
+                total += coef;
+            }
+        }
+
+        for obj_term in &self.problem.objective_terms {
+            total += Self::objective_term_value(obj_term, &bool_vars);
+        }
+
+        total
+    }
+
+    // Penalty paid for each named soft constraint (summed over its terms)
+    pub fn soft_constraint_penalties(&self) -> BTreeMap<String, f64> {
+        let bool_vars = self.get_bool_vars();
+
+        let mut output = BTreeMap::new();
+        for obj_term in &self.problem.objective_terms {
+            let Some(desc) = &obj_term.desc else {
+                continue;
+            };
+            *output.entry(desc.clone()).or_default() +=
+                Self::objective_term_value(obj_term, &bool_vars);
+        }
+        output
+    }
+
+    fn objective_term_value(obj_term: &ObjectiveTerm<V>, bool_vars: &BTreeMap<V, bool>) -> f64 {
+        let max_value = obj_term
+            .exprs
+            .iter()
+            .map(|expr| expr.eval(bool_vars).expect("Variables should be valid"))
+            .max();
+        match max_value {
+            Some(value) => obj_term.coef * (value as f64),
+            None => 0.,
+        }
+    }
+
     pub fn is_feasable(&self) -> bool {
         let precomputation = self.get_precomputation();
         self.cfg_repr
@@ -502,11 +1192,32 @@ impl<'a, V: VariableName, P: ProblemRepr<V>> Config<'a, V, P> {
     }
 }
 
+// A config can't be deserialized on its own as it refers to a problem.
+// Use Problem::config_from on the deserialized list instead.
+impl<'a, V: VariableName + Serialize, P: ProblemRepr<V>> Serialize for Config<'a, V, P> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let bool_vars: Vec<_> = self.get_bool_vars().into_iter().collect();
+        bool_vars.serialize(serializer)
+    }
+}
+
+impl<'a, V: VariableName + Serialize, P: ProblemRepr<V>> Serialize for FeasableConfig<'a, V, P> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        self.inner().serialize(serializer)
+    }
+}
+
 impl<'a, V: VariableName, P: ProblemRepr<V>> std::fmt::Display for Config<'a, V, P> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let variables_iter = self
             .problem
-            .variables_vec
+            .variables
             .iter()
             .enumerate()
             .map(|(i, var)| (var.clone(), unsafe { self.cfg_repr.get_unchecked(i) }));
@@ -3,6 +3,8 @@ mod tests;
 
 use std::collections::BTreeMap;
 
+use serde::{Deserialize, Serialize};
+
 pub trait VariableName:
     std::fmt::Debug
     + std::fmt::Display
@@ -32,20 +34,51 @@ impl<
 {
 }
 
-#[derive(Debug, Clone, Default, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    from = "ExprData<V>",
+    into = "ExprData<V>",
+    bound(serialize = "V: Serialize", deserialize = "V: Deserialize<'de>")
+)]
 pub struct Expr<V: VariableName> {
     coefs: BTreeMap<V, i32>,
     constant: i32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+// Variables are not necessarily strings so coefs can't be serialized as a map
+#[derive(Serialize, Deserialize)]
+struct ExprData<V> {
+    coefs: Vec<(V, i32)>,
+    constant: i32,
+}
+
+impl<V: VariableName> From<Expr<V>> for ExprData<V> {
+    fn from(value: Expr<V>) -> Self {
+        ExprData {
+            coefs: value.coefs.into_iter().collect(),
+            constant: value.constant,
+        }
+    }
+}
+
+impl<V: VariableName> From<ExprData<V>> for Expr<V> {
+    fn from(value: ExprData<V>) -> Self {
+        Expr {
+            coefs: value.coefs.into_iter().collect(),
+            constant: value.constant,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
 pub enum Sign {
     Equals,
     #[default]
     LessThan,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(bound(serialize = "V: Serialize", deserialize = "V: Deserialize<'de>"))]
 pub struct Constraint<V: VariableName> {
     sign: Sign,
     expr: Expr<V>,
@@ -138,6 +171,28 @@ impl<V: VariableName> Expr<V> {
 
         Expr { coefs, constant }
     }
+
+    pub fn max_value(&self) -> i32 {
+        self.constant + self.coefs.values().filter(|c| **c > 0).sum::<i32>()
+    }
+
+    pub fn min_value(&self) -> i32 {
+        self.constant + self.coefs.values().filter(|c| **c < 0).sum::<i32>()
+    }
+
+    // Evaluation is done in i64 so that it stays exact even if
+    // the i32 representation would overflow
+    pub fn eval(&self, vars: &BTreeMap<V, bool>) -> Option<i64> {
+        let mut total = i64::from(self.constant);
+
+        for (v, c) in &self.coefs {
+            if *vars.get(v)? {
+                total += i64::from(*c);
+            }
+        }
+
+        Some(total)
+    }
 }
 
 impl<V: VariableName> Constraint<V> {
@@ -184,6 +239,74 @@ impl<V: VariableName> Constraint<V> {
         output.reduce(vars);
         output
     }
+
+    pub fn implied_by<T: Into<V>>(&self, var: T) -> Vec<Constraint<V>> {
+        let var = var.into();
+
+        // Big-M linearization: when var is 0, relax the constraint
+        // by the largest value the expression can take
+        let relax = |expr: &Expr<V>| {
+            let big_m = expr.max_value().max(0);
+            let relaxed = expr + big_m * Expr::var(var.clone()) - big_m;
+            relaxed.leq(&Expr::constant(0))
+        };
+
+        match self.sign {
+            Sign::LessThan => vec![relax(&self.expr)],
+            Sign::Equals => vec![relax(&self.expr), relax(&-&self.expr)],
+        }
+    }
+
+    pub fn is_satisfied(&self, vars: &BTreeMap<V, bool>) -> Option<bool> {
+        let value = self.expr.eval(vars)?;
+
+        Some(match self.sign {
+            Sign::Equals => value == 0,
+            Sign::LessThan => value <= 0,
+        })
+    }
+}
+
+pub fn implies<V: VariableName, T: Into<V>, U: Into<V>>(
+    premise: T,
+    conclusion: U,
+) -> Constraint<V> {
+    Expr::var(premise).leq(&Expr::var(conclusion))
+}
+
+pub fn not<V: VariableName, T: Into<V>>(var: T) -> Expr<V> {
+    1 - Expr::var(var)
+}
+
+pub fn and<V: VariableName, T: Into<V>, U: Into<V>, I: IntoIterator<Item = U>>(
+    result: T,
+    operands: I,
+) -> Vec<Constraint<V>> {
+    let result = Expr::var(result);
+    let operands: Vec<_> = operands.into_iter().map(|x| Expr::var(x)).collect();
+
+    let mut output: Vec<_> = operands.iter().map(|x| result.leq(x)).collect();
+
+    let count = i32::try_from(operands.len()).expect("Operand count should fit in i32");
+    let sum = operands.iter().fold(Expr::constant(0), |acc, x| acc + x);
+    output.push(result.geq(&(sum - (count - 1))));
+
+    output
+}
+
+pub fn or<V: VariableName, T: Into<V>, U: Into<V>, I: IntoIterator<Item = U>>(
+    result: T,
+    operands: I,
+) -> Vec<Constraint<V>> {
+    let result = Expr::var(result);
+    let operands: Vec<_> = operands.into_iter().map(|x| Expr::var(x)).collect();
+
+    let mut output: Vec<_> = operands.iter().map(|x| result.geq(x)).collect();
+
+    let sum = operands.iter().fold(Expr::constant(0), |acc, x| acc + x);
+    output.push(result.leq(&sum));
+
+    output
 }
 
 impl<V: VariableName> std::fmt::Display for Expr<V> {
@@ -135,3 +135,115 @@ fn constraint_reduced() {
 
     assert_eq!(constraint1.reduced(&vars), constraint2);
 }
+
+#[test]
+fn expr_eval() {
+    let expr = 2 * Expr::<String>::var("a") - 3 * Expr::<String>::var("b") + 4;
+
+    let vars = BTreeMap::from([("a".into(), true), ("b".into(), true)]);
+    assert_eq!(expr.eval(&vars), Some(3));
+
+    let vars = BTreeMap::from([("a".into(), false), ("b".into(), true)]);
+    assert_eq!(expr.eval(&vars), Some(1));
+
+    let vars = BTreeMap::from([("a".into(), true)]);
+    assert_eq!(expr.eval(&vars), None);
+
+    let expr = i32::MAX * Expr::<String>::var("a") + i32::MAX * Expr::<String>::var("b");
+    let vars = BTreeMap::from([("a".into(), true), ("b".into(), true)]);
+    assert_eq!(expr.eval(&vars), Some(2 * i64::from(i32::MAX)));
+}
+
+#[test]
+fn constraint_is_satisfied() {
+    let a = Expr::<String>::var("a");
+    let b = Expr::<String>::var("b");
+
+    let leq = (&a + &b).leq(&Expr::constant(1));
+    let eq = (&a + &b).eq(&Expr::constant(1));
+
+    let vars = BTreeMap::from([("a".into(), true), ("b".into(), false)]);
+    assert_eq!(leq.is_satisfied(&vars), Some(true));
+    assert_eq!(eq.is_satisfied(&vars), Some(true));
+
+    let vars = BTreeMap::from([("a".into(), false), ("b".into(), false)]);
+    assert_eq!(leq.is_satisfied(&vars), Some(true));
+    assert_eq!(eq.is_satisfied(&vars), Some(false));
+
+    let vars = BTreeMap::from([("a".into(), true), ("b".into(), true)]);
+    assert_eq!(leq.is_satisfied(&vars), Some(false));
+    assert_eq!(eq.is_satisfied(&vars), Some(false));
+
+    let vars = BTreeMap::from([("a".into(), true)]);
+    assert_eq!(leq.is_satisfied(&vars), None);
+}
+
+fn all_assignments(vars: &[&str]) -> Vec<BTreeMap<String, bool>> {
+    (0..(1usize << vars.len()))
+        .map(|mask| {
+            vars.iter()
+                .enumerate()
+                .map(|(i, v)| (String::from(*v), (mask >> i) & 1 == 1))
+                .collect()
+        })
+        .collect()
+}
+
+fn all_satisfied(constraints: &[Constraint<String>], vars: &BTreeMap<String, bool>) -> bool {
+    constraints
+        .iter()
+        .all(|c| c.is_satisfied(vars).expect("All variables should be given"))
+}
+
+#[test]
+fn expr_min_max_value() {
+    let expr = 2 * Expr::<String>::var("a") - 3 * Expr::<String>::var("b") + 4;
+
+    assert_eq!(expr.max_value(), 6);
+    assert_eq!(expr.min_value(), 1);
+}
+
+#[test]
+fn constraint_implied_by() {
+    let a = Expr::<String>::var("a");
+    let b = Expr::<String>::var("b");
+
+    let leq = (&a + &b).leq(&Expr::constant(1));
+    let eq = (&a - &b).eq(&Expr::constant(0));
+
+    let leq_implied = leq.implied_by("x");
+    let eq_implied = eq.implied_by("x");
+
+    for vars in all_assignments(&["a", "b", "x"]) {
+        if vars["x"] {
+            assert_eq!(
+                all_satisfied(&leq_implied, &vars),
+                leq.is_satisfied(&vars).unwrap()
+            );
+            assert_eq!(
+                all_satisfied(&eq_implied, &vars),
+                eq.is_satisfied(&vars).unwrap()
+            );
+        } else {
+            assert!(all_satisfied(&leq_implied, &vars));
+            assert!(all_satisfied(&eq_implied, &vars));
+        }
+    }
+}
+
+#[test]
+fn boolean_helpers() {
+    let implies_constraint = implies::<String, _, _>("a", "b");
+    let and_constraints = and::<String, _, _, _>("r", ["a", "b"]);
+    let or_constraints = or::<String, _, _, _>("r", ["a", "b"]);
+    let not_expr = not::<String, _>("a");
+
+    for vars in all_assignments(&["a", "b", "r"]) {
+        let (a, b, r) = (vars["a"], vars["b"], vars["r"]);
+
+        assert_eq!(implies_constraint.is_satisfied(&vars), Some(!a || b));
+        assert_eq!(all_satisfied(&and_constraints, &vars), r == (a && b));
+        assert_eq!(all_satisfied(&or_constraints, &vars), r == (a || b));
+        assert_eq!(not_expr.eval(&vars), Some(if a { 0 } else { 1 }));
+    }
+}
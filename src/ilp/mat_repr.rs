@@ -7,7 +7,12 @@ use std::collections::{BTreeMap, BTreeSet};
 pub trait ProblemRepr<V: VariableName>: Clone + std::fmt::Debug + Send + Sync {
     type Config: ConfigRepr<V, Problem = Self>;
 
-    fn new(variables_vec: &Vec<V>, constraints: &BTreeSet<linexpr::Constraint<V>>) -> Self;
+    // Variables and constraints are interned: the representation only refers
+    // to them by their index (in iteration order for constraints)
+    fn new(
+        variables_lookup: &BTreeMap<V, usize>,
+        constraints: &BTreeSet<linexpr::Constraint<V>>,
+    ) -> Self;
 
     fn config_from(&self, vars: &BTreeMap<usize, i32>) -> Self::Config;
 }
@@ -29,7 +34,7 @@ pub trait ConfigRepr<V: VariableName>:
         &self,
         problem: &Self::Problem,
         precomputation: &Self::Precomputation,
-    ) -> BTreeMap<linexpr::Constraint<V>, i32>;
+    ) -> Vec<i32>;
     fn is_feasable(&self, problem: &Self::Problem, precomputation: &Self::Precomputation) -> bool;
 
     unsafe fn get_unchecked(&self, i: usize) -> i32;
@@ -13,8 +13,9 @@ pub struct NdProblem<V: VariableName> {
     leq_constants: Array1<i32>,
     eq_mat: Array2<i32>,
     eq_constants: Array1<i32>,
-    constraints_map: BTreeMap<linexpr::Constraint<V>, ConstraintRef>,
+    constraints_by_index: Vec<ConstraintRef>,
     constraints_ref: Vec<BTreeSet<ConstraintRef>>,
+    _phantom: std::marker::PhantomData<V>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -26,14 +27,11 @@ enum ConstraintRef {
 impl<V: VariableName> super::ProblemRepr<V> for NdProblem<V> {
     type Config = NdConfig<V>;
 
-    fn new(variables_vec: &Vec<V>, constraints: &BTreeSet<linexpr::Constraint<V>>) -> NdProblem<V> {
-        let p = variables_vec.len();
-
-        let variable_map: BTreeMap<_, _> = variables_vec
-            .iter()
-            .enumerate()
-            .map(|(i, v)| (v.clone(), i))
-            .collect();
+    fn new(
+        variables_lookup: &BTreeMap<V, usize>,
+        constraints: &BTreeSet<linexpr::Constraint<V>>,
+    ) -> NdProblem<V> {
+        let p = variables_lookup.len();
 
         let mut leq_count = 0usize;
         let mut eq_count = 0usize;
@@ -55,7 +53,7 @@ impl<V: VariableName> super::ProblemRepr<V> for NdProblem<V> {
         let mut leq_constants = Array1::zeros(leq_count);
         let mut eq_constants = Array1::zeros(eq_count);
 
-        let mut constraints_map = BTreeMap::new();
+        let mut constraints_by_index = Vec::with_capacity(constraints.len());
 
         let mut constraints_ref = vec![BTreeSet::new(); p];
 
@@ -66,23 +64,23 @@ impl<V: VariableName> super::ProblemRepr<V> for NdProblem<V> {
             match c.get_sign() {
                 linexpr::Sign::Equals => {
                     for (var, val) in c.coefs() {
-                        let j = variable_map[var];
+                        let j = variables_lookup[var];
                         eq_mat[(eq_index, j)] = *val;
 
                         constraints_ref[j].insert(ConstraintRef::Eq(eq_index));
                     }
-                    constraints_map.insert(c.clone(), ConstraintRef::Eq(eq_index));
+                    constraints_by_index.push(ConstraintRef::Eq(eq_index));
                     eq_constants[eq_index] = c.get_constant();
                     eq_index += 1;
                 }
                 linexpr::Sign::LessThan => {
                     for (var, val) in c.coefs() {
-                        let j = variable_map[var];
+                        let j = variables_lookup[var];
                         leq_mat[(leq_index, j)] = *val;
 
                         constraints_ref[j].insert(ConstraintRef::Leq(leq_index));
                     }
-                    constraints_map.insert(c.clone(), ConstraintRef::Leq(leq_index));
+                    constraints_by_index.push(ConstraintRef::Leq(leq_index));
                     leq_constants[leq_index] = c.get_constant();
                     leq_index += 1;
                 }
@@ -94,8 +92,9 @@ impl<V: VariableName> super::ProblemRepr<V> for NdProblem<V> {
             leq_constants,
             eq_mat,
             eq_constants,
-            constraints_map,
+            constraints_by_index,
             constraints_ref,
+            _phantom: std::marker::PhantomData,
         }
     }
 
@@ -207,20 +206,17 @@ impl<V: VariableName> super::ConfigRepr<V> for NdConfig<V> {
         &self,
         problem: &NdProblem<V>,
         precomputation: &Self::Precomputation,
-    ) -> BTreeMap<linexpr::Constraint<V>, i32> {
+    ) -> Vec<i32> {
         let (leq_column, eq_column) = precomputation;
 
-        let mut output = BTreeMap::new();
-
-        for (c, r) in &problem.constraints_map {
-            let val = match r {
+        problem
+            .constraints_by_index
+            .iter()
+            .map(|r| match r {
                 ConstraintRef::Eq(num) => eq_column[*num],
                 ConstraintRef::Leq(num) => leq_column[*num],
-            };
-            output.insert(c.clone(), val);
-        }
-
-        output
+            })
+            .collect()
     }
 
     fn is_feasable(&self, _problem: &NdProblem<V>, precomputation: &Self::Precomputation) -> bool {
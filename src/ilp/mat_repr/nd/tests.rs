@@ -353,13 +353,8 @@ fn compute_lhs() {
         .config_from([("a", true), ("b", true), ("c", true), ("d", true)])
         .unwrap();
 
-    let nd_problem = &pb.pb_repr;
-
-    use crate::ilp::mat_repr::ConfigRepr;
     assert_eq!(
-        config_0
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_0.cfg_repr.precompute(nd_problem)),
+        config_0.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -367,9 +362,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_1
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_1.cfg_repr.precompute(nd_problem)),
+        config_1.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -377,9 +370,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_2
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_2.cfg_repr.precompute(nd_problem)),
+        config_2.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -387,9 +378,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_3
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_3.cfg_repr.precompute(nd_problem)),
+        config_3.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -397,9 +386,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_4
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_4.cfg_repr.precompute(nd_problem)),
+        config_4.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -407,9 +394,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_5
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_5.cfg_repr.precompute(nd_problem)),
+        config_5.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -417,9 +402,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_6
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_6.cfg_repr.precompute(nd_problem)),
+        config_6.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -427,9 +410,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_7
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_7.cfg_repr.precompute(nd_problem)),
+        config_7.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -437,9 +418,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_8
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_8.cfg_repr.precompute(nd_problem)),
+        config_8.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -447,9 +426,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_9
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_9.cfg_repr.precompute(nd_problem)),
+        config_9.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -457,9 +434,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_a
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_a.cfg_repr.precompute(nd_problem)),
+        config_a.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -467,9 +442,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_b
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_b.cfg_repr.precompute(nd_problem)),
+        config_b.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -477,9 +450,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_c
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_c.cfg_repr.precompute(nd_problem)),
+        config_c.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -487,9 +458,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_d
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_d.cfg_repr.precompute(nd_problem)),
+        config_d.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -497,9 +466,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_e
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_e.cfg_repr.precompute(nd_problem)),
+        config_e.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -507,9 +474,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_f
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_f.cfg_repr.precompute(nd_problem)),
+        config_f.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -576,13 +541,8 @@ fn update_precomputation() {
     let mut config_f = config_e.clone();
     config_f.set_bool("a", true).unwrap(); // ["a","b","c","d"]
 
-    let nd_problem = &pb.pb_repr;
-
-    use crate::ilp::mat_repr::ConfigRepr;
     assert_eq!(
-        config_0
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_0.get_precomputation()),
+        config_0.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -591,9 +551,7 @@ fn update_precomputation() {
     );
     println!("{:?}", config_1.cfg_repr);
     assert_eq!(
-        config_1
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_1.get_precomputation()),
+        config_1.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -601,9 +559,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_2
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_2.get_precomputation()),
+        config_2.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -611,9 +567,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_3
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_3.get_precomputation()),
+        config_3.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -621,9 +575,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_4
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_4.get_precomputation()),
+        config_4.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -631,9 +583,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_5
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_5.get_precomputation()),
+        config_5.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -641,9 +591,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_6
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_6.get_precomputation()),
+        config_6.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -651,9 +599,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_7
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_7.get_precomputation()),
+        config_7.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -661,9 +607,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_8
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_8.get_precomputation()),
+        config_8.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -671,9 +615,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_9
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_9.get_precomputation()),
+        config_9.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -681,9 +623,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_a
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_a.get_precomputation()),
+        config_a.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -691,9 +631,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_b
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_b.get_precomputation()),
+        config_b.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -701,9 +639,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_c
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_c.get_precomputation()),
+        config_c.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -711,9 +647,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_d
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_d.get_precomputation()),
+        config_d.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -721,9 +655,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_e
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_e.get_precomputation()),
+        config_e.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -731,9 +663,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_f
-            .cfg_repr
-            .compute_lhs(nd_problem, &config_f.get_precomputation()),
+        config_f.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 1),
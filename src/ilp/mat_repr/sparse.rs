@@ -13,9 +13,10 @@ pub struct SprsProblem<V: VariableName> {
     leq_constants: CsVec<i32>,
     eq_mat: CsMat<i32>,
     eq_constants: CsVec<i32>,
-    leq_constraints_vec: Vec<linexpr::Constraint<V>>,
-    eq_constraints_vec: Vec<linexpr::Constraint<V>>,
+    leq_constraint_indices: Vec<usize>,
+    eq_constraint_indices: Vec<usize>,
     constraints_ref: Vec<BTreeSet<ConstraintRef>>,
+    _phantom: std::marker::PhantomData<V>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -28,16 +29,10 @@ impl<V: VariableName> super::ProblemRepr<V> for SprsProblem<V> {
     type Config = SprsConfig<V>;
 
     fn new(
-        variables_vec: &Vec<V>,
+        variables_lookup: &BTreeMap<V, usize>,
         constraints: &BTreeSet<linexpr::Constraint<V>>,
     ) -> SprsProblem<V> {
-        let p = variables_vec.len();
-
-        let variable_map: BTreeMap<_, _> = variables_vec
-            .iter()
-            .enumerate()
-            .map(|(i, v)| (v.clone(), i))
-            .collect();
+        let p = variables_lookup.len();
 
         let mut leq_count = 0usize;
         let mut eq_count = 0usize;
@@ -63,24 +58,24 @@ impl<V: VariableName> super::ProblemRepr<V> for SprsProblem<V> {
         let mut eq_constants_indices = Vec::new();
         let mut eq_constants_data = Vec::new();
 
-        let mut leq_constraints_vec = Vec::with_capacity(leq_count);
-        let mut eq_constraints_vec = Vec::with_capacity(eq_count);
+        let mut leq_constraint_indices = Vec::with_capacity(leq_count);
+        let mut eq_constraint_indices = Vec::with_capacity(eq_count);
 
         let mut constraints_ref = vec![BTreeSet::new(); p];
 
         let mut leq_index = 0usize;
         let mut eq_index = 0usize;
 
-        for c in constraints {
+        for (index, c) in constraints.iter().enumerate() {
             match c.get_sign() {
                 linexpr::Sign::Equals => {
                     for (var, val) in c.coefs() {
-                        let j = variable_map[var];
+                        let j = variables_lookup[var];
                         eq_mat_tri.add_triplet(eq_index, j, *val);
 
                         constraints_ref[j].insert(ConstraintRef::Eq(eq_index));
                     }
-                    eq_constraints_vec.push(c.clone());
+                    eq_constraint_indices.push(index);
 
                     let constant = c.get_constant();
                     if constant != 0 {
@@ -92,12 +87,12 @@ impl<V: VariableName> super::ProblemRepr<V> for SprsProblem<V> {
                 }
                 linexpr::Sign::LessThan => {
                     for (var, val) in c.coefs() {
-                        let j = variable_map[var];
+                        let j = variables_lookup[var];
                         leq_mat_tri.add_triplet(leq_index, j, *val);
 
                         constraints_ref[j].insert(ConstraintRef::Leq(leq_index));
                     }
-                    leq_constraints_vec.push(c.clone());
+                    leq_constraint_indices.push(index);
 
                     let constant = c.get_constant();
                     if constant != 0 {
@@ -120,9 +115,10 @@ impl<V: VariableName> super::ProblemRepr<V> for SprsProblem<V> {
             leq_constants,
             eq_mat,
             eq_constants,
-            leq_constraints_vec,
-            eq_constraints_vec,
+            leq_constraint_indices,
+            eq_constraint_indices,
             constraints_ref,
+            _phantom: std::marker::PhantomData,
         }
     }
 
@@ -242,35 +238,18 @@ impl<V: VariableName> super::ConfigRepr<V> for SprsConfig<V> {
         &self,
         problem: &SprsProblem<V>,
         precomputation: &Self::Precomputation,
-    ) -> BTreeMap<linexpr::Constraint<V>, i32> {
+    ) -> Vec<i32> {
         let (leq_column, eq_column) = precomputation;
 
-        let mut output = BTreeMap::new();
+        let constraint_count =
+            problem.leq_constraint_indices.len() + problem.eq_constraint_indices.len();
+        let mut output = vec![0; constraint_count];
 
-        let mut prev = 0usize;
         for (i, v) in leq_column.iter() {
-            for j in prev..i {
-                output.insert(problem.leq_constraints_vec[j].clone(), 0);
-            }
-            output.insert(problem.leq_constraints_vec[i].clone(), *v);
-            prev = i + 1;
-        }
-        let leq_count = problem.leq_constraints_vec.len();
-        for j in prev..leq_count {
-            output.insert(problem.leq_constraints_vec[j].clone(), 0);
+            output[problem.leq_constraint_indices[i]] = *v;
         }
-
-        let mut prev = 0usize;
         for (i, v) in eq_column.iter() {
-            for j in prev..i {
-                output.insert(problem.eq_constraints_vec[j].clone(), 0);
-            }
-            output.insert(problem.eq_constraints_vec[i].clone(), *v);
-            prev = i + 1;
-        }
-        let eq_count = problem.eq_constraints_vec.len();
-        for j in prev..eq_count {
-            output.insert(problem.eq_constraints_vec[j].clone(), 0);
+            output[problem.eq_constraint_indices[i]] = *v;
         }
 
         output
@@ -369,13 +369,8 @@ fn compute_lhs() {
         .config_from([("a", true), ("b", true), ("c", true), ("d", true)])
         .unwrap();
 
-    let sprs_problem = &pb.pb_repr;
-
-    use crate::ilp::mat_repr::ConfigRepr;
     assert_eq!(
-        config_0
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_0.cfg_repr.precompute(sprs_problem)),
+        config_0.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -384,9 +379,7 @@ fn compute_lhs() {
     );
     println!("{:?}", config_1.cfg_repr);
     assert_eq!(
-        config_1
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_1.cfg_repr.precompute(sprs_problem)),
+        config_1.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -394,9 +387,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_2
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_2.cfg_repr.precompute(sprs_problem)),
+        config_2.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -404,9 +395,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_3
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_3.cfg_repr.precompute(sprs_problem)),
+        config_3.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -414,9 +403,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_4
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_4.cfg_repr.precompute(sprs_problem)),
+        config_4.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -424,9 +411,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_5
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_5.cfg_repr.precompute(sprs_problem)),
+        config_5.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -434,9 +419,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_6
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_6.cfg_repr.precompute(sprs_problem)),
+        config_6.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -444,9 +427,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_7
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_7.cfg_repr.precompute(sprs_problem)),
+        config_7.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -454,9 +435,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_8
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_8.cfg_repr.precompute(sprs_problem)),
+        config_8.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -464,9 +443,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_9
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_9.cfg_repr.precompute(sprs_problem)),
+        config_9.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -474,9 +451,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_a
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_a.cfg_repr.precompute(sprs_problem)),
+        config_a.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -484,9 +459,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_b
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_b.cfg_repr.precompute(sprs_problem)),
+        config_b.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -494,9 +467,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_c
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_c.cfg_repr.precompute(sprs_problem)),
+        config_c.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -504,9 +475,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_d
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_d.cfg_repr.precompute(sprs_problem)),
+        config_d.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -514,9 +483,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_e
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_e.cfg_repr.precompute(sprs_problem)),
+        config_e.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -524,9 +491,7 @@ fn compute_lhs() {
         ])
     );
     assert_eq!(
-        config_f
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_f.cfg_repr.precompute(sprs_problem)),
+        config_f.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -593,13 +558,8 @@ fn update_precomputation() {
     let mut config_f = config_e.clone();
     config_f.set_bool("a", true).unwrap(); // ["a","b","c","d"]
 
-    let sprs_problem = &pb.pb_repr;
-
-    use crate::ilp::mat_repr::ConfigRepr;
     assert_eq!(
-        config_0
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_0.get_precomputation()),
+        config_0.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -608,9 +568,7 @@ fn update_precomputation() {
     );
     println!("{:?}", config_1.cfg_repr);
     assert_eq!(
-        config_1
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_1.get_precomputation()),
+        config_1.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -618,9 +576,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_2
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_2.get_precomputation()),
+        config_2.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -628,9 +584,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_3
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_3.get_precomputation()),
+        config_3.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), -1),
@@ -638,9 +592,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_4
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_4.get_precomputation()),
+        config_4.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -648,9 +600,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_5
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_5.get_precomputation()),
+        config_5.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -658,9 +608,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_6
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_6.get_precomputation()),
+        config_6.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -668,9 +616,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_7
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_7.get_precomputation()),
+        config_7.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -678,9 +624,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_8
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_8.get_precomputation()),
+        config_8.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -688,9 +632,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_9
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_9.get_precomputation()),
+        config_9.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -698,9 +640,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_a
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_a.get_precomputation()),
+        config_a.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -708,9 +648,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_b
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_b.get_precomputation()),
+        config_b.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 0),
@@ -718,9 +656,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_c
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_c.get_precomputation()),
+        config_c.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), -1),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -728,9 +664,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_d
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_d.get_precomputation()),
+        config_d.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -738,9 +672,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_e
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_e.get_precomputation()),
+        config_e.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 0),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -748,9 +680,7 @@ fn update_precomputation() {
         ])
     );
     assert_eq!(
-        config_f
-            .cfg_repr
-            .compute_lhs(sprs_problem, &config_f.get_precomputation()),
+        config_f.compute_lhs(),
         BTreeMap::from([
             ((&a + &b).leq(&Expr::constant(1)), 1),
             ((&c + &d).leq(&Expr::constant(1)), 1),
@@ -36,3 +36,40 @@ impl RandomGen for DefaultRndGen {
         thread_rng.gen_range(range)
     }
 }
+
+// Reproducible generator: the state is shared between clones
+#[derive(Clone, Debug)]
+pub struct SeededRndGen {
+    rng: std::sync::Arc<std::sync::Mutex<rand::rngs::StdRng>>,
+}
+
+impl SeededRndGen {
+    pub fn new(seed: u64) -> Self {
+        use rand::SeedableRng;
+        SeededRndGen {
+            rng: std::sync::Arc::new(std::sync::Mutex::new(rand::rngs::StdRng::seed_from_u64(
+                seed,
+            ))),
+        }
+    }
+}
+
+impl RandomGen for SeededRndGen {
+    fn randbool(&self) -> bool {
+        use rand::Rng;
+        let mut rng = self.rng.lock().unwrap();
+        rng.gen_bool(0.5)
+    }
+
+    fn random(&self) -> f64 {
+        use rand::Rng;
+        let mut rng = self.rng.lock().unwrap();
+        rng.gen::<f64>()
+    }
+
+    fn rand_in_range(&self, range: std::ops::Range<usize>) -> usize {
+        use rand::Rng;
+        let mut rng = self.rng.lock().unwrap();
+        rng.gen_range(range)
+    }
+}
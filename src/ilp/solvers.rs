@@ -1,31 +1,354 @@
+pub mod benchmark;
 #[cfg(feature = "coin_cbc")]
 pub mod coin_cbc;
 #[cfg(feature = "highs")]
 pub mod highs;
+pub mod jobs;
+pub mod local_search;
+pub mod portfolio;
+pub mod repair;
 
 use super::{Config, FeasableConfig};
 
 use super::linexpr::VariableName;
 use super::mat_repr::ProblemRepr;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SolveStatus {
+    Optimal,
+    Feasible,
+    TimeLimitReached,
+    Cancelled,
+    Infeasible,
+    #[default]
+    Unknown,
+}
+
+// Fields are None when the backend does not report them.
+// The branch-and-bound node count is not available from any backend.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SolveStats {
+    pub status: SolveStatus,
+    pub objective_value: Option<f64>,
+    // Only reported by cbc: the HiGHS bindings do not give access to the MIP dual bound
+    pub best_bound: Option<f64>,
+    // Simplex iterations (only reported by cbc)
+    pub iteration_count: Option<u64>,
+    pub solve_time: std::time::Duration,
+}
+
+impl SolveStats {
+    pub fn gap(&self) -> Option<f64> {
+        let objective_value = self.objective_value?;
+        let best_bound = self.best_bound?;
+
+        let diff = (objective_value - best_bound).abs();
+        if diff == 0. {
+            return Some(0.);
+        }
+        if objective_value == 0. {
+            return Some(f64::INFINITY);
+        }
+
+        Some(diff / objective_value.abs())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverOptions {
+    pub time_limit_in_seconds: Option<u32>,
+    pub relative_gap: Option<f64>,
+    pub absolute_gap: Option<f64>,
+    pub threads: Option<u32>,
+    pub random_seed: Option<u32>,
+    // Same input should always give the same output (used to reproduce bug reports)
+    pub deterministic: bool,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            // Never run to completion by default: hard instances could hang forever
+            time_limit_in_seconds: Some(3600),
+            relative_gap: None,
+            absolute_gap: None,
+            threads: None,
+            random_seed: None,
+            deterministic: false,
+        }
+    }
+}
+
+impl SolverOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_time_limit(time_limit_in_seconds: Option<u32>) -> Self {
+        SolverOptions {
+            time_limit_in_seconds,
+            ..Self::default()
+        }
+    }
+
+    // Fixes the random seed and forces single-threaded resolution
+    pub fn with_determinism(mut self, random_seed: u32) -> Self {
+        self.threads = Some(1);
+        self.random_seed = Some(random_seed);
+        self.deterministic = true;
+        self
+    }
+}
+
+// Named presets so that frontends do not need to expose raw solver knobs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum SolverProfile {
+    // Only look for a valid solution, without optimizing
+    QuickDraft,
+    #[default]
+    Balanced,
+    // No time limit and optimality must be proven
+    Exhaustive,
+}
+
+impl SolverProfile {
+    pub const ALL: [SolverProfile; 3] = [
+        SolverProfile::QuickDraft,
+        SolverProfile::Balanced,
+        SolverProfile::Exhaustive,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SolverProfile::QuickDraft => "quick-draft",
+            SolverProfile::Balanced => "balanced",
+            SolverProfile::Exhaustive => "exhaustive",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<SolverProfile> {
+        Self::ALL.into_iter().find(|profile| profile.name() == name)
+    }
+
+    pub fn minimize_objective(&self) -> bool {
+        match self {
+            SolverProfile::QuickDraft => false,
+            SolverProfile::Balanced | SolverProfile::Exhaustive => true,
+        }
+    }
+
+    pub fn options(&self) -> SolverOptions {
+        match self {
+            SolverProfile::QuickDraft => SolverOptions::with_time_limit(Some(10 * 60)),
+            SolverProfile::Balanced => SolverOptions::default(),
+            SolverProfile::Exhaustive => SolverOptions {
+                time_limit_in_seconds: None,
+                relative_gap: Some(0.),
+                absolute_gap: Some(0.),
+                ..SolverOptions::default()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveProgress {
+    Started,
+    TimeElapsed {
+        elapsed: std::time::Duration,
+        time_limit_fraction: Option<f64>,
+    },
+    IncumbentFound {
+        objective_value: Option<f64>,
+    },
+    BoundImproved {
+        best_bound: f64,
+    },
+    Finished(SolveStats),
+}
+
+pub type ProgressCallback<'c> = &'c (dyn Fn(SolveProgress) + Sync);
+pub type IncumbentCallback<'c, 'a, V, P> = &'c (dyn Fn(&FeasableConfig<'a, V, P>) + Sync);
+
+const PROGRESS_POLLING_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+// Reads back the value of a boolean variable from a solver (as a float).
+// Values are rounded, the tolerance only rejects values that are not close to 0 or 1.
+pub(crate) fn bool_from_solver_value(value: f64, tolerance: f64) -> Option<bool> {
+    let rounded = value >= 0.5;
+    let target = if rounded { 1. } else { 0. };
+    if (value - target).abs() > tolerance {
+        return None;
+    }
+    Some(rounded)
+}
+
+#[derive(Debug, Clone)]
+pub struct SolveResult<'a, V: VariableName, P: ProblemRepr<V>> {
+    pub config: Option<FeasableConfig<'a, V, P>>,
+    pub stats: SolveStats,
+}
+
 pub trait FeasabilitySolver<V: VariableName, P: ProblemRepr<V>>: Send + Sync {
-    fn find_closest_solution_with_time_limit<'a>(
+    fn find_closest_solution_with_options<'a>(
         &self,
         config: &Config<'a, V, P>,
-        time_limit_in_seconds: Option<u32>,
+        options: &SolverOptions,
     ) -> Option<FeasableConfig<'a, V, P>>;
 
     fn find_closest_solution<'a>(
         &self,
         config: &Config<'a, V, P>,
     ) -> Option<FeasableConfig<'a, V, P>> {
-        self.find_closest_solution_with_time_limit(config, None)
+        self.find_closest_solution_with_options(config, &SolverOptions::default())
     }
 
     fn solve<'a>(
         &self,
         config_hint: &Config<'a, V, P>,
         minimize_objective: bool,
-        time_limit_in_seconds: Option<u32>,
+        options: &SolverOptions,
     ) -> Option<FeasableConfig<'a, V, P>>;
+
+    fn solve_with_stats<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &SolverOptions,
+    ) -> SolveResult<'a, V, P> {
+        let start = std::time::Instant::now();
+        let config = self.solve(config_hint, minimize_objective, options);
+        let solve_time = start.elapsed();
+
+        let status = if config.is_some() {
+            SolveStatus::Feasible
+        } else {
+            SolveStatus::Unknown
+        };
+
+        SolveResult {
+            config,
+            stats: SolveStats {
+                status,
+                solve_time,
+                ..SolveStats::default()
+            },
+        }
+    }
+
+    // External solvers (cbc, HiGHS) cannot be interrupted while running.
+    // The token is polled before starting and while waiting for the backend,
+    // but as the config is borrowed, this call only returns once the backend does:
+    // cancellation is then reported in the stats and progress stops being sent.
+    // Use jobs::JobManager to get control back as soon as a job is cancelled.
+    fn solve_with_progress<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &SolverOptions,
+        cancellation_token: &CancellationToken,
+        progress: ProgressCallback<'_>,
+    ) -> SolveResult<'a, V, P> {
+        if cancellation_token.is_cancelled() {
+            let stats = SolveStats {
+                status: SolveStatus::Cancelled,
+                ..SolveStats::default()
+            };
+            progress(SolveProgress::Finished(stats));
+            return SolveResult {
+                config: None,
+                stats,
+            };
+        }
+
+        progress(SolveProgress::Started);
+        let start = std::time::Instant::now();
+
+        let mut result = std::thread::scope(|s| {
+            let handle =
+                s.spawn(|| self.solve_with_stats(config_hint, minimize_objective, options));
+
+            while !handle.is_finished() {
+                std::thread::sleep(PROGRESS_POLLING_INTERVAL);
+                if cancellation_token.is_cancelled() {
+                    continue;
+                }
+
+                let elapsed = start.elapsed();
+                let time_limit_fraction = options
+                    .time_limit_in_seconds
+                    .map(|t| (elapsed.as_secs_f64() / f64::from(t.max(1))).min(1.));
+                progress(SolveProgress::TimeElapsed {
+                    elapsed,
+                    time_limit_fraction,
+                });
+            }
+
+            handle.join().expect("Solver thread should not panic")
+        });
+
+        if result.config.is_some() {
+            progress(SolveProgress::IncumbentFound {
+                objective_value: result.stats.objective_value,
+            });
+        }
+        if let Some(best_bound) = result.stats.best_bound {
+            progress(SolveProgress::BoundImproved { best_bound });
+        }
+        if cancellation_token.is_cancelled() {
+            result.stats.status = SolveStatus::Cancelled;
+        }
+        progress(SolveProgress::Finished(result.stats));
+
+        result
+    }
+
+    // Reports every improving solution as soon as it is known so that callers
+    // can stop early and keep the best solution so far.
+    // Solvers that cannot expose intermediate solutions only report the final one:
+    // this is the case of cbc and HiGHS as their bindings have no incumbent callback.
+    // With them, nothing is available before the backend returns (and nothing at all
+    // if the solve is cancelled). The portfolio solver reports the result of each
+    // backend as soon as it finishes.
+    fn solve_anytime<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &SolverOptions,
+        cancellation_token: &CancellationToken,
+        progress: ProgressCallback<'_>,
+        incumbent: IncumbentCallback<'_, 'a, V, P>,
+    ) -> SolveResult<'a, V, P> {
+        let result = self.solve_with_progress(
+            config_hint,
+            minimize_objective,
+            options,
+            cancellation_token,
+            progress,
+        );
+        if let Some(config) = &result.config {
+            incumbent(config);
+        }
+        result
+    }
 }
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests;
+
+use crate::ilp::{DefaultRepr, Problem};
+
+use super::{FeasabilitySolver, ProblemRepr, SolveStatus, SolverOptions, VariableName};
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid problem file {0}: {1}")]
+    InvalidProblemFile(std::path::PathBuf, serde_json::Error),
+    #[error("Error while writing CSV: {0}")]
+    Csv(#[from] csv::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub type NamedProblem<V> = (String, Problem<V, DefaultRepr<V>>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkConfig {
+    pub name: String,
+    pub minimize_objective: bool,
+    pub options: SolverOptions,
+}
+
+impl From<super::SolverProfile> for BenchmarkConfig {
+    fn from(value: super::SolverProfile) -> Self {
+        BenchmarkConfig {
+            name: value.name().to_string(),
+            minimize_objective: value.minimize_objective(),
+            options: value.options(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkRecord {
+    pub instance: String,
+    pub config: String,
+    pub status: SolveStatus,
+    pub solve_time: std::time::Duration,
+    pub objective_value: Option<f64>,
+    pub best_bound: Option<f64>,
+    pub gap: Option<f64>,
+}
+
+// Every instance is solved once for each config
+pub fn run<V, P, S, I>(
+    solver: &S,
+    instances: I,
+    configs: &[BenchmarkConfig],
+) -> Vec<BenchmarkRecord>
+where
+    V: VariableName,
+    P: ProblemRepr<V>,
+    S: FeasabilitySolver<V, P>,
+    I: IntoIterator<Item = (String, Problem<V, P>)>,
+{
+    let mut records = Vec::new();
+
+    for (instance, problem) in instances {
+        for config in configs {
+            let result = solver.solve_with_stats(
+                &problem.default_config(),
+                config.minimize_objective,
+                &config.options,
+            );
+
+            // Not every backend reports the objective value
+            let objective_value = result
+                .stats
+                .objective_value
+                .or(result.config.as_ref().map(|c| c.objective_value()));
+            let stats = super::SolveStats {
+                objective_value,
+                ..result.stats
+            };
+
+            records.push(BenchmarkRecord {
+                instance: instance.clone(),
+                config: config.name.clone(),
+                status: stats.status,
+                solve_time: stats.solve_time,
+                objective_value: stats.objective_value,
+                best_bound: stats.best_bound,
+                gap: stats.gap(),
+            });
+        }
+    }
+
+    records
+}
+
+// Problem files are serialized problems (json) with the .json extension
+pub fn load_problems_from_dir<V>(dir: &std::path::Path) -> Result<Vec<NamedProblem<V>>>
+where
+    V: VariableName + for<'de> serde::Deserialize<'de>,
+{
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut problems = Vec::with_capacity(paths.len());
+    for path in paths {
+        let content = std::fs::read_to_string(&path)?;
+        let problem = serde_json::from_str(&content)
+            .map_err(|e| Error::InvalidProblemFile(path.clone(), e))?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        problems.push((name, problem));
+    }
+
+    Ok(problems)
+}
+
+fn optional_field(value: Option<f64>) -> String {
+    value.map(|x| x.to_string()).unwrap_or_default()
+}
+
+pub fn write_csv<W: std::io::Write>(records: &[BenchmarkRecord], writer: W) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record([
+        "instance",
+        "config",
+        "status",
+        "solve_time_in_seconds",
+        "objective_value",
+        "best_bound",
+        "gap",
+    ])?;
+    for record in records {
+        writer.write_record([
+            record.instance.clone(),
+            record.config.clone(),
+            format!("{:?}", record.status),
+            record.solve_time.as_secs_f64().to_string(),
+            optional_field(record.objective_value),
+            optional_field(record.best_bound),
+            optional_field(record.gap),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
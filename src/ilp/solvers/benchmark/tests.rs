@@ -0,0 +1,78 @@
+use crate::ilp::linexpr::Expr;
+use crate::ilp::solvers::{SolveStatus, SolverOptions, SolverProfile};
+use crate::ilp::tests::BruteForceSolver;
+use crate::ilp::{DefaultRepr, ProblemBuilder};
+
+#[test]
+fn benchmark_runs_every_instance_with_every_config() {
+    let pb1 = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .set_objective_contrib("X", 2.)
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+    let pb2 = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X"])
+        .unwrap()
+        .add_constraint(Expr::var("X").eq(&Expr::constant(2)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let configs = vec![
+        super::BenchmarkConfig::from(SolverProfile::QuickDraft),
+        super::BenchmarkConfig {
+            name: String::from("custom, config"),
+            minimize_objective: true,
+            options: SolverOptions::default(),
+        },
+    ];
+
+    let records = super::run(
+        &BruteForceSolver::default(),
+        [(String::from("pb1"), pb1), (String::from("pb2"), pb2)],
+        &configs,
+    );
+
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[0].instance, "pb1");
+    assert_eq!(records[0].config, "quick-draft");
+    assert_eq!(records[0].status, SolveStatus::Feasible);
+    assert_eq!(records[1].config, "custom, config");
+    assert_eq!(records[2].instance, "pb2");
+    assert_eq!(records[2].status, SolveStatus::Unknown);
+    assert_eq!(records[2].objective_value, None);
+
+    let mut output = Vec::new();
+    super::write_csv(&records, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(
+        lines[0],
+        "instance,config,status,solve_time_in_seconds,objective_value,best_bound,gap"
+    );
+    assert!(lines[2].starts_with("pb1,\"custom, config\",Feasible,"));
+    assert!(lines[3].ends_with(",,,"));
+}
+
+#[test]
+fn benchmark_loads_problems_from_dir() {
+    let dir = std::env::temp_dir().join(format!("benchmark_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X"])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+    std::fs::write(dir.join("b.json"), serde_json::to_string(&pb).unwrap()).unwrap();
+    std::fs::write(dir.join("a.json"), serde_json::to_string(&pb).unwrap()).unwrap();
+    std::fs::write(dir.join("notes.txt"), "not a problem").unwrap();
+
+    let problems = super::load_problems_from_dir::<String>(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let names: Vec<_> = problems.iter().map(|(name, _)| name.clone()).collect();
+    assert_eq!(names, vec![String::from("a"), String::from("b")]);
+}
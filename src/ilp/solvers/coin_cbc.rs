@@ -14,22 +14,36 @@ enum Objective {
     MinimumObjectiveFn,
 }
 
-use super::{FeasabilitySolver, ProblemRepr, VariableName};
+use super::{
+    bool_from_solver_value, FeasabilitySolver, ProblemRepr, SolveResult, SolveStats, SolveStatus,
+    SolverOptions, VariableName,
+};
 impl<V: VariableName, P: ProblemRepr<V>> FeasabilitySolver<V, P> for Solver {
-    fn find_closest_solution_with_time_limit<'a>(
+    fn find_closest_solution_with_options<'a>(
         &self,
         config: &Config<'a, V, P>,
-        time_limit_in_seconds: Option<u32>,
+        options: &SolverOptions,
     ) -> Option<FeasableConfig<'a, V, P>> {
-        self.solve_internal(config, Objective::MinimumDistance, time_limit_in_seconds)
+        self.solve_internal(config, Objective::MinimumDistance, options)
+            .config
     }
 
     fn solve<'a>(
         &self,
         config_hint: &Config<'a, V, P>,
         minimize_objective: bool,
-        time_limit_in_seconds: Option<u32>,
+        options: &SolverOptions,
     ) -> Option<FeasableConfig<'a, V, P>> {
+        self.solve_with_stats(config_hint, minimize_objective, options)
+            .config
+    }
+
+    fn solve_with_stats<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &SolverOptions,
+    ) -> SolveResult<'a, V, P> {
         self.solve_internal(
             config_hint,
             if minimize_objective {
@@ -37,7 +51,7 @@ impl<V: VariableName, P: ProblemRepr<V>> FeasabilitySolver<V, P> for Solver {
             } else {
                 Objective::None
             },
-            time_limit_in_seconds,
+            options,
         )
     }
 }
@@ -68,8 +82,8 @@ impl Solver {
         &self,
         init_config: &Config<'a, V, P>,
         objective: Objective,
-        time_limit_in_seconds: Option<u32>,
-    ) -> Option<FeasableConfig<'a, V, P>> {
+        options: &SolverOptions,
+    ) -> SolveResult<'a, V, P> {
         // cbc does not seem to shut up even if logging is disabled
         // we block output directly
         let stdout_gag = gag::Gag::stdout();
@@ -91,16 +105,64 @@ impl Solver {
             Objective::None => {}
         }
 
-        if let Some(time_limit) = time_limit_in_seconds {
-            cbc_model.model.set_parameter("timeMode", "elapsed");
-            cbc_model
-                .model
-                .set_parameter("seconds", &time_limit.to_string());
-        }
+        Self::apply_options(&mut cbc_model.model, options);
 
+        let start = std::time::Instant::now();
         let sol = cbc_model.model.solve();
+        let solve_time = start.elapsed();
+
+        let config = Self::reconstruct_config(problem, &sol, &cbc_model.cols);
+        let stats = Self::extract_stats(&sol, config.is_some(), solve_time);
 
-        Self::reconstruct_config(problem, &sol, &cbc_model.cols)
+        SolveResult { config, stats }
+    }
+
+    fn apply_options(model: &mut coin_cbc::Model, options: &SolverOptions) {
+        if let Some(time_limit) = options.time_limit_in_seconds {
+            model.set_parameter("timeMode", "elapsed");
+            model.set_parameter("seconds", &time_limit.to_string());
+        }
+        if let Some(relative_gap) = options.relative_gap {
+            model.set_parameter("ratioGap", &relative_gap.to_string());
+        }
+        if let Some(absolute_gap) = options.absolute_gap {
+            model.set_parameter("allowableGap", &absolute_gap.to_string());
+        }
+        if let Some(threads) = options.threads {
+            model.set_parameter("threads", &threads.to_string());
+        }
+        if let Some(random_seed) = options.random_seed {
+            model.set_parameter("randomCbcSeed", &random_seed.to_string());
+            model.set_parameter("randomSeed", &random_seed.to_string());
+        }
+    }
+
+    fn extract_stats(
+        sol: &coin_cbc::Solution,
+        found_solution: bool,
+        solve_time: std::time::Duration,
+    ) -> SolveStats {
+        let raw = sol.raw();
+
+        let status = if raw.is_proven_infeasible() {
+            SolveStatus::Infeasible
+        } else if !found_solution {
+            SolveStatus::Unknown
+        } else if raw.is_proven_optimal() {
+            SolveStatus::Optimal
+        } else if raw.is_seconds_limit_reached() {
+            SolveStatus::TimeLimitReached
+        } else {
+            SolveStatus::Feasible
+        };
+
+        SolveStats {
+            status,
+            objective_value: found_solution.then(|| raw.obj_value()),
+            best_bound: found_solution.then(|| raw.best_possible_value()),
+            iteration_count: u64::try_from(raw.iteration_count()).ok(),
+            solve_time,
+        }
     }
 
     fn build_model<V: VariableName, P: ProblemRepr<V>>(
@@ -220,10 +282,11 @@ impl Solver {
     ) -> Option<FeasableConfig<'a, V, P>> {
         use std::collections::BTreeMap;
 
+        let tolerance = problem.get_tolerance();
         let bool_vars: BTreeMap<_, _> = cols
             .iter()
-            .map(|(v, col)| (v.clone(), sol.col(*col) == 1.))
-            .collect();
+            .map(|(v, col)| Some((v.clone(), bool_from_solver_value(sol.col(*col), tolerance)?)))
+            .collect::<Option<_>>()?;
 
         let config = problem
             .config_from(bool_vars)
@@ -15,22 +15,36 @@ enum Objective {
     MinimumObjectiveFn,
 }
 
-use super::{FeasabilitySolver, ProblemRepr, VariableName};
+use super::{
+    bool_from_solver_value, FeasabilitySolver, ProblemRepr, SolveResult, SolveStats, SolveStatus,
+    SolverOptions, VariableName,
+};
 impl<V: VariableName, P: ProblemRepr<V>> FeasabilitySolver<V, P> for Solver {
-    fn find_closest_solution_with_time_limit<'a>(
+    fn find_closest_solution_with_options<'a>(
         &self,
         config: &Config<'a, V, P>,
-        time_limit_in_seconds: Option<u32>,
+        options: &SolverOptions,
     ) -> Option<FeasableConfig<'a, V, P>> {
-        self.solve_internal(config, Objective::MinimumDistance, time_limit_in_seconds)
+        self.solve_internal(config, Objective::MinimumDistance, options)
+            .config
     }
 
     fn solve<'a>(
         &self,
         config_hint: &Config<'a, V, P>,
         minimize_objective: bool,
-        time_limit_in_seconds: Option<u32>,
+        options: &SolverOptions,
     ) -> Option<FeasableConfig<'a, V, P>> {
+        self.solve_with_stats(config_hint, minimize_objective, options)
+            .config
+    }
+
+    fn solve_with_stats<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &SolverOptions,
+    ) -> SolveResult<'a, V, P> {
         self.solve_internal(
             config_hint,
             if minimize_objective {
@@ -38,7 +52,7 @@ impl<V: VariableName, P: ProblemRepr<V>> FeasabilitySolver<V, P> for Solver {
             } else {
                 Objective::None
             },
-            time_limit_in_seconds,
+            options,
         )
     }
 }
@@ -68,11 +82,17 @@ impl Solver {
         &self,
         init_config: &Config<'a, V, P>,
         objective: Objective,
-        time_limit_in_seconds: Option<u32>,
-    ) -> Option<FeasableConfig<'a, V, P>> {
+        options: &SolverOptions,
+    ) -> SolveResult<'a, V, P> {
         // When everything is solved for some reason this is sometimes an issue...
         if let Some(result) = init_config.clone().into_feasable() {
-            return Some(result);
+            return SolveResult {
+                config: Some(result),
+                stats: SolveStats {
+                    status: SolveStatus::Feasible,
+                    ..SolveStats::default()
+                },
+            };
         }
 
         let problem = init_config.get_problem();
@@ -80,18 +100,75 @@ impl Solver {
         let highs_problem = self.build_problem(problem, init_config, objective);
 
         use highs::Sense;
-        let mut model = highs_problem.problem.try_optimise(Sense::Minimise).ok()?;
+        let mut model = match highs_problem.problem.try_optimise(Sense::Minimise) {
+            Ok(m) => m,
+            Err(_) => {
+                return SolveResult {
+                    config: None,
+                    stats: SolveStats::default(),
+                }
+            }
+        };
         if self.disable_logging {
             model.make_quiet();
         }
 
-        if let Some(time_limit) = time_limit_in_seconds {
+        if let Some(time_limit) = options.time_limit_in_seconds {
             model.set_option("time_limit", f64::from(time_limit));
         }
+        if let Some(relative_gap) = options.relative_gap {
+            model.set_option("mip_rel_gap", relative_gap);
+        }
+        if let Some(absolute_gap) = options.absolute_gap {
+            model.set_option("mip_abs_gap", absolute_gap);
+        }
+        if let Some(threads) = options.threads {
+            model.set_option("threads", i32::try_from(threads).unwrap_or(i32::MAX));
+        }
+        if let Some(random_seed) = options.random_seed {
+            model.set_option(
+                "random_seed",
+                i32::try_from(random_seed).unwrap_or(i32::MAX),
+            );
+        }
 
-        let solved_problem = model.try_solve().ok()?;
+        let start = std::time::Instant::now();
+        let solved_problem = match model.try_solve() {
+            Ok(s) => s,
+            Err(_) => {
+                return SolveResult {
+                    config: None,
+                    stats: SolveStats {
+                        solve_time: start.elapsed(),
+                        ..SolveStats::default()
+                    },
+                }
+            }
+        };
+        let solve_time = start.elapsed();
+
+        let config = Self::reconstruct_config(problem, &solved_problem);
+        let status = match solved_problem.status() {
+            highs::HighsModelStatus::Infeasible => SolveStatus::Infeasible,
+            _ if config.is_none() => SolveStatus::Unknown,
+            highs::HighsModelStatus::Optimal => SolveStatus::Optimal,
+            highs::HighsModelStatus::ReachedTimeLimit => SolveStatus::TimeLimitReached,
+            _ => SolveStatus::Feasible,
+        };
 
-        Self::reconstruct_config(problem, &solved_problem)
+        // The bindings only expose the solution: the objective is computed back from it
+        // and the best bound and iteration count are not available
+        let objective_value = config.as_ref().map(|config| config.objective_value());
+
+        SolveResult {
+            config,
+            stats: SolveStats {
+                status,
+                objective_value,
+                solve_time,
+                ..SolveStats::default()
+            },
+        }
     }
 
     fn build_problem<V: VariableName, P: ProblemRepr<V>>(
@@ -213,12 +290,13 @@ impl Solver {
         let solution = solved_model.get_solution();
         let columns = solution.columns();
 
+        let tolerance = problem.get_tolerance();
         let bool_vars: BTreeMap<_, _> = problem
             .get_variables()
             .iter()
             .enumerate()
-            .map(|(i, var)| (var.clone(), columns[i] > 0.5))
-            .collect();
+            .map(|(i, var)| Some((var.clone(), bool_from_solver_value(columns[i], tolerance)?)))
+            .collect::<Option<_>>()?;
 
         let config = problem
             .config_from(bool_vars)
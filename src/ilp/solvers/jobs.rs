@@ -0,0 +1,329 @@
+#[cfg(test)]
+mod tests;
+
+use crate::ilp::{Config, FeasableConfig, Problem};
+
+use super::{
+    CancellationToken, FeasabilitySolver, ProblemRepr, SolveProgress, SolveStats, SolveStatus,
+    SolverOptions, VariableName, PROGRESS_POLLING_INTERVAL,
+};
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Condvar, Mutex};
+
+pub type JobId = u64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Queued,
+    Running {
+        last_progress: Option<SolveProgress>,
+    },
+    Finished(SolveStats),
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_done(&self) -> bool {
+        matches!(self, JobStatus::Finished(_) | JobStatus::Cancelled)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobResult<V: VariableName> {
+    pub bool_vars: Option<BTreeMap<V, bool>>,
+    pub stats: SolveStats,
+}
+
+struct Job<V: VariableName, P: ProblemRepr<V>> {
+    problem: Problem<V, P>,
+    hint: BTreeMap<V, bool>,
+    solver: Arc<dyn FeasabilitySolver<V, P>>,
+    minimize_objective: bool,
+    options: SolverOptions,
+}
+
+struct JobEntry<V: VariableName> {
+    status: JobStatus,
+    result: Option<JobResult<V>>,
+    incumbent: Option<BTreeMap<V, bool>>,
+    cancellation_token: CancellationToken,
+}
+
+struct JobQueue<V: VariableName, P: ProblemRepr<V>> {
+    pending: VecDeque<(JobId, Job<V, P>)>,
+    entries: BTreeMap<JobId, JobEntry<V>>,
+    next_id: JobId,
+    shutdown: bool,
+}
+
+type SharedQueue<V, P> = Arc<(Mutex<JobQueue<V, P>>, Condvar)>;
+
+// Runs solves on a fixed pool of worker threads.
+// Jobs own a copy of their problem so results are returned as variable values.
+// Cancelling a running job frees its worker right away: backends that cannot be
+// interrupted keep running in the background until they return.
+pub struct JobManager<V: VariableName + 'static, P: ProblemRepr<V> + 'static> {
+    queue: SharedQueue<V, P>,
+    workers: Vec<std::thread::JoinHandle<()>>,
+}
+
+impl<V: VariableName + 'static, P: ProblemRepr<V> + 'static> JobManager<V, P> {
+    pub fn new(worker_count: usize) -> Self {
+        assert!(worker_count > 0, "at least one worker is needed");
+
+        let queue: SharedQueue<V, P> = Arc::new((
+            Mutex::new(JobQueue {
+                pending: VecDeque::new(),
+                entries: BTreeMap::new(),
+                next_id: 0,
+                shutdown: false,
+            }),
+            Condvar::new(),
+        ));
+
+        let workers = (0..worker_count)
+            .map(|_| {
+                let queue = queue.clone();
+                std::thread::spawn(move || Self::worker_loop(queue))
+            })
+            .collect();
+
+        JobManager { queue, workers }
+    }
+
+    pub fn submit<S: FeasabilitySolver<V, P> + 'static>(
+        &self,
+        solver: S,
+        config_hint: &Config<'_, V, P>,
+        minimize_objective: bool,
+        options: SolverOptions,
+    ) -> JobId {
+        let job = Job {
+            problem: config_hint.get_problem().clone(),
+            hint: config_hint.get_bool_vars(),
+            solver: Arc::new(solver),
+            minimize_objective,
+            options,
+        };
+
+        let (lock, cvar) = &*self.queue;
+        let mut queue = lock.lock().unwrap();
+        let id = queue.next_id;
+        queue.next_id += 1;
+        queue.entries.insert(
+            id,
+            JobEntry {
+                status: JobStatus::Queued,
+                result: None,
+                incumbent: None,
+                cancellation_token: CancellationToken::new(),
+            },
+        );
+        queue.pending.push_back((id, job));
+        cvar.notify_all();
+
+        id
+    }
+
+    pub fn status(&self, id: JobId) -> Option<JobStatus> {
+        let (lock, _cvar) = &*self.queue;
+        let queue = lock.lock().unwrap();
+        queue.entries.get(&id).map(|entry| entry.status.clone())
+    }
+
+    pub fn job_ids(&self) -> Vec<JobId> {
+        let (lock, _cvar) = &*self.queue;
+        let queue = lock.lock().unwrap();
+        queue.entries.keys().copied().collect()
+    }
+
+    // Returns false if the job does not exist or is already done
+    pub fn cancel(&self, id: JobId) -> bool {
+        let (lock, cvar) = &*self.queue;
+        let mut queue = lock.lock().unwrap();
+
+        let Some(entry) = queue.entries.get_mut(&id) else {
+            return false;
+        };
+        match entry.status {
+            JobStatus::Queued => {
+                entry.status = JobStatus::Cancelled;
+                queue.pending.retain(|(job_id, _job)| *job_id != id);
+                cvar.notify_all();
+            }
+            JobStatus::Running { .. } => {
+                entry.cancellation_token.cancel();
+            }
+            JobStatus::Finished(_) | JobStatus::Cancelled => return false,
+        }
+        true
+    }
+
+    pub fn wait(&self, id: JobId) -> Option<JobStatus> {
+        let (lock, cvar) = &*self.queue;
+        let mut queue = lock.lock().unwrap();
+        loop {
+            let status = queue.entries.get(&id)?.status.clone();
+            if status.is_done() {
+                return Some(status);
+            }
+            queue = cvar.wait(queue).unwrap();
+        }
+    }
+
+    // Best solution found so far: available while the job is still running
+    // if the solver reports intermediate solutions (see FeasabilitySolver::solve_anytime)
+    pub fn best_so_far(&self, id: JobId) -> Option<BTreeMap<V, bool>> {
+        let (lock, _cvar) = &*self.queue;
+        let queue = lock.lock().unwrap();
+        queue.entries.get(&id)?.incumbent.clone()
+    }
+
+    // Removes a finished job and returns its result
+    pub fn take_result(&self, id: JobId) -> Option<JobResult<V>> {
+        let (lock, _cvar) = &*self.queue;
+        let mut queue = lock.lock().unwrap();
+
+        if !queue.entries.get(&id)?.status.is_done() {
+            return None;
+        }
+        queue.entries.remove(&id)?.result
+    }
+
+    // The solve runs on a detached thread owning the job so that a cancelled job
+    // is reported as such right away, even if the backend cannot be interrupted.
+    // The backend then finishes in the background and its result is dropped.
+    fn run_job(queue: &SharedQueue<V, P>, id: JobId, job: Job<V, P>, token: CancellationToken) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let solve_queue = queue.clone();
+        let solve_token = token.clone();
+        std::thread::spawn(move || {
+            let queue = solve_queue;
+            let config_hint = job
+                .problem
+                .config_from(job.hint)
+                .expect("Variables should be valid");
+
+            let progress = |progress: SolveProgress| {
+                let (lock, cvar) = &*queue;
+                let mut queue = lock.lock().unwrap();
+                if let Some(entry) = queue.entries.get_mut(&id) {
+                    if let JobStatus::Running { .. } = entry.status {
+                        entry.status = JobStatus::Running {
+                            last_progress: Some(progress),
+                        };
+                        cvar.notify_all();
+                    }
+                }
+            };
+
+            let incumbent = |config: &FeasableConfig<'_, V, P>| {
+                let (lock, cvar) = &*queue;
+                let mut queue = lock.lock().unwrap();
+                if let Some(entry) = queue.entries.get_mut(&id) {
+                    if let JobStatus::Running { .. } = entry.status {
+                        entry.incumbent = Some(config.get_bool_vars());
+                        cvar.notify_all();
+                    }
+                }
+            };
+
+            let result = job.solver.solve_anytime(
+                &config_hint,
+                job.minimize_objective,
+                &job.options,
+                &solve_token,
+                &progress,
+                &incumbent,
+            );
+
+            // The receiver is gone if the job was cancelled in the meantime
+            let _ = sender.send((result.config.map(|c| c.get_bool_vars()), result.stats));
+        });
+
+        let outcome = loop {
+            match receiver.recv_timeout(PROGRESS_POLLING_INTERVAL) {
+                Ok(outcome) => break Some(outcome),
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                    if token.is_cancelled() {
+                        break None;
+                    }
+                }
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                    panic!("Solver thread should not panic")
+                }
+            }
+        };
+
+        let (lock, cvar) = &**queue;
+        let mut queue = lock.lock().unwrap();
+        if let Some(entry) = queue.entries.get_mut(&id) {
+            let (bool_vars, stats) = match outcome {
+                Some(outcome) => outcome,
+                None => (
+                    None,
+                    SolveStats {
+                        status: SolveStatus::Cancelled,
+                        ..SolveStats::default()
+                    },
+                ),
+            };
+            entry.status = if stats.status == SolveStatus::Cancelled {
+                JobStatus::Cancelled
+            } else {
+                JobStatus::Finished(stats)
+            };
+            // When stopped early, the best solution so far is still worth keeping
+            let bool_vars = bool_vars.or(entry.incumbent.clone());
+            entry.result = Some(JobResult { bool_vars, stats });
+        }
+        cvar.notify_all();
+    }
+
+    fn worker_loop(queue: SharedQueue<V, P>) {
+        loop {
+            let (id, job, token) = {
+                let (lock, cvar) = &*queue;
+                let mut guard = lock.lock().unwrap();
+                loop {
+                    if guard.shutdown {
+                        return;
+                    }
+                    if let Some((id, job)) = guard.pending.pop_front() {
+                        let entry = guard
+                            .entries
+                            .get_mut(&id)
+                            .expect("Pending jobs should have an entry");
+                        entry.status = JobStatus::Running {
+                            last_progress: None,
+                        };
+                        break (id, job, entry.cancellation_token.clone());
+                    }
+                    guard = cvar.wait(guard).unwrap();
+                }
+            };
+
+            Self::run_job(&queue, id, job, token);
+        }
+    }
+}
+
+impl<V: VariableName + 'static, P: ProblemRepr<V> + 'static> Drop for JobManager<V, P> {
+    fn drop(&mut self) {
+        {
+            let (lock, cvar) = &*self.queue;
+            let mut queue = lock.lock().unwrap();
+            queue.shutdown = true;
+            for entry in queue.entries.values() {
+                entry.cancellation_token.cancel();
+            }
+            cvar.notify_all();
+        }
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
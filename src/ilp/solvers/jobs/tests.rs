@@ -0,0 +1,93 @@
+use super::{JobManager, JobStatus};
+use crate::ilp::linexpr::Expr;
+use crate::ilp::solvers::{FeasabilitySolver, SolveStatus, SolverOptions};
+use crate::ilp::tests::BruteForceSolver;
+use crate::ilp::{Config, DefaultRepr, FeasableConfig, ProblemBuilder};
+
+#[derive(Debug, Clone, Default)]
+struct SlowSolver {}
+
+impl FeasabilitySolver<String, DefaultRepr<String>> for SlowSolver {
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        _config: &Config<'a, String, DefaultRepr<String>>,
+        _options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, String, DefaultRepr<String>>> {
+        None
+    }
+
+    fn solve<'a>(
+        &self,
+        _config_hint: &Config<'a, String, DefaultRepr<String>>,
+        _minimize_objective: bool,
+        _options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, String, DefaultRepr<String>>> {
+        std::thread::sleep(std::time::Duration::from_secs(5));
+        None
+    }
+}
+
+#[test]
+fn job_runs_to_completion() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let manager = JobManager::new(2);
+    let id = manager.submit(
+        BruteForceSolver::default(),
+        &pb.default_config(),
+        false,
+        SolverOptions::new(),
+    );
+
+    let status = manager.wait(id).unwrap();
+    assert!(matches!(status, JobStatus::Finished(_)));
+
+    let best_so_far = manager.best_so_far(id).unwrap();
+    let result = manager.take_result(id).unwrap();
+    assert_eq!(result.bool_vars, Some(best_so_far));
+    assert_eq!(result.stats.status, SolveStatus::Feasible);
+    let config = pb.config_from(result.bool_vars.unwrap()).unwrap();
+    assert!(config.is_feasable());
+
+    assert_eq!(manager.status(id), None);
+    assert_eq!(manager.take_result(id), None);
+}
+
+#[test]
+fn jobs_can_be_cancelled() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X"])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let manager = JobManager::new(1);
+    let first = manager.submit(
+        SlowSolver::default(),
+        &pb.default_config(),
+        false,
+        SolverOptions::new(),
+    );
+    let second = manager.submit(
+        SlowSolver::default(),
+        &pb.default_config(),
+        false,
+        SolverOptions::new(),
+    );
+
+    // A single worker: the second job waits in the queue
+    assert!(manager.cancel(second));
+    assert_eq!(manager.status(second), Some(JobStatus::Cancelled));
+    assert!(!manager.cancel(second));
+
+    // The backend cannot be interrupted but the job is reported cancelled right away
+    let start = std::time::Instant::now();
+    assert!(manager.cancel(first));
+    assert_eq!(manager.wait(first), Some(JobStatus::Cancelled));
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    assert_eq!(manager.job_ids(), vec![first, second]);
+}
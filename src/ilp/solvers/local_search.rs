@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests;
+
+use crate::ilp::random::{DefaultRndGen, RandomGen, SeededRndGen};
+use crate::ilp::FeasableConfig;
+
+use super::{ProblemRepr, SolverOptions, VariableName};
+
+// Post-optimization of a feasable config (iterated local search).
+// Each step flips a random variable and repairs the result with a few local moves.
+// The new config is kept only if it is feasable and has a lower objective value,
+// so feasability is never lost.
+#[derive(Debug, Clone)]
+pub struct Improver<R: RandomGen = DefaultRndGen> {
+    random_gen: R,
+    max_steps: usize,
+    repair_steps: usize,
+}
+
+impl Default for Improver<DefaultRndGen> {
+    fn default() -> Self {
+        Improver::new()
+    }
+}
+
+impl Improver<DefaultRndGen> {
+    pub fn new() -> Self {
+        Improver::with_random_gen(DefaultRndGen::new())
+    }
+}
+
+impl<R: RandomGen> Improver<R> {
+    pub fn with_random_gen(random_gen: R) -> Self {
+        Improver {
+            random_gen,
+            max_steps: 1000,
+            repair_steps: 50,
+        }
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    pub fn with_repair_steps(mut self, repair_steps: usize) -> Self {
+        self.repair_steps = repair_steps;
+        self
+    }
+
+    pub fn improve<'a, V: VariableName, P: ProblemRepr<V>>(
+        &self,
+        config: &FeasableConfig<'a, V, P>,
+        time_budget: Option<std::time::Duration>,
+    ) -> FeasableConfig<'a, V, P> {
+        let start = std::time::Instant::now();
+
+        let variables = config.get_problem().get_variables().to_vec();
+        if variables.is_empty() {
+            return config.clone();
+        }
+
+        let repair_solver = super::repair::Solver::with_random_gen(self.random_gen.clone())
+            .with_max_steps(self.repair_steps);
+        let repair_options = SolverOptions::with_time_limit(None);
+
+        let mut best = config.clone();
+        let mut best_value = best.objective_value();
+        for _ in 0..self.max_steps {
+            if time_budget.is_some_and(|t| start.elapsed() >= t) {
+                break;
+            }
+
+            let var = self.random_gen.rand_elem(&variables);
+            let mut candidate = best.clone().into_inner();
+            let value = candidate.get_bool(&var).expect("Variable should be valid");
+            candidate
+                .set_bool(&var, !value)
+                .expect("Variable should be valid");
+
+            let Some(candidate) = repair_solver.repair(&candidate, &repair_options) else {
+                continue;
+            };
+            let candidate_value = candidate.objective_value();
+            if candidate_value < best_value {
+                best = candidate;
+                best_value = candidate_value;
+            }
+        }
+
+        best
+    }
+}
+
+// Number of steps replacing one second of time budget for reproducible searches
+pub const STEPS_PER_SECOND: usize = 100;
+
+// Improves a config for the given budget (in seconds).
+// Without seed, the budget is wall-clock time. With a seed, it is converted to a
+// number of steps so that the same seed always gives the same config.
+pub fn improve_within_budget<'a, V: VariableName, P: ProblemRepr<V>>(
+    config: &FeasableConfig<'a, V, P>,
+    budget_in_seconds: u32,
+    seed: Option<u64>,
+) -> FeasableConfig<'a, V, P> {
+    match seed {
+        Some(seed) => Improver::with_random_gen(SeededRndGen::new(seed))
+            .with_max_steps((budget_in_seconds as usize).saturating_mul(STEPS_PER_SECOND))
+            .improve(config, None),
+        None => Improver::new().with_max_steps(usize::MAX).improve(
+            config,
+            Some(std::time::Duration::from_secs(budget_in_seconds.into())),
+        ),
+    }
+}
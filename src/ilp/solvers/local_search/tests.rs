@@ -0,0 +1,109 @@
+use crate::ilp::linexpr::Expr;
+use crate::ilp::{DefaultRepr, ProblemBuilder};
+
+#[test]
+fn improve_lowers_objective_and_stays_feasable() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y", "Z"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y") + Expr::var("Z")).eq(&Expr::constant(1)))
+        .unwrap()
+        .set_objective_contribs([
+            (String::from("X"), 3.),
+            (String::from("Y"), 2.),
+            (String::from("Z"), 1.),
+        ])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let config = pb
+        .config_from([("X", true)])
+        .unwrap()
+        .into_feasable()
+        .unwrap();
+
+    let improver = super::Improver::new().with_max_steps(500);
+    let improved = improver.improve(&config, None);
+
+    assert!(improved.inner().is_feasable());
+    assert_eq!(improved.objective_value(), 1.);
+}
+
+#[test]
+fn improve_without_steps_keeps_config() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .set_objective_contrib("X", 1.)
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let config = pb
+        .config_from([("X", true)])
+        .unwrap()
+        .into_feasable()
+        .unwrap();
+
+    let improver = super::Improver::new().with_max_steps(0);
+    assert_eq!(improver.improve(&config, None), config);
+}
+
+#[test]
+fn improve_is_reproducible_with_seeded_random_gen() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["A", "B", "C", "D"])
+        .unwrap()
+        .add_constraint(
+            (Expr::var("A") + Expr::var("B") + Expr::var("C") + Expr::var("D"))
+                .eq(&Expr::constant(2)),
+        )
+        .unwrap()
+        .set_objective_contribs([(String::from("A"), 1.), (String::from("B"), 1.)])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let config = pb
+        .config_from([("A", true), ("B", true)])
+        .unwrap()
+        .into_feasable()
+        .unwrap();
+
+    let improve = |seed| {
+        super::Improver::with_random_gen(crate::ilp::random::SeededRndGen::new(seed))
+            .with_max_steps(100)
+            .improve(&config, None)
+    };
+    assert_eq!(improve(7), improve(7));
+}
+
+#[test]
+fn improve_within_budget_is_reproducible_with_seed() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["A", "B", "C", "D", "E"])
+        .unwrap()
+        .add_constraint(
+            (Expr::var("A") + Expr::var("B") + Expr::var("C") + Expr::var("D") + Expr::var("E"))
+                .eq(&Expr::constant(2)),
+        )
+        .unwrap()
+        .set_objective_contribs([
+            (String::from("A"), 2.),
+            (String::from("B"), 1.),
+            (String::from("C"), 3.),
+        ])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let config = pb
+        .config_from([("A", true), ("C", true)])
+        .unwrap()
+        .into_feasable()
+        .unwrap();
+
+    let first = super::improve_within_budget(&config, 1, Some(42));
+    let second = super::improve_within_budget(&config, 1, Some(42));
+    assert_eq!(first, second);
+    assert!(first.inner().is_feasable());
+}
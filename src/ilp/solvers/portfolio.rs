@@ -0,0 +1,332 @@
+#[cfg(test)]
+mod tests;
+
+use crate::ilp::{Config, FeasableConfig};
+
+use super::{
+    CancellationToken, FeasabilitySolver, IncumbentCallback, ProblemRepr, ProgressCallback,
+    SolveProgress, SolveResult, SolveStats, SolveStatus, SolverOptions, VariableName,
+    PROGRESS_POLLING_INTERVAL,
+};
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RaceMode {
+    #[default]
+    FirstSolution,
+    BestSolution,
+}
+
+#[derive(Clone)]
+struct Backend<V: VariableName, P: ProblemRepr<V>> {
+    solver: Arc<dyn FeasabilitySolver<V, P>>,
+    options: Option<SolverOptions>,
+}
+
+#[derive(Clone)]
+pub struct Solver<V: VariableName, P: ProblemRepr<V>> {
+    backends: Vec<Backend<V, P>>,
+    mode: RaceMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Task {
+    ClosestSolution,
+    Solve { minimize_objective: bool },
+}
+
+type RaceOutcome<V> = (usize, Option<BTreeMap<V, bool>>, SolveStats);
+
+impl<V: VariableName, P: ProblemRepr<V>> Default for Solver<V, P> {
+    fn default() -> Self {
+        Solver::new()
+    }
+}
+
+impl<V: VariableName, P: ProblemRepr<V>> Solver<V, P> {
+    pub fn new() -> Self {
+        Solver {
+            backends: Vec::new(),
+            mode: RaceMode::default(),
+        }
+    }
+
+    pub fn with_mode(mode: RaceMode) -> Self {
+        Solver {
+            backends: Vec::new(),
+            mode,
+        }
+    }
+
+    pub fn add_backend<S: FeasabilitySolver<V, P> + 'static>(mut self, solver: S) -> Self {
+        self.backends.push(Backend {
+            solver: Arc::new(solver),
+            options: None,
+        });
+        self
+    }
+
+    pub fn add_backend_with_options<S: FeasabilitySolver<V, P> + 'static>(
+        mut self,
+        solver: S,
+        options: SolverOptions,
+    ) -> Self {
+        self.backends.push(Backend {
+            solver: Arc::new(solver),
+            options: Some(options),
+        });
+        self
+    }
+
+    pub fn backend_count(&self) -> usize {
+        self.backends.len()
+    }
+}
+
+impl<V: VariableName + 'static, P: ProblemRepr<V> + 'static> FeasabilitySolver<V, P>
+    for Solver<V, P>
+{
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        config: &Config<'a, V, P>,
+        options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        self.race(config, Task::ClosestSolution, options, None, None)
+            .config
+    }
+
+    fn solve<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        self.solve_with_stats(config_hint, minimize_objective, options)
+            .config
+    }
+
+    fn solve_with_stats<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &SolverOptions,
+    ) -> SolveResult<'a, V, P> {
+        self.race(
+            config_hint,
+            Task::Solve { minimize_objective },
+            options,
+            None,
+            None,
+        )
+    }
+
+    fn solve_anytime<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &SolverOptions,
+        cancellation_token: &CancellationToken,
+        progress: ProgressCallback<'_>,
+        incumbent: IncumbentCallback<'_, 'a, V, P>,
+    ) -> SolveResult<'a, V, P> {
+        progress(SolveProgress::Started);
+
+        let mut result = self.race(
+            config_hint,
+            Task::Solve { minimize_objective },
+            options,
+            Some(cancellation_token),
+            Some(incumbent),
+        );
+
+        if cancellation_token.is_cancelled() {
+            result.stats.status = SolveStatus::Cancelled;
+        }
+        progress(SolveProgress::Finished(result.stats));
+
+        result
+    }
+}
+
+impl<V: VariableName + 'static, P: ProblemRepr<V> + 'static> Solver<V, P> {
+    fn run_backend(
+        index: usize,
+        backend: Backend<V, P>,
+        problem: crate::ilp::Problem<V, P>,
+        hint: BTreeMap<V, bool>,
+        task: Task,
+        options: SolverOptions,
+        cancellation_token: CancellationToken,
+    ) -> RaceOutcome<V> {
+        let config_hint = problem
+            .config_from(hint)
+            .expect("Variables should be valid");
+
+        let result = match task {
+            Task::ClosestSolution => {
+                let start = std::time::Instant::now();
+                let config = backend
+                    .solver
+                    .find_closest_solution_with_options(&config_hint, &options);
+                let status = if config.is_some() {
+                    SolveStatus::Feasible
+                } else {
+                    SolveStatus::Unknown
+                };
+                SolveResult {
+                    config,
+                    stats: SolveStats {
+                        status,
+                        solve_time: start.elapsed(),
+                        ..SolveStats::default()
+                    },
+                }
+            }
+            Task::Solve { minimize_objective } => backend.solver.solve_with_progress(
+                &config_hint,
+                minimize_objective,
+                &options,
+                &cancellation_token,
+                &|_| {},
+            ),
+        };
+
+        (
+            index,
+            result.config.map(|c| c.get_bool_vars()),
+            result.stats,
+        )
+    }
+
+    fn is_better(candidate: &SolveStats, current: &SolveStats) -> bool {
+        let candidate_optimal = candidate.status == SolveStatus::Optimal;
+        let current_optimal = current.status == SolveStatus::Optimal;
+        if candidate_optimal != current_optimal {
+            return candidate_optimal;
+        }
+
+        match (candidate.objective_value, current.objective_value) {
+            (Some(a), Some(b)) => a < b,
+            (Some(_), None) => true,
+            _ => false,
+        }
+    }
+
+    // Backends run in detached threads on their own copy of the problem so that
+    // the race can return as soon as a winner is known. Losing backends are
+    // cancelled and finish in the background.
+    // In deterministic mode, all backends run to completion and ties are broken
+    // by backend order so that thread scheduling does not change the result.
+    fn race<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        task: Task,
+        options: &SolverOptions,
+        external_token: Option<&CancellationToken>,
+        incumbent: Option<IncumbentCallback<'_, 'a, V, P>>,
+    ) -> SolveResult<'a, V, P> {
+        let problem = config_hint.get_problem();
+        let hint = config_hint.get_bool_vars();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut cancellation_tokens = Vec::with_capacity(self.backends.len());
+        for (index, backend) in self.backends.iter().enumerate() {
+            let cancellation_token = CancellationToken::new();
+            cancellation_tokens.push(cancellation_token.clone());
+
+            let backend = backend.clone();
+            let backend_options = backend.options.unwrap_or(*options);
+            let problem = problem.clone();
+            let hint = hint.clone();
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                let outcome = Self::run_backend(
+                    index,
+                    backend,
+                    problem,
+                    hint,
+                    task,
+                    backend_options,
+                    cancellation_token,
+                );
+                // The receiver might be gone if another backend already won
+                let _ = sender.send(outcome);
+            });
+        }
+        drop(sender);
+
+        let mode = if options.deterministic {
+            RaceMode::BestSolution
+        } else {
+            self.mode
+        };
+
+        let mut best: Option<(usize, BTreeMap<V, bool>, SolveStats)> = None;
+        let mut failure_stats = SolveStats::default();
+        loop {
+            let (index, vars_opt, stats) = match receiver.recv_timeout(PROGRESS_POLLING_INTERVAL) {
+                Ok(outcome) => outcome,
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                    if external_token.is_some_and(|t| t.is_cancelled()) {
+                        break;
+                    }
+                    continue;
+                }
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            };
+            match vars_opt {
+                Some(vars) => {
+                    let replace = match &best {
+                        Some((best_index, _, best_stats)) => {
+                            Self::is_better(&stats, best_stats)
+                                || (options.deterministic
+                                    && !Self::is_better(best_stats, &stats)
+                                    && index < *best_index)
+                        }
+                        None => true,
+                    };
+                    if replace {
+                        if let Some(incumbent) = incumbent {
+                            let config = problem
+                                .config_from(vars.clone())
+                                .expect("Variables should be valid")
+                                .into_feasable();
+                            if let Some(config) = &config {
+                                incumbent(config);
+                            }
+                        }
+                        best = Some((index, vars, stats));
+                    }
+                    if mode == RaceMode::FirstSolution {
+                        break;
+                    }
+                }
+                None => {
+                    if stats.status == SolveStatus::Infeasible {
+                        failure_stats = stats;
+                    }
+                }
+            }
+        }
+
+        for cancellation_token in &cancellation_tokens {
+            cancellation_token.cancel();
+        }
+
+        match best {
+            Some((_index, vars, stats)) => {
+                let config = problem
+                    .config_from(vars)
+                    .expect("Variables should be valid")
+                    .into_feasable();
+                SolveResult { config, stats }
+            }
+            None => SolveResult {
+                config: None,
+                stats: failure_stats,
+            },
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use crate::ilp::linexpr::Expr;
+use crate::ilp::solvers::{CancellationToken, FeasabilitySolver, SolveStatus, SolverOptions};
+use crate::ilp::tests::BruteForceSolver;
+use crate::ilp::{Config, DefaultRepr, FeasableConfig, ProblemBuilder};
+
+#[derive(Debug, Clone, Default)]
+struct FailingSolver {}
+
+impl FeasabilitySolver<String, DefaultRepr<String>> for FailingSolver {
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        _config: &Config<'a, String, DefaultRepr<String>>,
+        _options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, String, DefaultRepr<String>>> {
+        None
+    }
+
+    fn solve<'a>(
+        &self,
+        _config_hint: &Config<'a, String, DefaultRepr<String>>,
+        _minimize_objective: bool,
+        _options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, String, DefaultRepr<String>>> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct SlowSolver {}
+
+impl FeasabilitySolver<String, DefaultRepr<String>> for SlowSolver {
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        _config: &Config<'a, String, DefaultRepr<String>>,
+        _options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, String, DefaultRepr<String>>> {
+        None
+    }
+
+    fn solve<'a>(
+        &self,
+        _config_hint: &Config<'a, String, DefaultRepr<String>>,
+        _minimize_objective: bool,
+        _options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, String, DefaultRepr<String>>> {
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        None
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct FixedSolver {
+    true_var: String,
+    delay_in_ms: u64,
+}
+
+impl FeasabilitySolver<String, DefaultRepr<String>> for FixedSolver {
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        _config: &Config<'a, String, DefaultRepr<String>>,
+        _options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, String, DefaultRepr<String>>> {
+        None
+    }
+
+    fn solve<'a>(
+        &self,
+        config_hint: &Config<'a, String, DefaultRepr<String>>,
+        _minimize_objective: bool,
+        _options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, String, DefaultRepr<String>>> {
+        std::thread::sleep(std::time::Duration::from_millis(self.delay_in_ms));
+        config_hint
+            .get_problem()
+            .config_from([(self.true_var.clone(), true)])
+            .ok()?
+            .into_feasable()
+    }
+}
+
+#[test]
+fn portfolio_returns_solution_from_any_backend() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = super::Solver::new()
+        .add_backend(FailingSolver::default())
+        .add_backend(BruteForceSolver::default())
+        .add_backend(FailingSolver::default());
+    assert_eq!(solver.backend_count(), 3);
+
+    let result = solver.solve_with_stats(&pb.default_config(), false, &SolverOptions::default());
+
+    let config = result.config.expect("A backend should find a solution");
+    assert!(config.is_feasable());
+    assert!(std::ptr::eq(config.get_problem(), &pb));
+    assert_eq!(result.stats.status, SolveStatus::Feasible);
+}
+
+#[test]
+fn portfolio_best_solution_mode() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = super::Solver::with_mode(super::RaceMode::BestSolution)
+        .add_backend(BruteForceSolver::default())
+        .add_backend(FailingSolver::default());
+
+    let config = solver
+        .find_closest_solution(&pb.config_from([("Y", true)]).unwrap())
+        .expect("A backend should find a solution");
+    assert_eq!(config.get_bool("Y"), Ok(true));
+    assert_eq!(config.get_bool("X"), Ok(false));
+}
+
+#[test]
+fn portfolio_all_backends_fail() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = super::Solver::new()
+        .add_backend(FailingSolver::default())
+        .add_backend(FailingSolver::default());
+
+    let result = solver.solve_with_stats(&pb.default_config(), true, &SolverOptions::default());
+    assert!(result.config.is_none());
+}
+
+#[test]
+fn portfolio_reports_incumbents_and_stops_early() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = super::Solver::with_mode(super::RaceMode::BestSolution)
+        .add_backend(SlowSolver::default())
+        .add_backend(BruteForceSolver::default());
+
+    let cancellation_token = CancellationToken::new();
+    let incumbents = std::sync::Mutex::new(vec![]);
+    let incumbent = |config: &FeasableConfig<'_, String, DefaultRepr<String>>| {
+        incumbents.lock().unwrap().push(config.get_bool_vars());
+        // Good enough: stop without waiting for the slow backend
+        cancellation_token.cancel();
+    };
+
+    let start = std::time::Instant::now();
+    let result = solver.solve_anytime(
+        &pb.default_config(),
+        false,
+        &SolverOptions::new(),
+        &cancellation_token,
+        &|_| {},
+        &incumbent,
+    );
+
+    assert!(start.elapsed() < std::time::Duration::from_secs(2));
+    assert_eq!(result.stats.status, SolveStatus::Cancelled);
+    let config = result.config.unwrap();
+    assert_eq!(*incumbents.lock().unwrap(), vec![config.get_bool_vars()]);
+}
+
+#[test]
+fn portfolio_deterministic_mode_prefers_backend_order() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = super::Solver::new()
+        .add_backend(FixedSolver {
+            true_var: String::from("X"),
+            delay_in_ms: 300,
+        })
+        .add_backend(FixedSolver {
+            true_var: String::from("Y"),
+            delay_in_ms: 0,
+        });
+
+    let config = solver
+        .solve(&pb.default_config(), false, &SolverOptions::default())
+        .expect("A backend should find a solution");
+    assert_eq!(config.get_bool("Y"), Ok(true));
+
+    let options = SolverOptions::default().with_determinism(42);
+    let config = solver
+        .solve(&pb.default_config(), false, &options)
+        .expect("A backend should find a solution");
+    assert_eq!(config.get_bool("X"), Ok(true));
+}
@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod tests;
+
+use crate::ilp::linexpr::{Constraint, Sign};
+use crate::ilp::random::{DefaultRndGen, RandomGen};
+use crate::ilp::{Config, FeasableConfig, Problem};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{FeasabilitySolver, ProblemRepr, SolverOptions, VariableName};
+
+// Local search repair heuristic (WalkSAT-like).
+// It does not prove anything: it either finds a feasable config close to
+// the starting point or gives up. It is meant to provide anytime solutions
+// alongside exact solvers (for instance in a portfolio).
+#[derive(Debug, Clone)]
+pub struct Solver<R: RandomGen = DefaultRndGen> {
+    random_gen: R,
+    max_steps: usize,
+    noise: f64,
+}
+
+impl Default for Solver<DefaultRndGen> {
+    fn default() -> Self {
+        Solver::new()
+    }
+}
+
+impl Solver<DefaultRndGen> {
+    pub fn new() -> Self {
+        Solver::with_random_gen(DefaultRndGen::new())
+    }
+}
+
+impl<R: RandomGen> Solver<R> {
+    pub fn with_random_gen(random_gen: R) -> Self {
+        Solver {
+            random_gen,
+            max_steps: 10000,
+            noise: 0.2,
+        }
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    pub fn with_noise(mut self, noise: f64) -> Self {
+        assert!((0. ..=1.).contains(&noise), "noise should be in [0,1]");
+        self.noise = noise;
+        self
+    }
+
+    pub fn repair<'a, V: VariableName, P: ProblemRepr<V>>(
+        &self,
+        config: &Config<'a, V, P>,
+        options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        let start = std::time::Instant::now();
+        let time_limit = options
+            .time_limit_in_seconds
+            .map(|t| std::time::Duration::from_secs(u64::from(t)));
+
+        let mut config = config.clone();
+        let mut state = LhsState::new(&config);
+        for _ in 0..self.max_steps {
+            if state.violated.is_empty() {
+                return config.into_feasable();
+            }
+            if time_limit.is_some_and(|t| start.elapsed() >= t) {
+                return None;
+            }
+
+            // Pick a violated constraint and flip one of its variables
+            let violated: Vec<_> = state.violated.iter().copied().collect();
+            let constraint = state.constraints[self.random_gen.rand_elem(&violated)];
+            let vars: Vec<_> = constraint.variables().into_iter().collect();
+            if vars.is_empty() {
+                // Constant constraint that is violated: nothing can fix it
+                return None;
+            }
+
+            let var = if self.random_gen.random() < self.noise {
+                self.random_gen.rand_elem(&vars)
+            } else {
+                Self::best_flip(&config, &state, &vars)
+            };
+            Self::flip(&mut config, &mut state, &var);
+        }
+
+        config.into_feasable()
+    }
+
+    fn flip<V: VariableName, P: ProblemRepr<V>>(
+        config: &mut Config<'_, V, P>,
+        state: &mut LhsState<'_, V>,
+        var: &V,
+    ) {
+        let value = config.get_bool(var).expect("Variable should be valid");
+        config
+            .set_bool(var, !value)
+            .expect("Variable should be valid");
+        state.flip(var, value);
+    }
+
+    // Only the constraints containing a variable change when it is flipped
+    fn best_flip<V: VariableName, P: ProblemRepr<V>>(
+        config: &Config<'_, V, P>,
+        state: &LhsState<'_, V>,
+        vars: &[V],
+    ) -> V {
+        let mut best = None;
+        for var in vars {
+            let value = config.get_bool(var).expect("Variable should be valid");
+            let violation_change = state.violation_change(var, value);
+
+            match best {
+                Some((_, best_change)) if best_change <= violation_change => {}
+                _ => best = Some((var, violation_change)),
+            }
+        }
+
+        best.expect("There should be at least one variable")
+            .0
+            .clone()
+    }
+}
+
+// Left hand sides of the constraints (as given by Config::compute_lhs),
+// updated on each flip rather than recomputed.
+struct LhsState<'a, V: VariableName> {
+    constraints: Vec<&'a Constraint<V>>,
+    lhs: Vec<i32>,
+    // For each variable, the constraints it appears in with its coefficient
+    var_constraints: BTreeMap<V, Vec<(usize, i32)>>,
+    violated: BTreeSet<usize>,
+}
+
+impl<'a, V: VariableName> LhsState<'a, V> {
+    fn new<P: ProblemRepr<V>>(config: &Config<'a, V, P>) -> Self {
+        let constraints: Vec<_> = config.get_problem().get_constraints().iter().collect();
+        let lhs: Vec<_> = config.compute_lhs().into_values().collect();
+
+        let mut var_constraints = BTreeMap::<V, Vec<(usize, i32)>>::new();
+        for (i, constraint) in constraints.iter().enumerate() {
+            for (var, coef) in constraint.coefs() {
+                if *coef != 0 {
+                    var_constraints
+                        .entry(var.clone())
+                        .or_default()
+                        .push((i, *coef));
+                }
+            }
+        }
+
+        let violated = (0..constraints.len())
+            .filter(|i| Self::violation(constraints[*i], lhs[*i]) != 0)
+            .collect();
+
+        LhsState {
+            constraints,
+            lhs,
+            var_constraints,
+            violated,
+        }
+    }
+
+    fn violation(constraint: &Constraint<V>, lhs: i32) -> i32 {
+        match constraint.get_sign() {
+            Sign::Equals => lhs.abs(),
+            Sign::LessThan => lhs.max(0),
+        }
+    }
+
+    fn lhs_change(value: bool, coef: i32) -> i32 {
+        if value {
+            -coef
+        } else {
+            coef
+        }
+    }
+
+    // Change in total violation if the variable (currently equal to value) was flipped
+    fn violation_change(&self, var: &V, value: bool) -> i32 {
+        let Some(touched) = self.var_constraints.get(var) else {
+            return 0;
+        };
+        touched
+            .iter()
+            .map(|(i, coef)| {
+                let constraint = self.constraints[*i];
+                let new_lhs = self.lhs[*i] + Self::lhs_change(value, *coef);
+                Self::violation(constraint, new_lhs) - Self::violation(constraint, self.lhs[*i])
+            })
+            .sum()
+    }
+
+    fn flip(&mut self, var: &V, value: bool) {
+        let Some(touched) = self.var_constraints.get(var) else {
+            return;
+        };
+        for (i, coef) in touched {
+            self.lhs[*i] += Self::lhs_change(value, *coef);
+            if Self::violation(self.constraints[*i], self.lhs[*i]) != 0 {
+                self.violated.insert(*i);
+            } else {
+                self.violated.remove(i);
+            }
+        }
+    }
+}
+
+// Rounds a fractional solution (for instance from a relaxation) into a config.
+// The result is usually not feasable and should be passed through Solver::repair.
+pub fn round_config<'a, V, P, U, I>(
+    problem: &'a Problem<V, P>,
+    values: I,
+) -> crate::ilp::Result<Config<'a, V, P>, V>
+where
+    V: VariableName,
+    P: ProblemRepr<V>,
+    U: Into<V>,
+    I: IntoIterator<Item = (U, f64)>,
+{
+    problem.config_from(values.into_iter().map(|(var, value)| (var, value >= 0.5)))
+}
+
+impl<V: VariableName, P: ProblemRepr<V>, R: RandomGen> FeasabilitySolver<V, P> for Solver<R> {
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        config: &Config<'a, V, P>,
+        options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        self.repair(config, options)
+    }
+
+    // The objective is ignored: local moves only target feasability
+    fn solve<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        _minimize_objective: bool,
+        options: &SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        self.repair(config_hint, options)
+    }
+}
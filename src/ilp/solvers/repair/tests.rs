@@ -0,0 +1,112 @@
+use crate::ilp::linexpr::Expr;
+use crate::ilp::solvers::{FeasabilitySolver, SolverOptions};
+use crate::ilp::{DefaultRepr, ProblemBuilder};
+
+#[test]
+fn repair_finds_feasable_config() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["A", "B", "C", "D"])
+        .unwrap()
+        .add_constraint((Expr::var("A") + Expr::var("B")).eq(&Expr::constant(1)))
+        .unwrap()
+        .add_constraint((Expr::var("C") + Expr::var("D")).eq(&Expr::constant(1)))
+        .unwrap()
+        .add_constraint((Expr::var("A") + Expr::var("C")).leq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = super::Solver::new();
+    let config = pb.config_from([("A", true), ("C", true)]).unwrap();
+    let result = solver
+        .repair(&config, &SolverOptions::new())
+        .expect("A feasable config should be found");
+
+    assert!(result.inner().is_feasable());
+}
+
+#[test]
+fn repair_keeps_feasable_config() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["A", "B"])
+        .unwrap()
+        .add_constraint((Expr::var("A") + Expr::var("B")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = super::Solver::new();
+    let config = pb.config_from([("B", true)]).unwrap();
+    let result = solver.find_closest_solution(&config).unwrap();
+
+    assert_eq!(result.into_inner(), config);
+}
+
+#[test]
+fn repair_gives_up_on_infeasable_problem() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["A", "B"])
+        .unwrap()
+        .add_constraint((Expr::var("A") + Expr::var("B")).eq(&Expr::constant(1)))
+        .unwrap()
+        .add_constraint((Expr::var("A") + Expr::var("B")).eq(&Expr::constant(2)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = super::Solver::new().with_max_steps(100);
+    let config = pb.default_config();
+
+    assert!(solver
+        .solve(&config, false, &SolverOptions::new())
+        .is_none());
+}
+
+#[test]
+fn round_config_from_fractional_values() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["A", "B", "C"])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let config = super::round_config(&pb, [("A", 0.7), ("B", 0.2), ("C", 0.5)]).unwrap();
+
+    assert_eq!(config, pb.config_from([("A", true), ("C", true)]).unwrap());
+}
+
+#[test]
+fn lhs_state_follows_flips() {
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["A", "B", "C"])
+        .unwrap()
+        .add_constraint((Expr::var("A") + Expr::var("B")).eq(&Expr::constant(1)))
+        .unwrap()
+        .add_constraint((2 * Expr::var("A") - Expr::var("C")).leq(&Expr::constant(1)))
+        .unwrap()
+        .add_constraint((Expr::var("B") + Expr::var("C")).leq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let mut config = pb.config_from([("B", true)]).unwrap();
+    let mut state = super::LhsState::new(&config);
+
+    for var in ["A", "C", "B", "A", "C"] {
+        let var = String::from(var);
+        let value = config.get_bool(&var).unwrap();
+        let expected_change = state.violation_change(&var, value);
+        let violation_before: i32 = config.constraint_violations().into_values().sum();
+
+        super::Solver::<crate::ilp::random::DefaultRndGen>::flip(&mut config, &mut state, &var);
+
+        let violations = config.constraint_violations();
+        let violation_after: i32 = violations.values().sum();
+        assert_eq!(violation_after - violation_before, expected_change);
+        assert_eq!(
+            state.lhs,
+            config.compute_lhs().into_values().collect::<Vec<_>>()
+        );
+        let violated: std::collections::BTreeSet<_> = state
+            .violated
+            .iter()
+            .map(|i| state.constraints[*i].clone())
+            .collect();
+        assert_eq!(violated, violations.into_keys().collect());
+    }
+}
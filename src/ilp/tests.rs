@@ -133,7 +133,7 @@ fn problem_extra_variable() {
         .unwrap()
         .build::<DefaultRepr<String>>();
 
-    assert_eq!(pb.variables, BTreeSet::from([String::from("X")]));
+    assert_eq!(pb.variables, vec![String::from("X")]);
 }
 
 #[test]
@@ -147,14 +147,15 @@ fn problem_extra_variables() {
         .unwrap()
         .build::<DefaultRepr<String>>();
 
+    // Variables are kept sorted
     assert_eq!(
         pb.variables,
-        BTreeSet::from([
+        vec![
+            String::from("W"),
             String::from("X"),
             String::from("Y"),
             String::from("Z"),
-            String::from("W"),
-        ])
+        ]
     );
 }
 
@@ -202,3 +203,965 @@ fn problem_filter_variable() {
     assert_eq!(pb1.constraints, pb2.constraints);
     assert_eq!(pb1.variables, pb2.variables);
 }
+
+#[test]
+fn problem_with_groups_disabled() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y", "Z"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)))
+        .unwrap()
+        .add_grouped_constraint(
+            (Expr::var("Y") + Expr::var("Z")).eq(&Expr::constant(1)),
+            "group1",
+        )
+        .unwrap()
+        .add_grouped_constraints(
+            [
+                (Expr::var("X") + Expr::var("Z")).eq(&Expr::constant(1)),
+                Expr::var("X").eq(&Expr::constant(1)),
+            ],
+            "group2",
+        )
+        .unwrap()
+        .add_grouped_constraint(Expr::var("X").eq(&Expr::constant(1)), "group1")
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    assert_eq!(
+        pb.get_groups(),
+        BTreeSet::from([String::from("group1"), String::from("group2")])
+    );
+
+    let pb1 = pb.with_groups_disabled(["group1"]);
+    let expected1 = BTreeSet::from([
+        (Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)),
+        (Expr::var("X") + Expr::var("Z")).eq(&Expr::constant(1)),
+        Expr::var("X").eq(&Expr::constant(1)),
+    ]);
+    assert_eq!(pb1.constraints, expected1);
+
+    let pb2 = pb.with_groups_disabled(["group1", "group2"]);
+    let expected2 = BTreeSet::from([(Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1))]);
+    assert_eq!(pb2.constraints, expected2);
+    assert_eq!(pb2.get_groups(), BTreeSet::new());
+}
+
+#[test]
+fn problem_ungrouped_constraint_cannot_be_disabled() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_grouped_constraint(
+            (Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)),
+            "group",
+        )
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>()
+        .with_groups_disabled(["group"]);
+
+    assert_eq!(
+        pb.constraints,
+        BTreeSet::from([(Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1))])
+    );
+}
+
+#[test]
+fn problem_soft_constraints() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_soft_constraint(
+            (Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)),
+            2.,
+            "at_most_one",
+        )
+        .unwrap()
+        .add_soft_constraints([Expr::var("X").eq(&Expr::var("Y"))], 3., "same")
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    assert!(pb.get_constraints().is_empty());
+
+    let obj_terms = pb.get_objective_terms();
+    assert_eq!(obj_terms.len(), 2);
+
+    assert_eq!(obj_terms[0].coef, 2.);
+    assert_eq!(obj_terms[0].desc.as_deref(), Some("at_most_one"));
+    assert_eq!(
+        obj_terms[0].exprs,
+        BTreeSet::from([
+            Expr::var("X") + Expr::var("Y") - Expr::constant(1),
+            Expr::constant(0)
+        ])
+    );
+
+    assert_eq!(obj_terms[1].coef, 3.);
+    assert_eq!(obj_terms[1].desc.as_deref(), Some("same"));
+    assert_eq!(
+        obj_terms[1].exprs,
+        BTreeSet::from([
+            Expr::var("X") - Expr::var("Y"),
+            Expr::var("Y") - Expr::var("X")
+        ])
+    );
+}
+
+#[test]
+fn problem_soft_constraint_undeclared_variable() {
+    use crate::ilp::linexpr::Expr;
+
+    let res = ProblemBuilder::<String>::new()
+        .add_bool_variable("X")
+        .unwrap()
+        .add_soft_constraint(
+            (Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)),
+            1.,
+            "at_most_one",
+        );
+
+    assert_eq!(
+        res.err(),
+        Some(ConstraintError::UndeclaredVariable(String::from("Y")))
+    );
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct BruteForceSolver {}
+
+impl<V: VariableName, P: ProblemRepr<V>> solvers::FeasabilitySolver<V, P> for BruteForceSolver {
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        config: &Config<'a, V, P>,
+        _options: &solvers::SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        let problem = config.get_problem();
+        let variables: Vec<_> = problem.get_variables().to_vec();
+        let current = config.get_bool_vars();
+
+        let mut best: Option<(usize, FeasableConfig<'a, V, P>)> = None;
+        for mask in 0..(1usize << variables.len()) {
+            let values: Vec<_> = variables
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (v.clone(), (mask >> i) & 1 == 1))
+                .collect();
+            let dist = values.iter().filter(|(v, b)| current[v] != *b).count();
+            let candidate = problem.config_from(values).unwrap();
+            if let Some(feasable) = candidate.into_feasable() {
+                if best.as_ref().map(|(d, _)| dist < *d).unwrap_or(true) {
+                    best = Some((dist, feasable));
+                }
+            }
+        }
+
+        best.map(|(_, c)| c)
+    }
+
+    fn solve<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        _minimize_objective: bool,
+        options: &solvers::SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        self.find_closest_solution_with_options(config_hint, options)
+    }
+}
+
+// Brute force search is exhaustive so no solution proves the problem is infeasible
+#[derive(Debug, Clone, Default)]
+struct ProvingBruteForceSolver {}
+
+impl<V: VariableName, P: ProblemRepr<V>> solvers::FeasabilitySolver<V, P>
+    for ProvingBruteForceSolver
+{
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        config: &Config<'a, V, P>,
+        options: &solvers::SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        BruteForceSolver::default().find_closest_solution_with_options(config, options)
+    }
+
+    fn solve<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &solvers::SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        BruteForceSolver::default().solve(config_hint, minimize_objective, options)
+    }
+
+    fn solve_with_stats<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &solvers::SolverOptions,
+    ) -> solvers::SolveResult<'a, V, P> {
+        let config = self.solve(config_hint, minimize_objective, options);
+        let status = if config.is_some() {
+            solvers::SolveStatus::Feasible
+        } else {
+            solvers::SolveStatus::Infeasible
+        };
+
+        solvers::SolveResult {
+            config,
+            stats: solvers::SolveStats {
+                status,
+                ..solvers::SolveStats::default()
+            },
+        }
+    }
+}
+
+// Same as BruteForceSolver but honors minimize_objective
+#[derive(Debug, Clone, Default)]
+pub(super) struct MinimizingBruteForceSolver {}
+
+impl<V: VariableName, P: ProblemRepr<V>> solvers::FeasabilitySolver<V, P>
+    for MinimizingBruteForceSolver
+{
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        config: &Config<'a, V, P>,
+        options: &solvers::SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        BruteForceSolver::default().find_closest_solution_with_options(config, options)
+    }
+
+    fn solve<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        minimize_objective: bool,
+        options: &solvers::SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        if !minimize_objective {
+            return self.find_closest_solution_with_options(config_hint, options);
+        }
+
+        let problem = config_hint.get_problem();
+        let variables: Vec<_> = problem.get_variables().to_vec();
+
+        let mut best: Option<FeasableConfig<'a, V, P>> = None;
+        for mask in 0..(1usize << variables.len()) {
+            let values: Vec<_> = variables
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (v.clone(), (mask >> i) & 1 == 1))
+                .collect();
+            let candidate = problem.config_from(values).unwrap();
+            if let Some(feasable) = candidate.into_feasable() {
+                if best
+                    .as_ref()
+                    .map(|b| feasable.objective_value() < b.objective_value())
+                    .unwrap_or(true)
+                {
+                    best = Some(feasable);
+                }
+            }
+        }
+
+        best
+    }
+}
+
+#[test]
+fn problem_iis_feasable() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    assert_eq!(
+        pb.compute_iis(
+            &ProvingBruteForceSolver::default(),
+            &solvers::SolverOptions::default()
+        ),
+        None
+    );
+}
+
+#[test]
+fn problem_iis_infeasable() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y", "Z"])
+        .unwrap()
+        .add_constraints([
+            (Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)),
+            Expr::var("X").eq(&Expr::constant(1)),
+            Expr::var("Y").eq(&Expr::constant(1)),
+            (Expr::var("Y") + Expr::var("Z")).leq(&Expr::constant(1)),
+            Expr::var("Z").eq(&Expr::constant(1)),
+        ])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = ProvingBruteForceSolver::default();
+    let iis = pb
+        .compute_iis(&solver, &solvers::SolverOptions::default())
+        .expect("Problem should be infeasable");
+
+    assert_eq!(iis.len(), 3);
+    assert_eq!(
+        pb.subproblem_status(&solver, &solvers::SolverOptions::default(), &iis),
+        solvers::SolveStatus::Infeasible
+    );
+    for constraint in &iis {
+        let mut smaller = iis.clone();
+        smaller.remove(constraint);
+        assert_eq!(
+            pb.subproblem_status(&solver, &solvers::SolverOptions::default(), &smaller),
+            solvers::SolveStatus::Feasible
+        );
+    }
+}
+
+// Only proves infeasibility for the full problem: every smaller sub-solve "times out"
+struct TimeoutSolver {
+    constraint_count: usize,
+}
+
+impl<V: VariableName, P: ProblemRepr<V>> solvers::FeasabilitySolver<V, P> for TimeoutSolver {
+    fn find_closest_solution_with_options<'a>(
+        &self,
+        _config: &Config<'a, V, P>,
+        _options: &solvers::SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        None
+    }
+
+    fn solve<'a>(
+        &self,
+        _config_hint: &Config<'a, V, P>,
+        _minimize_objective: bool,
+        _options: &solvers::SolverOptions,
+    ) -> Option<FeasableConfig<'a, V, P>> {
+        None
+    }
+
+    fn solve_with_stats<'a>(
+        &self,
+        config_hint: &Config<'a, V, P>,
+        _minimize_objective: bool,
+        _options: &solvers::SolverOptions,
+    ) -> solvers::SolveResult<'a, V, P> {
+        let status = if config_hint.get_problem().get_constraints().len() == self.constraint_count {
+            solvers::SolveStatus::Infeasible
+        } else {
+            solvers::SolveStatus::TimeLimitReached
+        };
+
+        solvers::SolveResult {
+            config: None,
+            stats: solvers::SolveStats {
+                status,
+                ..solvers::SolveStats::default()
+            },
+        }
+    }
+}
+
+#[test]
+fn problem_iis_keeps_constraints_on_timeout() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraints([
+            Expr::var("X").eq(&Expr::constant(1)),
+            Expr::var("Y").eq(&Expr::constant(1)),
+            (Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)),
+        ])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let solver = TimeoutSolver {
+        constraint_count: 3,
+    };
+    let iis = pb
+        .compute_iis(&solver, &solvers::SolverOptions::default())
+        .expect("Problem should be infeasable");
+    assert_eq!(&iis, pb.get_constraints());
+
+    let solver = TimeoutSolver {
+        constraint_count: 0,
+    };
+    assert_eq!(
+        pb.compute_iis(&solver, &solvers::SolverOptions::default()),
+        None
+    );
+}
+
+#[test]
+fn problem_suggest_relaxations() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .add_grouped_constraint(Expr::var("X").eq(&Expr::constant(1)), "x_set")
+        .unwrap()
+        .add_grouped_constraint(Expr::var("Y").eq(&Expr::constant(1)), "y_set")
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let relaxable_groups =
+        BTreeMap::from([(String::from("x_set"), 2.), (String::from("y_set"), 1.)]);
+    let suggestions = pb.suggest_relaxations(
+        &MinimizingBruteForceSolver::default(),
+        &relaxable_groups,
+        5,
+        &solvers::SolverOptions::default(),
+    );
+
+    assert_eq!(
+        suggestions,
+        vec![
+            RelaxationSuggestion {
+                groups: BTreeSet::from([String::from("y_set")]),
+                cost: 1.,
+            },
+            RelaxationSuggestion {
+                groups: BTreeSet::from([String::from("x_set")]),
+                cost: 2.,
+            },
+        ]
+    );
+}
+
+#[test]
+fn problem_suggest_relaxations_multiple_groups() {
+    use crate::ilp::linexpr::Expr;
+
+    // X = 1 belongs to both groups so it is only dropped when both are relaxed
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(0)))
+        .unwrap()
+        .add_grouped_constraint(Expr::var("X").eq(&Expr::constant(1)), "a")
+        .unwrap()
+        .add_grouped_constraint(Expr::var("X").eq(&Expr::constant(1)), "b")
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let relaxable_groups = BTreeMap::from([(String::from("a"), 1.), (String::from("b"), 5.)]);
+    let suggestions = pb.suggest_relaxations(
+        &MinimizingBruteForceSolver::default(),
+        &relaxable_groups,
+        5,
+        &solvers::SolverOptions::default(),
+    );
+
+    assert_eq!(
+        suggestions,
+        vec![RelaxationSuggestion {
+            groups: BTreeSet::from([String::from("a"), String::from("b")]),
+            cost: 6.,
+        }]
+    );
+    let relaxed = pb.with_groups_disabled(suggestions[0].groups.clone());
+    assert_eq!(
+        relaxed.subproblem_status(
+            &ProvingBruteForceSolver::default(),
+            &solvers::SolverOptions::default(),
+            &relaxed.constraints
+        ),
+        solvers::SolveStatus::Feasible
+    );
+
+    let relaxable_groups = BTreeMap::from([(String::from("a"), 1.)]);
+    assert!(pb
+        .suggest_relaxations(
+            &MinimizingBruteForceSolver::default(),
+            &relaxable_groups,
+            5,
+            &solvers::SolverOptions::default(),
+        )
+        .is_empty());
+}
+
+#[test]
+fn problem_suggest_relaxations_feasable() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_grouped_constraint(
+            (Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)),
+            "sum",
+        )
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let relaxable_groups = BTreeMap::from([(String::from("sum"), 1.)]);
+    assert!(pb
+        .suggest_relaxations(
+            &MinimizingBruteForceSolver::default(),
+            &relaxable_groups,
+            5,
+            &solvers::SolverOptions::default(),
+        )
+        .is_empty());
+}
+
+#[test]
+fn solve_stats_gap() {
+    use solvers::SolveStats;
+
+    let stats = SolveStats {
+        objective_value: Some(100.),
+        best_bound: Some(97.),
+        ..SolveStats::default()
+    };
+    assert_eq!(stats.gap(), Some(0.03));
+
+    let stats = SolveStats {
+        objective_value: Some(0.),
+        best_bound: Some(0.),
+        ..SolveStats::default()
+    };
+    assert_eq!(stats.gap(), Some(0.));
+
+    let stats = SolveStats {
+        objective_value: Some(100.),
+        ..SolveStats::default()
+    };
+    assert_eq!(stats.gap(), None);
+}
+
+#[test]
+fn default_solve_with_stats() {
+    use crate::ilp::linexpr::Expr;
+    use solvers::{FeasabilitySolver, SolveStatus};
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let result = BruteForceSolver::default().solve_with_stats(
+        &pb.default_config(),
+        false,
+        &solvers::SolverOptions::default(),
+    );
+    assert!(result.config.is_some());
+    assert_eq!(result.stats.status, SolveStatus::Feasible);
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X"])
+        .unwrap()
+        .add_constraint(Expr::var("X").eq(&Expr::constant(2)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let result = BruteForceSolver::default().solve_with_stats(
+        &pb.default_config(),
+        false,
+        &solvers::SolverOptions::default(),
+    );
+    assert!(result.config.is_none());
+    assert_eq!(result.stats.status, SolveStatus::Unknown);
+}
+
+#[test]
+fn solve_with_progress_reports_events() {
+    use crate::ilp::linexpr::Expr;
+    use solvers::{CancellationToken, FeasabilitySolver, SolveProgress, SolveStatus};
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let events = std::sync::Mutex::new(Vec::new());
+    let progress = |p: SolveProgress| events.lock().unwrap().push(p);
+
+    let result = BruteForceSolver::default().solve_with_progress(
+        &pb.default_config(),
+        false,
+        &solvers::SolverOptions::default(),
+        &CancellationToken::new(),
+        &progress,
+    );
+    assert!(result.config.is_some());
+
+    let events = events.into_inner().unwrap();
+    assert_eq!(events.first(), Some(&SolveProgress::Started));
+    assert!(events.contains(&SolveProgress::IncumbentFound {
+        objective_value: None
+    }));
+    assert_eq!(events.last(), Some(&SolveProgress::Finished(result.stats)));
+    assert_eq!(result.stats.status, SolveStatus::Feasible);
+}
+
+#[test]
+fn solve_with_progress_cancelled() {
+    use crate::ilp::linexpr::Expr;
+    use solvers::{CancellationToken, FeasabilitySolver, SolveProgress, SolveStatus};
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).eq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let token = CancellationToken::new();
+    token.clone().cancel();
+    assert!(token.is_cancelled());
+
+    let events = std::sync::Mutex::new(Vec::new());
+    let progress = |p: SolveProgress| events.lock().unwrap().push(p);
+
+    let result = BruteForceSolver::default().solve_with_progress(
+        &pb.default_config(),
+        false,
+        &solvers::SolverOptions::default(),
+        &token,
+        &progress,
+    );
+    assert!(result.config.is_none());
+    assert_eq!(result.stats.status, SolveStatus::Cancelled);
+    assert_eq!(
+        events.into_inner().unwrap(),
+        vec![SolveProgress::Finished(result.stats)]
+    );
+}
+
+#[test]
+fn dense_and_default_repr_agree() {
+    use crate::ilp::linexpr::Expr;
+
+    let builder = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y", "Z"])
+        .unwrap()
+        .add_constraints([
+            (Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)),
+            (Expr::var("Y") + Expr::var("Z")).eq(&Expr::constant(1)),
+        ])
+        .unwrap();
+
+    let sparse_pb = builder.clone().build::<DefaultRepr<String>>();
+    let dense_pb = builder.build::<DenseRepr<String>>();
+
+    for mask in 0..8 {
+        let vars = [
+            ("X", mask & 1 == 1),
+            ("Y", mask & 2 == 2),
+            ("Z", mask & 4 == 4),
+        ];
+
+        let sparse_config = sparse_pb.config_from(vars).unwrap();
+        let dense_config = dense_pb.config_from(vars).unwrap();
+
+        assert_eq!(sparse_config.is_feasable(), dense_config.is_feasable());
+        assert_eq!(sparse_config.compute_lhs(), dense_config.compute_lhs());
+    }
+}
+
+#[test]
+fn config_constraint_violations() {
+    use crate::ilp::linexpr::Expr;
+
+    let c1 = (Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1));
+    let c2 = (Expr::var("X") + Expr::var("Z")).eq(&Expr::constant(1));
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y", "Z"])
+        .unwrap()
+        .add_constraints([c1.clone(), c2.clone()])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let config = pb.config_from([("X", true), ("Z", false)]).unwrap();
+    assert_eq!(config.constraint_violations(), BTreeMap::new());
+
+    let config = pb
+        .config_from([("X", true), ("Y", true), ("Z", true)])
+        .unwrap();
+    assert_eq!(
+        config.constraint_violations(),
+        BTreeMap::from([(c1.clone(), 1), (c2.clone(), 1)])
+    );
+
+    let config = pb.default_config();
+    assert_eq!(config.constraint_violations(), BTreeMap::from([(c2, 1)]));
+}
+
+#[test]
+fn config_objective_value() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y", "Z"])
+        .unwrap()
+        .set_objective_contrib("X", 2.)
+        .unwrap()
+        .add_soft_constraint(
+            (Expr::var("Y") + Expr::var("Z")).leq(&Expr::constant(1)),
+            3.,
+            "at_most_one",
+        )
+        .unwrap()
+        .add_objective_term(0.5, [Expr::var("X") - Expr::var("Z"), Expr::constant(-1)])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    assert_eq!(pb.default_config().objective_value(), 0.);
+
+    let config = pb.config_from([("X", true)]).unwrap();
+    assert_eq!(config.objective_value(), 2.5);
+
+    let config = pb.config_from([("Y", true), ("Z", true)]).unwrap();
+    assert_eq!(config.objective_value(), 2.5);
+
+    let config = pb
+        .config_from([("X", true), ("Y", true), ("Z", true)])
+        .unwrap();
+    assert_eq!(config.objective_value(), 5.);
+}
+
+#[test]
+fn config_soft_constraint_penalties() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y", "Z"])
+        .unwrap()
+        .add_soft_constraints(
+            [
+                (Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)),
+                (Expr::var("Y") + Expr::var("Z")).leq(&Expr::constant(1)),
+            ],
+            2.,
+            "at_most_one",
+        )
+        .unwrap()
+        .add_soft_constraint(Expr::var("X").eq(&Expr::constant(1)), 1., "x_set")
+        .unwrap()
+        .add_objective_term(5., [Expr::var("Z")])
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    assert_eq!(
+        pb.get_soft_constraint_descs(),
+        BTreeSet::from([String::from("at_most_one"), String::from("x_set")])
+    );
+
+    // Unnamed objective terms are not reported
+    let config = pb
+        .config_from([("X", true), ("Y", true), ("Z", true)])
+        .unwrap();
+    assert_eq!(
+        config.soft_constraint_penalties(),
+        BTreeMap::from([
+            (String::from("at_most_one"), 4.),
+            (String::from("x_set"), 0.)
+        ])
+    );
+    assert_eq!(config.objective_value(), 9.);
+}
+
+#[test]
+fn problem_stats() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y", "Z", "T"])
+        .unwrap()
+        .add_constraints([
+            (Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)),
+            (2 * Expr::var("X") - 5 * Expr::var("Z")).eq(&Expr::constant(1)),
+        ])
+        .unwrap()
+        .add_soft_constraint(Expr::var("T").eq(&Expr::constant(1)), 1., "t_set")
+        .unwrap()
+        .set_objective_contrib("Y", 1.)
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let stats = pb.stats();
+    assert_eq!(
+        stats,
+        ProblemStats {
+            bool_variable_count: 4,
+            constraint_count: 2,
+            equality_count: 1,
+            inequality_count: 1,
+            objective_term_count: 1,
+            soft_constraint_count: 1,
+            objective_contrib_count: 1,
+            nonzero_count: 4,
+            density: 0.5,
+            coefficient_range: Some((1, 5)),
+        }
+    );
+
+    let empty_stats = ProblemBuilder::<String>::new()
+        .build::<DefaultRepr<String>>()
+        .stats();
+    assert_eq!(empty_stats.density, 0.);
+    assert_eq!(empty_stats.coefficient_range, None);
+}
+
+#[test]
+fn problem_serde_roundtrip() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y", "Z"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)))
+        .unwrap()
+        .add_grouped_constraint(
+            (Expr::var("Y") - 2 * Expr::var("Z")).eq(&Expr::constant(0)),
+            "group",
+        )
+        .unwrap()
+        .add_soft_constraint(Expr::var("Z").eq(&Expr::constant(1)), 2.5, "z_set")
+        .unwrap()
+        .set_objective_contrib("X", 1.5)
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+
+    let json = serde_json::to_string(&pb).unwrap();
+    let pb2: Problem<String> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(pb.variables, pb2.variables);
+    assert_eq!(pb.constraints, pb2.constraints);
+    assert_eq!(pb.constraint_groups, pb2.constraint_groups);
+    assert_eq!(pb.objective_contribs, pb2.objective_contribs);
+    assert_eq!(pb.objective_terms.len(), pb2.objective_terms.len());
+    for (t1, t2) in pb.objective_terms.iter().zip(pb2.objective_terms.iter()) {
+        assert_eq!(t1.coef, t2.coef);
+        assert_eq!(t1.exprs, t2.exprs);
+        assert_eq!(t1.desc, t2.desc);
+    }
+
+    let config = pb.config_from([("X", true), ("Z", true)]).unwrap();
+    let config_json = serde_json::to_string(&config).unwrap();
+    let bool_vars: Vec<(String, bool)> = serde_json::from_str(&config_json).unwrap();
+    let config2 = pb2.config_from(bool_vars).unwrap();
+
+    assert_eq!(config.get_bool_vars(), config2.get_bool_vars());
+}
+
+#[test]
+fn problem_deserialization_is_validated() {
+    let json = r#"{
+        "variables": ["X"],
+        "constraints": [],
+        "constraint_groups": [],
+        "objective_terms": [],
+        "objective_contribs": [["Y", 1.0]]
+    }"#;
+    assert!(serde_json::from_str::<Problem<String>>(json).is_err());
+
+    let json = r#"{
+        "variables": ["X", "X"],
+        "constraints": [],
+        "constraint_groups": [],
+        "objective_terms": [],
+        "objective_contribs": []
+    }"#;
+    assert!(serde_json::from_str::<Problem<String>>(json).is_err());
+
+    let json = r#"{
+        "variables": ["X"],
+        "constraints": [],
+        "constraint_groups": [],
+        "objective_terms": [],
+        "objective_contribs": [["X", 1.0]]
+    }"#;
+    let pb: Problem<String> = serde_json::from_str(json).unwrap();
+    assert_eq!(pb.get_objective_contribs().get("X"), Some(&1.));
+}
+
+#[test]
+fn problem_tolerance() {
+    use crate::ilp::linexpr::Expr;
+
+    let pb = ProblemBuilder::<String>::new()
+        .add_bool_variables(["X", "Y"])
+        .unwrap()
+        .add_constraint((Expr::var("X") + Expr::var("Y")).leq(&Expr::constant(1)))
+        .unwrap()
+        .build::<DefaultRepr<String>>();
+    assert_eq!(pb.get_tolerance(), DEFAULT_TOLERANCE);
+
+    assert_eq!(
+        pb.clone().into_builder().with_tolerance(-1.).err(),
+        Some(ToleranceError::InvalidTolerance)
+    );
+    assert_eq!(
+        pb.clone().into_builder().with_tolerance(f64::NAN).err(),
+        Some(ToleranceError::InvalidTolerance)
+    );
+
+    let pb = pb.into_builder().with_tolerance(1e-3).unwrap();
+    assert_eq!(pb.get_tolerance(), 1e-3);
+
+    let pb = pb
+        .filter_variables(|v| v != "Y")
+        .build::<DefaultRepr<String>>();
+    assert_eq!(pb.get_tolerance(), 1e-3);
+
+    let json = serde_json::to_string(&pb).unwrap();
+    let pb2: Problem<String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(pb2.get_tolerance(), 1e-3);
+}
+
+#[test]
+fn solver_values_are_rounded() {
+    use crate::ilp::solvers::bool_from_solver_value;
+
+    assert_eq!(bool_from_solver_value(1., 1e-6), Some(true));
+    assert_eq!(bool_from_solver_value(0., 1e-6), Some(false));
+    assert_eq!(bool_from_solver_value(1. - 1e-7, 1e-6), Some(true));
+    assert_eq!(bool_from_solver_value(-1e-7, 1e-6), Some(false));
+    assert_eq!(bool_from_solver_value(0.9, 1e-6), None);
+    assert_eq!(bool_from_solver_value(0.9, 0.2), Some(true));
+    assert_eq!(bool_from_solver_value(0.4, 0.5), Some(false));
+}
+
+#[test]
+fn solver_profiles() {
+    use crate::ilp::solvers::{SolverOptions, SolverProfile};
+
+    for profile in SolverProfile::ALL {
+        assert_eq!(SolverProfile::from_name(profile.name()), Some(profile));
+    }
+    assert_eq!(SolverProfile::from_name("unknown"), None);
+
+    assert_eq!(SolverProfile::default(), SolverProfile::Balanced);
+    assert_eq!(SolverProfile::Balanced.options(), SolverOptions::default());
+    assert!(!SolverProfile::QuickDraft.minimize_objective());
+    assert_eq!(
+        SolverProfile::Exhaustive.options().time_limit_in_seconds,
+        None
+    );
+}
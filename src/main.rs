@@ -15,7 +15,8 @@ struct Cli {
     /// Create new database - won't override an existing one
     #[arg(short, long, default_value_t = false)]
     create: bool,
-    /// Sqlite file (to open or create) that contains the database
+    /// Sqlite file (to open or create) that contains the database.
+    /// A .json file is used as a canonical text file (suitable for version control).
     db: std::path::PathBuf,
     #[command(subcommand)]
     command: Option<CliCommand>,
@@ -49,11 +50,27 @@ enum ShellExtraCommand {
     Exit,
 }
 
+fn is_text_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
 async fn connect_db(create: bool, path: &std::path::Path) -> Result<sqlite::Store> {
+    if is_text_file(path) {
+        if create {
+            return Ok(sqlite::Store::new_text(path).await?);
+        }
+        return Ok(sqlite::Store::open_text(path).await?);
+    }
+
     if create {
         Ok(sqlite::Store::new_db(path).await?)
     } else {
-        Ok(sqlite::Store::open_db(path).await?)
+        let store = sqlite::Store::open_db(path).await?;
+        for migration in store.applied_migrations() {
+            eprintln!("Database migrated: {}", migration);
+        }
+        Ok(store)
     }
 }
 
@@ -99,7 +116,10 @@ impl reedline::Completer for ReedCompleter {
     }
 }
 
-async fn interactive_shell(app_state: &mut AppState<sqlite::Store>) -> Result<()> {
+async fn interactive_shell(
+    app_state: &mut AppState<sqlite::Store>,
+    store: &sqlite::Store,
+) -> Result<()> {
     use nu_ansi_term::{Color, Style};
     use reedline::{
         DefaultHinter, Emacs, FileBackedHistory, IdeMenu, KeyCode, KeyModifiers, MenuBuilder,
@@ -129,7 +149,12 @@ async fn interactive_shell(app_state: &mut AppState<sqlite::Store>) -> Result<()
         .with_history(Box::new(FileBackedHistory::new(10000).unwrap()));
 
     loop {
-        match respond(&mut rl, app_state).await {
+        let response = respond(&mut rl, app_state).await;
+        // Text files are saved after each command as sqlite files would be
+        if let Err(err) = store.save().await {
+            eprintln!("Failed to save database: {err}");
+        }
+        match response {
             Ok(quit) => {
                 if quit {
                     break;
@@ -207,19 +232,20 @@ async fn respond(
 async fn main() -> Result<()> {
     let args = Cli::parse();
 
-    let logic = Logic::new(connect_db(args.create, args.db.as_path()).await?);
+    let store = connect_db(args.create, args.db.as_path()).await?;
+    let logic = Logic::new(store.clone());
     let mut app_state = AppState::new(logic);
 
     collomatique::frontend::python::initialize();
 
     let Some(command) = args.command else {
-        interactive_shell(&mut app_state).await?;
+        interactive_shell(&mut app_state, &store).await?;
         return Ok(());
     };
 
-    let output =
-        collomatique::frontend::shell::execute_cli_command(command, &mut app_state).await?;
-    if let Some(msg) = output {
+    let output = collomatique::frontend::shell::execute_cli_command(command, &mut app_state).await;
+    store.save().await?;
+    if let Some(msg) = output? {
         print!("{}", msg);
     }
 
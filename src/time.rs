@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
 pub enum Weekday {
     #[default]
@@ -24,23 +27,17 @@ impl Weekday {
         .iter()
         .copied()
     }
+
+    // Sunday is followed by monday (of the next week)
+    pub fn next(&self) -> Self {
+        let index = (usize::from(*self) + 1) % 7;
+        Weekday::try_from(index).expect("Index should be less than 7")
+    }
 }
 
 impl std::fmt::Display for Weekday {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match *self {
-                Weekday::Monday => "Lundi",
-                Weekday::Tuesday => "Mardi",
-                Weekday::Wednesday => "Mercredi",
-                Weekday::Thursday => "Jeudi",
-                Weekday::Friday => "Vendredi",
-                Weekday::Saturday => "Samedi",
-                Weekday::Sunday => "Dimanche",
-            },
-        )
+        write!(f, "{}", Locale::default().weekday_name(*self))
     }
 }
 
@@ -105,22 +102,240 @@ impl Ord for Weekday {
     }
 }
 
+// Names are accepted in any supported locale
 impl TryFrom<&str> for Weekday {
     type Error = &'static str;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "Lundi" => Ok(Weekday::Monday),
-            "Mardi" => Ok(Weekday::Tuesday),
-            "Mercredi" => Ok(Weekday::Wednesday),
-            "Jeudi" => Ok(Weekday::Thursday),
-            "Vendredi" => Ok(Weekday::Friday),
-            "Samedi" => Ok(Weekday::Saturday),
-            "Dimanche" => Ok(Weekday::Sunday),
-            _ => Err("Unknown weekday"),
+        Locale::ALL
+            .into_iter()
+            .find_map(|locale| Weekday::iter().find(|day| locale.weekday_name(*day) == value))
+            .ok_or("Unknown weekday")
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, PartialOrd, Ord)]
+pub enum Locale {
+    #[default]
+    French,
+    English,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::French, Locale::English];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::French => "fr",
+            Locale::English => "en",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Locale> {
+        Self::ALL.into_iter().find(|locale| locale.code() == code)
+    }
+
+    pub fn weekday_name(&self, weekday: Weekday) -> &'static str {
+        match self {
+            Locale::French => match weekday {
+                Weekday::Monday => "Lundi",
+                Weekday::Tuesday => "Mardi",
+                Weekday::Wednesday => "Mercredi",
+                Weekday::Thursday => "Jeudi",
+                Weekday::Friday => "Vendredi",
+                Weekday::Saturday => "Samedi",
+                Weekday::Sunday => "Dimanche",
+            },
+            Locale::English => match weekday {
+                Weekday::Monday => "Monday",
+                Weekday::Tuesday => "Tuesday",
+                Weekday::Wednesday => "Wednesday",
+                Weekday::Thursday => "Thursday",
+                Weekday::Friday => "Friday",
+                Weekday::Saturday => "Saturday",
+                Weekday::Sunday => "Sunday",
+            },
+        }
+    }
+
+    // month is between 1 and 12
+    pub fn month_abbreviation(&self, month: u32) -> &'static str {
+        const FRENCH: [&str; 12] = [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ];
+        const ENGLISH: [&str; 12] = [
+            "Jan.", "Feb.", "Mar.", "Apr.", "May", "Jun.", "Jul.", "Aug.", "Sep.", "Oct.", "Nov.",
+            "Dec.",
+        ];
+        let index = (month as usize)
+            .checked_sub(1)
+            .expect("Month should be between 1 and 12");
+        match self {
+            Locale::French => FRENCH[index],
+            Locale::English => ENGLISH[index],
+        }
+    }
+
+    pub fn week_word(&self) -> &'static str {
+        match self {
+            Locale::French => "Semaine",
+            Locale::English => "Week",
+        }
+    }
+
+    pub fn format_date(&self, date: &Date) -> String {
+        match self {
+            Locale::French => format!(
+                "{:02}/{:02}/{}",
+                date.get_day(),
+                date.get_month(),
+                date.get_year()
+            ),
+            Locale::English => format!(
+                "{}-{:02}-{:02}",
+                date.get_year(),
+                date.get_month(),
+                date.get_day()
+            ),
+        }
+    }
+
+    pub fn format_time(&self, time: &Time) -> String {
+        match self {
+            Locale::French => format!("{:02}h{:02}", time.get_hour(), time.get_min()),
+            Locale::English => format!("{:02}:{:02}", time.get_hour(), time.get_min()),
         }
     }
 }
 
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum QuickEntryError {
+    #[error("Expected a day and a time (for instance \"{0}\")")]
+    MissingTime(&'static str),
+    #[error("Unknown day \"{0}\"")]
+    UnknownWeekday(String),
+    #[error("Time \"{0}\" cannot be parsed (expected something like \"{1}\")")]
+    InvalidTimeFormat(String, &'static str),
+    #[error("Time \"{0}\" is out of range")]
+    TimeOutOfRange(String),
+}
+
+impl Locale {
+    fn weekday_abbreviations(&self, weekday: Weekday) -> &'static [&'static str] {
+        match self {
+            Locale::French => match weekday {
+                Weekday::Monday => &["lun"],
+                Weekday::Tuesday => &["mar"],
+                Weekday::Wednesday => &["mer"],
+                Weekday::Thursday => &["jeu"],
+                Weekday::Friday => &["ven"],
+                Weekday::Saturday => &["sam"],
+                Weekday::Sunday => &["dim"],
+            },
+            Locale::English => match weekday {
+                Weekday::Monday => &["mon"],
+                Weekday::Tuesday => &["tue", "tues"],
+                Weekday::Wednesday => &["wed"],
+                Weekday::Thursday => &["thu", "thur", "thurs"],
+                Weekday::Friday => &["fri"],
+                Weekday::Saturday => &["sat"],
+                Weekday::Sunday => &["sun"],
+            },
+        }
+    }
+
+    fn quick_entry_example(&self) -> &'static str {
+        match self {
+            Locale::French => "lundi 14h",
+            Locale::English => "monday 2pm",
+        }
+    }
+
+    fn time_example(&self) -> &'static str {
+        match self {
+            Locale::French => "14h30",
+            Locale::English => "2:30pm",
+        }
+    }
+
+    // Full names and abbreviations, case-insensitive, with an optional final dot
+    pub fn parse_weekday(&self, text: &str) -> Result<Weekday, QuickEntryError> {
+        let normalized = text.trim().trim_end_matches('.').to_lowercase();
+        Weekday::iter()
+            .find(|day| {
+                self.weekday_name(*day).to_lowercase() == normalized
+                    || self
+                        .weekday_abbreviations(*day)
+                        .contains(&normalized.as_str())
+            })
+            .ok_or(QuickEntryError::UnknownWeekday(text.trim().to_string()))
+    }
+
+    // Accepted formats: "14h", "14h30", "8:30" and, in english, "2pm" or "2:30pm"
+    pub fn parse_time(&self, text: &str) -> Result<Time, QuickEntryError> {
+        let trimmed = text.trim();
+        let normalized = trimmed.to_lowercase();
+        let invalid =
+            || QuickEntryError::InvalidTimeFormat(trimmed.to_string(), self.time_example());
+
+        let (body, meridiem) = match *self {
+            Locale::English => {
+                if let Some(body) = normalized.strip_suffix("am") {
+                    (body.trim_end(), Some(false))
+                } else if let Some(body) = normalized.strip_suffix("pm") {
+                    (body.trim_end(), Some(true))
+                } else {
+                    (normalized.as_str(), None)
+                }
+            }
+            Locale::French => (normalized.as_str(), None),
+        };
+
+        let (hours, minutes) = match body.split_once(['h', ':']) {
+            Some((hours, minutes)) => (hours, minutes),
+            None if meridiem.is_some() => (body, ""),
+            None => return Err(invalid()),
+        };
+        let parse_number = |text: &str| -> Result<u32, QuickEntryError> {
+            if text.is_empty() || text.len() > 2 || !text.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            text.parse().map_err(|_| invalid())
+        };
+        let hours = parse_number(hours)?;
+        let minutes = if minutes.is_empty() {
+            0
+        } else if minutes.len() != 2 {
+            return Err(invalid());
+        } else {
+            parse_number(minutes)?
+        };
+
+        let hours = match meridiem {
+            None => hours,
+            Some(_) if hours == 0 || hours > 12 => {
+                return Err(QuickEntryError::TimeOutOfRange(trimmed.to_string()))
+            }
+            Some(pm) => hours % 12 + if pm { 12 } else { 0 },
+        };
+
+        Time::from_hm(hours, minutes).ok_or(QuickEntryError::TimeOutOfRange(trimmed.to_string()))
+    }
+
+    // Something like "lundi 14h" or "jeu 8:30"
+    pub fn parse_day_and_time(&self, text: &str) -> Result<(Weekday, Time), QuickEntryError> {
+        let (day, time) = text
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or(QuickEntryError::MissingTime(self.quick_entry_example()))?;
+
+        let weekday = self.parse_weekday(day)?;
+        let time =
+            self.parse_time(time.trim_start_matches(|c: char| c.is_whitespace() || c == 'à'))?;
+        Ok((weekday, time))
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Time {
     time_in_minutes: u32,
@@ -178,6 +393,10 @@ impl Time {
             .unwrap_or(false)
     }
 
+    pub fn minutes_until_end_of_day(&self) -> u32 {
+        MINUTES_PER_DAY - self.time_in_minutes
+    }
+
     pub fn iterate_until_end_of_day(&self, step_in_minutes: u32) -> TimeIterator {
         TimeIterator {
             current_time: Some(self.clone()),
@@ -228,3 +447,1000 @@ impl Ord for Time {
         self.time_in_minutes.cmp(&other.time_in_minutes)
     }
 }
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct Date {
+    // Field order matters for the derived Ord
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if month == 0 || month > 12 || day == 0 || day > Self::days_in_month(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    fn is_leap_year(year: i32) -> bool {
+        (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+    }
+
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            2 => {
+                if Self::is_leap_year(year) {
+                    29
+                } else {
+                    28
+                }
+            }
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    pub fn get_year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn get_month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn get_day(&self) -> u32 {
+        self.day
+    }
+
+    // Days since 1970-01-01 (proleptic gregorian calendar)
+    fn to_days(self) -> i64 {
+        let year = i64::from(self.year) - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    fn from_days(days: i64) -> Self {
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Date {
+            year: i32::try_from(year).expect("Year should fit in i32"),
+            month: u32::try_from(month).expect("Month should be valid"),
+            day: u32::try_from(day).expect("Day should be valid"),
+        }
+    }
+
+    pub fn add_days(&self, days: i64) -> Self {
+        Self::from_days(self.to_days() + days)
+    }
+
+    pub fn days_until(&self, other: &Date) -> i64 {
+        other.to_days() - self.to_days()
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        // 1970-01-01 was a thursday
+        let index = (self.to_days() + 3).rem_euclid(7);
+        Weekday::try_from(usize::try_from(index).expect("Index should be positive"))
+            .expect("Index should be less than 7")
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Locale::default().format_date(self))
+    }
+}
+
+impl Date {
+    // YYYY-MM-DD, whatever the locale
+    pub fn to_iso_string(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum DateError {
+    #[error("Date \"{0}\" should be written as YYYY-MM-DD")]
+    InvalidFormat(String),
+    #[error("Date \"{0}\" does not exist")]
+    InvalidDate(String),
+}
+
+// Accepted format: "2024-09-09"
+impl std::str::FromStr for Date {
+    type Err = DateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DateError::InvalidFormat(s.to_string());
+        let parts: Vec<_> = s.trim().split('-').collect();
+        let [year, month, day] = parts.as_slice() else {
+            return Err(invalid());
+        };
+        if [year, month, day]
+            .iter()
+            .any(|part| part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()))
+        {
+            return Err(invalid());
+        }
+
+        let year = year.parse().map_err(|_| invalid())?;
+        let month = month.parse().map_err(|_| invalid())?;
+        let day = day.parse().map_err(|_| invalid())?;
+        Date::new(year, month, day).ok_or(DateError::InvalidDate(s.to_string()))
+    }
+}
+
+// Monday of a week
+#[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct WeekStart {
+    monday: Date,
+}
+
+impl WeekStart {
+    pub fn new(monday: Date) -> Option<Self> {
+        if monday.weekday() != Weekday::Monday {
+            return None;
+        }
+        Some(WeekStart { monday })
+    }
+
+    pub fn containing(date: &Date) -> Self {
+        let offset = usize::from(date.weekday());
+        WeekStart {
+            monday: date.add_days(-i64::try_from(offset).expect("Offset should be small")),
+        }
+    }
+
+    pub fn get_monday(&self) -> Date {
+        self.monday
+    }
+
+    pub fn get_date(&self, weekday: Weekday) -> Date {
+        let offset = usize::from(weekday);
+        self.monday
+            .add_days(i64::try_from(offset).expect("Offset should be small"))
+    }
+
+    pub fn next(&self) -> Self {
+        self.add_weeks(1)
+    }
+
+    pub fn add_weeks(&self, weeks: i32) -> Self {
+        WeekStart {
+            monday: self.monday.add_days(7 * i64::from(weeks)),
+        }
+    }
+
+    // Negative if other is before self
+    pub fn weeks_between(&self, other: &WeekStart) -> i32 {
+        let days = self.monday.days_until(&other.monday);
+        i32::try_from(days / 7).expect("Number of weeks should fit in i32")
+    }
+
+    pub fn contains(&self, date: &Date) -> bool {
+        WeekStart::containing(date) == *self
+    }
+
+    // Consecutive weeks from self to last (included)
+    pub fn iterate_until(&self, last: &WeekStart) -> WeekIterator {
+        WeekIterator {
+            current_week: *self,
+            last_week: *last,
+        }
+    }
+}
+
+pub struct WeekIterator {
+    current_week: WeekStart,
+    last_week: WeekStart,
+}
+
+impl Iterator for WeekIterator {
+    type Item = WeekStart;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_week > self.last_week {
+            return None;
+        }
+        let week = self.current_week;
+        self.current_week = week.next();
+        Some(week)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining =
+            usize::try_from(self.current_week.weeks_between(&self.last_week) + 1).unwrap_or(0);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for WeekIterator {}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct HolidayCalendar {
+    excluded_weeks: std::collections::BTreeSet<WeekStart>,
+    excluded_ranges: Vec<std::ops::RangeInclusive<Date>>,
+}
+
+impl HolidayCalendar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_week(mut self, week: WeekStart) -> Self {
+        self.excluded_weeks.insert(week);
+        self
+    }
+
+    pub fn add_range(mut self, range: std::ops::RangeInclusive<Date>) -> Self {
+        self.excluded_ranges.push(range);
+        self
+    }
+
+    pub fn get_excluded_weeks(&self) -> &std::collections::BTreeSet<WeekStart> {
+        &self.excluded_weeks
+    }
+
+    pub fn get_excluded_ranges(&self) -> &Vec<std::ops::RangeInclusive<Date>> {
+        &self.excluded_ranges
+    }
+
+    pub fn is_holiday(&self, date: &Date) -> bool {
+        self.excluded_weeks.contains(&WeekStart::containing(date))
+            || self
+                .excluded_ranges
+                .iter()
+                .any(|range| range.contains(date))
+    }
+
+    // A week is only skipped if no day from monday to friday is a teaching day:
+    // a single bank holiday does not remove the whole week
+    pub fn is_holiday_week(&self, week: &WeekStart) -> bool {
+        Weekday::iter()
+            .take_while(|weekday| *weekday <= Weekday::Friday)
+            .all(|weekday| self.is_holiday(&week.get_date(weekday)))
+    }
+
+    // Teaching weeks that overlap with the range from start to end (included)
+    pub fn teaching_weeks(&self, start: &Date, end: &Date) -> Vec<WeekStart> {
+        if start > end {
+            return Vec::new();
+        }
+
+        WeekStart::containing(start)
+            .iterate_until(&WeekStart::containing(end))
+            .filter(|week| !self.is_holiday_week(week))
+            .collect()
+    }
+}
+
+impl WeekStart {
+    // ISO 8601 week: the week belongs to the year of its thursday
+    pub fn iso_week(&self) -> (i32, u32) {
+        let thursday = self.get_date(Weekday::Thursday);
+        let year = thursday.get_year();
+        let first_day = Date::new(year, 1, 1).expect("January 1st should be valid");
+        let day_of_year = first_day.days_until(&thursday);
+
+        let week = u32::try_from(day_of_year / 7 + 1).expect("Week number should be positive");
+        (year, week)
+    }
+
+    // Civil dates of the working days (monday to friday)
+    pub fn get_civil_range(&self) -> std::ops::RangeInclusive<Date> {
+        self.monday..=self.get_date(Weekday::Friday)
+    }
+
+    // Something like "Semaine 37 (9–13 sept.)"
+    pub fn label(&self) -> String {
+        self.label_in(Locale::default())
+    }
+
+    pub fn label_in(&self, locale: Locale) -> String {
+        let (_year, week) = self.iso_week();
+        let range = self.get_civil_range();
+        let (start, end) = (range.start(), range.end());
+
+        let month_name = |date: &Date| locale.month_abbreviation(date.get_month());
+        let dates = if start.get_month() == end.get_month() {
+            format!("{}–{} {}", start.get_day(), end.get_day(), month_name(end))
+        } else {
+            format!(
+                "{} {}–{} {}",
+                start.get_day(),
+                month_name(start),
+                end.get_day(),
+                month_name(end)
+            )
+        };
+
+        format!("{} {} ({})", locale.week_word(), week, dates)
+    }
+}
+
+// Maps colloscope week indices (relative to the first week of the period)
+// to actual weeks, holiday weeks being skipped
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WeekMapping {
+    first_week: WeekStart,
+    calendar: HolidayCalendar,
+}
+
+impl WeekMapping {
+    pub fn new(first_week: WeekStart, calendar: HolidayCalendar) -> Self {
+        WeekMapping {
+            first_week,
+            calendar,
+        }
+    }
+
+    pub fn get_first_week(&self) -> WeekStart {
+        self.first_week
+    }
+
+    pub fn get_calendar(&self) -> &HolidayCalendar {
+        &self.calendar
+    }
+
+    pub fn week_start(&self, index: u32) -> WeekStart {
+        let mut week = self.first_week;
+        while self.calendar.is_holiday_week(&week) {
+            week = week.next();
+        }
+        for _ in 0..index {
+            week = week.next();
+            while self.calendar.is_holiday_week(&week) {
+                week = week.next();
+            }
+        }
+        week
+    }
+
+    pub fn index_of(&self, week: &WeekStart) -> Option<u32> {
+        if *week < self.first_week || self.calendar.is_holiday_week(week) {
+            return None;
+        }
+
+        let count = self
+            .first_week
+            .iterate_until(week)
+            .filter(|current| !self.calendar.is_holiday_week(current))
+            .count();
+        // The week itself is a teaching week and was counted
+        Some(u32::try_from(count - 1).expect("Week index should fit in u32"))
+    }
+
+    pub fn index_of_date(&self, date: &Date) -> Option<u32> {
+        self.index_of(&WeekStart::containing(date))
+    }
+
+    pub fn iso_week(&self, index: u32) -> (i32, u32) {
+        self.week_start(index).iso_week()
+    }
+
+    pub fn get_civil_range(&self, index: u32) -> std::ops::RangeInclusive<Date> {
+        self.week_start(index).get_civil_range()
+    }
+
+    pub fn label(&self, index: u32) -> String {
+        self.week_start(index).label()
+    }
+
+    pub fn label_in(&self, index: u32, locale: Locale) -> String {
+        self.week_start(index).label_in(locale)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub enum HolidayZone {
+    A,
+    B,
+    C,
+}
+
+// School year from the first day of classes to the last one.
+// Terms are given by their first day, the first term starting with the year.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AcademicYear {
+    start: Date,
+    end: Date,
+    term_starts: std::collections::BTreeSet<Date>,
+    calendar: HolidayCalendar,
+}
+
+impl AcademicYear {
+    pub fn new(start: Date, end: Date) -> Option<Self> {
+        if start > end {
+            return None;
+        }
+        Some(AcademicYear {
+            start,
+            end,
+            term_starts: std::collections::BTreeSet::new(),
+            calendar: HolidayCalendar::new(),
+        })
+    }
+
+    // Official french calendar for the year starting in september of first_year.
+    // Only years with a known calendar are available.
+    pub fn french(first_year: i32, zone: HolidayZone) -> Option<Self> {
+        let d = |y, m, day| Date::new(y, m, day).expect("Preset dates should be valid");
+        let (start, end, common, winter, spring) = match first_year {
+            2024 => (
+                d(2024, 9, 2),
+                d(2025, 7, 4),
+                vec![
+                    d(2024, 10, 19)..=d(2024, 11, 3),
+                    d(2024, 12, 21)..=d(2025, 1, 5),
+                ],
+                match zone {
+                    HolidayZone::A => d(2025, 2, 22)..=d(2025, 3, 9),
+                    HolidayZone::B => d(2025, 2, 8)..=d(2025, 2, 23),
+                    HolidayZone::C => d(2025, 2, 15)..=d(2025, 3, 2),
+                },
+                match zone {
+                    HolidayZone::A => d(2025, 4, 19)..=d(2025, 5, 4),
+                    HolidayZone::B => d(2025, 4, 5)..=d(2025, 4, 21),
+                    HolidayZone::C => d(2025, 4, 12)..=d(2025, 4, 27),
+                },
+            ),
+            2025 => (
+                d(2025, 9, 1),
+                d(2026, 7, 3),
+                vec![
+                    d(2025, 10, 18)..=d(2025, 11, 2),
+                    d(2025, 12, 20)..=d(2026, 1, 4),
+                ],
+                match zone {
+                    HolidayZone::A => d(2026, 2, 7)..=d(2026, 2, 22),
+                    HolidayZone::B => d(2026, 2, 14)..=d(2026, 3, 1),
+                    HolidayZone::C => d(2026, 2, 21)..=d(2026, 3, 8),
+                },
+                match zone {
+                    HolidayZone::A => d(2026, 4, 4)..=d(2026, 4, 19),
+                    HolidayZone::B => d(2026, 4, 11)..=d(2026, 4, 26),
+                    HolidayZone::C => d(2026, 4, 18)..=d(2026, 5, 3),
+                },
+            ),
+            _ => return None,
+        };
+
+        let calendar = common
+            .into_iter()
+            .chain([winter, spring])
+            .fold(HolidayCalendar::new(), |calendar, range| {
+                calendar.add_range(range)
+            });
+
+        Some(
+            AcademicYear::new(start, end)
+                .expect("Preset start should be before preset end")
+                .with_calendar(calendar),
+        )
+    }
+
+    pub fn with_calendar(mut self, calendar: HolidayCalendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    // Term starts outside of the year are ignored
+    pub fn add_term_start(mut self, date: Date) -> Self {
+        if date > self.start && date <= self.end {
+            self.term_starts.insert(date);
+        }
+        self
+    }
+
+    pub fn get_start(&self) -> Date {
+        self.start
+    }
+
+    pub fn get_end(&self) -> Date {
+        self.end
+    }
+
+    pub fn get_term_starts(&self) -> &std::collections::BTreeSet<Date> {
+        &self.term_starts
+    }
+
+    pub fn get_calendar(&self) -> &HolidayCalendar {
+        &self.calendar
+    }
+
+    pub fn teaching_weeks(&self) -> Vec<WeekStart> {
+        self.calendar.teaching_weeks(&self.start, &self.end)
+    }
+
+    pub fn week_mapping(&self) -> WeekMapping {
+        WeekMapping::new(WeekStart::containing(&self.start), self.calendar.clone())
+    }
+
+    // None if the year has no teaching week at all
+    pub fn week_count(&self) -> Option<std::num::NonZeroU32> {
+        let count =
+            u32::try_from(self.teaching_weeks().len()).expect("Week count should fit in u32");
+        std::num::NonZeroU32::new(count)
+    }
+
+    // Week indices at which each term (but the first) starts.
+    // A term starting during holidays starts with the next teaching week.
+    pub fn periodicity_cuts(&self) -> std::collections::BTreeSet<std::num::NonZeroU32> {
+        let weeks = self.teaching_weeks();
+        self.term_starts
+            .iter()
+            .filter_map(|date| {
+                let first_week = WeekStart::containing(date);
+                let index = weeks.iter().position(|week| *week >= first_week)?;
+                std::num::NonZeroU32::new(
+                    u32::try_from(index).expect("Week index should fit in u32"),
+                )
+            })
+            .collect()
+    }
+}
+
+// Set of time intervals, grouped by day. D is usually a weekday but can
+// also be (week, weekday) or any other way of identifying a day.
+// Intervals are stored in minutes since midnight, sorted and disjoint.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SlotSet<D: Ord + Clone = Weekday> {
+    intervals: std::collections::BTreeMap<D, Vec<std::ops::Range<u32>>>,
+}
+
+impl<D: Ord + Clone> Default for SlotSet<D> {
+    fn default() -> Self {
+        SlotSet {
+            intervals: std::collections::BTreeMap::new(),
+        }
+    }
+}
+
+impl<D: Ord + Clone> SlotSet<D> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn minutes_range(start: &Time, duration_in_minutes: u32) -> std::ops::Range<u32> {
+        let start = start.get();
+        let end = (start + duration_in_minutes).min(MINUTES_PER_DAY);
+        start..end
+    }
+
+    fn normalize(mut ranges: Vec<std::ops::Range<u32>>) -> Vec<std::ops::Range<u32>> {
+        ranges.retain(|range| !range.is_empty());
+        ranges.sort_by_key(|range| range.start);
+
+        let mut output: Vec<std::ops::Range<u32>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match output.last_mut() {
+                Some(last) if range.start <= last.end => {
+                    last.end = last.end.max(range.end);
+                }
+                _ => output.push(range),
+            }
+        }
+        output
+    }
+
+    // Slots running past midnight are cut at the end of the day
+    pub fn insert(&mut self, day: D, start: &Time, duration_in_minutes: u32) {
+        let range = Self::minutes_range(start, duration_in_minutes);
+        let ranges = self.intervals.entry(day.clone()).or_default();
+        ranges.push(range);
+        let normalized = Self::normalize(std::mem::take(ranges));
+        if normalized.is_empty() {
+            self.intervals.remove(&day);
+        } else {
+            self.intervals.insert(day, normalized);
+        }
+    }
+
+    pub fn with_slot(mut self, day: D, start: &Time, duration_in_minutes: u32) -> Self {
+        self.insert(day, start, duration_in_minutes);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = &D> {
+        self.intervals.keys()
+    }
+
+    // (start, duration in minutes) of every interval of the day
+    pub fn intervals(&self, day: &D) -> Vec<(Time, u32)> {
+        self.intervals
+            .get(day)
+            .into_iter()
+            .flatten()
+            .map(|range| {
+                (
+                    Time::new(range.start).expect("Interval start should be a valid time"),
+                    range.end - range.start,
+                )
+            })
+            .collect()
+    }
+
+    pub fn contains(&self, day: &D, time: &Time) -> bool {
+        self.intervals
+            .get(day)
+            .is_some_and(|ranges| ranges.iter().any(|range| range.contains(&time.get())))
+    }
+
+    pub fn overlaps(&self, day: &D, start: &Time, duration_in_minutes: u32) -> bool {
+        let query = Self::minutes_range(start, duration_in_minutes);
+        self.intervals.get(day).is_some_and(|ranges| {
+            ranges
+                .iter()
+                .any(|range| range.start < query.end && query.start < range.end)
+        })
+    }
+
+    pub fn overlaps_set(&self, other: &SlotSet<D>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    pub fn union(&self, other: &SlotSet<D>) -> SlotSet<D> {
+        let mut intervals = self.intervals.clone();
+        for (day, ranges) in &other.intervals {
+            let entry = intervals.entry(day.clone()).or_default();
+            entry.extend(ranges.iter().cloned());
+            *entry = Self::normalize(std::mem::take(entry));
+        }
+        SlotSet { intervals }
+    }
+
+    pub fn intersection(&self, other: &SlotSet<D>) -> SlotSet<D> {
+        let mut intervals = std::collections::BTreeMap::new();
+        for (day, ranges) in &self.intervals {
+            let Some(other_ranges) = other.intervals.get(day) else {
+                continue;
+            };
+            let mut output = Vec::new();
+            for range in ranges {
+                for other_range in other_ranges {
+                    let start = range.start.max(other_range.start);
+                    let end = range.end.min(other_range.end);
+                    if start < end {
+                        output.push(start..end);
+                    }
+                }
+            }
+            let output = Self::normalize(output);
+            if !output.is_empty() {
+                intervals.insert(day.clone(), output);
+            }
+        }
+        SlotSet { intervals }
+    }
+
+    pub fn difference(&self, other: &SlotSet<D>) -> SlotSet<D> {
+        let mut intervals = std::collections::BTreeMap::new();
+        for (day, ranges) in &self.intervals {
+            let other_ranges = other.intervals.get(day).map(Vec::as_slice).unwrap_or(&[]);
+            let mut output = Vec::new();
+            for range in ranges {
+                let mut current = range.start;
+                for other_range in other_ranges {
+                    if other_range.end <= current || other_range.start >= range.end {
+                        continue;
+                    }
+                    if other_range.start > current {
+                        output.push(current..other_range.start);
+                    }
+                    current = current.max(other_range.end);
+                }
+                if current < range.end {
+                    output.push(current..range.end);
+                }
+            }
+            if !output.is_empty() {
+                intervals.insert(day.clone(), output);
+            }
+        }
+        SlotSet { intervals }
+    }
+
+    // Free intervals of at least min_duration minutes between from (included)
+    // and to (excluded, None meaning the end of the day)
+    pub fn free_gaps(
+        &self,
+        day: &D,
+        from: &Time,
+        to: Option<&Time>,
+        min_duration_in_minutes: u32,
+    ) -> Vec<(Time, u32)> {
+        let end = to.map(|t| t.get()).unwrap_or(MINUTES_PER_DAY);
+        let mut output = Vec::new();
+
+        let mut current = from.get();
+        let ranges = self.intervals.get(day).map(Vec::as_slice).unwrap_or(&[]);
+        for range in ranges.iter().chain(std::iter::once(&(end..end))) {
+            let gap_end = range.start.min(end);
+            if gap_end > current && gap_end - current >= min_duration_in_minutes {
+                output.push((
+                    Time::new(current).expect("Gap start should be a valid time"),
+                    gap_end - current,
+                ));
+            }
+            current = current.max(range.end);
+            if current >= end {
+                break;
+            }
+        }
+
+        output
+    }
+}
+
+// Repetition rule over week numbers (starting at 0)
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Recurrence {
+    Weekly,
+    // Every two weeks, starting at week offset (0 or 1)
+    Biweekly {
+        offset: u32,
+    },
+    // Every period weeks, starting at week offset
+    EveryNWeeks {
+        period: std::num::NonZeroU32,
+        offset: u32,
+    },
+    Custom(std::collections::BTreeSet<u32>),
+}
+
+impl Recurrence {
+    pub fn contains(&self, week: u32) -> bool {
+        match self {
+            Recurrence::Weekly => true,
+            Recurrence::Biweekly { offset } => week >= *offset && (week - offset).is_multiple_of(2),
+            Recurrence::EveryNWeeks { period, offset } => {
+                week >= *offset && (week - offset).is_multiple_of(period.get())
+            }
+            Recurrence::Custom(weeks) => weeks.contains(&week),
+        }
+    }
+
+    // Weeks of the recurrence among the first week_count weeks
+    pub fn weeks(&self, week_count: u32) -> std::collections::BTreeSet<u32> {
+        (0..week_count)
+            .filter(|week| self.contains(*week))
+            .collect()
+    }
+}
+
+// Semaine A / semaine B
+#[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub enum WeekParity {
+    A,
+    B,
+}
+
+impl WeekParity {
+    pub fn other(&self) -> Self {
+        match self {
+            WeekParity::A => WeekParity::B,
+            WeekParity::B => WeekParity::A,
+        }
+    }
+}
+
+impl std::fmt::Display for WeekParity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeekParity::A => write!(f, "A"),
+            WeekParity::B => write!(f, "B"),
+        }
+    }
+}
+
+// Alternation of A and B weeks, anchored on a week A.
+// By default every calendar week counts. When holidays are skipped,
+// holiday weeks have no parity and the alternation resumes after them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParityCalendar {
+    anchor: WeekStart,
+    holidays: Option<HolidayCalendar>,
+}
+
+impl ParityCalendar {
+    pub fn new(anchor: WeekStart) -> Self {
+        ParityCalendar {
+            anchor,
+            holidays: None,
+        }
+    }
+
+    pub fn skipping_holidays(mut self, calendar: HolidayCalendar) -> Self {
+        self.holidays = Some(calendar);
+        self
+    }
+
+    pub fn get_anchor(&self) -> WeekStart {
+        self.anchor
+    }
+
+    fn is_skipped(&self, week: &WeekStart) -> bool {
+        self.holidays
+            .as_ref()
+            .is_some_and(|calendar| calendar.is_holiday_week(week))
+    }
+
+    // None for skipped holiday weeks
+    pub fn parity(&self, week: &WeekStart) -> Option<WeekParity> {
+        if self.is_skipped(week) {
+            return None;
+        }
+
+        let (first, last) = if *week >= self.anchor {
+            (self.anchor, *week)
+        } else {
+            (*week, self.anchor)
+        };
+        // Counted weeks in [first, last)
+        let offset = first
+            .iterate_until(&last)
+            .filter(|current| *current != last && !self.is_skipped(current))
+            .count();
+
+        Some(if offset % 2 == 0 {
+            WeekParity::A
+        } else {
+            WeekParity::B
+        })
+    }
+
+    // Colloscope week indices (among the first week_count) having the given parity
+    pub fn week_pattern(
+        &self,
+        mapping: &WeekMapping,
+        week_count: u32,
+        parity: WeekParity,
+    ) -> std::collections::BTreeSet<u32> {
+        (0..week_count)
+            .filter(|index| self.parity(&mapping.week_start(*index)) == Some(parity))
+            .collect()
+    }
+
+    pub fn recurrence(
+        &self,
+        mapping: &WeekMapping,
+        week_count: u32,
+        parity: WeekParity,
+    ) -> Recurrence {
+        Recurrence::Custom(self.week_pattern(mapping, week_count, parity))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct NonZeroDurationInMinutes(std::num::NonZeroU32);
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum DurationError {
+    #[error("Duration \"{0}\" cannot be parsed")]
+    InvalidFormat(String),
+    #[error("Duration cannot be zero")]
+    Zero,
+    #[error("Duration is too large")]
+    Overflow,
+}
+
+impl NonZeroDurationInMinutes {
+    pub fn new(minutes: u32) -> Option<Self> {
+        std::num::NonZeroU32::new(minutes).map(NonZeroDurationInMinutes)
+    }
+
+    pub fn get(&self) -> std::num::NonZeroU32 {
+        self.0
+    }
+
+    pub fn checked_add(&self, other: NonZeroDurationInMinutes) -> Option<Self> {
+        self.0
+            .checked_add(other.0.get())
+            .map(NonZeroDurationInMinutes)
+    }
+
+    // None if the result would not be positive
+    pub fn checked_sub(&self, other: NonZeroDurationInMinutes) -> Option<Self> {
+        self.0.get().checked_sub(other.0.get()).and_then(Self::new)
+    }
+
+    pub fn checked_mul(&self, factor: std::num::NonZeroU32) -> Option<Self> {
+        self.0.checked_mul(factor).map(NonZeroDurationInMinutes)
+    }
+}
+
+impl From<NonZeroDurationInMinutes> for std::num::NonZeroU32 {
+    fn from(value: NonZeroDurationInMinutes) -> Self {
+        value.0
+    }
+}
+
+impl From<std::num::NonZeroU32> for NonZeroDurationInMinutes {
+    fn from(value: std::num::NonZeroU32) -> Self {
+        NonZeroDurationInMinutes(value)
+    }
+}
+
+// Accepted formats: "90", "2h", "1h30", "1h05"
+impl std::str::FromStr for NonZeroDurationInMinutes {
+    type Err = DurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let invalid = || DurationError::InvalidFormat(s.to_string());
+        let parse_number = |text: &str| -> Result<u32, DurationError> {
+            if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            text.parse::<u32>().map_err(|_| DurationError::Overflow)
+        };
+
+        let minutes = match trimmed.split_once(['h', 'H']) {
+            None => parse_number(trimmed)?,
+            Some((hours, minutes)) => {
+                let hours = parse_number(hours)?;
+                let minutes = if minutes.is_empty() {
+                    0
+                } else {
+                    let minutes = parse_number(minutes)?;
+                    if minutes >= MINUTES_PER_HOUR {
+                        return Err(invalid());
+                    }
+                    minutes
+                };
+                hours
+                    .checked_mul(MINUTES_PER_HOUR)
+                    .and_then(|x| x.checked_add(minutes))
+                    .ok_or(DurationError::Overflow)?
+            }
+        };
+
+        Self::new(minutes).ok_or(DurationError::Zero)
+    }
+}
+
+impl std::fmt::Display for NonZeroDurationInMinutes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let minutes = self.0.get();
+        let hours = minutes / MINUTES_PER_HOUR;
+        let remainder = minutes % MINUTES_PER_HOUR;
+        match (hours, remainder) {
+            (0, m) => write!(f, "{}", m),
+            (h, 0) => write!(f, "{}h", h),
+            (h, m) => write!(f, "{}h{:02}", h, m),
+        }
+    }
+}
+
+// The sum is None for an empty iterator or on overflow
+impl std::iter::Sum<NonZeroDurationInMinutes> for Option<NonZeroDurationInMinutes> {
+    fn sum<I: Iterator<Item = NonZeroDurationInMinutes>>(iter: I) -> Self {
+        let mut total: Option<NonZeroDurationInMinutes> = None;
+        for duration in iter {
+            total = Some(match total {
+                None => duration,
+                Some(value) => value.checked_add(duration)?,
+            });
+        }
+        total
+    }
+}
@@ -0,0 +1,540 @@
+use super::*;
+
+#[test]
+fn date_validation() {
+    assert!(Date::new(2024, 2, 29).is_some());
+    assert!(Date::new(2023, 2, 29).is_none());
+    assert!(Date::new(1900, 2, 29).is_none());
+    assert!(Date::new(2000, 2, 29).is_some());
+    assert!(Date::new(2024, 13, 1).is_none());
+    assert!(Date::new(2024, 4, 31).is_none());
+    assert!(Date::new(2024, 4, 0).is_none());
+}
+
+#[test]
+fn date_arithmetic() {
+    let date = Date::new(2024, 12, 30).unwrap();
+    assert_eq!(date.add_days(3), Date::new(2025, 1, 2).unwrap());
+    assert_eq!(date.add_days(-365), Date::new(2023, 12, 31).unwrap());
+    assert_eq!(
+        Date::new(2024, 2, 28).unwrap().add_days(1),
+        Date::new(2024, 2, 29).unwrap()
+    );
+    assert_eq!(date.days_until(&Date::new(2025, 3, 1).unwrap()), 61);
+    assert_eq!(Date::new(1970, 1, 1).unwrap().to_days(), 0);
+}
+
+#[test]
+fn date_parsing() {
+    assert_eq!("2024-09-09".parse(), Ok(Date::new(2024, 9, 9).unwrap()));
+    assert_eq!(" 2025-1-2 ".parse(), Ok(Date::new(2025, 1, 2).unwrap()));
+    assert_eq!(
+        "2023-02-29".parse::<Date>(),
+        Err(DateError::InvalidDate(String::from("2023-02-29")))
+    );
+    assert!(matches!(
+        "09/09/2024".parse::<Date>(),
+        Err(DateError::InvalidFormat(_))
+    ));
+    assert!(matches!(
+        "2024-09-+9".parse::<Date>(),
+        Err(DateError::InvalidFormat(_))
+    ));
+    assert_eq!(Date::new(2024, 9, 9).unwrap().to_iso_string(), "2024-09-09");
+}
+
+#[test]
+fn date_weekday() {
+    assert_eq!(Date::new(1970, 1, 1).unwrap().weekday(), Weekday::Thursday);
+    assert_eq!(Date::new(2024, 9, 2).unwrap().weekday(), Weekday::Monday);
+    assert_eq!(Date::new(2025, 1, 1).unwrap().weekday(), Weekday::Wednesday);
+    assert_eq!(Date::new(1969, 12, 28).unwrap().weekday(), Weekday::Sunday);
+}
+
+#[test]
+fn week_start() {
+    let monday = Date::new(2024, 9, 2).unwrap();
+    assert!(WeekStart::new(monday).is_some());
+    assert!(WeekStart::new(monday.add_days(1)).is_none());
+
+    let week = WeekStart::containing(&Date::new(2024, 9, 8).unwrap());
+    assert_eq!(week.get_monday(), monday);
+    assert_eq!(
+        week.get_date(Weekday::Friday),
+        Date::new(2024, 9, 6).unwrap()
+    );
+    assert_eq!(week.next().get_monday(), Date::new(2024, 9, 9).unwrap());
+    assert!(week.contains(&Date::new(2024, 9, 4).unwrap()));
+    assert!(!week.contains(&Date::new(2024, 9, 9).unwrap()));
+}
+
+#[test]
+fn teaching_weeks_skip_holidays() {
+    let calendar = HolidayCalendar::new()
+        // Autumn holidays (saturday to sunday)
+        .add_range(Date::new(2024, 10, 19).unwrap()..=Date::new(2024, 11, 3).unwrap())
+        // Single bank holiday does not remove the week
+        .add_range(Date::new(2024, 11, 11).unwrap()..=Date::new(2024, 11, 11).unwrap())
+        .add_week(WeekStart::new(Date::new(2024, 11, 25).unwrap()).unwrap());
+
+    let weeks = calendar.teaching_weeks(
+        &Date::new(2024, 10, 9).unwrap(),
+        &Date::new(2024, 12, 1).unwrap(),
+    );
+    let mondays: Vec<_> = weeks.iter().map(|w| w.get_monday()).collect();
+    assert_eq!(
+        mondays,
+        vec![
+            Date::new(2024, 10, 7).unwrap(),
+            Date::new(2024, 10, 14).unwrap(),
+            Date::new(2024, 11, 4).unwrap(),
+            Date::new(2024, 11, 11).unwrap(),
+            Date::new(2024, 11, 18).unwrap(),
+        ]
+    );
+
+    assert!(calendar.is_holiday(&Date::new(2024, 11, 11).unwrap()));
+    assert!(!calendar.is_holiday(&Date::new(2024, 11, 12).unwrap()));
+    assert!(calendar.is_holiday(&Date::new(2024, 11, 27).unwrap()));
+}
+
+#[test]
+fn iso_week_numbers() {
+    let week = |y, m, d| WeekStart::containing(&Date::new(y, m, d).unwrap());
+
+    assert_eq!(week(2024, 9, 9).iso_week(), (2024, 37));
+    assert_eq!(week(2024, 12, 31).iso_week(), (2025, 1));
+    assert_eq!(week(2021, 1, 3).iso_week(), (2020, 53));
+    assert_eq!(week(2026, 1, 1).iso_week(), (2026, 1));
+}
+
+#[test]
+fn week_labels() {
+    let week = WeekStart::new(Date::new(2024, 9, 9).unwrap()).unwrap();
+    assert_eq!(week.label(), "Semaine 37 (9–13 sept.)");
+
+    let week = WeekStart::new(Date::new(2024, 9, 30).unwrap()).unwrap();
+    assert_eq!(week.label(), "Semaine 40 (30 sept.–4 oct.)");
+}
+
+#[test]
+fn week_mapping_skips_holidays() {
+    let calendar = HolidayCalendar::new()
+        .add_range(Date::new(2024, 10, 19).unwrap()..=Date::new(2024, 11, 3).unwrap());
+    let mapping = WeekMapping::new(
+        WeekStart::new(Date::new(2024, 10, 7).unwrap()).unwrap(),
+        calendar,
+    );
+
+    assert_eq!(
+        mapping.week_start(0).get_monday(),
+        Date::new(2024, 10, 7).unwrap()
+    );
+    assert_eq!(
+        mapping.week_start(1).get_monday(),
+        Date::new(2024, 10, 14).unwrap()
+    );
+    assert_eq!(
+        mapping.week_start(2).get_monday(),
+        Date::new(2024, 11, 4).unwrap()
+    );
+    assert_eq!(mapping.iso_week(2), (2024, 45));
+    assert_eq!(
+        mapping.get_civil_range(2),
+        Date::new(2024, 11, 4).unwrap()..=Date::new(2024, 11, 8).unwrap()
+    );
+
+    for index in 0..5 {
+        assert_eq!(mapping.index_of(&mapping.week_start(index)), Some(index));
+    }
+    assert_eq!(
+        mapping.index_of_date(&Date::new(2024, 10, 23).unwrap()),
+        None
+    );
+    assert_eq!(
+        mapping.index_of_date(&Date::new(2024, 10, 1).unwrap()),
+        None
+    );
+    assert_eq!(
+        mapping.index_of_date(&Date::new(2024, 11, 6).unwrap()),
+        Some(2)
+    );
+}
+
+#[test]
+fn localized_formatting() {
+    assert_eq!(Weekday::Sunday.to_string(), "Dimanche");
+    assert_eq!(Locale::English.weekday_name(Weekday::Sunday), "Sunday");
+    assert_eq!(Weekday::try_from("Mercredi").unwrap(), Weekday::Wednesday);
+    assert_eq!(Weekday::try_from("Wednesday").unwrap(), Weekday::Wednesday);
+    assert!(Weekday::try_from("mercredi").is_err());
+
+    let date = Date::new(2024, 9, 9).unwrap();
+    assert_eq!(date.to_string(), "09/09/2024");
+    assert_eq!(Locale::English.format_date(&date), "2024-09-09");
+
+    let time = Time::from_hm(8, 5).unwrap();
+    assert_eq!(Locale::French.format_time(&time), "08h05");
+    assert_eq!(Locale::English.format_time(&time), "08:05");
+
+    let week = WeekStart::new(Date::new(2024, 9, 30).unwrap()).unwrap();
+    assert_eq!(week.label_in(Locale::English), "Week 40 (30 Sep.–4 Oct.)");
+
+    assert_eq!(Locale::from_code("en"), Some(Locale::English));
+    assert_eq!(Locale::from_code("de"), None);
+}
+
+#[test]
+fn slot_set_operations() {
+    let t = |h, m| Time::from_hm(h, m).unwrap();
+
+    let a = SlotSet::new()
+        .with_slot(Weekday::Monday, &t(8, 0), 120)
+        .with_slot(Weekday::Monday, &t(9, 0), 120)
+        .with_slot(Weekday::Tuesday, &t(14, 0), 60);
+    assert_eq!(a.intervals(&Weekday::Monday), vec![(t(8, 0), 180)]);
+
+    let b = SlotSet::new()
+        .with_slot(Weekday::Monday, &t(10, 0), 120)
+        .with_slot(Weekday::Wednesday, &t(8, 0), 60);
+
+    let union = a.union(&b);
+    assert_eq!(union.intervals(&Weekday::Monday), vec![(t(8, 0), 240)]);
+    assert_eq!(union.intervals(&Weekday::Wednesday), vec![(t(8, 0), 60)]);
+
+    let intersection = a.intersection(&b);
+    assert_eq!(
+        intersection.days().cloned().collect::<Vec<_>>(),
+        vec![Weekday::Monday]
+    );
+    assert_eq!(
+        intersection.intervals(&Weekday::Monday),
+        vec![(t(10, 0), 60)]
+    );
+    assert!(a.overlaps_set(&b));
+
+    let difference = a.difference(&b);
+    assert_eq!(difference.intervals(&Weekday::Monday), vec![(t(8, 0), 120)]);
+    assert_eq!(
+        difference.intervals(&Weekday::Tuesday),
+        vec![(t(14, 0), 60)]
+    );
+
+    assert!(a.overlaps(&Weekday::Monday, &t(10, 59), 60));
+    assert!(!a.overlaps(&Weekday::Monday, &t(11, 0), 60));
+    assert!(!a.overlaps(&Weekday::Monday, &t(7, 0), 60));
+    assert!(a.contains(&Weekday::Tuesday, &t(14, 30)));
+    assert!(!a.contains(&Weekday::Tuesday, &t(15, 0)));
+}
+
+#[test]
+fn slot_set_free_gaps() {
+    let t = |h, m| Time::from_hm(h, m).unwrap();
+
+    let busy = SlotSet::new()
+        .with_slot(Weekday::Monday, &t(8, 0), 60)
+        .with_slot(Weekday::Monday, &t(9, 30), 60)
+        .with_slot(Weekday::Monday, &t(12, 0), 120);
+
+    assert_eq!(
+        busy.free_gaps(&Weekday::Monday, &t(8, 0), Some(&t(18, 0)), 60),
+        vec![(t(10, 30), 90), (t(14, 0), 240)]
+    );
+    assert_eq!(
+        busy.free_gaps(&Weekday::Monday, &t(8, 0), Some(&t(18, 0)), 30),
+        vec![(t(9, 0), 30), (t(10, 30), 90), (t(14, 0), 240)]
+    );
+    assert_eq!(
+        busy.free_gaps(&Weekday::Tuesday, &t(8, 0), Some(&t(12, 0)), 60),
+        vec![(t(8, 0), 240)]
+    );
+    assert_eq!(
+        busy.free_gaps(&Weekday::Monday, &t(22, 0), None, 60),
+        vec![(t(22, 0), 120)]
+    );
+}
+
+#[test]
+fn recurrence_weeks() {
+    use std::collections::BTreeSet;
+
+    assert_eq!(Recurrence::Weekly.weeks(4), BTreeSet::from([0, 1, 2, 3]));
+    assert_eq!(
+        Recurrence::Biweekly { offset: 0 }.weeks(5),
+        BTreeSet::from([0, 2, 4])
+    );
+    assert_eq!(
+        Recurrence::Biweekly { offset: 1 }.weeks(5),
+        BTreeSet::from([1, 3])
+    );
+    assert_eq!(
+        Recurrence::EveryNWeeks {
+            period: std::num::NonZeroU32::new(3).unwrap(),
+            offset: 2
+        }
+        .weeks(10),
+        BTreeSet::from([2, 5, 8])
+    );
+    assert_eq!(
+        Recurrence::Custom(BTreeSet::from([1, 7, 12])).weeks(10),
+        BTreeSet::from([1, 7])
+    );
+}
+
+#[test]
+fn duration_parsing() {
+    let parse = |s: &str| s.parse::<NonZeroDurationInMinutes>();
+
+    assert_eq!(parse("90"), Ok(NonZeroDurationInMinutes::new(90).unwrap()));
+    assert_eq!(parse("2h"), Ok(NonZeroDurationInMinutes::new(120).unwrap()));
+    assert_eq!(
+        parse("1h30"),
+        Ok(NonZeroDurationInMinutes::new(90).unwrap())
+    );
+    assert_eq!(
+        parse(" 1H05 "),
+        Ok(NonZeroDurationInMinutes::new(65).unwrap())
+    );
+    assert_eq!(parse("0"), Err(DurationError::Zero));
+    assert_eq!(parse("0h"), Err(DurationError::Zero));
+    assert!(matches!(
+        parse("1h75"),
+        Err(DurationError::InvalidFormat(_))
+    ));
+    assert!(matches!(parse("h30"), Err(DurationError::InvalidFormat(_))));
+    assert!(matches!(parse("-5"), Err(DurationError::InvalidFormat(_))));
+    assert!(matches!(parse("abc"), Err(DurationError::InvalidFormat(_))));
+    assert_eq!(parse("99999999999"), Err(DurationError::Overflow));
+
+    for minutes in [5, 60, 90, 125] {
+        let duration = NonZeroDurationInMinutes::new(minutes).unwrap();
+        assert_eq!(parse(&duration.to_string()), Ok(duration));
+    }
+    assert_eq!(
+        NonZeroDurationInMinutes::new(65).unwrap().to_string(),
+        "1h05"
+    );
+}
+
+#[test]
+fn duration_arithmetic() {
+    let d = |m| NonZeroDurationInMinutes::new(m).unwrap();
+
+    assert_eq!(d(30).checked_add(d(45)), Some(d(75)));
+    assert_eq!(d(u32::MAX).checked_add(d(1)), None);
+    assert_eq!(d(60).checked_sub(d(15)), Some(d(45)));
+    assert_eq!(d(60).checked_sub(d(60)), None);
+    assert_eq!(d(60).checked_sub(d(90)), None);
+    assert_eq!(
+        d(20).checked_mul(std::num::NonZeroU32::new(3).unwrap()),
+        Some(d(60))
+    );
+
+    let total: Option<NonZeroDurationInMinutes> = [d(20), d(40), d(60)].into_iter().sum();
+    assert_eq!(total, Some(d(120)));
+    let empty: Option<NonZeroDurationInMinutes> = std::iter::empty().sum();
+    assert_eq!(empty, None);
+}
+
+#[test]
+fn week_start_arithmetic() {
+    let week = WeekStart::new(Date::new(2024, 12, 23).unwrap()).unwrap();
+
+    assert_eq!(
+        week.add_weeks(2).get_monday(),
+        Date::new(2025, 1, 6).unwrap()
+    );
+    assert_eq!(
+        week.add_weeks(-4).get_monday(),
+        Date::new(2024, 11, 25).unwrap()
+    );
+    assert_eq!(week.add_weeks(0), week);
+
+    assert_eq!(week.weeks_between(&week.add_weeks(5)), 5);
+    assert_eq!(week.add_weeks(5).weeks_between(&week), -5);
+    assert_eq!(week.weeks_between(&week), 0);
+}
+
+#[test]
+fn week_iterator() {
+    let first = WeekStart::new(Date::new(2024, 12, 23).unwrap()).unwrap();
+    let last = first.add_weeks(2);
+
+    let weeks: Vec<_> = first.iterate_until(&last).collect();
+    assert_eq!(weeks, vec![first, first.next(), last]);
+    assert_eq!(first.iterate_until(&last).len(), 3);
+
+    assert_eq!(first.iterate_until(&first).count(), 1);
+    assert_eq!(last.iterate_until(&first).count(), 0);
+    assert_eq!(last.iterate_until(&first).len(), 0);
+}
+
+#[test]
+fn academic_year_terms() {
+    let d = |y, m, day| Date::new(y, m, day).unwrap();
+
+    assert!(AcademicYear::new(d(2025, 7, 4), d(2024, 9, 2)).is_none());
+
+    let year = AcademicYear::new(d(2024, 9, 2), d(2024, 12, 20))
+        .unwrap()
+        .with_calendar(HolidayCalendar::new().add_range(d(2024, 10, 19)..=d(2024, 11, 3)))
+        // During holidays: starts with the week of november 4th
+        .add_term_start(d(2024, 10, 28))
+        .add_term_start(d(2024, 12, 2))
+        // Outside of the year
+        .add_term_start(d(2025, 1, 6))
+        .add_term_start(d(2024, 9, 2));
+
+    // 16 weeks minus 2 weeks of holidays
+    assert_eq!(year.week_count(), std::num::NonZeroU32::new(14));
+    assert_eq!(year.get_term_starts().len(), 2);
+    assert_eq!(
+        year.periodicity_cuts(),
+        std::collections::BTreeSet::from([
+            std::num::NonZeroU32::new(7).unwrap(),
+            std::num::NonZeroU32::new(11).unwrap(),
+        ])
+    );
+    assert_eq!(
+        year.week_mapping().week_start(7).get_monday(),
+        d(2024, 11, 4)
+    );
+}
+
+#[test]
+fn academic_year_french_presets() {
+    assert!(AcademicYear::french(1990, HolidayZone::A).is_none());
+
+    let zone_a = AcademicYear::french(2024, HolidayZone::A).unwrap();
+    let zone_b = AcademicYear::french(2024, HolidayZone::B).unwrap();
+    let zone_c = AcademicYear::french(2024, HolidayZone::C).unwrap();
+
+    // 44 weeks from september 2nd to july 4th, 8 of them being holidays
+    for year in [&zone_a, &zone_b, &zone_c] {
+        assert_eq!(year.week_count(), std::num::NonZeroU32::new(36));
+        assert!(year
+            .get_calendar()
+            .is_holiday(&Date::new(2024, 12, 25).unwrap()));
+    }
+
+    let winter = Date::new(2025, 2, 12).unwrap();
+    assert!(!zone_a.get_calendar().is_holiday(&winter));
+    assert!(zone_b.get_calendar().is_holiday(&winter));
+    assert!(!zone_c.get_calendar().is_holiday(&winter));
+
+    assert!(AcademicYear::french(2025, HolidayZone::C).is_some());
+}
+
+#[test]
+fn weekday_next() {
+    assert_eq!(Weekday::Monday.next(), Weekday::Tuesday);
+    assert_eq!(Weekday::Saturday.next(), Weekday::Sunday);
+    assert_eq!(Weekday::Sunday.next(), Weekday::Monday);
+}
+
+#[test]
+fn quick_entry_parsing() {
+    let t = |h, m| Time::from_hm(h, m).unwrap();
+
+    assert_eq!(
+        Locale::French.parse_day_and_time("lundi 14h"),
+        Ok((Weekday::Monday, t(14, 0)))
+    );
+    assert_eq!(
+        Locale::French.parse_day_and_time("jeu 8:30"),
+        Ok((Weekday::Thursday, t(8, 30)))
+    );
+    assert_eq!(
+        Locale::French.parse_day_and_time("  Mer.  à 10H05 "),
+        Ok((Weekday::Wednesday, t(10, 5)))
+    );
+    assert_eq!(
+        Locale::English.parse_day_and_time("thurs 2:30pm"),
+        Ok((Weekday::Thursday, t(14, 30)))
+    );
+    assert_eq!(
+        Locale::English.parse_day_and_time("Monday 12am"),
+        Ok((Weekday::Monday, t(0, 0)))
+    );
+
+    assert_eq!(
+        Locale::French.parse_day_and_time("lundi"),
+        Err(QuickEntryError::MissingTime("lundi 14h"))
+    );
+    assert_eq!(
+        Locale::French.parse_day_and_time("monday 14h"),
+        Err(QuickEntryError::UnknownWeekday("monday".to_string()))
+    );
+    assert_eq!(
+        Locale::French.parse_time("14"),
+        Err(QuickEntryError::InvalidTimeFormat(
+            "14".to_string(),
+            "14h30"
+        ))
+    );
+    assert_eq!(
+        Locale::French.parse_time("14h3"),
+        Err(QuickEntryError::InvalidTimeFormat(
+            "14h3".to_string(),
+            "14h30"
+        ))
+    );
+    assert_eq!(
+        Locale::French.parse_time("25h"),
+        Err(QuickEntryError::TimeOutOfRange("25h".to_string()))
+    );
+    assert_eq!(
+        Locale::English.parse_time("13pm"),
+        Err(QuickEntryError::TimeOutOfRange("13pm".to_string()))
+    );
+    assert!(Locale::French.parse_time("2pm").is_err());
+}
+
+#[test]
+fn week_parity() {
+    let anchor = WeekStart::new(Date::new(2024, 10, 7).unwrap()).unwrap();
+    let calendar = HolidayCalendar::new()
+        .add_range(Date::new(2024, 10, 19).unwrap()..=Date::new(2024, 11, 3).unwrap());
+
+    let calendar_weeks = ParityCalendar::new(anchor);
+    assert_eq!(calendar_weeks.parity(&anchor), Some(WeekParity::A));
+    assert_eq!(
+        calendar_weeks.parity(&anchor.add_weeks(1)),
+        Some(WeekParity::B)
+    );
+    assert_eq!(
+        calendar_weeks.parity(&anchor.add_weeks(-1)),
+        Some(WeekParity::B)
+    );
+    assert_eq!(
+        calendar_weeks.parity(&anchor.add_weeks(-2)),
+        Some(WeekParity::A)
+    );
+    // Week of november 4th
+    assert_eq!(
+        calendar_weeks.parity(&anchor.add_weeks(4)),
+        Some(WeekParity::A)
+    );
+
+    let teaching_weeks = ParityCalendar::new(anchor).skipping_holidays(calendar.clone());
+    assert_eq!(teaching_weeks.parity(&anchor.add_weeks(2)), None);
+    assert_eq!(
+        teaching_weeks.parity(&anchor.add_weeks(4)),
+        Some(WeekParity::A)
+    );
+    assert_eq!(
+        teaching_weeks.parity(&anchor.add_weeks(5)),
+        Some(WeekParity::B)
+    );
+
+    let mapping = WeekMapping::new(anchor.add_weeks(-1), calendar);
+    assert_eq!(
+        teaching_weeks.week_pattern(&mapping, 6, WeekParity::A),
+        std::collections::BTreeSet::from([1, 3, 5])
+    );
+    assert_eq!(
+        calendar_weeks.recurrence(&mapping, 6, WeekParity::B),
+        Recurrence::Custom(std::collections::BTreeSet::from([0, 2, 4]))
+    );
+    assert_eq!(WeekParity::A.other(), WeekParity::B);
+}